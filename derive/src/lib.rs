@@ -0,0 +1,78 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Derive macros for the `dusk-plonk` crate.
+//!
+//! They are re-exported by `dusk-plonk` when its `derive` feature is
+//! enabled, so this crate is not meant to be used directly.
+
+#![deny(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Index};
+
+/// Derives `dusk_plonk::circuit::PublicInputs` for a struct, converting each
+/// one of its fields into a `PublicInputValue` in declaration order.
+///
+/// Every field must implement `Clone` and
+/// `Into<dusk_plonk::circuit::PublicInputValue>`.
+#[proc_macro_derive(PublicInputs)]
+pub fn derive_public_inputs(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let fields: Vec<TokenStream2> = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .map(|field| {
+                    let name = &field.ident;
+                    quote!(#name)
+                })
+                .collect(),
+            Fields::Unnamed(fields) => (0..fields.unnamed.len())
+                .map(|i| {
+                    let index = Index::from(i);
+                    quote!(#index)
+                })
+                .collect(),
+            Fields::Unit => Vec::new(),
+        },
+        _ => {
+            return syn::Error::new_spanned(
+                &input.ident,
+                "PublicInputs can only be derived for structs",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::dusk_plonk::circuit::PublicInputs
+            for #name #ty_generics #where_clause
+        {
+            fn visit_public_input_values(
+                &self,
+                f: &mut dyn FnMut(::dusk_plonk::circuit::PublicInputValue),
+            ) {
+                #(
+                    f(::core::convert::Into::into(
+                        ::core::clone::Clone::clone(&self.#fields),
+                    ));
+                )*
+            }
+        }
+    };
+
+    expanded.into()
+}
@@ -0,0 +1,39 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::*;
+
+#[derive(PublicInputs)]
+struct Inputs {
+    sum: BlsScalar,
+    point: JubJubAffine,
+    scalar: JubJubScalar,
+}
+
+#[derive(PublicInputs)]
+struct TupleInputs(JubJubAffine, BlsScalar);
+
+#[test]
+fn derived_public_inputs_keep_declaration_order() {
+    let point = dusk_jubjub::GENERATOR;
+    let inputs = Inputs {
+        sum: BlsScalar::from(42u64),
+        point,
+        scalar: JubJubScalar::from(7u64),
+    };
+
+    let expected: Vec<PublicInputValue> = vec![
+        BlsScalar::from(42u64).into(),
+        point.into(),
+        JubJubScalar::from(7u64).into(),
+    ];
+    assert_eq!(inputs.to_public_input_values(), expected);
+
+    let inputs = TupleInputs(point, BlsScalar::one());
+    let expected: Vec<PublicInputValue> =
+        vec![point.into(), BlsScalar::one().into()];
+    assert_eq!(inputs.to_public_input_values(), expected);
+}
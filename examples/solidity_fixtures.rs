@@ -0,0 +1,92 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Writes the Solidity verifier of the circuit of the README into
+//! `solidity/src`, and test vectors of proofs of it checked by
+//! `PlonkVerifier::verify` into `solidity/test/fixtures`, for the Foundry
+//! tests to run them against the contract.
+
+use dusk_bytes::{DeserializableSlice, Serializable};
+use dusk_plonk::codegen::{
+    proof_to_evm_bytes, public_inputs_to_evm_words, solidity_verifier,
+};
+use dusk_plonk::prelude::*;
+use dusk_plonk::test_circuits::TestCircuit;
+use rand_core::OsRng;
+use std::fs;
+use std::path::Path;
+
+const TRANSCRIPT_INIT: &[u8] = b"dusk-plonk-evm";
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Returns the JSON object of a test vector, with the result of the Rust
+/// verifier as the expected one.
+fn test_vector(
+    verifier: &PlonkVerifier,
+    verifier_data: &VerifierData,
+    proof: &Proof,
+    pub_inputs: &[PublicInputValue],
+) -> String {
+    let expected = verifier
+        .verify(proof, pub_inputs, verifier_data.pi_pos())
+        .is_ok();
+    let words: Vec<String> = public_inputs_to_evm_words(pub_inputs)
+        .iter()
+        .map(|word| format!("\"0x{}\"", to_hex(word)))
+        .collect();
+
+    format!(
+        "{{\"proof\": \"0x{}\", \"publicInputs\": [{}], \"expected\": {}}}",
+        to_hex(&proof_to_evm_bytes(proof)),
+        words.join(", "),
+        expected
+    )
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+    let (pk, vd) = TestCircuit::default().compile(&pp)?;
+
+    let mut circuit = TestCircuit::valid();
+    let proof = circuit.gen_proof(&pp, &pk, TRANSCRIPT_INIT)?;
+
+    let pub_inputs = circuit.public_inputs();
+    circuit.d += BlsScalar::one();
+    let wrong_pub_inputs = circuit.public_inputs();
+
+    // Flip a bit of the evaluation of the linearisation polynomial, the
+    // second to last one of the proof
+    let mut tampered_bytes = proof.to_bytes();
+    tampered_bytes[Proof::SIZE - 2 * BlsScalar::SIZE] ^= 1;
+    let tampered_proof = Proof::from_slice(&tampered_bytes)?;
+
+    let verifier = PlonkVerifier::with_opening_key(
+        *vd.key(),
+        pp.opening_key().clone(),
+        TRANSCRIPT_INIT,
+    );
+    let vectors = format!(
+        "{{\n  \"valid\": {},\n  \"wrongPublicInput\": {},\n  \
+         \"tamperedProof\": {}\n}}\n",
+        test_vector(&verifier, &vd, &proof, &pub_inputs),
+        test_vector(&verifier, &vd, &proof, &wrong_pub_inputs),
+        test_vector(&verifier, &vd, &tampered_proof, &pub_inputs),
+    );
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("solidity");
+    fs::create_dir_all(dir.join("src"))?;
+    fs::create_dir_all(dir.join("test/fixtures"))?;
+    fs::write(
+        dir.join("src/PlonkVerifier.sol"),
+        solidity_verifier(&vd, pp.opening_key(), TRANSCRIPT_INIT)?,
+    )?;
+    fs::write(dir.join("test/fixtures/test_vectors.json"), vectors)?;
+
+    Ok(())
+}
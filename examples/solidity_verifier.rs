@@ -0,0 +1,43 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Prints a Solidity contract verifying the proofs of a circuit.
+//!
+//! Usage:
+//!
+//! ```text
+//! cargo run --example solidity_verifier --features codegen -- \
+//!     <verifier data> <opening key> <transcript init>
+//! ```
+//!
+//! where `<verifier data>` is a file holding the bytes of
+//! `VerifierData::to_var_bytes`, `<opening key>` one holding the bytes of
+//! `OpeningKey::to_bytes`, and `<transcript init>` the label the proofs are
+//! generated with.
+
+use dusk_bytes::DeserializableSlice;
+use dusk_plonk::codegen::solidity_verifier;
+use dusk_plonk::prelude::*;
+use std::{env, fs, process};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 4 {
+        eprintln!(
+            "usage: {} <verifier data> <opening key> <transcript init>",
+            args[0]
+        );
+        process::exit(1);
+    }
+
+    let verifier_data = VerifierData::from_slice(&fs::read(&args[1])?)?;
+    let opening_key = OpeningKey::from_slice(&fs::read(&args[2])?)?;
+    let contract =
+        solidity_verifier(&verifier_data, &opening_key, args[3].as_bytes())?;
+    print!("{}", contract);
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: MPL-2.0
+
+pragma solidity ^0.8.19;
+
+import {PlonkVerifier} from "../src/PlonkVerifier.sol";
+
+/// Cheatcodes of Foundry used by the tests
+interface Vm {
+    function readFile(string calldata path)
+        external
+        view
+        returns (string memory);
+
+    function parseJsonBytes(string calldata json, string calldata key)
+        external
+        pure
+        returns (bytes memory);
+
+    function parseJsonUintArray(string calldata json, string calldata key)
+        external
+        pure
+        returns (uint256[] memory);
+
+    function parseJsonBool(string calldata json, string calldata key)
+        external
+        pure
+        returns (bool);
+}
+
+/// Runs the test vectors written by the `solidity_fixtures` example of
+/// dusk-plonk against the verifier generated for their circuit, expecting the
+/// results of `PlonkVerifier::verify`.
+contract PlonkVerifierTest {
+    Vm internal constant VM =
+        Vm(address(uint160(uint256(keccak256("hevm cheat code")))));
+
+    // Order of the scalar field of BLS12-381
+    uint256 internal constant R =
+        0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001;
+
+    PlonkVerifier internal verifier;
+    string internal vectors;
+
+    function setUp() public {
+        verifier = new PlonkVerifier();
+        vectors = VM.readFile("test/fixtures/test_vectors.json");
+    }
+
+    function testValidProof() public view {
+        require(
+            VM.parseJsonBool(vectors, ".valid.expected"),
+            "the Rust verifier rejects the valid proof"
+        );
+        _check(".valid");
+    }
+
+    function testWrongPublicInput() public view {
+        _checkRejected(".wrongPublicInput");
+    }
+
+    function testTamperedProof() public view {
+        _checkRejected(".tamperedProof");
+    }
+
+    function testTamperedValidProof() public view {
+        bytes memory proof = VM.parseJsonBytes(vectors, ".valid.proof");
+        uint256[] memory publicInputs =
+            VM.parseJsonUintArray(vectors, ".valid.publicInputs");
+        require(verifier.verify(proof, publicInputs), "valid proof");
+
+        // Same byte flipped by `solidity_fixtures` for `.tamperedProof`, the
+        // first one of the second to last evaluation of the proof
+        proof[proof.length - 64] ^= 0x01;
+        require(!verifier.verify(proof, publicInputs), "tampered proof");
+    }
+
+    function testMalformedInputs() public view {
+        bytes memory proof = VM.parseJsonBytes(vectors, ".valid.proof");
+        uint256[] memory publicInputs =
+            VM.parseJsonUintArray(vectors, ".valid.publicInputs");
+
+        // Truncated proof
+        bytes memory truncated = new bytes(proof.length - 1);
+        for (uint256 i = 0; i < truncated.length; i++) {
+            truncated[i] = proof[i];
+        }
+        require(!verifier.verify(truncated, publicInputs), "truncated proof");
+
+        // Missing public input
+        uint256[] memory missing = new uint256[](publicInputs.length - 1);
+        for (uint256 i = 0; i < missing.length; i++) {
+            missing[i] = publicInputs[i];
+        }
+        require(!verifier.verify(proof, missing), "missing public input");
+
+        // Non canonical public input
+        publicInputs[0] += R;
+        require(
+            !verifier.verify(proof, publicInputs),
+            "non canonical public input"
+        );
+    }
+
+    function _check(string memory vector) internal view {
+        bytes memory proof =
+            VM.parseJsonBytes(vectors, string.concat(vector, ".proof"));
+        uint256[] memory publicInputs = VM.parseJsonUintArray(
+            vectors,
+            string.concat(vector, ".publicInputs")
+        );
+        bool expected =
+            VM.parseJsonBool(vectors, string.concat(vector, ".expected"));
+
+        require(verifier.verify(proof, publicInputs) == expected, vector);
+    }
+
+    /// Checks that both the Rust verifier and the contract reject the proof
+    /// of `vector`, so a vector regenerated with a wrong expectation can't
+    /// make the test pass vacuously.
+    function _checkRejected(string memory vector) internal view {
+        require(
+            !VM.parseJsonBool(vectors, string.concat(vector, ".expected")),
+            string.concat(vector, " is accepted by the Rust verifier")
+        );
+        _check(vector);
+    }
+}
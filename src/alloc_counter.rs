@@ -0,0 +1,119 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Global allocator of the tests of the crate, counting the allocations made
+//! by the thread running a closure passed to [`measure`].
+//!
+//! The counters are thread local, so the tests running in parallel don't
+//! disturb each other. The allocations made by the rayon workers spawned by
+//! the closure are not counted.
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::Cell;
+use std::alloc::System;
+
+/// Allocations made by the closure passed to [`measure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct AllocStats {
+    /// Number of calls to `alloc` and `realloc`
+    pub(crate) allocations: usize,
+    /// Maximum number of bytes held at once by the allocations made by the
+    /// closure
+    pub(crate) peak_bytes: usize,
+}
+
+/// State of the counters of a thread.
+#[derive(Clone, Copy)]
+struct Counter {
+    enabled: bool,
+    current: isize,
+    stats: AllocStats,
+}
+
+std::thread_local! {
+    static COUNTER: Cell<Counter> = const {
+        Cell::new(Counter {
+            enabled: false,
+            current: 0,
+            stats: AllocStats { allocations: 0, peak_bytes: 0 },
+        })
+    };
+}
+
+struct CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn record(allocated: usize, freed: usize) {
+    // The thread locals can't be accessed while the thread is torn down
+    let _ = COUNTER.try_with(|counter| {
+        let mut state = counter.get();
+        if !state.enabled {
+            return;
+        }
+
+        state.current += allocated as isize - freed as isize;
+        if allocated > 0 {
+            state.stats.allocations += 1;
+        }
+        state.stats.peak_bytes =
+            state.stats.peak_bytes.max(state.current.max(0) as usize);
+        counter.set(state);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(0, layout.size());
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        // Count the worst case of a moving reallocation, which holds both
+        // buffers at once
+        record(new_size, 0);
+        record(0, layout.size());
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Runs `f`, returning its result along with the allocations it made on the
+/// current thread.
+pub(crate) fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let set_enabled = |enabled: bool| {
+        COUNTER.with(|counter| {
+            let mut state = counter.get();
+            if enabled {
+                state.current = 0;
+                state.stats = AllocStats::default();
+            }
+            state.enabled = enabled;
+            counter.set(state);
+        })
+    };
+
+    set_enabled(true);
+    let result = f();
+    set_enabled(false);
+
+    (result, COUNTER.with(|counter| counter.get().stats))
+}
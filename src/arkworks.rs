@@ -0,0 +1,341 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Conversions between the types of this crate and the ones of
+//! `ark-bls12-381`, so proofs and keys can be checked by arkworks-based
+//! systems and the other way around.
+//!
+//! The scalars are encoded in little-endian by both libraries, while the
+//! coordinates of the points are converted through the big-endian
+//! uncompressed encoding of `dusk-bls12_381`, where the `c1` component of a
+//! G2 coordinate comes before its `c0`. The points coming from arkworks are
+//! checked to be on the curve and in the prime order subgroup.
+
+use crate::commitment_scheme::kzg10::{Commitment, OpeningKey};
+use crate::error::Error;
+use crate::proof_system::linearisation_poly::ProofEvaluations;
+use crate::proof_system::proof::PROOF_COMMITMENTS;
+use crate::proof_system::version::{HEADER, HEADER_SIZE};
+use crate::proof_system::Proof;
+use alloc::vec::Vec;
+use ark_ff::{BigInteger, PrimeField, Zero};
+use core::convert::TryFrom;
+use dusk_bls12_381::{BlsScalar, G1Affine, G2Affine};
+use dusk_bytes::Serializable;
+
+/// Number of scalars of the evaluations of a [`Proof`].
+const PROOF_EVALUATIONS: usize = ProofEvaluations::SIZE / BlsScalar::SIZE;
+
+/// Size in bytes of a coordinate of a point of G1.
+const FQ_SIZE: usize = 48;
+
+/// Returns the arkworks scalar of the same value.
+pub fn scalar_to_ark(scalar: &BlsScalar) -> ark_bls12_381::Fr {
+    ark_bls12_381::Fr::from_le_bytes_mod_order(&scalar.to_bytes())
+}
+
+/// Returns the [`BlsScalar`] of the same value.
+pub fn scalar_from_ark(scalar: &ark_bls12_381::Fr) -> BlsScalar {
+    let mut bytes = [0u8; BlsScalar::SIZE];
+    bytes.copy_from_slice(&scalar.into_repr().to_bytes_le());
+
+    // The scalar is reduced, so it has a canonical encoding
+    BlsScalar::from_bytes(&bytes).expect("The scalar fields are the same")
+}
+
+fn fq_to_ark(bytes: &[u8]) -> ark_bls12_381::Fq {
+    ark_bls12_381::Fq::from_be_bytes_mod_order(bytes)
+}
+
+fn fq_from_ark(fq: &ark_bls12_381::Fq) -> Vec<u8> {
+    fq.into_repr().to_bytes_be()
+}
+
+/// Returns the arkworks point of G1 of the same value.
+pub fn g1_to_ark(point: &G1Affine) -> ark_bls12_381::G1Affine {
+    if bool::from(point.is_identity()) {
+        return ark_bls12_381::G1Affine::zero();
+    }
+
+    let bytes = point.to_uncompressed();
+    let x = fq_to_ark(&bytes[..FQ_SIZE]);
+    let y = fq_to_ark(&bytes[FQ_SIZE..]);
+    ark_bls12_381::G1Affine::new(x, y, false)
+}
+
+/// Returns the point of G1 of the same value, or
+/// [`Error::PointMalformed`] if the arkworks point is not on the curve or
+/// not in the prime order subgroup.
+pub fn g1_from_ark(point: &ark_bls12_381::G1Affine) -> Result<G1Affine, Error> {
+    if point.infinity {
+        return Ok(G1Affine::identity());
+    }
+
+    let mut bytes = [0u8; 2 * FQ_SIZE];
+    bytes[..FQ_SIZE].copy_from_slice(&fq_from_ark(&point.x));
+    bytes[FQ_SIZE..].copy_from_slice(&fq_from_ark(&point.y));
+    Option::from(G1Affine::from_uncompressed(&bytes))
+        .ok_or(Error::PointMalformed)
+}
+
+/// Returns the arkworks point of G2 of the same value.
+pub fn g2_to_ark(point: &G2Affine) -> ark_bls12_381::G2Affine {
+    if bool::from(point.is_identity()) {
+        return ark_bls12_381::G2Affine::zero();
+    }
+
+    let bytes = point.to_uncompressed();
+    let fq = |i: usize| fq_to_ark(&bytes[i * FQ_SIZE..(i + 1) * FQ_SIZE]);
+    let x = ark_bls12_381::Fq2::new(fq(1), fq(0));
+    let y = ark_bls12_381::Fq2::new(fq(3), fq(2));
+    ark_bls12_381::G2Affine::new(x, y, false)
+}
+
+/// Returns the point of G2 of the same value, or
+/// [`Error::PointMalformed`] if the arkworks point is not on the curve or
+/// not in the prime order subgroup.
+pub fn g2_from_ark(point: &ark_bls12_381::G2Affine) -> Result<G2Affine, Error> {
+    if point.infinity {
+        return Ok(G2Affine::identity());
+    }
+
+    let mut bytes = [0u8; 4 * FQ_SIZE];
+    [&point.x.c1, &point.x.c0, &point.y.c1, &point.y.c0]
+        .iter()
+        .zip(bytes.chunks_mut(FQ_SIZE))
+        .for_each(|(fq, chunk)| chunk.copy_from_slice(&fq_from_ark(fq)));
+    Option::from(G2Affine::from_uncompressed(&bytes))
+        .ok_or(Error::PointMalformed)
+}
+
+/// [`Proof`] made of arkworks types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArkProof {
+    /// Commitments of the proof, in the order of [`Proof::to_bytes`].
+    pub commitments: [ark_bls12_381::G1Affine; PROOF_COMMITMENTS],
+    /// Evaluations of the proof, in the order of [`Proof::to_bytes`].
+    pub evaluations: [ark_bls12_381::Fr; PROOF_EVALUATIONS],
+}
+
+impl From<&Proof> for ArkProof {
+    fn from(proof: &Proof) -> Self {
+        let commitments = [
+            proof.a_comm,
+            proof.b_comm,
+            proof.c_comm,
+            proof.d_comm,
+            proof.z_comm,
+            proof.t_1_comm,
+            proof.t_2_comm,
+            proof.t_3_comm,
+            proof.t_4_comm,
+            proof.w_z_comm,
+            proof.w_zw_comm,
+        ];
+
+        let mut ark_commitments =
+            [ark_bls12_381::G1Affine::zero(); PROOF_COMMITMENTS];
+        ark_commitments
+            .iter_mut()
+            .zip(commitments.iter())
+            .for_each(|(ark, commitment)| *ark = g1_to_ark(&commitment.0));
+
+        let mut evaluations = [ark_bls12_381::Fr::zero(); PROOF_EVALUATIONS];
+        evaluations
+            .iter_mut()
+            .zip(proof.evaluations.to_bytes().chunks(BlsScalar::SIZE))
+            .for_each(|(ark, bytes)| {
+                *ark = ark_bls12_381::Fr::from_le_bytes_mod_order(bytes)
+            });
+
+        Self {
+            commitments: ark_commitments,
+            evaluations,
+        }
+    }
+}
+
+impl TryFrom<&ArkProof> for Proof {
+    type Error = Error;
+
+    fn try_from(proof: &ArkProof) -> Result<Self, Error> {
+        let mut bytes = [0u8; Proof::SIZE];
+        bytes[..HEADER_SIZE].copy_from_slice(&HEADER);
+
+        let (commitments, evaluations) = bytes[HEADER_SIZE..]
+            .split_at_mut(PROOF_COMMITMENTS * Commitment::SIZE);
+        for (ark, chunk) in proof
+            .commitments
+            .iter()
+            .zip(commitments.chunks_mut(Commitment::SIZE))
+        {
+            chunk.copy_from_slice(&g1_from_ark(ark)?.to_bytes());
+        }
+        proof
+            .evaluations
+            .iter()
+            .zip(evaluations.chunks_mut(BlsScalar::SIZE))
+            .for_each(|(ark, chunk)| {
+                chunk.copy_from_slice(&scalar_from_ark(ark).to_bytes())
+            });
+
+        Proof::from_bytes(&bytes)
+    }
+}
+
+/// [`OpeningKey`] made of arkworks types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArkOpeningKey {
+    /// The generator of G1.
+    pub g: ark_bls12_381::G1Affine,
+    /// The generator of G2.
+    pub h: ark_bls12_381::G2Affine,
+    /// The secret of the setup times the generator of G2.
+    pub beta_h: ark_bls12_381::G2Affine,
+}
+
+impl From<&OpeningKey> for ArkOpeningKey {
+    fn from(key: &OpeningKey) -> Self {
+        Self {
+            g: g1_to_ark(&key.g),
+            h: g2_to_ark(&key.h),
+            beta_h: g2_to_ark(&key.beta_h),
+        }
+    }
+}
+
+impl TryFrom<&ArkOpeningKey> for OpeningKey {
+    type Error = Error;
+
+    fn try_from(key: &ArkOpeningKey) -> Result<Self, Error> {
+        Ok(OpeningKey::new(
+            g1_from_ark(&key.g)?,
+            g2_from_ark(&key.h)?,
+            g2_from_ark(&key.beta_h)?,
+        ))
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment_scheme::kzg10::PublicParameters;
+    use crate::fft::Polynomial;
+    use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+    use dusk_bls12_381::{G1Projective, G2Projective};
+    use rand_core::OsRng;
+
+    fn random_g1() -> G1Affine {
+        (G1Affine::generator() * BlsScalar::random(&mut OsRng)).into()
+    }
+
+    #[test]
+    fn test_scalar_and_point_round_trips() {
+        let scalar = BlsScalar::random(&mut OsRng);
+        assert_eq!(scalar_from_ark(&scalar_to_ark(&scalar)), scalar);
+        assert_eq!(
+            scalar_to_ark(&BlsScalar::from(7u64)),
+            ark_bls12_381::Fr::from(7u64)
+        );
+
+        let g1 = random_g1();
+        assert_eq!(g1_from_ark(&g1_to_ark(&g1)).unwrap(), g1);
+        assert_eq!(
+            g1_to_ark(&G1Affine::generator()),
+            ark_bls12_381::G1Affine::prime_subgroup_generator()
+        );
+        assert_eq!(
+            g1_from_ark(&g1_to_ark(&G1Affine::identity())).unwrap(),
+            G1Affine::identity()
+        );
+
+        let g2: G2Affine =
+            (G2Affine::generator() * BlsScalar::random(&mut OsRng)).into();
+        assert_eq!(g2_from_ark(&g2_to_ark(&g2)).unwrap(), g2);
+        assert_eq!(
+            g2_to_ark(&G2Affine::generator()),
+            ark_bls12_381::G2Affine::prime_subgroup_generator()
+        );
+
+        // Should fail since the point is not on the curve
+        let mut off_curve = g1_to_ark(&g1);
+        off_curve.y = off_curve.x;
+        assert!(matches!(
+            g1_from_ark(&off_curve),
+            Err(Error::PointMalformed)
+        ));
+    }
+
+    #[test]
+    fn test_proof_round_trip() {
+        let mut bytes = [0u8; Proof::SIZE];
+        bytes[..HEADER_SIZE].copy_from_slice(&HEADER);
+        let (commitments, evaluations) = bytes[HEADER_SIZE..]
+            .split_at_mut(PROOF_COMMITMENTS * Commitment::SIZE);
+        commitments
+            .chunks_mut(Commitment::SIZE)
+            .for_each(|chunk| chunk.copy_from_slice(&random_g1().to_bytes()));
+        evaluations.chunks_mut(BlsScalar::SIZE).for_each(|chunk| {
+            chunk.copy_from_slice(&BlsScalar::random(&mut OsRng).to_bytes())
+        });
+        let proof = Proof::from_bytes(&bytes).unwrap();
+
+        let ark_proof = ArkProof::from(&proof);
+        assert_eq!(ark_proof.commitments[0], g1_to_ark(&proof.a_comm.0));
+        assert_eq!(
+            ark_proof.evaluations[0],
+            scalar_to_ark(&proof.evaluations.a_eval)
+        );
+        assert_eq!(Proof::try_from(&ark_proof).unwrap(), proof);
+    }
+
+    #[test]
+    fn test_cross_verification() {
+        let pp = PublicParameters::setup(1 << 4, &mut OsRng).unwrap();
+        let (ck, opening_key) = pp.trim(1 << 4).unwrap();
+
+        let ark_key = ArkOpeningKey::from(&opening_key);
+        let key = OpeningKey::try_from(&ark_key).unwrap();
+        assert_eq!(key.to_bytes(), opening_key.to_bytes());
+
+        // Commit and open with the dusk types
+        let poly = Polynomial::rand(1 << 4, &mut OsRng);
+        let point = BlsScalar::random(&mut OsRng);
+        let value = poly.evaluate(&point);
+        let commitment = ck.commit(&poly).unwrap();
+        let witness = ck.commit(&poly.ruffini(point)).unwrap();
+
+        // Check the pairing with the arkworks types:
+        // e(W, beta_h - z * h) = e(C - v * g, h)
+        let check = |value: &BlsScalar| {
+            let z = scalar_to_ark(&point).into_repr();
+            let v = scalar_to_ark(value).into_repr();
+            let lhs_g2 = ark_key.beta_h.into_projective() - ark_key.h.mul(z);
+            let rhs_g1 =
+                g1_to_ark(&commitment.0).into_projective() - ark_key.g.mul(v);
+
+            ark_bls12_381::Bls12_381::pairing(
+                g1_to_ark(&witness.0),
+                lhs_g2.into_affine(),
+            ) == ark_bls12_381::Bls12_381::pairing(
+                rhs_g1.into_affine(),
+                ark_key.h,
+            )
+        };
+        assert!(check(&value));
+        assert!(!check(&(value + BlsScalar::one())));
+
+        // The dusk types agree with the pairing check
+        let dusk_lhs =
+            G2Projective::from(opening_key.beta_h) - opening_key.h * point;
+        let dusk_rhs = G1Projective::from(commitment.0) - opening_key.g * value;
+        assert_eq!(
+            dusk_bls12_381::pairing(&witness.0, &dusk_lhs.into()),
+            dusk_bls12_381::pairing(&dusk_rhs.into(), &opening_key.h)
+        );
+    }
+}
@@ -0,0 +1,353 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Pluggable backend for the heavy computations performed by the prover.
+//!
+//! Most of the proving time is spent computing multi-scalar multiplications
+//! for the polynomial commitments and FFTs over the evaluation domains. The
+//! [`ComputeBackend`] trait allows to offload these operations to a GPU or any
+//! other kind of accelerator, by attaching it to the
+//! [`CommitKey`](crate::commitment_scheme::kzg10::CommitKey) used to prove.
+//!
+//! When no backend is specified, the [`CpuBackend`] is used.
+
+use crate::fft::{fft_slice_in_place, EvaluationDomain};
+use alloc::vec::Vec;
+#[cfg(not(feature = "constant_time"))]
+use dusk_bls12_381::multiscalar_mul::msm_variable_base;
+use dusk_bls12_381::{BlsScalar, G1Affine, G1Projective, GENERATOR};
+
+/// Operations that the prover delegates to a compute backend.
+pub trait ComputeBackend: Send + Sync {
+    /// Computes the multi-scalar multiplication of the `bases` by the
+    /// `scalars`.
+    ///
+    /// The `scalars` may hold less elements than the `bases`, in which case
+    /// the trailing bases are ignored.
+    fn msm(&self, bases: &[G1Affine], scalars: &[BlsScalar]) -> G1Projective;
+
+    /// Computes in place the FFT of `evals` over the multiplicative subgroup
+    /// generated by `group_gen`, or the inverse FFT if `inverse` is set,
+    /// including the scaling by the inverse of the subgroup order.
+    ///
+    /// The order of the subgroup is always the length of `evals`, which is a
+    /// power of two.
+    fn fft_in_place(
+        &self,
+        evals: &mut [BlsScalar],
+        group_gen: BlsScalar,
+        inverse: bool,
+    );
+}
+
+/// Default [`ComputeBackend`] that performs all of the computations on the
+/// CPU.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CpuBackend;
+
+impl ComputeBackend for CpuBackend {
+    #[cfg(not(feature = "constant_time"))]
+    fn msm(&self, bases: &[G1Affine], scalars: &[BlsScalar]) -> G1Projective {
+        msm_variable_base(bases, scalars)
+    }
+
+    /// The bucket method of `msm_variable_base` is variable-time on the
+    /// scalars, which are the coefficients of the witness polynomials when
+    /// committing to them. With the `constant_time` feature each term is
+    /// computed with a constant-time scalar multiplication instead, which is
+    /// much slower.
+    #[cfg(feature = "constant_time")]
+    fn msm(&self, bases: &[G1Affine], scalars: &[BlsScalar]) -> G1Projective {
+        bases
+            .iter()
+            .zip(scalars)
+            .fold(G1Projective::identity(), |acc, (base, scalar)| {
+                acc + base * scalar
+            })
+    }
+
+    fn fft_in_place(
+        &self,
+        evals: &mut [BlsScalar],
+        group_gen: BlsScalar,
+        inverse: bool,
+    ) {
+        fft_slice_in_place(evals, group_gen, inverse);
+    }
+}
+
+/// Insecure [`ComputeBackend`] attached to the mock public parameters created
+/// with [`PublicParameters::mock`](crate::prelude::PublicParameters::mock).
+///
+/// Since all of the mock powers are the same point, a commitment is just the
+/// sum of the coefficients of the polynomial times that point, which is
+/// computed with a single scalar multiplication.
+///
+/// **This backend only produces correct results for bases that are all
+/// equal, and the proofs created with it are not real proofs.**
+#[cfg(feature = "testing")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MockBackend;
+
+#[cfg(feature = "testing")]
+impl ComputeBackend for MockBackend {
+    fn msm(&self, bases: &[G1Affine], scalars: &[BlsScalar]) -> G1Projective {
+        match bases.first() {
+            Some(base) => {
+                base * scalars
+                    .iter()
+                    .fold(BlsScalar::zero(), |acc, scalar| acc + scalar)
+            }
+            None => G1Projective::identity(),
+        }
+    }
+
+    fn fft_in_place(
+        &self,
+        evals: &mut [BlsScalar],
+        group_gen: BlsScalar,
+        inverse: bool,
+    ) {
+        CpuBackend.fft_in_place(evals, group_gen, inverse);
+    }
+}
+
+/// Shared handle to the [`ComputeBackend`] attached to a
+/// [`CommitKey`](crate::commitment_scheme::kzg10::CommitKey).
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub(crate) struct BackendHandle(pub(crate) std::sync::Arc<dyn ComputeBackend>);
+
+#[cfg(feature = "std")]
+impl Default for BackendHandle {
+    fn default() -> Self {
+        BackendHandle(std::sync::Arc::new(CpuBackend))
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Debug for BackendHandle {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("ComputeBackend")
+    }
+}
+
+/// Compute a FFT using the `backend`.
+pub(crate) fn fft(
+    backend: &dyn ComputeBackend,
+    domain: &EvaluationDomain,
+    coeffs: &[BlsScalar],
+) -> Vec<BlsScalar> {
+    let mut coeffs = coeffs.to_vec();
+    coeffs.resize(domain.size(), BlsScalar::zero());
+    backend.fft_in_place(&mut coeffs, domain.group_gen, false);
+    coeffs
+}
+
+/// Compute an IFFT using the `backend`.
+pub(crate) fn ifft(
+    backend: &dyn ComputeBackend,
+    domain: &EvaluationDomain,
+    evals: &[BlsScalar],
+) -> Vec<BlsScalar> {
+    let mut evals = evals.to_vec();
+    evals.resize(domain.size(), BlsScalar::zero());
+    backend.fft_in_place(&mut evals, domain.group_gen, true);
+    evals
+}
+
+/// Compute a FFT over a coset of the domain using the `backend`.
+pub(crate) fn coset_fft(
+    backend: &dyn ComputeBackend,
+    domain: &EvaluationDomain,
+    coeffs: &[BlsScalar],
+) -> Vec<BlsScalar> {
+    let mut coeffs = coeffs.to_vec();
+    EvaluationDomain::distribute_powers(&mut coeffs, GENERATOR);
+    coeffs.resize(domain.size(), BlsScalar::zero());
+    backend.fft_in_place(&mut coeffs, domain.group_gen, false);
+    coeffs
+}
+
+/// Compute an IFFT over a coset of the domain using the `backend`, modifying
+/// the input vector in place.
+pub(crate) fn coset_ifft_in_place(
+    backend: &dyn ComputeBackend,
+    domain: &EvaluationDomain,
+    evals: &mut Vec<BlsScalar>,
+) {
+    evals.resize(domain.size(), BlsScalar::zero());
+    backend.fft_in_place(evals, domain.group_gen, true);
+    EvaluationDomain::distribute_powers(evals, domain.generator_inv);
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment_scheme::kzg10::PublicParameters;
+    use crate::constraint_system::helper::*;
+    use crate::proof_system::{Prover, Verifier};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use rand_core::OsRng;
+    use std::sync::Arc;
+
+    /// Backend counting the calls made to it before delegating them to the
+    /// [`CpuBackend`].
+    #[derive(Default)]
+    struct CountingBackend {
+        msm_calls: AtomicUsize,
+        fft_calls: AtomicUsize,
+    }
+
+    impl ComputeBackend for CountingBackend {
+        fn msm(
+            &self,
+            bases: &[G1Affine],
+            scalars: &[BlsScalar],
+        ) -> G1Projective {
+            self.msm_calls.fetch_add(1, Ordering::SeqCst);
+            CpuBackend.msm(bases, scalars)
+        }
+
+        fn fft_in_place(
+            &self,
+            evals: &mut [BlsScalar],
+            group_gen: BlsScalar,
+            inverse: bool,
+        ) {
+            self.fft_calls.fetch_add(1, Ordering::SeqCst);
+            CpuBackend.fft_in_place(evals, group_gen, inverse)
+        }
+    }
+
+    #[test]
+    fn test_counting_backend() {
+        let backend = Arc::new(CountingBackend::default());
+
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+        let ck = ck.with_backend(backend.clone());
+
+        let mut prover = Prover::new(b"backend");
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let msm_calls = backend.msm_calls.load(Ordering::SeqCst);
+        let fft_calls = backend.fft_calls.load(Ordering::SeqCst);
+        assert!(msm_calls > 0);
+        assert!(fft_calls > 0);
+
+        let mut verifier = Verifier::new(b"backend");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // Proofs created with the default backend are still valid
+        let mut prover = Prover::new(b"backend");
+        dummy_gadget(10, prover.mut_cs());
+        let cpu_ck = public_parameters.trim(2 * 20).unwrap().0;
+        prover.preprocess(&cpu_ck).unwrap();
+        let cpu_proof = prover.prove(&cpu_ck).unwrap();
+        assert!(verifier.verify(&cpu_proof, &vk, &public_inputs).is_ok());
+    }
+
+    #[test]
+    fn test_counting_backend_preprocess() {
+        let backend = Arc::new(CountingBackend::default());
+
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (cpu_ck, _) = public_parameters.trim(2 * 20).unwrap();
+        let ck = cpu_ck.clone().with_backend(backend.clone());
+
+        let mut verifier = Verifier::new(b"backend");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+
+        // The 11 selectors and the 4 sigmas are interpolated and committed
+        assert_eq!(backend.fft_calls.load(Ordering::SeqCst), 15);
+        assert_eq!(backend.msm_calls.load(Ordering::SeqCst), 15);
+
+        let mut cpu_verifier = Verifier::new(b"backend");
+        dummy_gadget(10, cpu_verifier.mut_cs());
+        cpu_verifier.preprocess(&cpu_ck).unwrap();
+        assert_eq!(verifier.verifier_key, cpu_verifier.verifier_key);
+
+        let mut prover = Prover::new(b"backend");
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+
+        // The prover also evaluates them, and the linear polynomial, over the
+        // coset of the domain of size 4n
+        assert_eq!(backend.fft_calls.load(Ordering::SeqCst), 15 + 31);
+        assert_eq!(backend.msm_calls.load(Ordering::SeqCst), 15 + 15);
+
+        let mut cpu_prover = Prover::new(b"backend");
+        dummy_gadget(10, cpu_prover.mut_cs());
+        cpu_prover.preprocess(&cpu_ck).unwrap();
+        assert_eq!(prover.prover_key, cpu_prover.prover_key);
+    }
+
+    #[cfg(feature = "constant_time")]
+    #[test]
+    fn test_constant_time_msm() {
+        use crate::util::random_scalar;
+        use dusk_bls12_381::multiscalar_mul::msm_variable_base;
+
+        let bases: Vec<G1Affine> = (0..32)
+            .map(|_| (G1Affine::generator() * random_scalar(&mut OsRng)).into())
+            .collect();
+        let scalars: Vec<BlsScalar> =
+            (0..20).map(|_| random_scalar(&mut OsRng)).collect();
+
+        assert_eq!(
+            CpuBackend.msm(&bases, &scalars),
+            msm_variable_base(&bases, &scalars)
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_mock_backend() {
+        let public_parameters = PublicParameters::mock(2 * 30).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"mock");
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::new(b"mock");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // Unsatisfied circuits are still rejected
+        let mut prover = Prover::new(b"mock");
+        prover.set_sanity_checks(false);
+        dummy_gadget(10, prover.mut_cs());
+        let one = prover.mut_cs().add_input(BlsScalar::one());
+        prover
+            .mut_cs()
+            .constrain_to_constant(one, BlsScalar::zero(), None);
+        prover.preprocess(&ck).unwrap();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::new(b"mock");
+        dummy_gadget(10, verifier.mut_cs());
+        let one = verifier.mut_cs().add_input(BlsScalar::one());
+        verifier
+            .mut_cs()
+            .constrain_to_constant(one, BlsScalar::zero(), None);
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_err());
+    }
+}
@@ -6,18 +6,28 @@
 
 //! Tools & traits for PLONK circuits
 
-use crate::commitment_scheme::kzg10::PublicParameters;
+use crate::commitment_scheme::kzg10::{
+    CommitKey, OpeningKey, PublicParameters,
+};
 use crate::constraint_system::StandardComposer;
 use crate::error::Error;
-use crate::proof_system::{Proof, Prover, ProverKey, Verifier, VerifierKey};
+use crate::proof_system::{
+    verify_aggregated, AggregatedProof, Proof, Prover, ProverKey, Verifier,
+    VerifierKey, VerifierScratch,
+};
+use crate::transcript::TranscriptLabels;
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
 use alloc::vec::Vec;
 #[cfg(feature = "canon")]
 use canonical_derive::Canon;
+use core::{cmp, convert::TryFrom};
 use dusk_bls12_381::BlsScalar;
 use dusk_bytes::{DeserializableSlice, Serializable, Write};
 use dusk_jubjub::{JubJubAffine, JubJubScalar};
+use merlin::Transcript;
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "canon", derive(Canon))]
 /// Structure that represents a PLONK Circuit Public Input converted into it's
 /// &\[[`BlsScalar`]\] repr.
@@ -29,6 +39,37 @@ impl From<BlsScalar> for PublicInputValue {
     }
 }
 
+impl From<u64> for PublicInputValue {
+    fn from(value: u64) -> Self {
+        BlsScalar::from(value).into()
+    }
+}
+
+/// Negative values are mapped to their additive inverse in the scalar field,
+/// so `-x` becomes `p - x` where `p` is the field modulus.
+impl From<i64> for PublicInputValue {
+    fn from(value: i64) -> Self {
+        let scalar = BlsScalar::from(value.unsigned_abs());
+        match value < 0 {
+            true => (-scalar).into(),
+            false => scalar.into(),
+        }
+    }
+}
+
+/// Converts the canonical little-endian encoding of a [`BlsScalar`].
+///
+/// Fails with [`Error::BlsScalarMalformed`] if the bytes encode an integer
+/// that is not smaller than the field modulus.
+impl TryFrom<[u8; 32]> for PublicInputValue {
+    type Error = Error;
+
+    fn try_from(bytes: [u8; 32]) -> Result<Self, Error> {
+        let scalar: Option<BlsScalar> = BlsScalar::from_bytes(&bytes).ok();
+        scalar.map(Self::from).ok_or(Error::BlsScalarMalformed)
+    }
+}
+
 impl From<JubJubScalar> for PublicInputValue {
     fn from(scalar: JubJubScalar) -> Self {
         Self(vec![scalar.into()])
@@ -41,7 +82,74 @@ impl From<JubJubAffine> for PublicInputValue {
     }
 }
 
-#[derive(Debug, Clone)]
+impl PublicInputValue {
+    /// Returns the bits of a [`JubJubScalar`] in the layout the ECC scalar
+    /// multiplication gadgets decompose their scalars into.
+    ///
+    /// The value holds 252 entries, each one being either zero or one, where
+    /// the entry `i` is the bit of weight `2^i` of the canonical encoding of
+    /// the scalar, i.e. the bits are ordered little-endian. Since the JubJub
+    /// scalar field modulus is smaller than `2^252`, no bit is dropped.
+    ///
+    /// A circuit exposing the bits of a scalar as public inputs, lowest bit
+    /// first, is verified by passing this value at their positions.
+    pub fn from_jubjub_scalar_bits(scalar: JubJubScalar) -> Self {
+        let bytes = scalar.to_bytes();
+        let bits = (0..252)
+            .map(|i| BlsScalar::from(((bytes[i / 8] >> (i % 8)) & 1) as u64))
+            .collect();
+
+        Self(bits)
+    }
+
+    /// Returns an iterator over the [`BlsScalar`]s of the value, in the order
+    /// of the positions they take in the circuit.
+    pub fn iter(&self) -> impl Iterator<Item = &BlsScalar> {
+        self.0.iter()
+    }
+
+    /// Returns the [`BlsScalar`]s of the value, in the order of the positions
+    /// they take in the circuit.
+    pub fn as_slice(&self) -> &[BlsScalar] {
+        &self.0
+    }
+}
+
+/// Structures holding the public inputs of a [`Circuit`], which can be
+/// converted into the [`PublicInputValue`]s used to verify its proofs.
+///
+/// Instead of implementing it by hand, it can be derived with the `derive`
+/// feature enabled. The derived implementation converts each field into a
+/// [`PublicInputValue`] in declaration order, so the prover and the verifier
+/// always agree on the order of the public inputs.
+///
+/// ```ignore
+/// use dusk_plonk::prelude::*;
+///
+/// #[derive(PublicInputs)]
+/// struct Inputs {
+///     sum: BlsScalar,
+///     point: JubJubAffine,
+/// }
+/// ```
+pub trait PublicInputs {
+    /// Calls `f` with the [`PublicInputValue`] of each one of the public
+    /// inputs, in order.
+    fn visit_public_input_values(&self, f: &mut dyn FnMut(PublicInputValue));
+
+    /// Returns the [`PublicInputValue`]s of the public inputs, in order.
+    fn to_public_input_values(&self) -> Vec<PublicInputValue> {
+        let mut values = Vec::new();
+        self.visit_public_input_values(&mut |value| values.push(value));
+        values
+    }
+}
+
+/// Derive macro generating an implementation of [`PublicInputs`].
+#[cfg(feature = "derive")]
+pub use dusk_plonk_derive::PublicInputs;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 /// Collection of structs/objects that the Verifier will use in order to
 /// de/serialize data needed for Circuit proof verification.
 /// This structure can be seen as a link between the [`Circuit`] public input
@@ -98,6 +206,7 @@ impl VerifierData {
         for _ in 0..pos_num {
             pi_pos.push(u32::from_reader(&mut buf)? as usize);
         }
+        check_pi_positions(&pi_pos)?;
 
         Ok(Self { key, pi_pos })
     }
@@ -141,32 +250,26 @@ impl VerifierData {
 ///         let a = composer.add_input(self.a);
 ///         let b = composer.add_input(self.b);
 ///         // Make first constraint a + b = c
-///         composer.poly_gate(
-///             a,
-///             b,
-///             zero,
-///             BlsScalar::zero(),
-///             BlsScalar::one(),
-///             BlsScalar::one(),
-///             BlsScalar::zero(),
-///             BlsScalar::zero(),
-///             Some(-self.c),
-///         );
+///         ArithmeticGate::new()
+///             .left(a)
+///             .right(b)
+///             .output(zero)
+///             .q_l(BlsScalar::one())
+///             .q_r(BlsScalar::one())
+///             .pi(Some(-self.c))
+///             .apply(composer);
 ///         // Check that a and b are in range
 ///         composer.range_gate(a, 1 << 6);
 ///         composer.range_gate(b, 1 << 5);
 ///         // Make second constraint a * b = d
-///         composer.poly_gate(
-///             a,
-///             b,
-///             zero,
-///             BlsScalar::one(),
-///             BlsScalar::zero(),
-///             BlsScalar::zero(),
-///             BlsScalar::one(),
-///             BlsScalar::zero(),
-///             Some(-self.d),
-///         );
+///         ArithmeticGate::new()
+///             .left(a)
+///             .right(b)
+///             .output(zero)
+///             .q_m(BlsScalar::one())
+///             .q_o(BlsScalar::one())
+///             .pi(Some(-self.d))
+///             .apply(composer);
 ///
 ///         let e = composer.add_input(self.e.into());
 ///         let scalar_mul_result =
@@ -230,37 +333,27 @@ where
 {
     /// Circuit identifier associated constant.
     const CIRCUIT_ID: [u8; 32];
+    /// Maximum number of gates of the circuit. Compiling a circuit with more
+    /// gates fails with [`Error::CircuitTooLarge`], which allows to catch
+    /// size regressions in tests. There's no limit by default.
+    const MAX_GATES: usize = usize::MAX;
     /// Gadget implementation used to fill the composer.
     fn gadget(&mut self, composer: &mut StandardComposer) -> Result<(), Error>;
     /// Compiles the circuit by using a function that returns a `Result`
     /// with the `ProverKey`, `VerifierKey` and the circuit size.
+    ///
+    /// Compilation is deterministic: the keys only depend on the circuit
+    /// description and the `PublicParameters`, so compiling the same circuit
+    /// with the same parameters always produces the same key bytes,
+    /// regardless of the witness values the circuit holds.
     fn compile(
         &mut self,
         pub_params: &PublicParameters,
     ) -> Result<(ProverKey, VerifierData), Error> {
         // Setup PublicParams
+        timing_phase!(SRS_TRIMMING);
         let (ck, _) = pub_params.trim(self.padded_circuit_size())?;
-        // Generate & save `ProverKey` with some random values.
-        let mut prover = Prover::new(b"CircuitCompilation");
-        self.gadget(prover.mut_cs())?;
-        let pi_pos = prover.mut_cs().pi_positions();
-        prover.preprocess(&ck)?;
-
-        // Generate & save `VerifierKey` with some random values.
-        let mut verifier = Verifier::new(b"CircuitCompilation");
-        self.gadget(verifier.mut_cs())?;
-        verifier.preprocess(&ck)?;
-        Ok((
-            prover
-                .prover_key
-                .expect("Unexpected error. Missing ProverKey in compilation"),
-            VerifierData::new(
-                verifier.verifier_key.expect(
-                    "Unexpected error. Missing VerifierKey in compilation",
-                ),
-                pi_pos,
-            ),
-        ))
+        compile_circuit(self, &ck)
     }
 
     /// Generates a proof using the provided `CircuitInputs` & `ProverKey`
@@ -271,20 +364,262 @@ where
         prover_key: &ProverKey,
         transcript_init: &'static [u8],
     ) -> Result<Proof, Error> {
+        timing_phase!(SRS_TRIMMING);
         let (ck, _) = pub_params.trim(self.padded_circuit_size())?;
-        // New Prover instance
-        let mut prover = Prover::new(transcript_init);
-        // Fill witnesses for Prover
-        self.gadget(prover.mut_cs())?;
-        // Add ProverKey to Prover
-        prover.prover_key = Some(prover_key.clone());
-        prover.prove(&ck)
+        prove_circuit(self, &ck, prover_key, transcript_init)
+    }
+
+    /// Generates a proof as [`Circuit::gen_proof`] does, using only the
+    /// [`CommitKey`] of the [`PublicParameters`] used to compile the circuit,
+    /// so provers don't need to hold the whole CRS.
+    ///
+    /// The `commit_key` is expected to be trimmed to at least the
+    /// [`Circuit::padded_circuit_size`], as the one returned by
+    /// [`PublicParameters::trim`], which can be stored with
+    /// [`CommitKey::to_var_bytes`].
+    fn gen_proof_with_commit_key(
+        &mut self,
+        commit_key: &CommitKey,
+        prover_key: &ProverKey,
+        transcript_init: &'static [u8],
+    ) -> Result<Proof, Error> {
+        prove_circuit(self, commit_key, prover_key, transcript_init)
+    }
+
+    /// Verifies a proof of the circuit using only the serialized
+    /// [`VerifierKey`] of the circuit and [`OpeningKey`] of the
+    /// [`PublicParameters`] used to compile it, so light clients don't need
+    /// to hold the whole CRS.
+    ///
+    /// The bytes are the ones of [`VerifierKey::to_bytes`] and
+    /// [`OpeningKey::to_bytes`], which are of a constant size. The rest of
+    /// the arguments are the same as for [`verify_proof`].
+    fn verify_proof_light(
+        verifier_key_bytes: &[u8],
+        opening_key_bytes: &[u8],
+        proof: &Proof,
+        pub_inputs_values: &[PublicInputValue],
+        pub_inputs_positions: &[usize],
+        transcript_init: &'static [u8],
+    ) -> Result<(), Error> {
+        let verifier_key = VerifierKey::from_slice(verifier_key_bytes)?;
+        let opening_key = OpeningKey::from_slice(opening_key_bytes)?;
+
+        PlonkVerifier::with_opening_key(
+            verifier_key,
+            opening_key,
+            transcript_init,
+        )
+        .verify(proof, pub_inputs_values, pub_inputs_positions)
     }
 
     /// Returns the Circuit size padded to the next power of two.
+    ///
+    /// It is the gate budget of the circuit when compiling and proving it,
+    /// which fail with [`Error::CircuitTooLarge`] if the
+    /// [`Circuit::gadget`] adds more gates.
     fn padded_circuit_size(&self) -> usize;
 }
 
+/// Object-safe counterpart of [`Circuit`], implemented for every circuit, so
+/// circuits of different types can be compiled together with
+/// [`compile_many_dyn`].
+///
+/// [`Circuit`] can't be made into a trait object because of its associated
+/// constants and of the [`Sized`] bound its provided methods rely on.
+pub trait DynCircuit {
+    /// Returns the [`Circuit::padded_circuit_size`] of the circuit.
+    fn padded_size(&self) -> usize;
+
+    /// Compiles the circuit as [`Circuit::compile`] does, with the
+    /// `commit_key` trimmed to at least its [`DynCircuit::padded_size`].
+    fn compile_with_commit_key(
+        &mut self,
+        commit_key: &CommitKey,
+    ) -> Result<(ProverKey, VerifierData), Error>;
+}
+
+impl<C: Circuit> DynCircuit for C {
+    fn padded_size(&self) -> usize {
+        self.padded_circuit_size()
+    }
+
+    fn compile_with_commit_key(
+        &mut self,
+        commit_key: &CommitKey,
+    ) -> Result<(ProverKey, VerifierData), Error> {
+        compile_circuit(self, commit_key)
+    }
+}
+
+/// Returns the gate budget of the `circuit`, the smallest of its
+/// [`Circuit::MAX_GATES`] and [`Circuit::padded_circuit_size`].
+fn gate_budget<C: Circuit>(circuit: &C) -> usize {
+    cmp::min(C::MAX_GATES, circuit.padded_circuit_size())
+}
+
+/// Compiles the `circuit` with the `commit_key`, which is expected to be
+/// trimmed to at least the [`Circuit::padded_circuit_size`].
+fn compile_circuit<C: Circuit>(
+    circuit: &mut C,
+    commit_key: &CommitKey,
+) -> Result<(ProverKey, VerifierData), Error> {
+    trace_enter!(_compile, "compile", size = circuit.padded_circuit_size());
+    // Generate & save `ProverKey` with the current circuit values.
+    let mut prover = Prover::new(b"CircuitCompilation");
+    prover.mut_cs().set_max_gates(gate_budget(circuit));
+    timing_phase!(WITNESS_ASSIGNMENT);
+    circuit.gadget(prover.mut_cs())?;
+    prover.cs.check_gate_budget()?;
+    let pi_pos = prover.mut_cs().pi_positions();
+    check_pi_positions(&pi_pos)?;
+    prover.preprocess(commit_key)?;
+
+    // Generate & save `VerifierKey` with the current circuit values.
+    let mut verifier = Verifier::new(b"CircuitCompilation");
+    timing_phase!(WITNESS_ASSIGNMENT);
+    circuit.gadget(verifier.mut_cs())?;
+    verifier.preprocess(commit_key)?;
+    Ok((
+        prover
+            .prover_key
+            .expect("Unexpected error. Missing ProverKey in compilation"),
+        VerifierData::new(
+            verifier
+                .verifier_key
+                .expect("Unexpected error. Missing VerifierKey in compilation"),
+            pi_pos,
+        ),
+    ))
+}
+
+/// Proves the `circuit` with the `prover_key`, which is borrowed instead of
+/// being cloned into the [`Prover`].
+fn prove_circuit<C: Circuit>(
+    circuit: &mut C,
+    commit_key: &CommitKey,
+    prover_key: &ProverKey,
+    transcript_init: &'static [u8],
+) -> Result<Proof, Error> {
+    // New Prover instance
+    let mut prover = Prover::new(transcript_init);
+    prover.mut_cs().set_max_gates(gate_budget(circuit));
+    // Fill witnesses for Prover
+    timing_phase!(WITNESS_ASSIGNMENT);
+    circuit.gadget(prover.mut_cs())?;
+    prover.cs.check_gate_budget()?;
+    prover.prove_with_preprocessed(commit_key, prover_key)
+}
+
+/// Generates the proofs of the `circuits`, in the same order, on a pool of
+/// `threads` threads, as [`Circuit::gen_proof`] does for each of them.
+///
+/// The `pub_params` are trimmed once for the whole batch, and the resulting
+/// commit key and the `prover_key` are shared by the threads instead of
+/// being cloned. A `threads` count of zero uses one thread per CPU.
+///
+/// Returns the first error met by any of the proofs, or
+/// [`Error::ThreadPoolCreation`] if the thread pool can't be created.
+#[cfg(feature = "std")]
+pub fn prove_batch<C: Circuit + Send>(
+    pub_params: &PublicParameters,
+    prover_key: &ProverKey,
+    circuits: Vec<C>,
+    threads: usize,
+    transcript_init: &'static [u8],
+) -> Result<Vec<Proof>, Error> {
+    use rayon::prelude::*;
+
+    let size = match circuits.iter().map(C::padded_circuit_size).max() {
+        Some(size) => size,
+        None => return Ok(Vec::new()),
+    };
+    let (ck, _) = pub_params.trim(size)?;
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|_| Error::ThreadPoolCreation)?;
+
+    pool.install(|| {
+        circuits
+            .into_par_iter()
+            .map(|mut circuit| {
+                prove_circuit(&mut circuit, &ck, prover_key, transcript_init)
+            })
+            .collect()
+    })
+}
+
+/// Compiles the `circuits` concurrently on the rayon thread pool, as
+/// [`Circuit::compile`] does for each of them, returning their keys in the
+/// same order.
+///
+/// The `pub_params` are trimmed once to the largest
+/// [`Circuit::padded_circuit_size`], and the resulting commit key is shared
+/// by the threads. Since compilation is deterministic, the keys are the same
+/// as the ones of [`Circuit::compile`].
+///
+/// Returns the first error met by any of the compilations.
+///
+/// The circuits are all of the same type, see [`compile_many_dyn`] to
+/// compile circuits of different types.
+#[cfg(feature = "std")]
+pub fn compile_many<C: Circuit + Send>(
+    pub_params: &PublicParameters,
+    circuits: &mut [C],
+) -> Result<Vec<(ProverKey, VerifierData)>, Error> {
+    compile_concurrently(
+        pub_params,
+        circuits,
+        |circuit| circuit.padded_circuit_size(),
+        |circuit, ck| compile_circuit(circuit, ck),
+    )
+}
+
+/// Compiles the `circuits`, which can be of different types, as
+/// [`compile_many`] does.
+#[cfg(feature = "std")]
+pub fn compile_many_dyn(
+    pub_params: &PublicParameters,
+    circuits: &mut [Box<dyn DynCircuit + Send>],
+) -> Result<Vec<(ProverKey, VerifierData)>, Error> {
+    compile_concurrently(
+        pub_params,
+        circuits,
+        |circuit| circuit.padded_size(),
+        |circuit, ck| circuit.compile_with_commit_key(ck),
+    )
+}
+
+/// Compiles the `circuits` with `compile` on the rayon thread pool, with the
+/// `pub_params` trimmed once to the largest of their `padded_size`s.
+#[cfg(feature = "std")]
+fn compile_concurrently<T, F>(
+    pub_params: &PublicParameters,
+    circuits: &mut [T],
+    padded_size: impl Fn(&T) -> usize,
+    compile: F,
+) -> Result<Vec<(ProverKey, VerifierData)>, Error>
+where
+    T: Send,
+    F: Fn(&mut T, &CommitKey) -> Result<(ProverKey, VerifierData), Error>
+        + Sync,
+{
+    use rayon::prelude::*;
+
+    let size = match circuits.iter().map(padded_size).max() {
+        Some(size) => size,
+        None => return Ok(Vec::new()),
+    };
+    let (ck, _) = pub_params.trim(size)?;
+
+    circuits
+        .par_iter_mut()
+        .map(|circuit| compile(circuit, &ck))
+        .collect()
+}
+
 /// Verifies a proof using the provided `CircuitInputs` & `VerifierKey`
 /// instances.
 pub fn verify_proof(
@@ -295,18 +630,225 @@ pub fn verify_proof(
     pub_inputs_positions: &[usize],
     transcript_init: &'static [u8],
 ) -> Result<(), Error> {
-    let mut verifier = Verifier::new(transcript_init);
-    verifier.verifier_key = Some(*verifier_key);
-    verifier.verify(
+    PlonkVerifier::new(*verifier_key, pub_params, transcript_init).verify(
+        proof,
+        pub_inputs_values,
+        pub_inputs_positions,
+    )
+}
+
+/// Verifies a proof as [`verify_proof`] does, with a `verifier_key` fetched
+/// from an untrusted source, checking first that it's the key identified by
+/// the `digest` cached by the caller.
+///
+/// The `digest` is the [`VerifierKey::fingerprint`] of the expected key, so
+/// light clients only have to store 32 bytes per circuit. Fails with
+/// [`Error::VerifierKeyDigestMismatch`] if the fingerprint of the
+/// `verifier_key` is not the `digest`.
+pub fn verify_proof_with_digest(
+    pub_params: &PublicParameters,
+    digest: &[u8; 32],
+    verifier_key: &VerifierKey,
+    proof: &Proof,
+    pub_inputs_values: &[PublicInputValue],
+    pub_inputs_positions: &[usize],
+    transcript_init: &'static [u8],
+) -> Result<(), Error> {
+    if &verifier_key.fingerprint() != digest {
+        return Err(Error::VerifierKeyDigestMismatch);
+    }
+
+    verify_proof(
+        pub_params,
+        verifier_key,
         proof,
-        pub_params.opening_key(),
-        build_pi(
+        pub_inputs_values,
+        pub_inputs_positions,
+        transcript_init,
+    )
+}
+
+/// Standalone verifier of the proofs of a circuit, which only requires its
+/// [`VerifierKey`] and the [`OpeningKey`] of the [`PublicParameters`] used to
+/// compile it.
+///
+/// Unlike the [`Verifier`], it doesn't need the circuit to be built, which
+/// makes it suitable for light clients or offline verification where the
+/// [`Circuit`] type is not available.
+#[derive(Debug, Clone)]
+pub struct PlonkVerifier {
+    verifier_key: VerifierKey,
+    opening_key: OpeningKey,
+    transcript_init: &'static [u8],
+}
+
+impl PlonkVerifier {
+    /// Creates a new `PlonkVerifier` for the circuit of the `verifier_key`.
+    ///
+    /// The `transcript_init` must be the same label used to generate the
+    /// proofs, see [`Circuit::gen_proof`].
+    pub fn new(
+        verifier_key: VerifierKey,
+        pub_params: &PublicParameters,
+        transcript_init: &'static [u8],
+    ) -> Self {
+        Self {
+            verifier_key,
+            opening_key: pub_params.opening_key().clone(),
+            transcript_init,
+        }
+    }
+
+    /// Creates a new `PlonkVerifier` for the circuit of the `verifier_key`
+    /// from the [`OpeningKey`] of the [`PublicParameters`] used to compile
+    /// it, which is all that's needed from them to verify a proof.
+    pub fn with_opening_key(
+        verifier_key: VerifierKey,
+        opening_key: OpeningKey,
+        transcript_init: &'static [u8],
+    ) -> Self {
+        Self {
+            verifier_key,
+            opening_key,
+            transcript_init,
+        }
+    }
+
+    /// Returns the [`VerifierKey`] of the circuit.
+    pub const fn key(&self) -> &VerifierKey {
+        &self.verifier_key
+    }
+
+    /// Verifies the `proof` against the public inputs values, which are
+    /// placed at the given positions of the circuit.
+    pub fn verify(
+        &self,
+        proof: &Proof,
+        pub_inputs_values: &[PublicInputValue],
+        pub_inputs_positions: &[usize],
+    ) -> Result<(), Error> {
+        let mut verifier = Verifier::new(self.transcript_init);
+        verifier.verifier_key = Some(self.verifier_key);
+        verifier.verify(
+            proof,
+            &self.opening_key,
+            build_pi(
+                pub_inputs_values,
+                pub_inputs_positions,
+                self.verifier_key.padded_circuit_size(),
+            )?
+            .as_slice(),
+        )
+    }
+
+    /// Verifies all the proofs of the `aggregated` proof at once, each one
+    /// against the public inputs values at the same index of
+    /// `pub_inputs_values`, which are placed at the given positions of the
+    /// circuit.
+    ///
+    /// See [`verify_aggregated`](crate::proof_system::verify_aggregated).
+    pub fn verify_aggregated(
+        &self,
+        aggregated: &AggregatedProof,
+        pub_inputs_values: &[&[PublicInputValue]],
+        pub_inputs_positions: &[usize],
+    ) -> Result<(), Error> {
+        let pub_inputs: Vec<Vec<BlsScalar>> = pub_inputs_values
+            .iter()
+            .map(|values| {
+                build_pi(
+                    values,
+                    pub_inputs_positions,
+                    self.verifier_key.padded_circuit_size(),
+                )
+            })
+            .collect::<Result<_, _>>()?;
+        let pub_inputs: Vec<&[BlsScalar]> =
+            pub_inputs.iter().map(|pi| pi.as_slice()).collect();
+
+        verify_aggregated(
+            aggregated,
+            &self.verifier_key,
+            &self.opening_key,
+            &Transcript::new(self.transcript_init),
+            &pub_inputs,
+        )
+    }
+}
+
+/// Reusable context to verify a stream of proofs, keeping the buffers used
+/// by a verification to reuse them in the following ones.
+///
+/// The dense public inputs vector, which has the size of the circuit, and
+/// the buffers used to evaluate the public inputs polynomial and to compute
+/// the linearisation commitment are only allocated when they need to grow.
+///
+/// The context holds no state that depends on the proofs it verified, so the
+/// result of [`VerifierContext::verify_into`] is the same as the one of
+/// [`verify_proof`].
+#[derive(Debug, Clone)]
+pub struct VerifierContext {
+    opening_key: OpeningKey,
+    transcript_init: &'static [u8],
+    pub_inputs: Vec<BlsScalar>,
+    scratch: VerifierScratch,
+}
+
+impl VerifierContext {
+    /// Creates a new `VerifierContext` for proofs generated with the
+    /// `pub_params` and the `transcript_init` label.
+    pub fn new(
+        pub_params: &PublicParameters,
+        transcript_init: &'static [u8],
+    ) -> Self {
+        Self {
+            opening_key: pub_params.opening_key().clone(),
+            transcript_init,
+            pub_inputs: Vec::new(),
+            scratch: VerifierScratch::default(),
+        }
+    }
+
+    /// Verifies the `proof` of the circuit of the `verifier_key` against
+    /// the public inputs values, which are placed at the given positions of
+    /// the circuit, reusing the buffers of the context.
+    pub fn verify_into(
+        &mut self,
+        proof: &Proof,
+        verifier_key: &VerifierKey,
+        pub_inputs_values: &[PublicInputValue],
+        pub_inputs_positions: &[usize],
+    ) -> Result<(), Error> {
+        fill_pi(
+            &mut self.pub_inputs,
             pub_inputs_values,
             pub_inputs_positions,
             verifier_key.padded_circuit_size(),
+        )?;
+
+        proof.verify_with_scratch(
+            verifier_key,
+            &mut Transcript::new(self.transcript_init),
+            &self.opening_key,
+            &self.pub_inputs,
+            &TranscriptLabels::DEFAULT,
+            &mut self.scratch,
         )
-        .as_slice(),
-    )
+    }
+}
+
+/// Checks that no public input position appears more than once, since the
+/// values placed at a repeated position would overwrite each other.
+pub(crate) fn check_pi_positions(pub_input_pos: &[usize]) -> Result<(), Error> {
+    let mut sorted = pub_input_pos.to_vec();
+    sorted.sort_unstable();
+
+    match sorted.windows(2).find(|pair| pair[0] == pair[1]) {
+        Some(pair) => {
+            Err(Error::DuplicatePublicInputPosition { position: pair[0] })
+        }
+        None => Ok(()),
+    }
 }
 
 /// Build PI vector for Proof verifications.
@@ -314,17 +856,34 @@ fn build_pi(
     pub_input_values: &[PublicInputValue],
     pub_input_pos: &[usize],
     trim_size: usize,
-) -> Vec<BlsScalar> {
-    let mut pi = vec![BlsScalar::zero(); trim_size];
+) -> Result<Vec<BlsScalar>, Error> {
+    let mut pi = Vec::new();
+    fill_pi(&mut pi, pub_input_values, pub_input_pos, trim_size)?;
+
+    Ok(pi)
+}
+
+/// Fills the `pi` buffer with the PI vector for Proof verifications, see
+/// [`build_pi`].
+fn fill_pi(
+    pi: &mut Vec<BlsScalar>,
+    pub_input_values: &[PublicInputValue],
+    pub_input_pos: &[usize],
+    trim_size: usize,
+) -> Result<(), Error> {
+    check_pi_positions(pub_input_pos)?;
+
+    pi.clear();
+    pi.resize(trim_size, BlsScalar::zero());
     pub_input_values
         .iter()
-        .map(|pub_input| pub_input.0.clone())
-        .flatten()
+        .flat_map(PublicInputValue::iter)
         .zip(pub_input_pos.iter().copied())
         .for_each(|(value, pos)| {
             pi[pos] = -value;
         });
-    pi
+
+    Ok(())
 }
 
 #[cfg(feature = "std")]
@@ -333,71 +892,74 @@ mod tests {
     use super::*;
     use crate::constraint_system::StandardComposer;
     use crate::proof_system::ProverKey;
+    use crate::test_circuits::TestCircuit;
 
-    // Implements a circuit that checks:
-    // 1) a + b = c where C is a PI
-    // 2) a <= 2^6
-    // 3) b <= 2^5
-    // 4) a * b = d where D is a PI
-    // 5) JubJub::GENERATOR * e(JubJubScalar) = f where F is a PI
+    // Implements a circuit that checks `s * P = R` where the bits of `s`
+    // and `R` are PI
     #[derive(Debug, Default)]
-    pub struct TestCircuit {
-        a: BlsScalar,
-        b: BlsScalar,
-        c: BlsScalar,
-        d: BlsScalar,
-        e: JubJubScalar,
-        f: JubJubAffine,
+    struct ScalarBitsCircuit {
+        scalar: JubJubScalar,
+        point: JubJubAffine,
+        result: JubJubAffine,
     }
 
-    impl Circuit for TestCircuit {
-        const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+    impl Circuit for ScalarBitsCircuit {
+        const CIRCUIT_ID: [u8; 32] = [0xbb; 32];
         fn gadget(
             &mut self,
             composer: &mut StandardComposer,
         ) -> Result<(), Error> {
-            let a = composer.add_input(self.a);
-            let b = composer.add_input(self.b);
-            // Make first constraint a + b = c
-            composer.poly_gate(
-                a,
-                b,
-                composer.zero_var,
-                BlsScalar::zero(),
-                BlsScalar::one(),
-                BlsScalar::one(),
-                BlsScalar::zero(),
-                BlsScalar::zero(),
-                Some(-self.c),
-            );
-            // Check that a and b are in range
-            composer.range_gate(a, 1 << 6);
-            composer.range_gate(b, 1 << 5);
-            // Make second constraint a * b = d
-            composer.poly_gate(
-                a,
-                b,
-                composer.zero_var,
-                BlsScalar::one(),
-                BlsScalar::zero(),
-                BlsScalar::zero(),
-                BlsScalar::one(),
-                BlsScalar::zero(),
-                Some(-self.d),
-            );
-
-            let e = composer.add_input(self.e.into());
-            let scalar_mul_result = composer
-                .fixed_base_scalar_mul(e, dusk_jubjub::GENERATOR_EXTENDED);
-            // Apply the constrain
-            composer.assert_equal_public_point(scalar_mul_result, self.f);
+            let bytes = self.scalar.to_bytes();
+            let scalar = (0..252).fold(composer.zero_var(), |acc, i| {
+                let bit =
+                    BlsScalar::from(((bytes[i / 8] >> (i % 8)) & 1) as u64);
+                let bit_var = composer.add_input(bit);
+                composer.constrain_to_constant(
+                    bit_var,
+                    BlsScalar::zero(),
+                    Some(-bit),
+                );
+
+                composer.add(
+                    (BlsScalar::pow_of_2(i as u64), bit_var),
+                    (BlsScalar::one(), acc),
+                    BlsScalar::zero(),
+                    None,
+                )
+            });
+
+            let point = composer.add_affine(self.point);
+            let result = composer.variable_base_scalar_mul(scalar, point);
+            composer.assert_equal_public_point(result, self.result);
             Ok(())
         }
         fn padded_circuit_size(&self) -> usize {
-            1 << 11
+            1 << 13
         }
     }
 
+    /// Keys, proof and public inputs of [`TestCircuit::valid`].
+    type TestCircuitProof =
+        (ProverKey, VerifierData, Proof, [PublicInputValue; 3]);
+
+    /// Compiles the [`TestCircuit`] and proves [`TestCircuit::valid`] with the
+    /// `b"Test"` transcript.
+    fn test_circuit_proof(
+        pp: &PublicParameters,
+    ) -> Result<TestCircuitProof, Error> {
+        let (pk, vd) = TestCircuit::default().compile(pp)?;
+
+        let mut circuit = TestCircuit::valid();
+        let proof = circuit.gen_proof(pp, &pk, b"Test")?;
+        let public_inputs = [
+            PublicInputValue::from(circuit.c),
+            PublicInputValue::from(circuit.d),
+            PublicInputValue::from(circuit.f),
+        ];
+
+        Ok((pk, vd, proof, public_inputs))
+    }
+
     #[test]
     fn test_full() -> Result<(), Error> {
         use rand_core::OsRng;
@@ -423,11 +985,9 @@ mod tests {
         let pp =
             unsafe { PublicParameters::from_slice_unchecked(pp.as_slice()) };
 
-        // Initialize the circuit
-        let mut circuit = TestCircuit::default();
-
-        // Compile the circuit
-        let (pk_p, og_verifier_data) = circuit.compile(&pp)?;
+        // Compile the circuit and prove it
+        let (pk_p, og_verifier_data, proof, public_inputs) =
+            test_circuit_proof(&pp)?;
 
         // Write the keys
         File::create(&pk_path)
@@ -452,39 +1012,963 @@ mod tests {
         assert_eq!(og_verifier_data.key(), verif_data.key());
         assert_eq!(og_verifier_data.pi_pos(), verif_data.pi_pos());
 
-        // Prover POV
-        let proof = {
+        // Verify without the circuit
+        let verifier = PlonkVerifier::new(*verif_data.key(), &pp, b"Test");
+        verifier.verify(&proof, &public_inputs, verif_data.pi_pos())?;
+        assert!(verifier
+            .verify(&proof, &public_inputs[1..], verif_data.pi_pos())
+            .is_err());
+
+        verify_proof(
+            &pp,
+            &verif_data.key(),
+            &proof,
+            &public_inputs,
+            &verif_data.pi_pos(),
+            b"Test",
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn test_public_parameters_from_mmap() -> Result<(), Error> {
+        use rand_core::OsRng;
+        use std::fs::File;
+        use std::io::Write;
+        use tempdir::TempDir;
+
+        let tmp = TempDir::new("plonk-pp-mmap").expect("IO error");
+        let pp_path = tmp.path().join("pp");
+        let raw_path = tmp.path().join("pp_raw");
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        File::create(&pp_path)
+            .and_then(|mut f| f.write_all(&pp.to_var_bytes()))
+            .expect("IO error");
+        File::create(&raw_path)
+            .and_then(|mut f| f.write_all(&pp.to_raw_var_bytes()))
+            .expect("IO error");
+
+        let pp_mmap = PublicParameters::from_mmap(&pp_path)?;
+        let raw_mmap =
+            unsafe { PublicParameters::from_mmap_unchecked(&raw_path)? };
+        assert_eq!(pp_mmap.to_var_bytes(), pp.to_var_bytes());
+        assert_eq!(raw_mmap.to_var_bytes(), pp.to_var_bytes());
+
+        let (_, vd, proof, public_inputs) = test_circuit_proof(&raw_mmap)?;
+        verify_proof(
+            &pp_mmap,
+            vd.key(),
+            &proof,
+            &public_inputs,
+            vd.pi_pos(),
+            b"Test",
+        )?;
+
+        // A truncated file is rejected by both loaders
+        let bytes = pp.to_raw_var_bytes();
+        File::create(&raw_path)
+            .and_then(|mut f| f.write_all(&bytes[..bytes.len() - 1]))
+            .expect("IO error");
+        assert!(matches!(
+            unsafe { PublicParameters::from_mmap_unchecked(&raw_path) },
+            Err(Error::NotEnoughBytes)
+        ));
+        assert!(
+            PublicParameters::from_mmap(&tmp.path().join("missing")).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deterministic_compilation() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+
+        let (pk, vd) = TestCircuit::default().compile(&pp)?;
+        let (pk_again, vd_again) = TestCircuit::default().compile(&pp)?;
+        assert_eq!(pk.to_var_bytes(), pk_again.to_var_bytes());
+        assert_eq!(vd.to_var_bytes(), vd_again.to_var_bytes());
+
+        // The witness values don't leak into the keys
+        let (pk_witness, vd_witness) = TestCircuit::valid().compile(&pp)?;
+        assert_eq!(pk.to_var_bytes(), pk_witness.to_var_bytes());
+        assert_eq!(vd.to_var_bytes(), vd_witness.to_var_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregated_verification() -> Result<(), Error> {
+        use crate::proof_system::aggregate;
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let (pk, vd) = TestCircuit::default().compile(&pp)?;
+        let verifier = PlonkVerifier::new(*vd.key(), &pp, b"Test");
+
+        let e = JubJubScalar::from(2u64);
+        let f = JubJubAffine::from(dusk_jubjub::GENERATOR_EXTENDED * e);
+
+        let inputs = [(20u64, 5u64), (3, 4), (10, 10)];
+        let mut proofs = Vec::new();
+        let mut values = Vec::new();
+        for (a, b) in inputs.iter() {
             let mut circuit = TestCircuit {
-                a: BlsScalar::from(20u64),
-                b: BlsScalar::from(5u64),
-                c: BlsScalar::from(25u64),
-                d: BlsScalar::from(100u64),
-                e: JubJubScalar::from(2u64),
-                f: JubJubAffine::from(
-                    dusk_jubjub::GENERATOR_EXTENDED * JubJubScalar::from(2u64),
-                ),
+                a: BlsScalar::from(*a),
+                b: BlsScalar::from(*b),
+                c: BlsScalar::from(a + b),
+                d: BlsScalar::from(a * b),
+                e,
+                f,
             };
+            proofs.push(circuit.gen_proof(&pp, &pk, b"Test")?);
+            values.push(vec![
+                PublicInputValue::from(a + b),
+                PublicInputValue::from(a * b),
+                PublicInputValue::from(f),
+            ]);
+        }
+        let values: Vec<&[PublicInputValue]> =
+            values.iter().map(|v| v.as_slice()).collect();
+
+        let aggregated = aggregate(&proofs);
+        assert_eq!(aggregated.proofs(), proofs.as_slice());
+        verifier.verify_aggregated(&aggregated, &values, vd.pi_pos())?;
 
-            circuit.gen_proof(&pp, &pk, b"Test")
-        }?;
+        // Should fail since the public inputs of the second proof are the
+        // ones of the third
+        let mut tampered = values.clone();
+        tampered[1] = values[2];
+        assert!(verifier
+            .verify_aggregated(&aggregated, &tampered, vd.pi_pos())
+            .is_err());
 
-        // Verifier POV
-        let public_inputs: Vec<PublicInputValue> = vec![
-            BlsScalar::from(25u64).into(),
-            BlsScalar::from(100u64).into(),
-            JubJubAffine::from(
-                dusk_jubjub::GENERATOR_EXTENDED * JubJubScalar::from(2u64),
+        // Should fail since the public inputs don't match the proofs
+        assert!(verifier
+            .verify_aggregated(&aggregated, &values[..2], vd.pi_pos())
+            .is_err());
+        assert!(verifier
+            .verify_aggregated(&aggregate(&[]), &[], vd.pi_pos())
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prove_batch() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        // The keys and the provers can be shared and sent across threads
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ProverKey>();
+        assert_send_sync::<CommitKey>();
+        assert_send_sync::<PublicParameters>();
+        assert_send_sync::<Prover>();
+        assert_send_sync::<StandardComposer>();
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let (pk, vd) = TestCircuit::default().compile(&pp)?;
+
+        let (circuits, public_inputs): (Vec<_>, Vec<_>) = (1..17u64)
+            .map(|i| {
+                let e = JubJubScalar::from(i);
+                let f = JubJubAffine::from(dusk_jubjub::GENERATOR_EXTENDED * e);
+                let circuit = TestCircuit {
+                    a: BlsScalar::from(20u64),
+                    b: BlsScalar::from(i),
+                    c: BlsScalar::from(20 + i),
+                    d: BlsScalar::from(20 * i),
+                    e,
+                    f,
+                };
+                let public_inputs = [
+                    PublicInputValue::from(20 + i),
+                    PublicInputValue::from(20 * i),
+                    PublicInputValue::from(f),
+                ];
+
+                (circuit, public_inputs)
+            })
+            .unzip();
+
+        let proofs = prove_batch(&pp, &pk, circuits, 4, b"Test")?;
+        assert_eq!(proofs.len(), 16);
+
+        // The proofs are in the order of the circuits
+        for (proof, public_inputs) in proofs.iter().zip(public_inputs.iter()) {
+            verify_proof(
+                &pp,
+                vd.key(),
+                proof,
+                public_inputs,
+                vd.pi_pos(),
+                b"Test",
+            )?;
+        }
+
+        assert!(
+            prove_batch::<TestCircuit>(&pp, &pk, Vec::new(), 4, b"Test")?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compile_many() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let mut circuits = [TestCircuit::default(), TestCircuit::default()];
+        let keys = compile_many(&pp, &mut circuits)?;
+        assert_eq!(keys.len(), 2);
+
+        // The keys are the ones of a sequential compilation
+        let (pk, vd, _, public_inputs) = test_circuit_proof(&pp)?;
+        for (prover_key, verifier_data) in keys.iter() {
+            assert_eq!(prover_key.to_var_bytes(), pk.to_var_bytes());
+            assert_eq!(verifier_data.to_var_bytes(), vd.to_var_bytes());
+        }
+
+        for (prover_key, verifier_data) in keys.iter() {
+            let proof =
+                TestCircuit::valid().gen_proof(&pp, prover_key, b"Test")?;
+
+            verify_proof(
+                &pp,
+                verifier_data.key(),
+                &proof,
+                &public_inputs,
+                verifier_data.pi_pos(),
+                b"Test",
+            )?;
+        }
+
+        assert!(compile_many::<TestCircuit>(&pp, &mut [])?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compile_many_dyn() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 14, &mut OsRng)?;
+        let mut circuits: Vec<Box<dyn DynCircuit + Send>> = vec![
+            Box::new(TestCircuit::default()),
+            Box::new(ScalarBitsCircuit::default()),
+        ];
+        let keys = compile_many_dyn(&pp, &mut circuits)?;
+        assert_eq!(keys.len(), 2);
+
+        // The keys are the ones of the compilation of each circuit
+        let expected = [
+            TestCircuit::default().compile(&pp)?,
+            ScalarBitsCircuit::default().compile(&pp)?,
+        ];
+        for ((pk, vd), (prover_key, verifier_data)) in
+            expected.iter().zip(keys.iter())
+        {
+            assert_eq!(prover_key.to_var_bytes(), pk.to_var_bytes());
+            assert_eq!(verifier_data.to_var_bytes(), vd.to_var_bytes());
+        }
+
+        assert!(compile_many_dyn(&pp, &mut [])?.is_empty());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn test_trace_spans() -> Result<(), Error> {
+        use rand_core::OsRng;
+        use std::sync::{Arc, Mutex};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        /// Collects the names of the spans created
+        #[derive(Default, Clone)]
+        struct SpanCollector(Arc<Mutex<Vec<&'static str>>>);
+
+        impl Subscriber for SpanCollector {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut names = self.0.lock().unwrap();
+                names.push(span.metadata().name());
+                Id::from_u64(names.len() as u64)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let collector = SpanCollector::default();
+        tracing::subscriber::with_default(collector.clone(), || {
+            test_circuit_proof(&pp).map(|_| ())
+        })?;
+
+        let names = collector.0.lock().unwrap();
+        [
+            "compile",
+            "preprocess",
+            "selector ifft",
+            "selector msm",
+            "selector coset fft",
+            "prove",
+            "round 1",
+            "witness ifft",
+            "witness msm",
+            "round 2",
+            "permutation poly",
+            "permutation msm",
+            "round 3",
+            "public inputs ifft",
+            "quotient poly",
+            "quotient msm",
+            "round 4",
+            "linearisation poly",
+            "round 5",
+            "opening msm",
+        ]
+        .iter()
+        .for_each(|name| assert!(names.contains(name), "missing {}", name));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_proof_light() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let (_, vd, proof, public_inputs) = test_circuit_proof(&pp)?;
+
+        // Only the verification material is kept by the light client
+        let vk_bytes = vd.key().to_bytes();
+        let opening_key_bytes = pp.opening_key().to_bytes();
+        drop(pp);
+
+        TestCircuit::verify_proof_light(
+            &vk_bytes,
+            &opening_key_bytes,
+            &proof,
+            &public_inputs,
+            vd.pi_pos(),
+            b"Test",
+        )?;
+
+        // Should fail since the public inputs don't match the proof
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs[0] = PublicInputValue::from(26u64);
+        assert!(TestCircuit::verify_proof_light(
+            &vk_bytes,
+            &opening_key_bytes,
+            &proof,
+            &wrong_inputs,
+            vd.pi_pos(),
+            b"Test",
+        )
+        .is_err());
+
+        // Should fail since the opening key is truncated
+        assert!(TestCircuit::verify_proof_light(
+            &vk_bytes,
+            &opening_key_bytes[1..],
+            &proof,
+            &public_inputs,
+            vd.pi_pos(),
+            b"Test",
+        )
+        .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gen_proof_with_commit_key() -> Result<(), Error> {
+        use dusk_bls12_381::G1Affine;
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let (pk, vd, _, public_inputs) = test_circuit_proof(&pp)?;
+
+        // Each half of the trimmed keys is distributed on its own
+        let (ck, opening_key) = pp.trim(1 << 11)?;
+        let ck_bytes = ck.to_var_bytes();
+        let opening_key_bytes = opening_key.to_bytes();
+        drop(pp);
+
+        let ck = CommitKey::from_slice(&ck_bytes)?;
+        let proof = TestCircuit::valid()
+            .gen_proof_with_commit_key(&ck, &pk, b"Test")?;
+
+        TestCircuit::verify_proof_light(
+            &vd.key().to_bytes(),
+            &opening_key_bytes,
+            &proof,
+            &public_inputs,
+            vd.pi_pos(),
+            b"Test",
+        )?;
+
+        // Should fail since the commit key is too small for the circuit
+        let small_ck = CommitKey::from_slice(&ck_bytes[..64 * G1Affine::SIZE])?;
+        assert!(TestCircuit::default()
+            .gen_proof_with_commit_key(&small_ck, &pk, b"Test")
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verifier_context() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let (pk, vd) = TestCircuit::default().compile(&pp)?;
+
+        let proofs: Vec<(Proof, [PublicInputValue; 3])> = (1..4u64)
+            .map(|i| {
+                let e = JubJubScalar::from(i);
+                let f = JubJubAffine::from(dusk_jubjub::GENERATOR_EXTENDED * e);
+                let mut circuit = TestCircuit {
+                    a: BlsScalar::from(20u64),
+                    b: BlsScalar::from(i),
+                    c: BlsScalar::from(20 + i),
+                    d: BlsScalar::from(20 * i),
+                    e,
+                    f,
+                };
+                let public_inputs = [
+                    PublicInputValue::from(20 + i),
+                    PublicInputValue::from(20 * i),
+                    PublicInputValue::from(f),
+                ];
+
+                Ok((circuit.gen_proof(&pp, &pk, b"Test")?, public_inputs))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        // The context gives the same results as the stateless verification,
+        // whatever the results of the previous verifications
+        let mut context = VerifierContext::new(&pp, b"Test");
+        for (i, (proof, _)) in proofs.iter().enumerate() {
+            for (j, (_, public_inputs)) in proofs.iter().enumerate() {
+                let expected = verify_proof(
+                    &pp,
+                    vd.key(),
+                    proof,
+                    public_inputs,
+                    vd.pi_pos(),
+                    b"Test",
+                );
+                let result = context.verify_into(
+                    proof,
+                    vd.key(),
+                    public_inputs,
+                    vd.pi_pos(),
+                );
+                assert_eq!(result.is_ok(), expected.is_ok());
+                assert_eq!(result.is_ok(), i == j);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verifier_context_allocations() -> Result<(), Error> {
+        use crate::alloc_counter;
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let (_, vd, proof, public_inputs) = test_circuit_proof(&pp)?;
+
+        let verify = || {
+            verify_proof(
+                &pp,
+                vd.key(),
+                &proof,
+                &public_inputs,
+                vd.pi_pos(),
+                b"Test",
             )
-            .into(),
+        };
+        let mut context = VerifierContext::new(&pp, b"Test");
+        let mut verify_into = || {
+            context.verify_into(&proof, vd.key(), &public_inputs, vd.pi_pos())
+        };
+
+        // The first verifications warm up the thread pool and fill the
+        // buffers of the context
+        verify()?;
+        verify_into()?;
+
+        let (result, stateless) = alloc_counter::measure(verify);
+        result?;
+        let (result, reused) = alloc_counter::measure(&mut verify_into);
+        result?;
+
+        // The context doesn't allocate the dense public inputs and the
+        // linearisation buffers again. The parallel
+        // iterators may run on other threads, so only the difference is
+        // checked, not the exact counts
+        assert!(reused.allocations < stateless.allocations);
+        assert!(reused.peak_bytes < stateless.peak_bytes);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_public_input_position() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let (_, vd, proof, public_inputs) = test_circuit_proof(&pp)?;
+
+        // The second value is placed at the position of the first one
+        let mut pi_pos = vd.pi_pos().clone();
+        pi_pos[1] = pi_pos[0];
+        let res = verify_proof(
+            &pp,
+            vd.key(),
+            &proof,
+            &public_inputs,
+            &pi_pos,
+            b"Test",
+        );
+        assert!(matches!(
+            res,
+            Err(Error::DuplicatePublicInputPosition { position })
+                if position == pi_pos[0]
+        ));
+
+        // Deserializing verifier data with a repeated position fails too
+        let bytes = VerifierData::new(*vd.key(), pi_pos).to_var_bytes();
+        assert!(matches!(
+            VerifierData::from_slice(&bytes),
+            Err(Error::DuplicatePublicInputPosition { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_public_input_value_conversions() {
+        assert_eq!(
+            PublicInputValue::from(42u64),
+            PublicInputValue::from(BlsScalar::from(42u64))
+        );
+        assert_eq!(
+            PublicInputValue::from(42i64),
+            PublicInputValue::from(BlsScalar::from(42u64))
+        );
+        assert_eq!(
+            PublicInputValue::from(-42i64),
+            PublicInputValue::from(-BlsScalar::from(42u64))
+        );
+        assert_eq!(
+            PublicInputValue::from(i64::MIN),
+            PublicInputValue::from(-BlsScalar::pow_of_2(63))
+        );
+
+        let scalar = -BlsScalar::from(7u64);
+        assert_eq!(
+            PublicInputValue::try_from(scalar.to_bytes()).unwrap(),
+            PublicInputValue::from(scalar)
+        );
+
+        // The modulus is a non-canonical encoding of zero
+        let mut modulus = (-BlsScalar::one()).to_bytes();
+        modulus[0] += 1;
+        assert!(matches!(
+            PublicInputValue::try_from(modulus),
+            Err(Error::BlsScalarMalformed)
+        ));
+        assert!(PublicInputValue::try_from([0xff; 32]).is_err());
+    }
+
+    #[test]
+    fn test_public_input_value_accessors() {
+        let point = JubJubAffine::from(
+            dusk_jubjub::GENERATOR_EXTENDED * JubJubScalar::from(2u64),
+        );
+        let value = PublicInputValue::from(point);
+        assert_eq!(value.as_slice(), &[point.get_x(), point.get_y()]);
+        assert!(value.iter().eq(value.as_slice().iter()));
+
+        let value = PublicInputValue::from(BlsScalar::from(42u64));
+        assert_eq!(value.as_slice(), &[BlsScalar::from(42u64)]);
+        assert_eq!(value.iter().count(), 1);
+    }
+
+    #[test]
+    fn test_verifier_key_equality() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+
+        let (_, vd) = TestCircuit::default().compile(&pp)?;
+        let (_, vd_again) = TestCircuit::default().compile(&pp)?;
+        assert_eq!(vd.key(), vd_again.key());
+        assert_eq!(vd, vd_again);
+
+        // The commitments depend on the SRS
+        let other_pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let (_, vd_other) = TestCircuit::default().compile(&other_pp)?;
+        assert_ne!(vd.key(), vd_other.key());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verifier_key_fingerprint() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+
+        let (_, vd) = TestCircuit::default().compile(&pp)?;
+        let (_, vd_again) = TestCircuit::default().compile(&pp)?;
+        assert_eq!(vd.key().fingerprint(), vd_again.key().fingerprint());
+
+        // The same circuit compiled with a different SRS
+        let other_pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let (_, vd_other) = TestCircuit::default().compile(&other_pp)?;
+        assert_ne!(vd.key().fingerprint(), vd_other.key().fingerprint());
+
+        let expected = blake2b_simd::Params::new()
+            .hash_length(32)
+            .hash(&vd.key().to_bytes());
+        assert_eq!(&vd.key().fingerprint()[..], expected.as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_proof_with_digest() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 14, &mut OsRng)?;
+        let (_, vd, proof, public_inputs) = test_circuit_proof(&pp)?;
+        let digest = vd.key().fingerprint();
+
+        // The digest is stable across compilations and distinct across
+        // circuits
+        let (_, vd_again) = TestCircuit::default().compile(&pp)?;
+        assert_eq!(vd_again.key().fingerprint(), digest);
+        let (_, vd_other) = ScalarBitsCircuit::default().compile(&pp)?;
+        assert_ne!(vd_other.key().fingerprint(), digest);
+
+        verify_proof_with_digest(
+            &pp,
+            &digest,
+            vd.key(),
+            &proof,
+            &public_inputs,
+            vd.pi_pos(),
+            b"Test",
+        )?;
+
+        // Should fail since the key fetched is the one of another circuit
+        assert!(matches!(
+            verify_proof_with_digest(
+                &pp,
+                &digest,
+                vd_other.key(),
+                &proof,
+                &public_inputs,
+                vd.pi_pos(),
+                b"Test",
+            ),
+            Err(Error::VerifierKeyDigestMismatch)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verifier_key_from_commitments() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let (_, vd, proof, public_inputs) = test_circuit_proof(&pp)?;
+
+        let key = VerifierKey::from_commitments(
+            &vd.key().selector_commitments(),
+            &vd.key().permutation_commitments(),
+            vd.key().n,
+        )?;
+        assert_eq!(&key, vd.key());
+
+        verify_proof(&pp, &key, &proof, &public_inputs, vd.pi_pos(), b"Test")?;
+
+        // Swapping two selectors gives a key that rejects the proof
+        let mut selectors = vd.key().selector_commitments();
+        selectors.swap(1, 2);
+        let swapped = VerifierKey::from_commitments(
+            &selectors,
+            &vd.key().permutation_commitments(),
+            vd.key().n,
+        )?;
+        assert!(verify_proof(
+            &pp,
+            &swapped,
+            &proof,
+            &public_inputs,
+            vd.pi_pos(),
+            b"Test",
+        )
+        .is_err());
+
+        assert!(matches!(
+            VerifierKey::from_commitments(
+                &selectors[..10],
+                &vd.key().permutation_commitments(),
+                vd.key().n,
+            ),
+            Err(Error::InvalidCommitmentCount {
+                found: 10,
+                expected: 11
+            })
+        ));
+        assert!(matches!(
+            VerifierKey::from_commitments(&selectors, &selectors, vd.key().n),
+            Err(Error::InvalidCommitmentCount {
+                found: 11,
+                expected: 4
+            })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_num_variables() -> Result<(), Error> {
+        use std::collections::BTreeSet;
+
+        let mut composer = StandardComposer::new();
+        TestCircuit::default().gadget(&mut composer)?;
+
+        // Every variable sitting on a wire is allocated, and the gadgets
+        // don't allocate more than the wires of their gates
+        let wired: BTreeSet<usize> = composer
+            .w_l
+            .iter()
+            .chain(composer.w_r.iter())
+            .chain(composer.w_o.iter())
+            .chain(composer.w_4.iter())
+            .map(|var| var.0)
+            .collect();
+        assert!(composer.num_variables() >= wired.len());
+        assert!(composer.num_variables() <= 4 * composer.circuit_size());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_estimated_prove_memory() -> Result<(), Error> {
+        use core::mem::size_of;
+        use dusk_bls12_381::G1Affine;
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let (pk, vd) = TestCircuit::default().compile(&pp)?;
+        let n = vd.key().padded_circuit_size();
+
+        // Measure the polynomials and evaluations held by the key, and the
+        // points of the commit key used to prove
+        let selectors = [
+            &pk.arithmetic.q_m,
+            &pk.arithmetic.q_l,
+            &pk.arithmetic.q_r,
+            &pk.arithmetic.q_o,
+            &pk.arithmetic.q_c,
+            &pk.arithmetic.q_4,
+            &pk.arithmetic.q_arith,
+            &pk.logic.q_c,
+            &pk.logic.q_logic,
+            &pk.range.q_range,
+            &pk.fixed_base.q_l,
+            &pk.fixed_base.q_r,
+            &pk.fixed_base.q_c,
+            &pk.fixed_base.q_fixed_group_add,
+            &pk.variable_base.q_variable_group_add,
+            &pk.permutation.left_sigma,
+            &pk.permutation.right_sigma,
+            &pk.permutation.out_sigma,
+            &pk.permutation.fourth_sigma,
         ];
+        let coeffs: usize =
+            selectors.iter().map(|(poly, _)| poly.coeffs.len()).sum();
+        let evals: usize = selectors
+            .iter()
+            .map(|(_, evals)| evals.evals.len())
+            .chain(Some(pk.permutation.linear_evaluations.evals.len()))
+            .chain(Some(pk.v_h_coset_4n.evals.len()))
+            .sum();
+        let (ck, _) = pp.trim(TestCircuit::default().padded_circuit_size())?;
+
+        let key = (coeffs + evals) * size_of::<BlsScalar>()
+            + ck.powers_of_g.len() * size_of::<G1Affine>();
+        let working_set = 32 * n * size_of::<BlsScalar>();
+
+        // The estimate bounds the measured sizes, and only differs from them
+        // by the leading zero coefficients trimmed from the polynomials
+        let estimate = pk.estimated_prove_memory();
+        let trimmed = (selectors.len() * n - coeffs) * size_of::<BlsScalar>();
+        assert!(key + working_set <= estimate);
+        assert_eq!(estimate - working_set - key, trimmed);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_gates() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        /// [`TestCircuit`] with a gate budget smaller than its size
+        #[derive(Debug, Default)]
+        struct TightCircuit(TestCircuit);
+
+        impl Circuit for TightCircuit {
+            const CIRCUIT_ID: [u8; 32] = [0xfe; 32];
+            const MAX_GATES: usize = 10;
+
+            fn gadget(
+                &mut self,
+                composer: &mut StandardComposer,
+            ) -> Result<(), Error> {
+                self.0.gadget(composer)
+            }
+
+            fn padded_circuit_size(&self) -> usize {
+                self.0.padded_circuit_size()
+            }
+        }
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        assert!(matches!(
+            TightCircuit::default().compile(&pp),
+            Err(Error::CircuitTooLarge {
+                max: 10,
+                at_gate: 10,
+                ..
+            })
+        ));
+        assert!(TestCircuit::default().compile(&pp).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_padded_circuit_size_exceeded() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        /// [`TestCircuit`] claiming a padded size smaller than its size
+        #[derive(Debug, Default)]
+        struct UndersizedCircuit(TestCircuit);
 
+        impl Circuit for UndersizedCircuit {
+            const CIRCUIT_ID: [u8; 32] = [0xfd; 32];
+
+            fn gadget(
+                &mut self,
+                composer: &mut StandardComposer,
+            ) -> Result<(), Error> {
+                self.0.gadget(composer)
+            }
+
+            fn padded_circuit_size(&self) -> usize {
+                1 << 4
+            }
+        }
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        assert!(matches!(
+            UndersizedCircuit::default().compile(&pp),
+            Err(Error::CircuitTooLarge {
+                max: 16,
+                at_gate: 16,
+                ..
+            })
+        ));
+
+        // Proving is checked as well
+        let (pk, _) = TestCircuit::default().compile(&pp)?;
+        assert!(matches!(
+            UndersizedCircuit::default().gen_proof(&pp, &pk, b"Test"),
+            Err(Error::CircuitTooLarge { max: 16, .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_jubjub_scalar_bits_public_input() -> Result<(), Error> {
+        use dusk_jubjub::GENERATOR_EXTENDED;
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 14, &mut OsRng)?;
+        let (pk, vd) = ScalarBitsCircuit::default().compile(&pp)?;
+
+        let scalar = JubJubScalar::random(&mut OsRng);
+        let point = GENERATOR_EXTENDED * JubJubScalar::random(&mut OsRng);
+        let result: JubJubAffine = (point * scalar).into();
+
+        let proof = ScalarBitsCircuit {
+            scalar,
+            point: point.into(),
+            result,
+        }
+        .gen_proof(&pp, &pk, b"ScalarBits")?;
+
+        let public_inputs = [
+            PublicInputValue::from_jubjub_scalar_bits(scalar),
+            PublicInputValue::from(result),
+        ];
         verify_proof(
             &pp,
-            &verif_data.key(),
+            vd.key(),
             &proof,
             &public_inputs,
-            &verif_data.pi_pos(),
-            b"Test",
+            vd.pi_pos(),
+            b"ScalarBits",
+        )?;
+
+        // Should fail since the bits belong to another scalar
+        let public_inputs = [
+            PublicInputValue::from_jubjub_scalar_bits(
+                scalar + JubJubScalar::one(),
+            ),
+            PublicInputValue::from(result),
+        ];
+        assert!(verify_proof(
+            &pp,
+            vd.key(),
+            &proof,
+            &public_inputs,
+            vd.pi_pos(),
+            b"ScalarBits",
         )
+        .is_err());
+
+        // The lowest bit comes first
+        let bits =
+            PublicInputValue::from_jubjub_scalar_bits(JubJubScalar::from(6u64));
+        assert_eq!(bits.0.len(), 252);
+        assert_eq!(bits.0[0], BlsScalar::zero());
+        assert_eq!(bits.0[1], BlsScalar::one());
+        assert_eq!(bits.0[2], BlsScalar::one());
+        assert!(bits.0[3..].iter().all(|bit| bit == &BlsScalar::zero()));
+
+        Ok(())
     }
 }
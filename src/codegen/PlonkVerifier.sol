@@ -0,0 +1,1131 @@
+// SPDX-License-Identifier: MPL-2.0
+//
+// Generated by dusk-plonk {{VERSION}} for a single circuit. Do not edit, run
+// the generator again instead.
+
+pragma solidity ^0.8.19;
+
+/// @title Verifier of the dusk-plonk proofs of a single circuit
+/// @notice Replays the verification of `PlonkVerifier::verify` of dusk-plonk
+/// for the circuit whose verifier key is baked into this contract. Requires
+/// the BLS12-381 precompiles of EIP-2537.
+contract PlonkVerifier {
+    // Order of the scalar field of BLS12-381
+    uint256 internal constant R =
+        0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001;
+    // 2^256 mod R, to reduce the 512 bits of the challenges
+    uint256 internal constant TWO_256 =
+        0x1824b159acc5056f998c4fefecbc4ff55884b7fa0003480200000001fffffffe;
+    // (p - 1) / 2, where p is the order of the base field, split in its 16
+    // most significant bytes and its 32 least significant ones
+    uint256 internal constant P_HALF_HI = 0x0d0088f51cbff34d258dd3db21a5d66b;
+    uint256 internal constant P_HALF_LO =
+        0xb23ba5c279c2895fb39869507b587b120f55ffff58a9ffffdcff7fffffffd555;
+
+    // Precompiles
+    uint256 internal constant MODEXP = 0x05;
+    uint256 internal constant G1_MSM = 0x0c;
+    uint256 internal constant PAIRING_CHECK = 0x0f;
+
+    // Size of the evaluation domain of the circuit, its inverse and its
+    // generator
+    uint256 internal constant N = {{N}};
+    uint256 internal constant N_INV = {{N_INV}};
+    uint256 internal constant OMEGA = {{OMEGA}};
+    // Constants of the cosets of the permutation argument
+    uint256 internal constant K1 = {{K1}};
+    uint256 internal constant K2 = {{K2}};
+    uint256 internal constant K3 = {{K3}};
+    // Parameter `d` of the twisted Edwards form of Jubjub
+    uint256 internal constant EDWARDS_D = {{EDWARDS_D}};
+
+    // Number of public inputs of the circuit
+    uint256 internal constant PUBLIC_INPUTS = {{PUBLIC_INPUTS}};
+    // Inverses of the roots of unity of the positions of the public inputs,
+    // 32 bytes each
+    bytes internal constant PI_OMEGAS = hex"{{PI_OMEGAS}}";
+    // Label the transcripts of the proofs are created with
+    bytes internal constant TRANSCRIPT_INIT = hex"{{TRANSCRIPT_INIT}}";
+
+    // Commitments of the verifier key, 128 bytes each
+    bytes internal constant SELECTORS ={{SELECTORS}};
+    uint256 internal constant Q_M = 0;
+    uint256 internal constant Q_L = 1;
+    uint256 internal constant Q_R = 2;
+    uint256 internal constant Q_O = 3;
+    uint256 internal constant Q_4 = 4;
+    uint256 internal constant Q_C = 5;
+    uint256 internal constant Q_RANGE = 6;
+    uint256 internal constant Q_LOGIC = 7;
+    uint256 internal constant Q_FIXED_GROUP_ADD = 8;
+    uint256 internal constant Q_VARIABLE_GROUP_ADD = 9;
+    uint256 internal constant FOURTH_SIGMA = 10;
+    uint256 internal constant LEFT_SIGMA = 11;
+    uint256 internal constant RIGHT_SIGMA = 12;
+    uint256 internal constant OUT_SIGMA = 13;
+
+    // Opening key: the generator of G1 and the generator of G2 and its
+    // product by the secret of the setup
+    bytes internal constant G = hex"{{G}}";
+    bytes internal constant H = hex"{{H}}";
+    bytes internal constant BETA_H = hex"{{BETA_H}}";
+
+    // Layout of the proofs: the 11 commitments, 128 bytes each, followed by
+    // the 16 evaluations, 32 bytes each
+    uint256 internal constant PROOF_SIZE = 1920;
+    uint256 internal constant EVALUATIONS_OFFSET = 1408;
+    uint256 internal constant A_COMM = 0;
+    uint256 internal constant B_COMM = 1;
+    uint256 internal constant C_COMM = 2;
+    uint256 internal constant D_COMM = 3;
+    uint256 internal constant Z_COMM = 4;
+    uint256 internal constant T_1_COMM = 5;
+    uint256 internal constant T_2_COMM = 6;
+    uint256 internal constant T_3_COMM = 7;
+    uint256 internal constant T_4_COMM = 8;
+    uint256 internal constant W_Z_COMM = 9;
+    uint256 internal constant W_ZW_COMM = 10;
+    uint256 internal constant A_EVAL = 0;
+    uint256 internal constant B_EVAL = 1;
+    uint256 internal constant C_EVAL = 2;
+    uint256 internal constant D_EVAL = 3;
+    uint256 internal constant A_NEXT_EVAL = 4;
+    uint256 internal constant B_NEXT_EVAL = 5;
+    uint256 internal constant D_NEXT_EVAL = 6;
+    uint256 internal constant Q_ARITH_EVAL = 7;
+    uint256 internal constant Q_C_EVAL = 8;
+    uint256 internal constant Q_L_EVAL = 9;
+    uint256 internal constant Q_R_EVAL = 10;
+    uint256 internal constant LEFT_SIGMA_EVAL = 11;
+    uint256 internal constant RIGHT_SIGMA_EVAL = 12;
+    uint256 internal constant OUT_SIGMA_EVAL = 13;
+    uint256 internal constant LIN_POLY_EVAL = 14;
+    uint256 internal constant PERM_EVAL = 15;
+
+    // Strobe-128, as used by the Merlin transcripts
+    uint256 internal constant STROBE_R = 166;
+    uint8 internal constant FLAG_I = 1;
+    uint8 internal constant FLAG_A = 1 << 1;
+    uint8 internal constant FLAG_C = 1 << 2;
+    uint8 internal constant FLAG_M = 1 << 4;
+    uint256 internal constant MASK_64 = 0xffffffffffffffff;
+    // Rotation offsets of the lanes of Keccak-f[1600], one byte each
+    bytes internal constant ROTATIONS =
+        hex"00013e1c1b242c063714030a2b1927292d0f150812023d380e";
+    // Round constants of Keccak-f[1600], 8 bytes each
+    bytes internal constant ROUND_CONSTANTS =
+        hex"00000000000000010000000000008082800000000000808a"
+        hex"8000000080008000000000000000808b0000000080000001"
+        hex"80000000800080818000000000008009000000000000008a"
+        hex"00000000000000880000000080008009000000008000000a"
+        hex"000000008000808b800000000000008b8000000000008089"
+        hex"800000000000800380000000000080028000000000000080"
+        hex"000000000000800a800000008000000a8000000080008081"
+        hex"800000000000808000000000800000018000000080008008";
+
+    struct Proof {
+        uint256[4][11] commitments;
+        uint256[16] evaluations;
+    }
+
+    struct Challenges {
+        uint256 beta;
+        uint256 gamma;
+        uint256 alpha;
+        uint256 rangeSep;
+        uint256 logicSep;
+        uint256 fixedBaseSep;
+        uint256 varBaseSep;
+        uint256 z;
+        uint256 v;
+        uint256 vShifted;
+        uint256 u;
+    }
+
+    struct Transcript {
+        uint256[25] state;
+        uint256 pos;
+        uint256 posBegin;
+    }
+
+    /// @notice Returns whether `proof` is a valid proof of the circuit for
+    /// the `publicInputs`, the scalars placed at the positions of the
+    /// public inputs of the circuit in order.
+    /// @dev Malformed proofs and public inputs are rejected as invalid.
+    function verify(bytes calldata proof, uint256[] calldata publicInputs)
+        external
+        view
+        returns (bool)
+    {
+        if (
+            proof.length != PROOF_SIZE || publicInputs.length != PUBLIC_INPUTS
+        ) {
+            return false;
+        }
+        for (uint256 i = 0; i < publicInputs.length; i++) {
+            if (publicInputs[i] >= R) {
+                return false;
+            }
+        }
+
+        (bool ok, Proof memory decoded) = _decodeProof(proof);
+        if (!ok) {
+            return false;
+        }
+
+        return _verify(decoded, publicInputs);
+    }
+
+    function _verify(Proof memory proof, uint256[] calldata publicInputs)
+        internal
+        view
+        returns (bool)
+    {
+        Transcript memory transcript = _newTranscript();
+        Challenges memory ch = _challenges(transcript, proof);
+
+        uint256 zH = _sub(_expmod(ch.z, N), 1);
+        uint256 l1 =
+            mulmod(zH, _inverse(mulmod(N, _sub(ch.z, 1), R)), R);
+        uint256 tEval = _quotientEvaluation(
+            proof.evaluations,
+            ch,
+            publicInputs,
+            zH,
+            l1
+        );
+
+        _appendEvaluations(transcript, proof.evaluations, tEval);
+        ch.v = _challengeScalar(transcript, "aggregate_witness");
+        ch.vShifted = _challengeScalar(transcript, "aggregate_witness");
+        _appendCommitment(transcript, "w_z", proof.commitments[W_Z_COMM]);
+        _appendCommitment(transcript, "w_z_w", proof.commitments[W_ZW_COMM]);
+        ch.u = _challengeScalar(transcript, "batch");
+
+        return _pairingCheck(proof, ch, tEval, l1);
+    }
+
+    // Proof decoding
+
+    function _decodeProof(bytes calldata data)
+        internal
+        pure
+        returns (bool ok, Proof memory proof)
+    {
+        for (uint256 i = 0; i < 11; i++) {
+            for (uint256 j = 0; j < 4; j++) {
+                proof.commitments[i][j] = _word(data, 128 * i + 32 * j);
+            }
+        }
+        for (uint256 i = 0; i < 16; i++) {
+            proof.evaluations[i] = _word(data, EVALUATIONS_OFFSET + 32 * i);
+            if (proof.evaluations[i] >= R) {
+                return (false, proof);
+            }
+        }
+
+        ok = true;
+    }
+
+    function _word(bytes calldata data, uint256 offset)
+        internal
+        pure
+        returns (uint256)
+    {
+        return uint256(bytes32(data[offset:offset + 32]));
+    }
+
+    // Challenges and evaluations
+
+    function _challenges(Transcript memory transcript, Proof memory proof)
+        internal
+        pure
+        returns (Challenges memory ch)
+    {
+        _appendCommitment(transcript, "w_l", proof.commitments[A_COMM]);
+        _appendCommitment(transcript, "w_r", proof.commitments[B_COMM]);
+        _appendCommitment(transcript, "w_o", proof.commitments[C_COMM]);
+        _appendCommitment(transcript, "w_4", proof.commitments[D_COMM]);
+
+        ch.beta = _challengeScalar(transcript, "beta");
+        _appendScalar(transcript, "beta", ch.beta);
+        ch.gamma = _challengeScalar(transcript, "gamma");
+
+        _appendCommitment(transcript, "z", proof.commitments[Z_COMM]);
+
+        ch.alpha = _challengeScalar(transcript, "alpha");
+        ch.rangeSep =
+            _challengeScalar(transcript, "range separation challenge");
+        ch.logicSep =
+            _challengeScalar(transcript, "logic separation challenge");
+        ch.fixedBaseSep =
+            _challengeScalar(transcript, "fixed base separation challenge");
+        ch.varBaseSep =
+            _challengeScalar(transcript, "variable base separation challenge");
+
+        _appendCommitment(transcript, "t_1", proof.commitments[T_1_COMM]);
+        _appendCommitment(transcript, "t_2", proof.commitments[T_2_COMM]);
+        _appendCommitment(transcript, "t_3", proof.commitments[T_3_COMM]);
+        _appendCommitment(transcript, "t_4", proof.commitments[T_4_COMM]);
+
+        ch.z = _challengeScalar(transcript, "z");
+    }
+
+    function _appendEvaluations(
+        Transcript memory transcript,
+        uint256[16] memory e,
+        uint256 tEval
+    ) internal pure {
+        _appendScalar(transcript, "a_eval", e[A_EVAL]);
+        _appendScalar(transcript, "b_eval", e[B_EVAL]);
+        _appendScalar(transcript, "c_eval", e[C_EVAL]);
+        _appendScalar(transcript, "d_eval", e[D_EVAL]);
+        _appendScalar(transcript, "a_next_eval", e[A_NEXT_EVAL]);
+        _appendScalar(transcript, "b_next_eval", e[B_NEXT_EVAL]);
+        _appendScalar(transcript, "d_next_eval", e[D_NEXT_EVAL]);
+        _appendScalar(transcript, "left_sig_eval", e[LEFT_SIGMA_EVAL]);
+        _appendScalar(transcript, "right_sig_eval", e[RIGHT_SIGMA_EVAL]);
+        _appendScalar(transcript, "out_sig_eval", e[OUT_SIGMA_EVAL]);
+        _appendScalar(transcript, "q_arith_eval", e[Q_ARITH_EVAL]);
+        _appendScalar(transcript, "q_c_eval", e[Q_C_EVAL]);
+        _appendScalar(transcript, "q_l_eval", e[Q_L_EVAL]);
+        _appendScalar(transcript, "q_r_eval", e[Q_R_EVAL]);
+        _appendScalar(transcript, "perm_eval", e[PERM_EVAL]);
+        _appendScalar(transcript, "t_eval", tEval);
+        _appendScalar(transcript, "r_eval", e[LIN_POLY_EVAL]);
+    }
+
+    function _quotientEvaluation(
+        uint256[16] memory e,
+        Challenges memory ch,
+        uint256[] calldata publicInputs,
+        uint256 zH,
+        uint256 l1
+    ) internal view returns (uint256) {
+        uint256 sum = addmod(
+            e[LIN_POLY_EVAL],
+            _publicInputsEvaluation(publicInputs, ch.z, zH),
+            R
+        );
+
+        // (a + beta * sigma_1 + gamma) * (b + beta * sigma_2 + gamma) *
+        // (c + beta * sigma_3 + gamma) * (d + gamma) * z_hat * alpha
+        uint256 perm = mulmod(
+            _sigmaProduct(e, ch),
+            addmod(e[D_EVAL], ch.gamma, R),
+            R
+        );
+        perm = mulmod(perm, mulmod(e[PERM_EVAL], ch.alpha, R), R);
+        sum = _sub(sum, perm);
+
+        // l_1(z) * alpha^2
+        sum = _sub(sum, mulmod(l1, mulmod(ch.alpha, ch.alpha, R), R));
+
+        return mulmod(sum, _inverse(zH), R);
+    }
+
+    function _publicInputsEvaluation(
+        uint256[] calldata publicInputs,
+        uint256 z,
+        uint256 zH
+    ) internal view returns (uint256 sum) {
+        bytes memory omegas = PI_OMEGAS;
+        for (uint256 i = 0; i < publicInputs.length; i++) {
+            if (publicInputs[i] == 0) {
+                continue;
+            }
+            // The circuit holds the negation of the public inputs
+            uint256 denominator = _sub(mulmod(_load(omegas, 32 * i), z, R), 1);
+            sum = addmod(
+                sum,
+                mulmod(_neg(publicInputs[i]), _inverse(denominator), R),
+                R
+            );
+        }
+
+        sum = mulmod(sum, mulmod(zH, N_INV, R), R);
+    }
+
+    function _sigmaProduct(uint256[16] memory e, Challenges memory ch)
+        internal
+        pure
+        returns (uint256 product)
+    {
+        product = addmod(
+            addmod(e[A_EVAL], mulmod(ch.beta, e[LEFT_SIGMA_EVAL], R), R),
+            ch.gamma,
+            R
+        );
+        product = mulmod(
+            product,
+            addmod(
+                addmod(e[B_EVAL], mulmod(ch.beta, e[RIGHT_SIGMA_EVAL], R), R),
+                ch.gamma,
+                R
+            ),
+            R
+        );
+        product = mulmod(
+            product,
+            addmod(
+                addmod(e[C_EVAL], mulmod(ch.beta, e[OUT_SIGMA_EVAL], R), R),
+                ch.gamma,
+                R
+            ),
+            R
+        );
+    }
+
+    // Linearisation commitment
+
+    /// Returns the scalars of the linearisation commitment, in the order of
+    /// the selectors up to `FOURTH_SIGMA`, followed by the one of the
+    /// commitment to the permutation polynomial.
+    function _linearisationScalars(
+        uint256[16] memory e,
+        Challenges memory ch,
+        uint256 l1
+    ) internal pure returns (uint256[12] memory s) {
+        uint256 qArith = e[Q_ARITH_EVAL];
+        s[Q_M] = mulmod(mulmod(e[A_EVAL], e[B_EVAL], R), qArith, R);
+        s[Q_L] = mulmod(e[A_EVAL], qArith, R);
+        s[Q_R] = mulmod(e[B_EVAL], qArith, R);
+        s[Q_O] = mulmod(e[C_EVAL], qArith, R);
+        s[Q_4] = mulmod(e[D_EVAL], qArith, R);
+        s[Q_C] = qArith;
+        s[Q_RANGE] = _rangeScalar(e, ch.rangeSep);
+        s[Q_LOGIC] = _logicScalar(e, ch.logicSep);
+        s[Q_FIXED_GROUP_ADD] = _fixedBaseScalar(e, ch.fixedBaseSep);
+        s[Q_VARIABLE_GROUP_ADD] = _variableBaseScalar(e, ch.varBaseSep);
+
+        // -(a + beta * sigma_1 + gamma) * (b + beta * sigma_2 + gamma) *
+        // (c + beta * sigma_3 + gamma) * beta * z_hat * alpha
+        s[FOURTH_SIGMA] = _neg(
+            mulmod(
+                mulmod(_sigmaProduct(e, ch), ch.beta, R),
+                mulmod(e[PERM_EVAL], ch.alpha, R),
+                R
+            )
+        );
+        s[11] = _permutationScalar(e, ch, l1);
+    }
+
+    function _rangeScalar(uint256[16] memory e, uint256 sep)
+        internal
+        pure
+        returns (uint256 sum)
+    {
+        uint256 kappa = mulmod(sep, sep, R);
+        uint256 k = kappa;
+
+        sum = _delta(_sub(e[C_EVAL], mulmod(4, e[D_EVAL], R)));
+        sum = addmod(
+            sum,
+            mulmod(_delta(_sub(e[B_EVAL], mulmod(4, e[C_EVAL], R))), k, R),
+            R
+        );
+        k = mulmod(k, kappa, R);
+        sum = addmod(
+            sum,
+            mulmod(_delta(_sub(e[A_EVAL], mulmod(4, e[B_EVAL], R))), k, R),
+            R
+        );
+        k = mulmod(k, kappa, R);
+        sum = addmod(
+            sum,
+            mulmod(
+                _delta(_sub(e[D_NEXT_EVAL], mulmod(4, e[A_EVAL], R))),
+                k,
+                R
+            ),
+            R
+        );
+
+        sum = mulmod(sum, sep, R);
+    }
+
+    function _logicScalar(uint256[16] memory e, uint256 sep)
+        internal
+        pure
+        returns (uint256 sum)
+    {
+        uint256 kappa = mulmod(sep, sep, R);
+        uint256 k = kappa;
+
+        uint256 a = _sub(e[A_NEXT_EVAL], mulmod(4, e[A_EVAL], R));
+        uint256 b = _sub(e[B_NEXT_EVAL], mulmod(4, e[B_EVAL], R));
+        uint256 d = _sub(e[D_NEXT_EVAL], mulmod(4, e[D_EVAL], R));
+        uint256 w = e[C_EVAL];
+
+        sum = _delta(a);
+        sum = addmod(sum, mulmod(_delta(b), k, R), R);
+        k = mulmod(k, kappa, R);
+        sum = addmod(sum, mulmod(_delta(d), k, R), R);
+        k = mulmod(k, kappa, R);
+        sum = addmod(sum, mulmod(_sub(w, mulmod(a, b, R)), k, R), R);
+        k = mulmod(k, kappa, R);
+        // Computed on its own to keep `e` within reach of the stack
+        uint256 xorAnd = _deltaXorAnd(a, b, w, d, e[Q_C_EVAL]);
+        sum = addmod(sum, mulmod(xorAnd, k, R), R);
+
+        sum = mulmod(sum, sep, R);
+    }
+
+    function _fixedBaseScalar(uint256[16] memory e, uint256 sep)
+        internal
+        pure
+        returns (uint256 sum)
+    {
+        uint256 kappa = mulmod(sep, sep, R);
+        uint256 k = kappa;
+
+        uint256 bit = _sub(e[D_NEXT_EVAL], mulmod(2, e[D_EVAL], R));
+        uint256 yAlpha = addmod(
+            mulmod(mulmod(bit, bit, R), _sub(e[Q_R_EVAL], 1), R),
+            1,
+            R
+        );
+        uint256 xAlpha = mulmod(e[Q_L_EVAL], bit, R);
+
+        // Bit consistency
+        sum = mulmod(mulmod(bit, _sub(bit, 1), R), addmod(bit, 1, R), R);
+
+        // xy_alpha consistency
+        uint256 check = _sub(mulmod(bit, e[Q_C_EVAL], R), e[C_EVAL]);
+        sum = addmod(sum, mulmod(check, k, R), R);
+        k = mulmod(k, kappa, R);
+
+        (uint256 xCheck, uint256 yCheck) =
+            _fixedBaseAccumulators(e, xAlpha, yAlpha);
+        sum = addmod(sum, mulmod(xCheck, k, R), R);
+        k = mulmod(k, kappa, R);
+        sum = addmod(sum, mulmod(yCheck, k, R), R);
+
+        sum = mulmod(sum, sep, R);
+    }
+
+    /// Returns the consistency checks of the `x` and `y` accumulators of
+    /// the fixed base scalar multiplication, apart to keep `e` within reach
+    /// of the stack.
+    function _fixedBaseAccumulators(
+        uint256[16] memory e,
+        uint256 xAlpha,
+        uint256 yAlpha
+    ) internal pure returns (uint256 xCheck, uint256 yCheck) {
+        // xy_alpha * x * y * d
+        uint256 t = mulmod(
+            mulmod(mulmod(e[C_EVAL], e[A_EVAL], R), e[B_EVAL], R),
+            EDWARDS_D,
+            R
+        );
+
+        // x accumulator consistency
+        xCheck = _sub(
+            addmod(e[A_NEXT_EVAL], mulmod(e[A_NEXT_EVAL], t, R), R),
+            addmod(
+                mulmod(xAlpha, e[B_EVAL], R),
+                mulmod(yAlpha, e[A_EVAL], R),
+                R
+            )
+        );
+        // y accumulator consistency
+        yCheck = _sub(
+            _sub(e[B_NEXT_EVAL], mulmod(e[B_NEXT_EVAL], t, R)),
+            addmod(
+                mulmod(xAlpha, e[A_EVAL], R),
+                mulmod(yAlpha, e[B_EVAL], R),
+                R
+            )
+        );
+    }
+
+    function _variableBaseScalar(uint256[16] memory e, uint256 sep)
+        internal
+        pure
+        returns (uint256 sum)
+    {
+        uint256 kappa = mulmod(sep, sep, R);
+
+        // x_1 = a, y_1 = b, x_2 = c, y_2 = d, x_3 = a_next, y_3 = b_next and
+        // x_1 * y_2 = d_next
+        uint256 x1y2 = e[D_NEXT_EVAL];
+        uint256 y1x2 = mulmod(e[B_EVAL], e[C_EVAL], R);
+        uint256 dxy = mulmod(mulmod(EDWARDS_D, x1y2, R), y1x2, R);
+
+        // x_1 * y_2 consistency
+        sum = _sub(mulmod(e[A_EVAL], e[D_EVAL], R), x1y2);
+
+        // x_3 consistency
+        uint256 check = _sub(
+            addmod(x1y2, y1x2, R),
+            addmod(e[A_NEXT_EVAL], mulmod(e[A_NEXT_EVAL], dxy, R), R)
+        );
+        sum = addmod(sum, mulmod(check, kappa, R), R);
+
+        // y_3 consistency
+        check = _sub(
+            addmod(
+                mulmod(e[B_EVAL], e[D_EVAL], R),
+                mulmod(e[A_EVAL], e[C_EVAL], R),
+                R
+            ),
+            _sub(e[B_NEXT_EVAL], mulmod(e[B_NEXT_EVAL], dxy, R))
+        );
+        sum = addmod(sum, mulmod(check, mulmod(kappa, kappa, R), R), R);
+
+        sum = mulmod(sum, sep, R);
+    }
+
+    function _permutationScalar(
+        uint256[16] memory e,
+        Challenges memory ch,
+        uint256 l1
+    ) internal pure returns (uint256 product) {
+        uint256 betaZ = mulmod(ch.beta, ch.z, R);
+
+        // (a + beta * z + gamma) * (b + beta * k1 * z + gamma) *
+        // (c + beta * k2 * z + gamma) * (d + beta * k3 * z + gamma) * alpha
+        product = addmod(addmod(e[A_EVAL], betaZ, R), ch.gamma, R);
+        product = mulmod(
+            product,
+            addmod(addmod(e[B_EVAL], mulmod(betaZ, K1, R), R), ch.gamma, R),
+            R
+        );
+        product = mulmod(
+            product,
+            addmod(addmod(e[C_EVAL], mulmod(betaZ, K2, R), R), ch.gamma, R),
+            R
+        );
+        product = mulmod(
+            product,
+            addmod(addmod(e[D_EVAL], mulmod(betaZ, K3, R), R), ch.gamma, R),
+            R
+        );
+        product = mulmod(product, ch.alpha, R);
+
+        // l_1(z) * alpha^2
+        product = addmod(
+            product,
+            mulmod(l1, mulmod(ch.alpha, ch.alpha, R), R),
+            R
+        );
+    }
+
+    function _delta(uint256 f) internal pure returns (uint256) {
+        return
+            mulmod(
+                mulmod(f, _sub(f, 1), R),
+                mulmod(_sub(f, 2), _sub(f, 3), R),
+                R
+            );
+    }
+
+    function _deltaXorAnd(
+        uint256 a,
+        uint256 b,
+        uint256 w,
+        uint256 c,
+        uint256 qC
+    ) internal pure returns (uint256) {
+        uint256 aPlusB = addmod(a, b, R);
+
+        // w * (w * (4w - 18(a + b) + 81) + 18(a^2 + b^2) - 81(a + b) + 83)
+        uint256 f = addmod(
+            _sub(mulmod(4, w, R), mulmod(18, aPlusB, R)),
+            81,
+            R
+        );
+        f = mulmod(w, f, R);
+        f = addmod(
+            f,
+            mulmod(18, addmod(mulmod(a, a, R), mulmod(b, b, R), R), R),
+            R
+        );
+        f = addmod(_sub(f, mulmod(81, aPlusB, R)), 83, R);
+        f = mulmod(w, f, R);
+
+        // 3(a + b + c) - 2F + q_c * (9c - 3(a + b))
+        uint256 sum = _sub(mulmod(3, addmod(aPlusB, c, R), R), mulmod(2, f, R));
+        return
+            addmod(
+                sum,
+                mulmod(qC, _sub(mulmod(9, c, R), mulmod(3, aPlusB, R)), R),
+                R
+            );
+    }
+
+    // Opening
+
+    /// Checks the openings of the proof, batched with `u` as
+    /// `e(-W, beta_h) * e(C, h) = 1`, where `W = w_z + u * w_zw` and `C` is
+    /// the sum of the terms of `_linearisationTerms` and `_openingTerms`.
+    function _pairingCheck(
+        Proof memory proof,
+        Challenges memory ch,
+        uint256 tEval,
+        uint256 l1
+    ) internal view returns (bool) {
+        bool ok;
+        uint256[4] memory c;
+        (ok, c) = _msm(
+            abi.encodePacked(
+                _linearisationTerms(proof, ch, l1),
+                _openingTerms(proof, ch, tEval)
+            )
+        );
+        if (!ok) {
+            return false;
+        }
+
+        bytes memory terms =
+            _addTerm(new bytes(0), proof.commitments[W_Z_COMM], R - 1);
+        terms = _addTerm(terms, proof.commitments[W_ZW_COMM], _neg(ch.u));
+        uint256[4] memory negW;
+        (ok, negW) = _msm(terms);
+        if (!ok) {
+            return false;
+        }
+
+        bytes memory input = abi.encodePacked(negW, BETA_H, c, H);
+        uint256 result;
+        assembly {
+            ok := staticcall(
+                gas(),
+                PAIRING_CHECK,
+                add(input, 0x20),
+                mload(input),
+                0x00,
+                0x20
+            )
+            result := mload(0x00)
+        }
+
+        return ok && result == 1;
+    }
+
+    /// Returns the terms of the commitments of the linearisation
+    /// commitment, which is opened at `z` with the factor `v`.
+    function _linearisationTerms(
+        Proof memory proof,
+        Challenges memory ch,
+        uint256 l1
+    ) internal pure returns (bytes memory terms) {
+        bytes memory selectors = SELECTORS;
+        uint256[12] memory s =
+            _linearisationScalars(proof.evaluations, ch, l1);
+
+        terms = new bytes(0);
+        for (uint256 i = 0; i <= FOURTH_SIGMA; i++) {
+            terms = _addTerm(
+                terms,
+                _point(selectors, 128 * i),
+                mulmod(ch.v, s[i], R)
+            );
+        }
+
+        // The permutation polynomial is opened at `z * w` as well, as the
+        // first polynomial of the shifted opening, whose factor is `u`
+        terms = _addTerm(
+            terms,
+            proof.commitments[Z_COMM],
+            addmod(mulmod(ch.v, s[11], R), ch.u, R)
+        );
+    }
+
+    /// Returns the terms of the other commitments opened at `z`, scaled by
+    /// the powers of `v`, of the ones opened at `z * w`, scaled by `u` times
+    /// the powers of the second `v`, of the witnesses of the openings and of
+    /// the generator, scaled by the evaluations.
+    function _openingTerms(
+        Proof memory proof,
+        Challenges memory ch,
+        uint256 tEval
+    ) internal view returns (bytes memory terms) {
+        bytes memory selectors = SELECTORS;
+        uint256[9] memory vp = _powers(ch.v, 1);
+        uint256[9] memory wp = _powers(ch.vShifted, ch.u);
+
+        // Commitment to the quotient polynomial, split in 4 parts
+        uint256 zN = _expmod(ch.z, N);
+        terms = _addTerm(new bytes(0), proof.commitments[T_1_COMM], 1);
+        terms = _addTerm(terms, proof.commitments[T_2_COMM], zN);
+        terms = _addTerm(
+            terms,
+            proof.commitments[T_3_COMM],
+            mulmod(zN, zN, R)
+        );
+        terms = _addTerm(
+            terms,
+            proof.commitments[T_4_COMM],
+            mulmod(mulmod(zN, zN, R), zN, R)
+        );
+
+        terms = _addTerm(
+            terms,
+            proof.commitments[A_COMM],
+            addmod(vp[2], wp[1], R)
+        );
+        terms = _addTerm(
+            terms,
+            proof.commitments[B_COMM],
+            addmod(vp[3], wp[2], R)
+        );
+        terms = _addTerm(terms, proof.commitments[C_COMM], vp[4]);
+        terms = _addTerm(
+            terms,
+            proof.commitments[D_COMM],
+            addmod(vp[5], wp[3], R)
+        );
+        terms = _addTerm(terms, _point(selectors, 128 * LEFT_SIGMA), vp[6]);
+        terms = _addTerm(terms, _point(selectors, 128 * RIGHT_SIGMA), vp[7]);
+        terms = _addTerm(terms, _point(selectors, 128 * OUT_SIGMA), vp[8]);
+
+        // Witnesses of the openings at `z` and `z * w`
+        terms = _addTerm(terms, proof.commitments[W_Z_COMM], ch.z);
+        terms = _addTerm(
+            terms,
+            proof.commitments[W_ZW_COMM],
+            mulmod(ch.u, mulmod(ch.z, OMEGA, R), R)
+        );
+
+        terms = _addTerm(
+            terms,
+            _point(G, 0),
+            _neg(_openedEvaluation(proof, vp, wp, tEval))
+        );
+    }
+
+    function _openedEvaluation(
+        Proof memory proof,
+        uint256[9] memory vp,
+        uint256[9] memory wp,
+        uint256 tEval
+    ) internal pure returns (uint256 sum) {
+        uint256[16] memory e = proof.evaluations;
+
+        sum = tEval;
+        sum = addmod(sum, mulmod(vp[1], e[LIN_POLY_EVAL], R), R);
+        sum = addmod(sum, mulmod(vp[2], e[A_EVAL], R), R);
+        sum = addmod(sum, mulmod(vp[3], e[B_EVAL], R), R);
+        sum = addmod(sum, mulmod(vp[4], e[C_EVAL], R), R);
+        sum = addmod(sum, mulmod(vp[5], e[D_EVAL], R), R);
+        sum = addmod(sum, mulmod(vp[6], e[LEFT_SIGMA_EVAL], R), R);
+        sum = addmod(sum, mulmod(vp[7], e[RIGHT_SIGMA_EVAL], R), R);
+        sum = addmod(sum, mulmod(vp[8], e[OUT_SIGMA_EVAL], R), R);
+
+        sum = addmod(sum, mulmod(wp[0], e[PERM_EVAL], R), R);
+        sum = addmod(sum, mulmod(wp[1], e[A_NEXT_EVAL], R), R);
+        sum = addmod(sum, mulmod(wp[2], e[B_NEXT_EVAL], R), R);
+        sum = addmod(sum, mulmod(wp[3], e[D_NEXT_EVAL], R), R);
+    }
+
+    /// Returns `scale * x^i` for `i` from 0 to 8.
+    function _powers(uint256 x, uint256 scale)
+        internal
+        pure
+        returns (uint256[9] memory powers)
+    {
+        powers[0] = scale;
+        for (uint256 i = 1; i < 9; i++) {
+            powers[i] = mulmod(powers[i - 1], x, R);
+        }
+    }
+
+    /// Appends the term of `point` and `scalar` to the input of `_msm`.
+    function _addTerm(
+        bytes memory terms,
+        uint256[4] memory point,
+        uint256 scalar
+    ) internal pure returns (bytes memory) {
+        return abi.encodePacked(terms, point, scalar);
+    }
+
+    function _point(bytes memory data, uint256 offset)
+        internal
+        pure
+        returns (uint256[4] memory point)
+    {
+        for (uint256 i = 0; i < 4; i++) {
+            point[i] = _load(data, offset + 32 * i);
+        }
+    }
+
+    function _load(bytes memory data, uint256 offset)
+        internal
+        pure
+        returns (uint256 word)
+    {
+        assembly {
+            word := mload(add(add(data, 0x20), offset))
+        }
+    }
+
+    /// Returns the sum of the points of G1 of the `terms`, each multiplied
+    /// by its scalar, or `false` if a point is not a valid point of the
+    /// prime order subgroup.
+    function _msm(bytes memory terms)
+        internal
+        view
+        returns (bool ok, uint256[4] memory point)
+    {
+        assembly {
+            ok := staticcall(
+                gas(),
+                G1_MSM,
+                add(terms, 0x20),
+                mload(terms),
+                point,
+                0x80
+            )
+        }
+    }
+
+    // Scalar field
+
+    function _sub(uint256 a, uint256 b) internal pure returns (uint256) {
+        return addmod(a, R - b, R);
+    }
+
+    function _neg(uint256 a) internal pure returns (uint256) {
+        return (R - a) % R;
+    }
+
+    function _inverse(uint256 a) internal view returns (uint256) {
+        return _expmod(a, R - 2);
+    }
+
+    function _expmod(uint256 base, uint256 exponent)
+        internal
+        view
+        returns (uint256 result)
+    {
+        bool ok;
+        assembly {
+            let p := mload(0x40)
+            mstore(p, 0x20)
+            mstore(add(p, 0x20), 0x20)
+            mstore(add(p, 0x40), 0x20)
+            mstore(add(p, 0x60), base)
+            mstore(add(p, 0x80), exponent)
+            mstore(add(p, 0xa0), R)
+            ok := staticcall(gas(), MODEXP, p, 0xc0, p, 0x20)
+            result := mload(p)
+        }
+        require(ok, "modexp failed");
+    }
+
+    // Merlin transcript
+
+    function _newTranscript()
+        internal
+        pure
+        returns (Transcript memory transcript)
+    {
+        bytes memory init = abi.encodePacked(
+            uint8(1),
+            uint8(STROBE_R + 2),
+            uint8(1),
+            uint8(0),
+            uint8(1),
+            uint8(96),
+            "STROBEv1.0.2"
+        );
+        for (uint256 i = 0; i < init.length; i++) {
+            _xorByte(transcript, i, uint8(init[i]));
+        }
+        _keccakF(transcript.state);
+
+        _beginOp(transcript, FLAG_M | FLAG_A);
+        _absorb(transcript, "Merlin v1.0");
+        _appendMessage(transcript, "dom-sep", TRANSCRIPT_INIT);
+    }
+
+    function _appendMessage(
+        Transcript memory transcript,
+        bytes memory label,
+        bytes memory message
+    ) internal pure {
+        _beginOp(transcript, FLAG_M | FLAG_A);
+        _absorb(transcript, label);
+        _absorb(transcript, _le32(message.length));
+        _beginOp(transcript, FLAG_A);
+        _absorb(transcript, message);
+    }
+
+    /// Appends the scalar in its 32 bytes little-endian encoding.
+    function _appendScalar(
+        Transcript memory transcript,
+        bytes memory label,
+        uint256 scalar
+    ) internal pure {
+        bytes memory le = new bytes(32);
+        for (uint256 i = 0; i < 32; i++) {
+            le[i] = bytes1(uint8(scalar >> (8 * i)));
+        }
+        _appendMessage(transcript, label, le);
+    }
+
+    /// Appends the point in the 48 bytes compressed encoding of
+    /// dusk-bls12_381.
+    function _appendCommitment(
+        Transcript memory transcript,
+        bytes memory label,
+        uint256[4] memory point
+    ) internal pure {
+        bytes memory compressed;
+        if ((point[0] | point[1] | point[2] | point[3]) == 0) {
+            // Point at infinity
+            compressed = new bytes(48);
+            compressed[0] = 0xc0;
+        } else {
+            uint256 flags = 0x80;
+            if (
+                point[2] > P_HALF_HI ||
+                (point[2] == P_HALF_HI && point[3] > P_HALF_LO)
+            ) {
+                flags |= 0x20;
+            }
+            compressed = abi.encodePacked(
+                bytes16(uint128(point[0] | (flags << 120))),
+                point[1]
+            );
+        }
+        _appendMessage(transcript, label, compressed);
+    }
+
+    /// Draws a challenge from 64 bytes of the transcript, reduced as
+    /// `BlsScalar::from_bytes_wide` does.
+    function _challengeScalar(
+        Transcript memory transcript,
+        bytes memory label
+    ) internal pure returns (uint256) {
+        _beginOp(transcript, FLAG_M | FLAG_A);
+        _absorb(transcript, label);
+        _absorb(transcript, _le32(64));
+        _beginOp(transcript, FLAG_I | FLAG_A | FLAG_C);
+
+        uint256 lo;
+        uint256 hi;
+        for (uint256 i = 0; i < 64; i++) {
+            uint256 pos = transcript.pos;
+            uint256 shift = (pos & 7) << 3;
+            uint256 b = (transcript.state[pos >> 3] >> shift) & 0xff;
+            transcript.state[pos >> 3] &= ~(uint256(0xff) << shift);
+            transcript.pos = pos + 1;
+            if (transcript.pos == STROBE_R) {
+                _runF(transcript);
+            }
+
+            if (i < 32) {
+                lo |= b << (8 * i);
+            } else {
+                hi |= b << (8 * (i - 32));
+            }
+        }
+
+        return addmod(mulmod(hi, TWO_256, R), lo % R, R);
+    }
+
+    function _le32(uint256 x) internal pure returns (bytes memory) {
+        return
+            abi.encodePacked(
+                uint8(x),
+                uint8(x >> 8),
+                uint8(x >> 16),
+                uint8(x >> 24)
+            );
+    }
+
+    function _beginOp(Transcript memory transcript, uint8 flags)
+        internal
+        pure
+    {
+        uint256 oldBegin = transcript.posBegin;
+        transcript.posBegin = transcript.pos + 1;
+        _absorb(transcript, abi.encodePacked(uint8(oldBegin), flags));
+
+        // The C flag forces to run F
+        if ((flags & FLAG_C) != 0 && transcript.pos != 0) {
+            _runF(transcript);
+        }
+    }
+
+    function _absorb(Transcript memory transcript, bytes memory data)
+        internal
+        pure
+    {
+        for (uint256 i = 0; i < data.length; i++) {
+            _xorByte(transcript, transcript.pos, uint8(data[i]));
+            transcript.pos += 1;
+            if (transcript.pos == STROBE_R) {
+                _runF(transcript);
+            }
+        }
+    }
+
+    function _runF(Transcript memory transcript) internal pure {
+        _xorByte(transcript, transcript.pos, uint8(transcript.posBegin));
+        _xorByte(transcript, transcript.pos + 1, 0x04);
+        _xorByte(transcript, STROBE_R + 1, 0x80);
+        _keccakF(transcript.state);
+        transcript.pos = 0;
+        transcript.posBegin = 0;
+    }
+
+    /// XORs the byte at `pos` of the state, whose lanes are little-endian.
+    function _xorByte(
+        Transcript memory transcript,
+        uint256 pos,
+        uint8 value
+    ) internal pure {
+        transcript.state[pos >> 3] ^= uint256(value) << ((pos & 7) << 3);
+    }
+
+    /// Applies Keccak-f[1600] to the lanes of the state, the lane `(x, y)`
+    /// being at index `x + 5 * y`.
+    function _keccakF(uint256[25] memory a) internal pure {
+        bytes memory rotations = ROTATIONS;
+        bytes memory roundConstants = ROUND_CONSTANTS;
+        uint256[5] memory c;
+        uint256[25] memory b;
+
+        for (uint256 round = 0; round < 24; round++) {
+            // Theta
+            for (uint256 x = 0; x < 5; x++) {
+                c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+            }
+            for (uint256 x = 0; x < 5; x++) {
+                uint256 d = c[(x + 4) % 5] ^ _rotl(c[(x + 1) % 5], 1);
+                for (uint256 y = 0; y < 25; y += 5) {
+                    a[x + y] ^= d;
+                }
+            }
+
+            // Rho and pi
+            for (uint256 x = 0; x < 5; x++) {
+                for (uint256 y = 0; y < 5; y++) {
+                    b[y + 5 * ((2 * x + 3 * y) % 5)] = _rotl(
+                        a[x + 5 * y],
+                        uint8(rotations[x + 5 * y])
+                    );
+                }
+            }
+
+            // Chi
+            for (uint256 x = 0; x < 5; x++) {
+                for (uint256 y = 0; y < 25; y += 5) {
+                    a[x + y] =
+                        b[x + y] ^
+                        (~b[((x + 1) % 5) + y] & b[((x + 2) % 5) + y]);
+                }
+            }
+
+            // Iota
+            a[0] ^= _load(roundConstants, 8 * round) >> 192;
+        }
+    }
+
+    function _rotl(uint256 x, uint256 n) internal pure returns (uint256) {
+        return ((x << n) | (x >> (64 - n))) & MASK_64;
+    }
+}
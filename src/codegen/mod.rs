@@ -0,0 +1,369 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Generation of Solidity verifiers of the proofs of a circuit.
+//!
+//! [`solidity_verifier`] emits a self-contained contract verifying the
+//! proofs of the circuit of a [`VerifierData`] as
+//! [`PlonkVerifier::verify`](crate::circuit::PlonkVerifier::verify) does.
+//! The commitments of the [`VerifierKey`](crate::proof_system::VerifierKey),
+//! the positions of the public inputs and the [`OpeningKey`] are baked into
+//! the contract as constants, so it only takes the proof and the public
+//! inputs. The multi-scalar multiplications and the pairing check are done
+//! with the BLS12-381 precompiles of EIP-2537.
+//!
+//! The contract takes the proofs in the encoding of [`proof_to_evm_bytes`],
+//! where the points are uncompressed as the precompiles expect them, and the
+//! public inputs as the `uint256` values of
+//! [`public_inputs_to_evm_words`].
+
+use crate::circuit::{check_pi_positions, PublicInputValue, VerifierData};
+use crate::commitment_scheme::kzg10::OpeningKey;
+use crate::error::Error;
+use crate::fft::EvaluationDomain;
+use crate::permutation::constants::{K1, K2, K3};
+use crate::proof_system::linearisation_poly::ProofEvaluations;
+use crate::proof_system::proof::PROOF_COMMITMENTS;
+use crate::proof_system::Proof;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use dusk_bls12_381::{BlsScalar, G1Affine, G2Affine};
+use dusk_bytes::Serializable;
+use dusk_jubjub::EDWARDS_D;
+
+/// Template of the generated contracts.
+const TEMPLATE: &str = include_str!("PlonkVerifier.sol");
+
+/// Size in bytes of a coordinate of a point of G1 in the encoding of
+/// `dusk-bls12_381`.
+const FQ_SIZE: usize = 48;
+
+/// Size in bytes of a coordinate of a point of G1 in the encoding of
+/// EIP-2537, which pads the 48 bytes of [`FQ_SIZE`] to 64.
+const EVM_FQ_SIZE: usize = 64;
+
+/// Size in bytes of a point of G1 in the encoding of EIP-2537.
+const EVM_G1_SIZE: usize = 2 * EVM_FQ_SIZE;
+
+/// Size in bytes of the encoding of a [`Proof`] returned by
+/// [`proof_to_evm_bytes`].
+pub const EVM_PROOF_SIZE: usize =
+    PROOF_COMMITMENTS * EVM_G1_SIZE + ProofEvaluations::SIZE;
+
+/// Returns the source of a Solidity contract verifying the proofs of the
+/// circuit of the `verifier_data`, generated with the `transcript_init`
+/// label and the [`PublicParameters`] the `opening_key` comes from.
+///
+/// The `verify(bytes proof, uint256[] publicInputs)` function of the
+/// contract returns whether the proof is valid for the public inputs, as
+/// [`PlonkVerifier::verify`](crate::circuit::PlonkVerifier::verify) does,
+/// and rejects malformed inputs as invalid proofs.
+///
+/// Fails with [`Error::DuplicatePublicInputPosition`] or
+/// [`Error::PublicInputPositionOutOfRange`] if the positions of the public
+/// inputs can't be the ones of a circuit of the key.
+///
+/// [`PublicParameters`]: crate::commitment_scheme::kzg10::PublicParameters
+pub fn solidity_verifier(
+    verifier_data: &VerifierData,
+    opening_key: &OpeningKey,
+    transcript_init: &[u8],
+) -> Result<String, Error> {
+    let key = verifier_data.key();
+    let pi_pos = verifier_data.pi_pos();
+
+    let size = key.padded_circuit_size();
+    check_pi_positions(pi_pos)?;
+    if let Some(&position) = pi_pos.iter().find(|&&pos| pos >= size) {
+        return Err(Error::PublicInputPositionOutOfRange { position, size });
+    }
+
+    let domain = EvaluationDomain::new(size)?;
+    let pi_omegas: Vec<u8> = pi_pos
+        .iter()
+        .flat_map(|&pos| {
+            scalar_to_evm(&domain.group_gen_inv.pow(&[pos as u64, 0, 0, 0]))
+        })
+        .collect();
+
+    // In the order of the indices of the selectors in the template
+    let selectors: String = [
+        key.arithmetic.q_m,
+        key.arithmetic.q_l,
+        key.arithmetic.q_r,
+        key.arithmetic.q_o,
+        key.arithmetic.q_4,
+        key.arithmetic.q_c,
+        key.range.q_range,
+        key.logic.q_logic,
+        key.fixed_base.q_fixed_group_add,
+        key.variable_base.q_variable_group_add,
+        key.permutation.fourth_sigma,
+        key.permutation.left_sigma,
+        key.permutation.right_sigma,
+        key.permutation.out_sigma,
+    ]
+    .iter()
+    .map(|commitment| {
+        format!("\n        hex\"{}\"", to_hex(&g1_to_evm(&commitment.0)))
+    })
+    .collect();
+
+    let replacements = [
+        ("{{VERSION}}", String::from(env!("CARGO_PKG_VERSION"))),
+        ("{{N}}", format!("{}", size)),
+        ("{{N_INV}}", scalar_literal(&domain.size_inv)),
+        ("{{OMEGA}}", scalar_literal(&domain.group_gen)),
+        ("{{K1}}", scalar_literal(&K1)),
+        ("{{K2}}", scalar_literal(&K2)),
+        ("{{K3}}", scalar_literal(&K3)),
+        ("{{EDWARDS_D}}", scalar_literal(&EDWARDS_D)),
+        ("{{PUBLIC_INPUTS}}", format!("{}", pi_pos.len())),
+        ("{{PI_OMEGAS}}", to_hex(&pi_omegas)),
+        ("{{TRANSCRIPT_INIT}}", to_hex(transcript_init)),
+        ("{{SELECTORS}}", selectors),
+        ("{{G}}", to_hex(&g1_to_evm(&opening_key.g))),
+        ("{{H}}", to_hex(&g2_to_evm(&opening_key.h))),
+        ("{{BETA_H}}", to_hex(&g2_to_evm(&opening_key.beta_h))),
+    ];
+
+    Ok(replacements
+        .iter()
+        .fold(String::from(TEMPLATE), |contract, (placeholder, value)| {
+            contract.replace(placeholder, value)
+        }))
+}
+
+/// Returns the encoding of the `proof` taken by the contracts of
+/// [`solidity_verifier`], of [`EVM_PROOF_SIZE`] bytes.
+///
+/// The commitments come first, in the order of [`Proof::to_bytes`], each
+/// one as the 128 bytes of its uncompressed encoding in EIP-2537. They're
+/// followed by the evaluations, in the same order, each one as a big-endian
+/// `uint256`.
+pub fn proof_to_evm_bytes(proof: &Proof) -> Vec<u8> {
+    let commitments = [
+        proof.a_comm,
+        proof.b_comm,
+        proof.c_comm,
+        proof.d_comm,
+        proof.z_comm,
+        proof.t_1_comm,
+        proof.t_2_comm,
+        proof.t_3_comm,
+        proof.t_4_comm,
+        proof.w_z_comm,
+        proof.w_zw_comm,
+    ];
+
+    let mut bytes = Vec::with_capacity(EVM_PROOF_SIZE);
+    commitments.iter().for_each(|commitment| {
+        bytes.extend_from_slice(&g1_to_evm(&commitment.0))
+    });
+    proof
+        .evaluations
+        .to_bytes()
+        .chunks(BlsScalar::SIZE)
+        .for_each(|chunk| bytes.extend(chunk.iter().rev()));
+
+    bytes
+}
+
+/// Returns the public inputs of the `values` as the big-endian `uint256`s
+/// taken by the contracts of [`solidity_verifier`], in order.
+///
+/// A [`JubJubAffine`](dusk_jubjub::JubJubAffine) public input takes two
+/// words, its `x` and `y` coordinates.
+pub fn public_inputs_to_evm_words(
+    values: &[PublicInputValue],
+) -> Vec<[u8; 32]> {
+    values
+        .iter()
+        .flat_map(|value| value.iter().map(scalar_to_evm))
+        .collect()
+}
+
+/// Returns the big-endian encoding of the `scalar`.
+fn scalar_to_evm(scalar: &BlsScalar) -> [u8; 32] {
+    let mut bytes = scalar.to_bytes();
+    bytes.reverse();
+    bytes
+}
+
+/// Returns the `scalar` as a Solidity hexadecimal literal.
+fn scalar_literal(scalar: &BlsScalar) -> String {
+    format!("0x{}", to_hex(&scalar_to_evm(scalar)))
+}
+
+/// Returns the encoding of the `point` in EIP-2537, where each coordinate is
+/// padded to 64 bytes and the point at infinity is encoded as zeros.
+fn g1_to_evm(point: &G1Affine) -> [u8; EVM_G1_SIZE] {
+    let mut bytes = [0u8; EVM_G1_SIZE];
+    if bool::from(point.is_identity()) {
+        return bytes;
+    }
+
+    let uncompressed = point.to_uncompressed();
+    uncompressed
+        .chunks(FQ_SIZE)
+        .zip(bytes.chunks_mut(EVM_FQ_SIZE))
+        .for_each(|(fq, chunk)| {
+            chunk[EVM_FQ_SIZE - FQ_SIZE..].copy_from_slice(fq)
+        });
+
+    bytes
+}
+
+/// Returns the encoding of the `point` in EIP-2537, where the `c0`
+/// component of a coordinate comes before its `c1`, unlike in the
+/// uncompressed encoding of `dusk-bls12_381`.
+fn g2_to_evm(point: &G2Affine) -> [u8; 2 * EVM_G1_SIZE] {
+    let mut bytes = [0u8; 2 * EVM_G1_SIZE];
+    if bool::from(point.is_identity()) {
+        return bytes;
+    }
+
+    // x.c1, x.c0, y.c1, y.c0
+    let uncompressed = point.to_uncompressed();
+    let fq = |i: usize| &uncompressed[i * FQ_SIZE..(i + 1) * FQ_SIZE];
+    [fq(1), fq(0), fq(3), fq(2)]
+        .iter()
+        .zip(bytes.chunks_mut(EVM_FQ_SIZE))
+        .for_each(|(fq, chunk)| {
+            chunk[EVM_FQ_SIZE - FQ_SIZE..].copy_from_slice(fq)
+        });
+
+    bytes
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 * bytes.len());
+    bytes.iter().for_each(|byte| {
+        write!(hex, "{:02x}", byte).expect("Writing to a string can't fail")
+    });
+
+    hex
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::Circuit;
+    use crate::commitment_scheme::kzg10::PublicParameters;
+    use crate::test_circuits::MulCircuit;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_solidity_verifier() -> Result<(), Error> {
+        let pp = PublicParameters::setup(1 << 5, &mut OsRng)?;
+        let (_, vd) = MulCircuit::default().compile(&pp)?;
+        // The circuit is compiled to the domain of its gates, which is
+        // smaller than its `padded_circuit_size`
+        let n = vd.key().padded_circuit_size();
+
+        let contract =
+            solidity_verifier(&vd, pp.opening_key(), b"dusk-plonk-evm")?;
+        assert!(!contract.contains("{{"));
+        assert!(
+            contract.contains(&format!("uint256 internal constant N = {};", n))
+        );
+        assert!(
+            contract.contains("uint256 internal constant PUBLIC_INPUTS = 1;")
+        );
+        assert!(contract.contains(&format!(
+            "bytes internal constant TRANSCRIPT_INIT = hex\"{}\";",
+            to_hex(b"dusk-plonk-evm")
+        )));
+
+        // The selectors are baked in the order of the template
+        let domain = EvaluationDomain::new(n)?;
+        let omega = scalar_to_evm(&domain.group_gen_inv.pow(&[
+            vd.pi_pos()[0] as u64,
+            0,
+            0,
+            0,
+        ]));
+        assert!(contract.contains(&format!(
+            "bytes internal constant PI_OMEGAS = hex\"{}\";",
+            to_hex(&omega)
+        )));
+        assert!(contract.contains(&format!(
+            "SELECTORS =\n        hex\"{}\"",
+            to_hex(&g1_to_evm(&vd.key().arithmetic.q_m.0))
+        )));
+        assert!(contract.contains(&format!(
+            "hex\"{}\";",
+            to_hex(&g1_to_evm(&vd.key().permutation.out_sigma.0))
+        )));
+
+        // Should fail since the positions are outside of the circuit
+        let vd = VerifierData::new(*vd.key(), vec![n]);
+        assert!(matches!(
+            solidity_verifier(&vd, pp.opening_key(), b"dusk-plonk-evm"),
+            Err(Error::PublicInputPositionOutOfRange { position, size })
+                if position == n && size == n
+        ));
+
+        // Should fail since a position is repeated
+        let vd = VerifierData::new(*vd.key(), vec![3, 3]);
+        assert!(matches!(
+            solidity_verifier(&vd, pp.opening_key(), b"dusk-plonk-evm"),
+            Err(Error::DuplicatePublicInputPosition { position: 3 })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evm_encodings() -> Result<(), Error> {
+        let pp = PublicParameters::setup(1 << 5, &mut OsRng)?;
+        let (pk, _) = MulCircuit::default().compile(&pp)?;
+        let proof =
+            MulCircuit::valid().gen_proof(&pp, &pk, b"dusk-plonk-evm")?;
+
+        let bytes = proof_to_evm_bytes(&proof);
+        assert_eq!(bytes.len(), EVM_PROOF_SIZE);
+
+        // The coordinates are padded to 64 bytes
+        let a_comm = proof.a_comm.0.to_uncompressed();
+        assert_eq!(&bytes[..16], &[0u8; 16]);
+        assert_eq!(&bytes[16..64], &a_comm[..48]);
+        assert_eq!(&bytes[64..80], &[0u8; 16]);
+        assert_eq!(&bytes[80..128], &a_comm[48..]);
+
+        // The evaluations are big-endian
+        let offset = PROOF_COMMITMENTS * EVM_G1_SIZE;
+        let mut a_eval = proof.evaluations.a_eval.to_bytes();
+        a_eval.reverse();
+        assert_eq!(&bytes[offset..offset + 32], &a_eval);
+        let mut perm_eval = proof.evaluations.perm_eval.to_bytes();
+        perm_eval.reverse();
+        assert_eq!(&bytes[EVM_PROOF_SIZE - 32..], &perm_eval);
+
+        // The point at infinity is encoded as zeros
+        assert_eq!(g1_to_evm(&G1Affine::identity()), [0u8; EVM_G1_SIZE]);
+
+        // The G2 coordinates are in the order of EIP-2537
+        let h = pp.opening_key().h.to_uncompressed();
+        let evm_h = g2_to_evm(&pp.opening_key().h);
+        assert_eq!(&evm_h[16..64], &h[48..96]);
+        assert_eq!(&evm_h[80..128], &h[..48]);
+        assert_eq!(&evm_h[144..192], &h[144..]);
+        assert_eq!(&evm_h[208..], &h[96..144]);
+
+        let words = public_inputs_to_evm_words(&[
+            BlsScalar::from(15u64).into(),
+            dusk_jubjub::JubJubAffine::identity().into(),
+        ]);
+        assert_eq!(words.len(), 3);
+        assert_eq!(words[0][31], 15);
+        assert_eq!(words[2][31], 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,344 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Sketch of an inner product argument polynomial commitment scheme over
+//! BLS12-381 G1, in the fashion of Bulletproofs.
+//!
+//! A polynomial `p` with coefficients `a` is committed to as the Pedersen
+//! vector commitment `C = <a, G>`. Its evaluation `v = <a, b>` at `z`, with
+//! `b = (1, z, z^2, ...)`, is proven by folding `a`, `b` and `G` in halves
+//! for `log2(n)` rounds, sending the cross terms `L` and `R` of each round.
+//!
+//! This scheme doesn't need a trusted setup, but its openings are
+//! logarithmic in size and take a linear time to verify. It isn't used by
+//! the proof system and is only meant to exercise the
+//! [`PolyCommitmentScheme`] abstraction: it has not been audited, and the
+//! generators of [`IpaParams::setup`] are random multiples of the G1
+//! generator whose discrete logarithms are known to whoever samples them.
+
+use crate::commitment_scheme::kzg10::Commitment;
+use crate::commitment_scheme::PolyCommitmentScheme;
+use crate::error::Error;
+use crate::transcript::TranscriptProtocol;
+use crate::util;
+use alloc::vec::Vec;
+use dusk_bls12_381::{
+    multiscalar_mul::msm_variable_base, BlsScalar, G1Affine, G1Projective,
+};
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+
+/// Generators of an [`IpaScheme`], used both to commit and to verify.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IpaParams {
+    generators: Vec<G1Affine>,
+    u: G1Affine,
+}
+
+impl IpaParams {
+    /// Samples the generators to commit to polynomials with up to
+    /// `2^log_size` coefficients.
+    pub fn setup<R: RngCore + CryptoRng>(log_size: u32, rng: &mut R) -> Self {
+        let generators = (0..1usize << log_size)
+            .map(|_| util::random_g1_point(rng).into())
+            .collect();
+        let u = util::random_g1_point(rng).into();
+
+        Self { generators, u }
+    }
+
+    /// Returns the maximum number of coefficients of the polynomials that
+    /// can be committed to.
+    pub fn size(&self) -> usize {
+        self.generators.len()
+    }
+}
+
+/// Opening of an [`IpaScheme`] commitment, holding the cross terms of each
+/// folding round and the last remaining coefficient.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IpaOpening {
+    l: Vec<G1Affine>,
+    r: Vec<G1Affine>,
+    a: BlsScalar,
+}
+
+/// Inner product argument commitments, see the [module](self) documentation.
+#[derive(Debug, Clone, Copy)]
+pub struct IpaScheme;
+
+impl IpaScheme {
+    /// Binds the claim to the transcript and returns the point `U'` the
+    /// inner product is committed to.
+    fn claim_base(
+        params: &IpaParams,
+        commitment: &G1Affine,
+        point: BlsScalar,
+        value: BlsScalar,
+        transcript: &mut Transcript,
+    ) -> G1Projective {
+        transcript
+            .append_commitment(b"ipa_commitment", &Commitment(*commitment));
+        transcript.append_scalar(b"ipa_point", &point);
+        transcript.append_scalar(b"ipa_value", &value);
+
+        params.u * transcript.challenge_scalar(b"ipa_base")
+    }
+
+    /// Returns the powers `(1, z, z^2, ...)` of the `point`, as many as
+    /// there are generators.
+    fn evaluation_vector(
+        params: &IpaParams,
+        point: BlsScalar,
+    ) -> Vec<BlsScalar> {
+        util::powers_of(&point, params.size() - 1)
+    }
+}
+
+fn inner_product(a: &[BlsScalar], b: &[BlsScalar]) -> BlsScalar {
+    a.iter()
+        .zip(b)
+        .fold(BlsScalar::zero(), |acc, (a, b)| acc + a * b)
+}
+
+impl PolyCommitmentScheme for IpaScheme {
+    type CommitmentKey = IpaParams;
+    type VerificationKey = IpaParams;
+    type Commitment = G1Affine;
+    type Opening = IpaOpening;
+
+    fn commit(
+        params: &IpaParams,
+        coeffs: &[BlsScalar],
+    ) -> Result<G1Affine, Error> {
+        if coeffs.len() > params.size() {
+            return Err(Error::PolynomialDegreeTooLarge);
+        }
+
+        Ok(msm_variable_base(&params.generators, coeffs).into())
+    }
+
+    fn open(
+        params: &IpaParams,
+        coeffs: &[BlsScalar],
+        commitment: &G1Affine,
+        point: BlsScalar,
+        transcript: &mut Transcript,
+    ) -> Result<(BlsScalar, IpaOpening), Error> {
+        if coeffs.len() > params.size() {
+            return Err(Error::PolynomialDegreeTooLarge);
+        }
+
+        let mut a = coeffs.to_vec();
+        a.resize(params.size(), BlsScalar::zero());
+        let mut b = Self::evaluation_vector(params, point);
+        let mut g = params.generators.clone();
+
+        let value = inner_product(&a, &b);
+        let u = Self::claim_base(params, commitment, point, value, transcript);
+        let u = G1Affine::from(u);
+
+        let mut l = Vec::new();
+        let mut r = Vec::new();
+        while a.len() > 1 {
+            let half = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(half);
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = g.split_at(half);
+
+            let l_i =
+                msm_variable_base(g_hi, a_lo) + u * inner_product(a_lo, b_hi);
+            let r_i =
+                msm_variable_base(g_lo, a_hi) + u * inner_product(a_hi, b_lo);
+            let l_i = G1Affine::from(l_i);
+            let r_i = G1Affine::from(r_i);
+
+            transcript.append_commitment(b"ipa_l", &Commitment(l_i));
+            transcript.append_commitment(b"ipa_r", &Commitment(r_i));
+            let x = transcript.challenge_scalar(b"ipa_round");
+            let x_inv = x.invert().ok_or(Error::DivisionByZero)?;
+
+            a = a_lo
+                .iter()
+                .zip(a_hi)
+                .map(|(lo, hi)| lo * x + hi * x_inv)
+                .collect();
+            b = b_lo
+                .iter()
+                .zip(b_hi)
+                .map(|(lo, hi)| lo * x_inv + hi * x)
+                .collect();
+            g = g_lo
+                .iter()
+                .zip(g_hi)
+                .map(|(lo, hi)| G1Affine::from(lo * x_inv + hi * x))
+                .collect();
+
+            l.push(l_i);
+            r.push(r_i);
+        }
+
+        Ok((value, IpaOpening { l, r, a: a[0] }))
+    }
+
+    fn verify_opening(
+        params: &IpaParams,
+        commitment: &G1Affine,
+        point: BlsScalar,
+        value: BlsScalar,
+        opening: &IpaOpening,
+        transcript: &mut Transcript,
+    ) -> Result<(), Error> {
+        let rounds = params.size().trailing_zeros() as usize;
+        if opening.l.len() != rounds || opening.r.len() != rounds {
+            return Err(Error::ProofVerificationError);
+        }
+
+        let u = Self::claim_base(params, commitment, point, value, transcript);
+        let mut p = G1Projective::from(commitment) + u * value;
+        let mut b = Self::evaluation_vector(params, point);
+        let mut g: Vec<G1Projective> =
+            params.generators.iter().map(G1Projective::from).collect();
+
+        for (l_i, r_i) in opening.l.iter().zip(opening.r.iter()) {
+            transcript.append_commitment(b"ipa_l", &Commitment(*l_i));
+            transcript.append_commitment(b"ipa_r", &Commitment(*r_i));
+            let x = transcript.challenge_scalar(b"ipa_round");
+            let x_inv = x.invert().ok_or(Error::DivisionByZero)?;
+
+            p += l_i * x.square() + r_i * x_inv.square();
+
+            let half = b.len() / 2;
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = g.split_at(half);
+            b = b_lo
+                .iter()
+                .zip(b_hi)
+                .map(|(lo, hi)| lo * x_inv + hi * x)
+                .collect();
+            g = g_lo
+                .iter()
+                .zip(g_hi)
+                .map(|(lo, hi)| lo * x_inv + hi * x)
+                .collect();
+        }
+
+        match p == g[0] * opening.a + u * (opening.a * b[0]) {
+            true => Ok(()),
+            false => Err(Error::ProofVerificationError),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_ipa_scheme() -> Result<(), Error> {
+        let params = IpaParams::setup(4, &mut OsRng);
+
+        let claims: Vec<(G1Affine, BlsScalar, BlsScalar, IpaOpening)> =
+            [1, 5, 16]
+                .iter()
+                .map(|len| {
+                    let coeffs: Vec<BlsScalar> = (0..*len)
+                        .map(|_| BlsScalar::random(&mut OsRng))
+                        .collect();
+                    let point = BlsScalar::random(&mut OsRng);
+
+                    let commitment = IpaScheme::commit(&params, &coeffs)?;
+                    let (value, opening) = IpaScheme::open(
+                        &params,
+                        &coeffs,
+                        &commitment,
+                        point,
+                        &mut Transcript::new(b"ipa"),
+                    )?;
+
+                    let expected = coeffs
+                        .iter()
+                        .rev()
+                        .fold(BlsScalar::zero(), |acc, c| acc * point + c);
+                    assert_eq!(value, expected);
+
+                    Ok((commitment, point, value, opening))
+                })
+                .collect::<Result<_, Error>>()?;
+
+        for (commitment, point, value, opening) in claims.iter() {
+            IpaScheme::verify_opening(
+                &params,
+                commitment,
+                *point,
+                *value,
+                opening,
+                &mut Transcript::new(b"ipa"),
+            )?;
+
+            // Should fail since the value is not the evaluation
+            assert!(IpaScheme::verify_opening(
+                &params,
+                commitment,
+                *point,
+                value + BlsScalar::one(),
+                opening,
+                &mut Transcript::new(b"ipa"),
+            )
+            .is_err());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ipa_batch_verify() -> Result<(), Error> {
+        let params = IpaParams::setup(3, &mut OsRng);
+        let coeffs: Vec<BlsScalar> =
+            (0..8).map(|_| BlsScalar::random(&mut OsRng)).collect();
+        let commitment = IpaScheme::commit(&params, &coeffs)?;
+
+        // The claims are opened and verified on the same transcript
+        let points = [BlsScalar::from(2u64), BlsScalar::from(3u64)];
+        let mut transcript = Transcript::new(b"ipa_batch");
+        let mut claims = Vec::new();
+        for point in points.iter() {
+            let (value, opening) = IpaScheme::open(
+                &params,
+                &coeffs,
+                &commitment,
+                *point,
+                &mut transcript,
+            )?;
+            claims.push((commitment, *point, value, opening));
+        }
+
+        IpaScheme::batch_verify(
+            &params,
+            &claims,
+            &mut Transcript::new(b"ipa_batch"),
+        )?;
+
+        claims[0].2 += BlsScalar::one();
+        assert!(IpaScheme::batch_verify(
+            &params,
+            &claims,
+            &mut Transcript::new(b"ipa_batch"),
+        )
+        .is_err());
+
+        // Committing to more coefficients than generators fails
+        let coeffs = vec![BlsScalar::one(); 9];
+        assert!(matches!(
+            IpaScheme::commit(&params, &coeffs),
+            Err(Error::PolynomialDegreeTooLarge)
+        ));
+
+        Ok(())
+    }
+}
@@ -8,27 +8,74 @@
 //! that support the generation and usage of Commit and
 //! Opening keys.
 use super::{proof::Proof, Commitment};
+#[cfg(feature = "std")]
+use crate::backend::BackendHandle;
+#[cfg(not(feature = "std"))]
+use crate::backend::CpuBackend;
 use crate::{
-    error::Error, fft::Polynomial, transcript::TranscriptProtocol, util,
+    backend::ComputeBackend, error::Error, fft::Polynomial,
+    transcript::TranscriptProtocol, util,
 };
 use alloc::vec::Vec;
-use dusk_bls12_381::{
-    multiscalar_mul::msm_variable_base, BlsScalar, G1Affine, G1Projective,
-    G2Affine, G2Prepared,
-};
+use dusk_bls12_381::{BlsScalar, G1Affine, G1Projective, G2Affine, G2Prepared};
 use dusk_bytes::{DeserializableSlice, Serializable};
 use merlin::Transcript;
+#[cfg(feature = "std")]
+use std::sync::Arc;
 
 /// CommitKey is used to commit to a polynomial which is bounded by the
 /// max_degree.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct CommitKey {
     /// Group elements of the form `{ \beta^i G }`, where `i` ranges from 0 to
     /// `degree`.
     pub(crate) powers_of_g: Vec<G1Affine>,
+    /// Backend computing the MSMs and FFTs performed with this key.
+    #[cfg(feature = "std")]
+    pub(crate) backend: BackendHandle,
+}
+
+impl PartialEq for CommitKey {
+    fn eq(&self, other: &Self) -> bool {
+        // The backend doesn't change the result of the computations
+        self.powers_of_g == other.powers_of_g
+    }
 }
 
 impl CommitKey {
+    /// Creates a [`CommitKey`] from the powers of G1 using the
+    /// [`CpuBackend`](crate::backend::CpuBackend).
+    pub(crate) fn new(powers_of_g: Vec<G1Affine>) -> Self {
+        CommitKey {
+            powers_of_g,
+            #[cfg(feature = "std")]
+            backend: BackendHandle::default(),
+        }
+    }
+
+    /// Sets the [`ComputeBackend`] used to compute the multi-scalar
+    /// multiplications and FFTs of the proofs created with this key.
+    ///
+    /// The backend is kept when the key is trimmed.
+    #[cfg(feature = "std")]
+    pub fn with_backend(mut self, backend: Arc<dyn ComputeBackend>) -> Self {
+        self.backend = BackendHandle(backend);
+        self
+    }
+
+    /// Returns the [`ComputeBackend`] attached to this key.
+    pub(crate) fn backend(&self) -> &dyn ComputeBackend {
+        #[cfg(feature = "std")]
+        {
+            self.backend.0.as_ref()
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            &CpuBackend
+        }
+    }
+
     /// Serialize the [`CommitKey`] into bytes.
     ///
     /// This operation is designed to store the raw representation of the
@@ -79,10 +126,20 @@ impl CommitKey {
             .map(|(c, _)| G1Affine::from_slice_unchecked(c))
             .collect();
 
-        Self { powers_of_g }
+        Self::new(powers_of_g)
     }
 
     /// Serialises the [`CommitKey`] into a byte slice.
+    ///
+    /// Along with [`OpeningKey::to_bytes`], this allows to distribute each
+    /// half of the keys returned by [`PublicParameters::trim`] on its own:
+    /// provers only need the [`CommitKey`], see
+    /// [`Circuit::gen_proof_with_commit_key`], and verifiers only the
+    /// [`OpeningKey`].
+    ///
+    /// [`PublicParameters::trim`]: super::PublicParameters::trim
+    /// [`Circuit::gen_proof_with_commit_key`]:
+    /// crate::circuit::Circuit::gen_proof_with_commit_key
     pub fn to_var_bytes(&self) -> Vec<u8> {
         self.powers_of_g
             .iter()
@@ -100,12 +157,17 @@ impl CommitKey {
     /// file, we recommend to use [`CommitKey::from_slice_unchecked`] and
     /// [`CommitKey::to_raw_var_bytes`].
     pub fn from_slice(bytes: &[u8]) -> Result<CommitKey, Error> {
+        // A key without any power can't commit to anything
+        if bytes.is_empty() {
+            return Err(Error::NotEnoughBytes);
+        }
+
         let powers_of_g = bytes
             .chunks(G1Affine::SIZE)
             .map(|chunk| G1Affine::from_slice(chunk))
             .collect::<Result<Vec<G1Affine>, dusk_bytes::Error>>()?;
 
-        Ok(CommitKey { powers_of_g })
+        Ok(CommitKey::new(powers_of_g))
     }
 
     /// Returns the maximum degree polynomial that you can commit to.
@@ -136,6 +198,8 @@ impl CommitKey {
 
         let truncated_powers = Self {
             powers_of_g: self.powers_of_g[..=truncated_degree].to_vec(),
+            #[cfg(feature = "std")]
+            backend: self.backend.clone(),
         };
 
         Ok(truncated_powers)
@@ -169,10 +233,14 @@ impl CommitKey {
         self.check_commit_degree_is_within_bounds(polynomial.degree())?;
 
         // Compute commitment
-        Ok(Commitment::from(msm_variable_base(
-            &self.powers_of_g,
-            &polynomial.coeffs,
-        )))
+        //
+        // XXX: Unless the `constant_time` feature is enabled, the MSM of the
+        // CPU backend uses a bucket method which is variable-time on the
+        // scalars, which are the coefficients of the witness polynomials when
+        // committing to them.
+        Ok(Commitment::from(
+            self.backend().msm(&self.powers_of_g, &polynomial.coeffs),
+        ))
     }
 
     /// Computes a single witness for multiple polynomials at the same point, by
@@ -184,8 +252,9 @@ impl CommitKey {
         polynomials: &[Polynomial],
         point: &BlsScalar,
         transcript: &mut Transcript,
+        label: &'static [u8],
     ) -> Polynomial {
-        let challenge = transcript.challenge_scalar(b"aggregate_witness");
+        let challenge = transcript.challenge_scalar(label);
         let powers = util::powers_of(&challenge, polynomials.len() - 1);
 
         assert_eq!(powers.len(), polynomials.len());
@@ -264,11 +333,36 @@ impl OpeningKey {
         points: &[BlsScalar],
         proofs: &[Proof],
         transcript: &mut Transcript,
+        label: &'static [u8],
     ) -> Result<(), Error> {
+        let (affine_total_w, affine_total_c) =
+            self.pairing_inputs(points, proofs, transcript, label);
+
+        let pairing = dusk_bls12_381::multi_miller_loop(&[
+            (&affine_total_w, &self.prepared_beta_h),
+            (&affine_total_c, &self.prepared_h),
+        ])
+        .final_exponentiation();
+
+        if pairing != dusk_bls12_381::Gt::identity() {
+            return Err(Error::PairingCheckFailure);
+        };
+        Ok(())
+    }
+
+    /// Returns the points `(W, C)` of G1 folding the batch of openings, which
+    /// are valid if `e(W, beta_h) * e(C, h)` is the identity.
+    pub(crate) fn pairing_inputs(
+        &self,
+        points: &[BlsScalar],
+        proofs: &[Proof],
+        transcript: &mut Transcript,
+        label: &'static [u8],
+    ) -> (G1Affine, G1Affine) {
         let mut total_c = G1Projective::identity();
         let mut total_w = G1Projective::identity();
 
-        let challenge = transcript.challenge_scalar(b"batch"); // XXX: Verifier can add their own randomness at this point
+        let challenge = transcript.challenge_scalar(label); // XXX: Verifier can add their own randomness at this point
         let powers = util::powers_of(&challenge, proofs.len() - 1);
         // Instead of multiplying g and gamma_g in each turn, we simply
         // accumulate their coefficients and perform a final
@@ -287,19 +381,7 @@ impl OpeningKey {
         }
         total_c -= self.g * g_multiplier;
 
-        let affine_total_w = G1Affine::from(-total_w);
-        let affine_total_c = G1Affine::from(total_c);
-
-        let pairing = dusk_bls12_381::multi_miller_loop(&[
-            (&affine_total_w, &self.prepared_beta_h),
-            (&affine_total_c, &self.prepared_h),
-        ])
-        .final_exponentiation();
-
-        if pairing != dusk_bls12_381::Gt::identity() {
-            return Err(Error::PairingCheckFailure);
-        };
-        Ok(())
+        (G1Affine::from(-total_w), G1Affine::from(total_c))
     }
 }
 
@@ -309,6 +391,7 @@ mod test {
     use super::*;
     use crate::commitment_scheme::kzg10::{AggregateProof, PublicParameters};
     use crate::fft::Polynomial;
+    use crate::transcript::{U_LABEL, V_LABEL};
     use dusk_bls12_381::BlsScalar;
     use dusk_bytes::Serializable;
     use merlin::Transcript;
@@ -368,8 +451,12 @@ mod test {
         }
 
         // Compute the aggregate witness for polynomials
-        let witness_poly =
-            ck.compute_aggregate_witness(polynomials, point, transcript);
+        let witness_poly = ck.compute_aggregate_witness(
+            polynomials,
+            point,
+            transcript,
+            V_LABEL,
+        );
 
         // Commit to witness polynomial
         let witness_commitment = ck.commit(&witness_poly)?;
@@ -441,6 +528,7 @@ mod test {
             &[point_a, point_b],
             &[proof_a, proof_b],
             &mut Transcript::new(b""),
+            U_LABEL,
         )
     }
     #[test]
@@ -472,8 +560,8 @@ mod test {
 
         // Verifier's View
         let ok = {
-            let flattened_proof =
-                aggregated_proof.flatten(&mut Transcript::new(b"agg_flatten"));
+            let flattened_proof = aggregated_proof
+                .flatten(&mut Transcript::new(b"agg_flatten"), V_LABEL);
             check(&opening_key, point, flattened_proof)
         };
 
@@ -520,12 +608,14 @@ mod test {
         // Verifier's View
 
         let mut transcript = Transcript::new(b"agg_batch");
-        let flattened_proof = aggregated_proof.flatten(&mut transcript);
+        let flattened_proof =
+            aggregated_proof.flatten(&mut transcript, V_LABEL);
 
         opening_key.batch_check(
             &[point_a, point_b],
             &[flattened_proof, single_proof],
             &mut transcript,
+            U_LABEL,
         )
     }
 
@@ -539,6 +629,19 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn commit_key_from_invalid_slice() -> Result<(), Error> {
+        let (commit_key, _) = setup_test(7)?;
+        let ck_bytes = commit_key.to_var_bytes();
+
+        assert!(matches!(
+            CommitKey::from_slice(&[]),
+            Err(Error::NotEnoughBytes)
+        ));
+        assert!(CommitKey::from_slice(&ck_bytes[1..]).is_err());
+        Ok(())
+    }
+
     #[test]
     fn opening_key_dusk_bytes() -> Result<(), Error> {
         let (_, opening_key) = setup_test(7)?;
@@ -10,9 +10,11 @@ cfg_if::cfg_if!(
 if #[cfg(feature = "alloc")]
 {
     pub mod key;
+    mod scheme;
     pub mod srs;
     pub use key::{CommitKey, OpeningKey};
     pub(crate) use proof::alloc::AggregateProof;
+    pub use scheme::Kzg10Scheme;
     pub use srs::PublicParameters;
 });
 
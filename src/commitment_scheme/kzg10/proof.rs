@@ -65,8 +65,12 @@ pub(crate) mod alloc {
         /// Flattens an `AggregateProof` into a `Proof`.
         /// The transcript must have the same view as the transcript that was
         /// used to aggregate the witness in the proving stage.
-        pub(crate) fn flatten(&self, transcript: &mut Transcript) -> Proof {
-            let challenge = transcript.challenge_scalar(b"aggregate_witness");
+        pub(crate) fn flatten(
+            &self,
+            transcript: &mut Transcript,
+            label: &'static [u8],
+        ) -> Proof {
+            let challenge = transcript.challenge_scalar(label);
             let powers = powers_of(
                 &challenge,
                 self.commitments_to_polynomials.len() - 1,
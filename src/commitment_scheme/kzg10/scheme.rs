@@ -0,0 +1,170 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! [`PolyCommitmentScheme`] implementation of KZG10.
+
+use super::proof::Proof;
+use super::{CommitKey, Commitment, OpeningKey};
+use crate::commitment_scheme::PolyCommitmentScheme;
+use crate::error::Error;
+use crate::fft::Polynomial;
+use crate::transcript::U_LABEL;
+use alloc::vec::Vec;
+use dusk_bls12_381::{BlsScalar, G1Affine};
+use merlin::Transcript;
+
+/// KZG10 commitments, as used by the proof system.
+///
+/// The opening is the commitment to the witness polynomial
+/// `(p(x) - p(z)) / (x - z)`, and batches of openings are checked with a
+/// single pairing equation.
+#[derive(Debug, Clone, Copy)]
+pub struct Kzg10Scheme;
+
+impl PolyCommitmentScheme for Kzg10Scheme {
+    type CommitmentKey = CommitKey;
+    type VerificationKey = OpeningKey;
+    type Commitment = G1Affine;
+    type Opening = G1Affine;
+
+    fn commit(ck: &CommitKey, coeffs: &[BlsScalar]) -> Result<G1Affine, Error> {
+        ck.commit(&Polynomial::from_coefficients_slice(coeffs))
+            .map(|commitment| commitment.0)
+    }
+
+    fn open(
+        ck: &CommitKey,
+        coeffs: &[BlsScalar],
+        _commitment: &G1Affine,
+        point: BlsScalar,
+        _transcript: &mut Transcript,
+    ) -> Result<(BlsScalar, G1Affine), Error> {
+        let polynomial = Polynomial::from_coefficients_slice(coeffs);
+        if polynomial.degree() > ck.max_degree() {
+            return Err(Error::PolynomialDegreeTooLarge);
+        }
+
+        // The witness has a lower degree than the polynomial, so it's
+        // committed without the bounds check, which rejects constants
+        let witness = polynomial.ruffini(point);
+        let witness = ck.backend().msm(&ck.powers_of_g, &witness.coeffs);
+
+        Ok((polynomial.evaluate(&point), witness.into()))
+    }
+
+    fn verify_opening(
+        vk: &OpeningKey,
+        commitment: &G1Affine,
+        point: BlsScalar,
+        value: BlsScalar,
+        opening: &G1Affine,
+        transcript: &mut Transcript,
+    ) -> Result<(), Error> {
+        Self::batch_verify(
+            vk,
+            &[(*commitment, point, value, *opening)],
+            transcript,
+        )
+    }
+
+    fn batch_verify(
+        vk: &OpeningKey,
+        claims: &[(G1Affine, BlsScalar, BlsScalar, G1Affine)],
+        transcript: &mut Transcript,
+    ) -> Result<(), Error> {
+        if claims.is_empty() {
+            return Ok(());
+        }
+
+        let points: Vec<BlsScalar> =
+            claims.iter().map(|(_, point, _, _)| *point).collect();
+        let proofs: Vec<Proof> = claims
+            .iter()
+            .map(|(commitment, _, value, opening)| Proof {
+                commitment_to_witness: Commitment(*opening),
+                evaluated_point: *value,
+                commitment_to_polynomial: Commitment(*commitment),
+            })
+            .collect();
+
+        vk.batch_check(&points, &proofs, transcript, U_LABEL)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment_scheme::kzg10::PublicParameters;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_kzg10_scheme() -> Result<(), Error> {
+        let pp = PublicParameters::setup(16, &mut OsRng)?;
+        let (ck, vk) = pp.trim(16)?;
+
+        let claims: Vec<(G1Affine, BlsScalar, BlsScalar, G1Affine)> = (1..4)
+            .map(|degree| {
+                let coeffs: Vec<BlsScalar> = (0..=degree)
+                    .map(|_| BlsScalar::random(&mut OsRng))
+                    .collect();
+                let point = BlsScalar::random(&mut OsRng);
+
+                let commitment = Kzg10Scheme::commit(&ck, &coeffs)?;
+                let (value, opening) = Kzg10Scheme::open(
+                    &ck,
+                    &coeffs,
+                    &commitment,
+                    point,
+                    &mut Transcript::new(b"kzg10"),
+                )?;
+                assert_eq!(
+                    value,
+                    Polynomial::from_coefficients_vec(coeffs).evaluate(&point)
+                );
+
+                Ok((commitment, point, value, opening))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        for (commitment, point, value, opening) in claims.iter() {
+            Kzg10Scheme::verify_opening(
+                &vk,
+                commitment,
+                *point,
+                *value,
+                opening,
+                &mut Transcript::new(b"kzg10"),
+            )?;
+            assert!(Kzg10Scheme::verify_opening(
+                &vk,
+                commitment,
+                *point,
+                value + BlsScalar::one(),
+                opening,
+                &mut Transcript::new(b"kzg10"),
+            )
+            .is_err());
+        }
+
+        Kzg10Scheme::batch_verify(
+            &vk,
+            &claims,
+            &mut Transcript::new(b"kzg10"),
+        )?;
+
+        let mut wrong = claims.clone();
+        wrong[1].2 += BlsScalar::one();
+        assert!(Kzg10Scheme::batch_verify(
+            &vk,
+            &wrong,
+            &mut Transcript::new(b"kzg10")
+        )
+        .is_err());
+
+        Ok(())
+    }
+}
@@ -7,11 +7,36 @@
 //! The Public Parameters can also be referred to as the Structured Reference
 //! String (SRS).
 use super::key::{CommitKey, OpeningKey};
-use crate::{error::Error, util};
+#[cfg(feature = "std")]
+use crate::backend::ComputeBackend;
+use crate::{error::Error, transcript::TranscriptProtocol, util};
 use alloc::vec::Vec;
-use dusk_bls12_381::{G1Affine, G1Projective, G2Affine};
+use dusk_bls12_381::{
+    multiscalar_mul::msm_variable_base, BlsScalar, G1Affine, G1Projective,
+    G2Affine,
+};
 use dusk_bytes::{DeserializableSlice, Serializable};
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+use merlin::Transcript;
 use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "mmap")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+#[cfg(feature = "mmap")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+/// Size exponent of the Powers of Tau ceremony over BLS12-381, which holds
+/// `2^21` powers of tau in G2 and `2^22 - 1` in G1.
+pub const PPOT_POWER: u32 = 21;
+
+/// Length of the hash of the previous challenge heading a Powers of Tau
+/// response.
+#[cfg(feature = "std")]
+const PPOT_HASH_SIZE: usize = 64;
 
 /// The Public Parameters can also be referred to as the Structured Reference
 /// String (SRS). It is available to both the prover and verifier and allows the
@@ -38,6 +63,14 @@ impl PublicParameters {
         &self.opening_key
     }
 
+    /// Sets the [`ComputeBackend`] of the [`CommitKey`] contained in the
+    /// `PublicParameters` instance, which is kept by the trimmed keys.
+    #[cfg(feature = "std")]
+    pub fn with_backend(mut self, backend: Arc<dyn ComputeBackend>) -> Self {
+        self.commit_key = self.commit_key.with_backend(backend);
+        self
+    }
+
     /// Setup generates the public parameters using a random number generator.
     /// This method will in most cases be used for testing and exploration.
     /// In reality, a `Trusted party` or a `Multiparty Computation` will used to
@@ -55,6 +88,15 @@ impl PublicParameters {
         // Generate the secret scalar beta
         let beta = util::random_scalar(&mut rng);
 
+        Ok(Self::from_secret(max_degree, beta, rng))
+    }
+
+    /// Generates the public parameters for the secret scalar `beta`.
+    fn from_secret<R: RngCore + CryptoRng>(
+        max_degree: usize,
+        beta: BlsScalar,
+        mut rng: &mut R,
+    ) -> PublicParameters {
         // Compute powers of beta up to and including beta^max_degree
         let powers_of_beta = util::powers_of(&beta, max_degree);
 
@@ -73,11 +115,181 @@ impl PublicParameters {
         let h: G2Affine = util::random_g2_point(&mut rng).into();
         let beta_h: G2Affine = (h * beta).into();
 
-        Ok(PublicParameters {
-            commit_key: CommitKey {
-                powers_of_g: normalised_g,
-            },
+        PublicParameters {
+            commit_key: CommitKey::new(normalised_g),
             opening_key: OpeningKey::new(g.into(), h, beta_h),
+        }
+    }
+
+    /// Returns new [`PublicParameters`] with the `extra_powers_g1` appended
+    /// to the powers of the [`CommitKey`], increasing the max degree by the
+    /// number of extra powers. Extending by an empty slice returns a copy of
+    /// the current parameters.
+    ///
+    /// This allows to support larger circuits without discarding the
+    /// existing trusted material, as long as whoever holds the secret scalar
+    /// beta (or runs the ceremony) provides the next powers in order.
+    ///
+    /// Every extra power is checked to be beta times the previous one via
+    /// the pairing equation `e(P_{i+1}, H) = e(P_i, beta * H)`. The checks are
+    /// batched into a single one using a random linear combination derived
+    /// from the powers. Returns [`Error::PairingCheckFailure`] if any of the
+    /// extra powers is not consistent with the existing ones.
+    pub fn try_extend_from(
+        &self,
+        extra_powers_g1: &[G1Affine],
+    ) -> Result<PublicParameters, Error> {
+        let mut extended = self.clone();
+        if extra_powers_g1.is_empty() {
+            return Ok(extended);
+        }
+
+        let powers_of_g = &self.commit_key.powers_of_g;
+        let last = powers_of_g[powers_of_g.len() - 1];
+
+        let mut transcript = Transcript::new(b"extend_srs");
+        transcript.append_message(b"last", &last.to_bytes());
+        extra_powers_g1
+            .iter()
+            .for_each(|p| transcript.append_message(b"extra", &p.to_bytes()));
+        let challenge = transcript.challenge_scalar(b"extend");
+        let coeffs = util::powers_of(&challenge, extra_powers_g1.len() - 1);
+
+        // Each extra power is paired with the one preceding it
+        let mut previous = Vec::with_capacity(extra_powers_g1.len());
+        previous.push(last);
+        previous
+            .extend_from_slice(&extra_powers_g1[..extra_powers_g1.len() - 1]);
+
+        let next = G1Affine::from(-msm_variable_base(extra_powers_g1, &coeffs));
+        let previous = G1Affine::from(msm_variable_base(&previous, &coeffs));
+
+        let pairing = dusk_bls12_381::multi_miller_loop(&[
+            (&next, &self.opening_key.prepared_h),
+            (&previous, &self.opening_key.prepared_beta_h),
+        ])
+        .final_exponentiation();
+
+        if pairing != dusk_bls12_381::Gt::identity() {
+            return Err(Error::PairingCheckFailure);
+        }
+
+        extended
+            .commit_key
+            .powers_of_g
+            .extend_from_slice(extra_powers_g1);
+
+        Ok(extended)
+    }
+
+    /// Loads the [`PublicParameters`] of the given `max_degree` from a
+    /// response of the Powers of Tau ceremony over BLS12-381, which holds
+    /// `2^PPOT_POWER` powers of tau, see [`PPOT_POWER`].
+    ///
+    /// See [`PublicParameters::from_ppot_with_power`] for the format and
+    /// the checks performed on the powers.
+    #[cfg(feature = "std")]
+    pub fn from_ppot<R: Read>(
+        reader: R,
+        max_degree: usize,
+    ) -> Result<PublicParameters, Error> {
+        Self::from_ppot_with_power(reader, PPOT_POWER, max_degree)
+    }
+
+    /// Loads the [`PublicParameters`] of the given `max_degree` from a
+    /// response of a Powers of Tau ceremony of size `2^power`.
+    ///
+    /// The response starts with the 64 bytes BLAKE2b hash of the challenge,
+    /// followed by the `2^(power + 1) - 1` powers of tau in G1 and the
+    /// `2^power` powers of tau in G2, all of them in compressed form. Only
+    /// the first `max_degree + 1` powers in G1 and the first two powers in
+    /// G2 are read, so the rest of the response, including the powers of
+    /// alpha and beta, can be missing.
+    ///
+    /// Returns [`Error::DegreeIsZero`] if `max_degree` is zero and
+    /// [`Error::PpotDegreeTooLarge`] if the ceremony doesn't hold enough
+    /// powers for it. Returns [`Error::NotEnoughBytes`] if the response is
+    /// truncated before the last power that is read. The powers are checked
+    /// to be consecutive powers of the tau in G2 as in
+    /// [`PublicParameters::try_extend_from`], returning
+    /// [`Error::PairingCheckFailure`] otherwise.
+    #[cfg(feature = "std")]
+    pub fn from_ppot_with_power<R: Read>(
+        mut reader: R,
+        power: u32,
+        max_degree: usize,
+    ) -> Result<PublicParameters, Error> {
+        if max_degree < 1 {
+            return Err(Error::DegreeIsZero);
+        }
+
+        let powers_g1 = (1usize << (power + 1)) - 1;
+        if max_degree >= powers_g1 {
+            return Err(Error::PpotDegreeTooLarge {
+                degree: max_degree,
+                max: powers_g1 - 1,
+            });
+        }
+
+        skip_bytes(&mut reader, PPOT_HASH_SIZE)?;
+
+        let mut buf = [0u8; G1Affine::SIZE];
+        let powers_of_g = (0..=max_degree)
+            .map(|_| {
+                reader
+                    .read_exact(&mut buf)
+                    .map_err(|_| Error::NotEnoughBytes)?;
+                Ok(G1Affine::from_bytes(&buf)?)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let remaining = powers_g1 - max_degree - 1;
+        skip_bytes(&mut reader, remaining * G1Affine::SIZE)?;
+
+        let mut buf = [0u8; G2Affine::SIZE];
+        let mut read_g2 = || -> Result<G2Affine, Error> {
+            reader
+                .read_exact(&mut buf)
+                .map_err(|_| Error::NotEnoughBytes)?;
+            Ok(G2Affine::from_bytes(&buf)?)
+        };
+        let h = read_g2()?;
+        let beta_h = read_g2()?;
+
+        let first = PublicParameters {
+            commit_key: CommitKey::new(vec![powers_of_g[0]]),
+            opening_key: OpeningKey::new(powers_of_g[0], h, beta_h),
+        };
+        first.try_extend_from(&powers_of_g[1..])
+    }
+
+    /// Generates mock public parameters for testing, in which the secret
+    /// scalar beta is one and every power is the generator of G1.
+    ///
+    /// The [`CommitKey`] uses the [`MockBackend`](crate::backend::MockBackend),
+    /// so committing to a polynomial takes a single scalar multiplication.
+    /// Circuits are compiled, proved and verified through the same pipeline
+    /// and the verifier still checks the gate and permutation identities at
+    /// a random point, but skipping the setup and the MSMs makes it orders
+    /// of magnitude faster.
+    ///
+    /// **The commitments are not binding since beta is known, so the proofs
+    /// and keys generated with these parameters are not real proofs and must
+    /// never be used outside of tests.**
+    #[cfg(feature = "testing")]
+    pub fn mock(max_degree: usize) -> Result<PublicParameters, Error> {
+        if max_degree < 1 {
+            return Err(Error::DegreeIsZero);
+        }
+
+        let g = G1Affine::generator();
+        let h = G2Affine::generator();
+        let commit_key = CommitKey::new(vec![g; max_degree + 1])
+            .with_backend(Arc::new(crate::backend::MockBackend));
+
+        Ok(PublicParameters {
+            commit_key,
+            opening_key: OpeningKey::new(g, h, h),
         })
     }
 
@@ -159,6 +371,51 @@ impl PublicParameters {
         Ok(pp)
     }
 
+    /// Loads the [`PublicParameters`] serialized with
+    /// [`PublicParameters::to_var_bytes`] in the file at `path`, performing
+    /// the same checks as [`PublicParameters::from_slice`].
+    ///
+    /// The file is memory-mapped instead of being read into a buffer, so the
+    /// points are parsed from the pages of the file without holding a copy
+    /// of its bytes in memory.
+    #[cfg(feature = "mmap")]
+    pub fn from_mmap(path: &Path) -> Result<PublicParameters, Error> {
+        let mmap = map_file(path)?;
+        Self::from_slice(&mmap)
+    }
+
+    /// Loads the [`PublicParameters`] serialized with
+    /// [`PublicParameters::to_raw_var_bytes`] in the file at `path`, see
+    /// [`PublicParameters::from_slice_unchecked`].
+    ///
+    /// The file is memory-mapped instead of being read into a buffer, so the
+    /// points are copied from the pages of the file without holding a copy
+    /// of its bytes in memory. Returns [`Error::NotEnoughBytes`] if the file
+    /// is shorter than its header claims.
+    ///
+    /// # Safety
+    /// The content of the file is not checked, as for
+    /// [`PublicParameters::from_slice_unchecked`].
+    #[cfg(feature = "mmap")]
+    pub unsafe fn from_mmap_unchecked(
+        path: &Path,
+    ) -> Result<PublicParameters, Error> {
+        let mmap = map_file(path)?;
+
+        let header = OpeningKey::SIZE + u64::SIZE;
+        if mmap.len() < header {
+            return Err(Error::NotEnoughBytes);
+        }
+        let mut len = [0u8; u64::SIZE];
+        len.copy_from_slice(&mmap[OpeningKey::SIZE..header]);
+        let len = u64::from_le_bytes(len) as usize;
+        if (mmap.len() - header) / G1Affine::RAW_SIZE < len {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        Ok(Self::from_slice_unchecked(&mmap))
+    }
+
     /// Trim truncates the [`PublicParameters`] to allow the prover to commit to
     /// polynomials up to the and including the truncated degree.
     /// Returns the [`CommitKey`] and [`OpeningKey`] used to generate and verify
@@ -183,11 +440,36 @@ impl PublicParameters {
     }
 }
 
+/// Maps the file at `path` into memory for reading.
+#[cfg(feature = "mmap")]
+fn map_file(path: &Path) -> Result<Mmap, Error> {
+    let file = File::open(path)?;
+
+    // The mapping is only read while the parameters are parsed, during which
+    // the file is expected not to be modified
+    let mmap = unsafe { Mmap::map(&file)? };
+
+    Ok(mmap)
+}
+
+/// Discards the next `len` bytes of the `reader`, failing with
+/// [`Error::NotEnoughBytes`] if it ends before.
+#[cfg(feature = "std")]
+fn skip_bytes<R: Read>(reader: &mut R, len: usize) -> Result<(), Error> {
+    let skipped =
+        io::copy(&mut reader.by_ref().take(len as u64), &mut io::sink())
+            .map_err(|_| Error::NotEnoughBytes)?;
+
+    match skipped == len as u64 {
+        true => Ok(()),
+        false => Err(Error::NotEnoughBytes),
+    }
+}
+
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod test {
     use super::*;
-    use dusk_bls12_381::BlsScalar;
     use rand_core::OsRng;
 
     #[test]
@@ -231,4 +513,158 @@ mod test {
         assert_eq!(pp.opening_key.h, pp_p.opening_key.h);
         assert_eq!(pp.opening_key.beta_h, pp_p.opening_key.beta_h);
     }
+
+    /// Returns the powers `beta^i * g` for `i` in `[from, to]`.
+    fn powers_in_range(
+        pp: &PublicParameters,
+        beta: BlsScalar,
+        from: u64,
+        to: u64,
+    ) -> Vec<G1Affine> {
+        (from..=to)
+            .map(|i| (pp.opening_key.g * beta.pow(&[i, 0, 0, 0])).into())
+            .collect()
+    }
+
+    #[test]
+    fn test_extend_public_parameters() {
+        let beta = util::random_scalar(&mut OsRng);
+        let pp = PublicParameters::from_secret(1 << 4, beta, &mut OsRng);
+
+        // Extending by nothing keeps the same parameters
+        let same = pp.try_extend_from(&[]).unwrap();
+        assert_eq!(same.commit_key, pp.commit_key);
+
+        // Extending by a single power
+        let extra = powers_in_range(&pp, beta, 17, 17);
+        let by_one = pp.try_extend_from(&extra).unwrap();
+        assert_eq!(by_one.max_degree(), pp.max_degree() + 1);
+        assert_eq!(
+            by_one.commit_key.powers_of_g[..17],
+            pp.commit_key.powers_of_g[..]
+        );
+
+        // Extending twice is the same as extending once to the final degree
+        let extra = powers_in_range(&pp, beta, 18, 32);
+        let twice = by_one.try_extend_from(&extra).unwrap();
+        let extra = powers_in_range(&pp, beta, 17, 32);
+        let once = pp.try_extend_from(&extra).unwrap();
+        assert_eq!(twice.max_degree(), 1 << 5);
+        assert_eq!(twice.commit_key, once.commit_key);
+        assert!(twice.trim(1 << 5).is_ok());
+    }
+
+    #[test]
+    fn test_extend_public_parameters_inconsistent_powers() {
+        let beta = util::random_scalar(&mut OsRng);
+        let pp = PublicParameters::from_secret(1 << 4, beta, &mut OsRng);
+
+        // Skipping a power
+        let extra = powers_in_range(&pp, beta, 18, 20);
+        assert!(pp.try_extend_from(&extra).is_err());
+
+        // Powers of a different secret
+        let other = util::random_scalar(&mut OsRng);
+        let extra = powers_in_range(&pp, other, 17, 20);
+        assert!(pp.try_extend_from(&extra).is_err());
+
+        // Swapping two of the powers
+        let mut extra = powers_in_range(&pp, beta, 17, 20);
+        extra.swap(1, 2);
+        assert!(pp.try_extend_from(&extra).is_err());
+    }
+
+    /// Returns the response of a Powers of Tau ceremony of size `2^power`
+    /// for the secret `tau`, truncated after the powers of tau in G2.
+    fn ppot_response(tau: BlsScalar, power: u32) -> Vec<u8> {
+        let mut response = vec![0u8; PPOT_HASH_SIZE];
+        util::powers_of(&tau, (1 << (power + 1)) - 2)
+            .iter()
+            .for_each(|t| {
+                let point = G1Affine::from(G1Affine::generator() * t);
+                response.extend_from_slice(&point.to_bytes());
+            });
+        util::powers_of(&tau, (1 << power) - 1)
+            .iter()
+            .for_each(|t| {
+                let point = G2Affine::from(G2Affine::generator() * t);
+                response.extend_from_slice(&point.to_bytes());
+            });
+
+        response
+    }
+
+    #[test]
+    fn test_public_parameters_from_ppot() {
+        let tau = util::random_scalar(&mut OsRng);
+        let response = ppot_response(tau, 3);
+
+        let pp = PublicParameters::from_ppot_with_power(&response[..], 3, 10)
+            .unwrap();
+        assert_eq!(pp.max_degree(), 10);
+        assert_eq!(pp.opening_key.g, G1Affine::generator());
+        assert_eq!(pp.opening_key.h, G2Affine::generator());
+        assert_eq!(
+            pp.opening_key.beta_h,
+            G2Affine::from(G2Affine::generator() * tau)
+        );
+        pp.commit_key
+            .powers_of_g
+            .iter()
+            .zip(util::powers_of(&tau, 10))
+            .for_each(|(p, t)| {
+                assert_eq!(*p, G1Affine::from(G1Affine::generator() * t))
+            });
+
+        // The largest degree takes every power in G1
+        let pp = PublicParameters::from_ppot_with_power(&response[..], 3, 14)
+            .unwrap();
+        assert_eq!(pp.max_degree(), 14);
+    }
+
+    #[test]
+    fn test_public_parameters_from_invalid_ppot() {
+        let tau = util::random_scalar(&mut OsRng);
+        let response = ppot_response(tau, 3);
+
+        assert!(matches!(
+            PublicParameters::from_ppot_with_power(&response[..], 3, 0),
+            Err(Error::DegreeIsZero)
+        ));
+        assert!(matches!(
+            PublicParameters::from_ppot_with_power(&response[..], 3, 15),
+            Err(Error::PpotDegreeTooLarge {
+                degree: 15,
+                max: 14
+            })
+        ));
+
+        // The response ends before tau in G2
+        let truncated =
+            &response[..PPOT_HASH_SIZE + 15 * G1Affine::SIZE + G2Affine::SIZE];
+        assert!(matches!(
+            PublicParameters::from_ppot_with_power(truncated, 3, 10),
+            Err(Error::NotEnoughBytes)
+        ));
+
+        // The fixture is smaller than the BLS12-381 ceremony
+        assert!(matches!(
+            PublicParameters::from_ppot(&response[..], 10),
+            Err(Error::NotEnoughBytes)
+        ));
+
+        // Swapping two of the powers in G1
+        let mut swapped = response.clone();
+        let (first, second) = (
+            PPOT_HASH_SIZE + 2 * G1Affine::SIZE,
+            PPOT_HASH_SIZE + 3 * G1Affine::SIZE,
+        );
+        let power = swapped[first..second].to_vec();
+        swapped.copy_within(second..second + G1Affine::SIZE, first);
+        swapped[second..second + G1Affine::SIZE].copy_from_slice(&power);
+        assert!(matches!(
+            PublicParameters::from_ppot_with_power(&swapped[..], 3, 10),
+            Err(Error::PairingCheckFailure)
+        ));
+    }
 }
@@ -12,12 +12,87 @@
 //! commitment scheme to be homomorphic. `Merkle Tree like` techniques such as
 //! FRI are not homomorphic and therefore for PLONK to be usable with all
 //! commitment schemes without modification, one would need to remove the
-//! lineariser
-// XXX: This empty trait is left here so that Rust docs does not complain that
-// we are documenting nothing. It is also a reminder that we ideally should find
-// a good abstraction.
-trait CommitmentScheme {
-    type Proof;
-}
+//! lineariser.
+//!
+//! The [`PolyCommitmentScheme`] trait abstracts the operations that a
+//! homomorphic polynomial commitment scheme offers, so alternative backends
+//! can be implemented and tested against the same interface. The proof
+//! system still uses [`kzg10`] directly.
+
+use crate::error::Error;
+use dusk_bls12_381::BlsScalar;
+use merlin::Transcript;
 
+#[cfg(feature = "alloc")]
+pub mod ipa;
 pub mod kzg10;
+
+#[cfg(feature = "alloc")]
+pub use ipa::IpaScheme;
+#[cfg(feature = "alloc")]
+pub use kzg10::Kzg10Scheme;
+
+/// Commitment scheme to polynomials over [`BlsScalar`] that can be opened
+/// at a single point.
+///
+/// The polynomials are given by their coefficients, the constant term
+/// first. The `transcript` arguments are used to derive the challenges of
+/// the schemes that need them, so the prover and the verifier must provide
+/// transcripts holding the same messages.
+pub trait PolyCommitmentScheme {
+    /// Key used to commit to polynomials and open them.
+    type CommitmentKey;
+    /// Key used to verify the openings.
+    type VerificationKey;
+    /// Commitment to a polynomial.
+    type Commitment: Copy;
+    /// Proof that a committed polynomial evaluates to a value at a point.
+    type Opening;
+
+    /// Commits to the polynomial with the given `coeffs`.
+    fn commit(
+        ck: &Self::CommitmentKey,
+        coeffs: &[BlsScalar],
+    ) -> Result<Self::Commitment, Error>;
+
+    /// Returns the evaluation at `point` of the polynomial with the given
+    /// `coeffs`, which was committed to as `commitment`, along with the
+    /// proof of the evaluation.
+    fn open(
+        ck: &Self::CommitmentKey,
+        coeffs: &[BlsScalar],
+        commitment: &Self::Commitment,
+        point: BlsScalar,
+        transcript: &mut Transcript,
+    ) -> Result<(BlsScalar, Self::Opening), Error>;
+
+    /// Checks that the polynomial committed to as `commitment` evaluates to
+    /// `value` at `point`.
+    fn verify_opening(
+        vk: &Self::VerificationKey,
+        commitment: &Self::Commitment,
+        point: BlsScalar,
+        value: BlsScalar,
+        opening: &Self::Opening,
+        transcript: &mut Transcript,
+    ) -> Result<(), Error>;
+
+    /// Checks a batch of `(commitment, point, value, opening)` claims,
+    /// failing if any of them doesn't hold.
+    ///
+    /// The default implementation checks each claim in turn, schemes able
+    /// to amortize the checks should override it.
+    fn batch_verify(
+        vk: &Self::VerificationKey,
+        claims: &[(Self::Commitment, BlsScalar, BlsScalar, Self::Opening)],
+        transcript: &mut Transcript,
+    ) -> Result<(), Error> {
+        claims
+            .iter()
+            .try_for_each(|(commitment, point, value, opening)| {
+                Self::verify_opening(
+                    vk, commitment, *point, *value, opening, transcript,
+                )
+            })
+    }
+}
@@ -4,10 +4,102 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use crate::constraint_system::witness::{DIVISION, INVERSE, SQRT};
 use crate::constraint_system::StandardComposer;
 use crate::constraint_system::Variable;
+use crate::error::Error;
 use dusk_bls12_381::BlsScalar;
 
+/// Builder of an arithmetic gate, which constrains its wires `a`, `b` and
+/// `c` to satisfy
+/// `(a * b) * q_m + a * q_l + b * q_r + q_c + PI + q_o * c = 0`.
+///
+/// The wires that are not set default to the zero [`Variable`] of the
+/// composer, the selectors to zero and the gate has no public input unless
+/// one is set.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ArithmeticGate {
+    pub(crate) left: Option<Variable>,
+    pub(crate) right: Option<Variable>,
+    pub(crate) output: Option<Variable>,
+    pub(crate) q_m: BlsScalar,
+    pub(crate) q_l: BlsScalar,
+    pub(crate) q_r: BlsScalar,
+    pub(crate) q_o: BlsScalar,
+    pub(crate) q_c: BlsScalar,
+    pub(crate) pi: Option<BlsScalar>,
+}
+
+impl ArithmeticGate {
+    /// Creates a gate with all the wires set to zero and no selectors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the left wire `a`.
+    pub fn left(mut self, a: Variable) -> Self {
+        self.left = Some(a);
+        self
+    }
+
+    /// Sets the right wire `b`.
+    pub fn right(mut self, b: Variable) -> Self {
+        self.right = Some(b);
+        self
+    }
+
+    /// Sets the output wire `c`.
+    pub fn output(mut self, c: Variable) -> Self {
+        self.output = Some(c);
+        self
+    }
+
+    /// Sets the selector of the product `a * b`.
+    pub fn q_m(mut self, q_m: BlsScalar) -> Self {
+        self.q_m = q_m;
+        self
+    }
+
+    /// Sets the selector of the left wire.
+    pub fn q_l(mut self, q_l: BlsScalar) -> Self {
+        self.q_l = q_l;
+        self
+    }
+
+    /// Sets the selector of the right wire.
+    pub fn q_r(mut self, q_r: BlsScalar) -> Self {
+        self.q_r = q_r;
+        self
+    }
+
+    /// Sets the selector of the output wire.
+    pub fn q_o(mut self, q_o: BlsScalar) -> Self {
+        self.q_o = q_o;
+        self
+    }
+
+    /// Sets the constant selector.
+    pub fn q_c(mut self, q_c: BlsScalar) -> Self {
+        self.q_c = q_c;
+        self
+    }
+
+    /// Sets the public input of the gate.
+    pub fn pi(mut self, pi: Option<BlsScalar>) -> Self {
+        self.pi = pi;
+        self
+    }
+
+    /// Adds the gate to the circuit description of the `composer`,
+    /// returning its left, right and output wires.
+    pub fn apply(
+        self,
+        composer: &mut StandardComposer,
+    ) -> (Variable, Variable, Variable) {
+        composer.add_arithmetic_gate(self)
+    }
+}
+
 impl StandardComposer {
     /// Adds a width-3 add gate to the circuit, linking the addition of the
     /// provided inputs, scaled by the selector coefficients with the output
@@ -311,14 +403,369 @@ impl StandardComposer {
 
         self.big_mul_gate(a, b, c, Some(d), q_m, q_o, q_c, q_4, pi)
     }
+
+    /// Returns a [`Variable`] holding `x + constant`, with the constant
+    /// baked into the `q_c` selector of the gate.
+    ///
+    /// Only the output is allocated, adding a single gate to the circuit
+    /// description.
+    pub fn add_constant(
+        &mut self,
+        x: Variable,
+        constant: BlsScalar,
+    ) -> Variable {
+        self.add(
+            (BlsScalar::one(), x),
+            (BlsScalar::zero(), self.zero_var),
+            constant,
+            None,
+        )
+    }
+
+    /// Returns a [`Variable`] holding `x * constant`, with the constant
+    /// baked into the `q_l` selector of the gate.
+    ///
+    /// Only the output is allocated, adding a single gate to the circuit
+    /// description.
+    pub fn mul_constant(
+        &mut self,
+        x: Variable,
+        constant: BlsScalar,
+    ) -> Variable {
+        self.add(
+            (constant, x),
+            (BlsScalar::zero(), self.zero_var),
+            BlsScalar::zero(),
+            None,
+        )
+    }
+
+    /// Returns a [`Variable`] holding `constant + sum c_i * x_i` for the
+    /// `terms` `(c_i, x_i)`.
+    ///
+    /// The first gate sums up to three terms and the constant, then each
+    /// following gate adds two more terms to the running sum, which sits on
+    /// the fourth wire. Thus, `n > 3` terms take `1 + ceil((n - 3) / 2)`
+    /// gates, and up to three terms take a single one.
+    pub fn linear_combination(
+        &mut self,
+        terms: &[(BlsScalar, Variable)],
+        constant: BlsScalar,
+    ) -> Variable {
+        let zero = (BlsScalar::zero(), self.zero_var);
+        let (first, rest) = terms.split_at(terms.len().min(3));
+        let term = |i: usize| first.get(i).copied().unwrap_or(zero);
+
+        let sum = self.big_add(term(0), term(1), Some(term(2)), constant, None);
+
+        rest.chunks(2).fold(sum, |sum, pair| {
+            self.big_add(
+                pair[0],
+                pair.get(1).copied().unwrap_or(zero),
+                Some((BlsScalar::one(), sum)),
+                BlsScalar::zero(),
+                None,
+            )
+        })
+    }
+
+    /// Adds the constraints proving that the value of `var` has a square
+    /// root `r` in the field, returning the [`Variable`]s holding `r` and
+    /// `-r`. If the value of `var` is zero, both of them are zero.
+    ///
+    /// Forces `r * r = var` and `r + r_neg = 0`, adding two gates to the
+    /// circuit description.
+    ///
+    /// Returns [`Error::NoSquareRoot`] if the value of `var` has no square
+    /// root in the field, without adding any gate.
+    pub fn sqrt(
+        &mut self,
+        var: Variable,
+    ) -> Result<(Variable, Variable), Error> {
+        let r = self.hint(SQRT, &[var])?;
+
+        // r * r - var = 0
+        ArithmeticGate::new()
+            .left(r)
+            .right(r)
+            .output(var)
+            .q_m(BlsScalar::one())
+            .q_o(-BlsScalar::one())
+            .apply(self);
+
+        let r_neg = self.add(
+            (-BlsScalar::one(), r),
+            (BlsScalar::zero(), self.zero_var),
+            BlsScalar::zero(),
+            None,
+        );
+
+        Ok((r, r_neg))
+    }
+
+    /// Constrains the value of `a` to be a quadratic residue, by proving
+    /// that a witness `r` with `r * r = a` exists, adding a single gate to
+    /// the circuit description.
+    ///
+    /// The circuit will only be satisfied if the value of `a` has a square
+    /// root in the field. Otherwise, `r` is assigned the value zero so the
+    /// witness can still be computed, for example while compiling the
+    /// circuit.
+    pub fn assert_is_square(&mut self, a: Variable) {
+        let r = self
+            .hint(SQRT, &[a])
+            .unwrap_or_else(|_| self.add_input(BlsScalar::zero()));
+
+        // r * r - a = 0
+        ArithmeticGate::new()
+            .left(r)
+            .right(r)
+            .output(a)
+            .q_m(BlsScalar::one())
+            .q_o(-BlsScalar::one())
+            .apply(self);
+    }
+
+    /// Adds the constraint `var * inv = 1`, returning the [`Variable`]
+    /// holding the multiplicative inverse `inv` of the value of `var`.
+    ///
+    /// The circuit will only be satisfied if the value of `var` is not zero.
+    /// In that case, the inverse is assigned the value zero so the witness
+    /// can still be computed, for example while compiling the circuit.
+    pub fn inverse(&mut self, var: Variable) -> Variable {
+        let inv = self
+            .hint(INVERSE, &[var])
+            .expect("The inverse is defined for any value");
+
+        // var * inv - 1 = 0
+        ArithmeticGate::new()
+            .left(var)
+            .right(inv)
+            .q_m(BlsScalar::one())
+            .q_c(-BlsScalar::one())
+            .apply(self);
+
+        inv
+    }
+
+    /// Constrains the value of `var` to not be zero, by proving that its
+    /// multiplicative inverse exists.
+    pub fn assert_nonzero(&mut self, var: Variable) {
+        self.inverse(var);
+    }
+
+    /// Returns the [`Variable`]s holding the multiplicative inverse of the
+    /// value of `var` and a boolean set to one if the value of `var` is zero,
+    /// in which case the inverse is zero too.
+    ///
+    /// Unlike [`StandardComposer::inverse`], the circuit is satisfied for any
+    /// value of `var`, which allows gadgets to handle the zero case.
+    ///
+    /// Forces `var * inv + is_zero = 1`, `var * is_zero = 0` and
+    /// `inv * is_zero = 0`, adding three gates to the circuit description.
+    pub fn try_inverse(&mut self, var: Variable) -> (Variable, Variable) {
+        let inv = self
+            .hint(INVERSE, &[var])
+            .expect("The inverse is defined for any value");
+        let is_zero = self.add_input(
+            BlsScalar::one() - self.variables[&var] * self.variables[&inv],
+        );
+
+        // var * inv + is_zero - 1 = 0
+        ArithmeticGate::new()
+            .left(var)
+            .right(inv)
+            .output(is_zero)
+            .q_m(BlsScalar::one())
+            .q_o(BlsScalar::one())
+            .q_c(-BlsScalar::one())
+            .apply(self);
+
+        // var * is_zero = 0
+        ArithmeticGate::new()
+            .left(var)
+            .right(is_zero)
+            .q_m(BlsScalar::one())
+            .apply(self);
+
+        // inv * is_zero = 0
+        ArithmeticGate::new()
+            .left(inv)
+            .right(is_zero)
+            .q_m(BlsScalar::one())
+            .apply(self);
+
+        (inv, is_zero)
+    }
+
+    /// Returns a boolean [`Variable`] which is `1` if the values of `a` and
+    /// `b` are equal and `0` otherwise.
+    ///
+    /// Unlike [`StandardComposer::assert_equal`], the circuit is satisfied
+    /// for any values of `a` and `b`. The result is the zero flag of
+    /// [`StandardComposer::try_inverse`] applied to `a - b`, adding four
+    /// gates to the circuit description.
+    pub fn is_equal(&mut self, a: Variable, b: Variable) -> Variable {
+        let diff = self.add(
+            (BlsScalar::one(), a),
+            (-BlsScalar::one(), b),
+            BlsScalar::zero(),
+            None,
+        );
+
+        let (_, is_zero) = self.try_inverse(diff);
+        is_zero
+    }
+
+    /// Returns a [`Variable`] holding the field division `a / b`.
+    ///
+    /// The quotient `q` is computed with the inverse of `b` and constrained
+    /// to satisfy `q * b = a`, while `b` is constrained to be non-zero with
+    /// [`StandardComposer::assert_nonzero`]. This function adds two gates to
+    /// the circuit description.
+    ///
+    /// Returns [`Error::DivisionByZero`] if the value of `b` is zero, since
+    /// the witness can't be computed.
+    pub fn field_division(
+        &mut self,
+        a: Variable,
+        b: Variable,
+    ) -> Result<Variable, Error> {
+        let q = self.hint(DIVISION, &[a, b])?;
+
+        self.assert_nonzero(b);
+
+        // q * b - a = 0
+        ArithmeticGate::new()
+            .left(q)
+            .right(b)
+            .output(a)
+            .q_m(BlsScalar::one())
+            .q_o(-BlsScalar::one())
+            .apply(self);
+
+        Ok(q)
+    }
 }
 
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod tests {
     use crate::constraint_system::helper::*;
+    use crate::constraint_system::{
+        ArithmeticGate, StandardComposer, Variable,
+    };
+    use crate::error::Error;
+    use alloc::vec::Vec;
     use dusk_bls12_381::BlsScalar;
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_arithmetic_gate_matches_poly_gate() {
+        let one = BlsScalar::one();
+        let zero = BlsScalar::zero();
+        let two = BlsScalar::from(2u64);
+
+        // q_m, q_l, q_r, q_o, q_c, pi
+        let configurations = [
+            (zero, one, one, -one, zero, None),
+            (one, zero, zero, -one, zero, None),
+            (zero, one, zero, zero, -two, None),
+            (one, two, -one, zero, one, Some(-BlsScalar::from(7u64))),
+            (zero, zero, zero, zero, zero, Some(one)),
+        ];
+
+        for (q_m, q_l, q_r, q_o, q_c, pi) in configurations.iter() {
+            let mut old = StandardComposer::new();
+            let a = old.add_input(two);
+            let b = old.add_input(BlsScalar::from(3u64));
+            let c = old.add_input(BlsScalar::from(5u64));
+            let old_wires =
+                old.poly_gate(a, b, c, *q_m, *q_l, *q_r, *q_o, *q_c, *pi);
+
+            let mut new = StandardComposer::new();
+            let a = new.add_input(two);
+            let b = new.add_input(BlsScalar::from(3u64));
+            let c = new.add_input(BlsScalar::from(5u64));
+            let new_wires = ArithmeticGate::new()
+                .left(a)
+                .right(b)
+                .output(c)
+                .q_m(*q_m)
+                .q_l(*q_l)
+                .q_r(*q_r)
+                .q_o(*q_o)
+                .q_c(*q_c)
+                .pi(*pi)
+                .apply(&mut new);
+
+            assert_eq!(old_wires, new_wires);
+            assert_eq!(old.w_l, new.w_l);
+            assert_eq!(old.w_r, new.w_r);
+            assert_eq!(old.w_o, new.w_o);
+            assert_eq!(old.w_4, new.w_4);
+            assert_eq!(old.q_m, new.q_m);
+            assert_eq!(old.q_l, new.q_l);
+            assert_eq!(old.q_r, new.q_r);
+            assert_eq!(old.q_o, new.q_o);
+            assert_eq!(old.q_c, new.q_c);
+            assert_eq!(old.q_4, new.q_4);
+            assert_eq!(old.q_arith, new.q_arith);
+            assert_eq!(
+                old.public_inputs_sparse_store,
+                new.public_inputs_sparse_store
+            );
+        }
+
+        // The wires that are not set are the zero variable
+        let mut composer = StandardComposer::new();
+        let wires = ArithmeticGate::new().q_c(one).apply(&mut composer);
+        let zero_var = composer.zero_var;
+        assert_eq!(wires, (zero_var, zero_var, zero_var));
+    }
+
+    #[test]
+    fn test_arithmetic_gate() {
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(2u64));
+                let b = composer.add_input(BlsScalar::from(3u64));
+                let c = composer.add_input(BlsScalar::from(11u64));
+
+                // 2 * a * b - c + 1 = 0
+                ArithmeticGate::new()
+                    .left(a)
+                    .right(b)
+                    .output(c)
+                    .q_m(BlsScalar::from(2u64))
+                    .q_o(-BlsScalar::one())
+                    .pi(Some(BlsScalar::one()))
+                    .apply(composer);
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(2u64));
+                let b = composer.add_input(BlsScalar::from(3u64));
+                let c = composer.add_input(BlsScalar::from(12u64));
+
+                ArithmeticGate::new()
+                    .left(a)
+                    .right(b)
+                    .output(c)
+                    .q_m(BlsScalar::from(2u64))
+                    .q_o(-BlsScalar::one())
+                    .pi(Some(BlsScalar::one()))
+                    .apply(composer);
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
     #[test]
     fn test_public_inputs() {
         let res = gadget_tester(
@@ -514,4 +961,372 @@ mod tests {
         );
         assert!(res.is_err());
     }
+
+    #[test]
+    fn test_sqrt() {
+        let res = gadget_tester(
+            |composer| {
+                for value in [0u64, 1, 4, 9, 1 << 40].iter() {
+                    let var = composer.add_input(BlsScalar::from(*value));
+                    let (r, r_neg) = composer.sqrt(var).unwrap();
+
+                    let root = composer.variables[&r];
+                    assert_eq!(root * root, BlsScalar::from(*value));
+                    assert_eq!(composer.variables[&r_neg], -root);
+
+                    let square = composer.mul(
+                        BlsScalar::one(),
+                        r_neg,
+                        r_neg,
+                        BlsScalar::zero(),
+                        None,
+                    );
+                    composer.assert_equal(square, var);
+                }
+            },
+            64,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_sqrt_non_residue() {
+        // The multiplicative generator of the field is never a square
+        let mut composer = StandardComposer::new();
+        let var = composer.add_input(BlsScalar::from(7u64));
+        let size = composer.circuit_size();
+
+        assert!(matches!(composer.sqrt(var), Err(Error::NoSquareRoot)));
+        assert_eq!(composer.circuit_size(), size);
+    }
+
+    #[test]
+    fn test_assert_is_square() {
+        let res = gadget_tester(
+            |composer| {
+                let var = composer.add_input(BlsScalar::from(9u64));
+                composer.assert_is_square(var);
+
+                // The witness root is either 3 or -3
+                let three = BlsScalar::from(3u64);
+                assert!(composer
+                    .variables
+                    .values()
+                    .any(|value| value == &three || value == &-three));
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the multiplicative generator of the field is
+        // never a square
+        let res = gadget_tester(
+            |composer| {
+                let var = composer.add_input(BlsScalar::from(7u64));
+                composer.assert_is_square(var);
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_inverse() {
+        let res = gadget_tester(
+            |composer| {
+                for value in [1u64, 2, 0xdead_beef].iter() {
+                    let var = composer.add_input(BlsScalar::from(*value));
+                    let inv = composer.inverse(var);
+
+                    let expected = BlsScalar::from(*value).invert().unwrap();
+                    assert_eq!(composer.variables[&inv], expected);
+                    composer.constrain_to_constant(inv, expected, None);
+                }
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since zero has no inverse
+        let res = gadget_tester(
+            |composer| {
+                let var = composer.add_input(BlsScalar::zero());
+                composer.assert_nonzero(var);
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_try_inverse() {
+        let res = gadget_tester(
+            |composer| {
+                for value in [0u64, 1, 2, 0xdead_beef].iter() {
+                    let var = composer.add_input(BlsScalar::from(*value));
+                    let (inv, is_zero) = composer.try_inverse(var);
+
+                    let expected = BlsScalar::from(*value)
+                        .invert()
+                        .unwrap_or(BlsScalar::zero());
+                    let expected_is_zero =
+                        BlsScalar::from((*value == 0) as u64);
+                    composer.constrain_to_constant(inv, expected, None);
+                    composer.constrain_to_constant(
+                        is_zero,
+                        expected_is_zero,
+                        None,
+                    );
+                }
+            },
+            64,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the value is not zero
+        let res = gadget_tester(
+            |composer| {
+                let var = composer.add_input(BlsScalar::from(5u64));
+                let (_, is_zero) = composer.try_inverse(var);
+                composer.constrain_to_constant(is_zero, BlsScalar::one(), None);
+            },
+            32,
+        );
+        assert!(res.is_err());
+
+        // Should fail since the inverse of zero is not zero
+        let res = gadget_tester(
+            |composer| {
+                let var = composer.add_input(BlsScalar::zero());
+                let (inv, _) = composer.try_inverse(var);
+                composer.variables.insert(inv, BlsScalar::from(5u64));
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_is_equal() {
+        let res = gadget_tester(
+            |composer| {
+                let values = [
+                    (5u64, 5u64, 1u64),
+                    (5, 7, 0),
+                    (0, 0, 1),
+                    (0, 1, 0),
+                    (1 << 40, 1 << 40, 1),
+                ];
+                for (a, b, expected) in values.iter() {
+                    let a = composer.add_input(BlsScalar::from(*a));
+                    let b = composer.add_input(BlsScalar::from(*b));
+                    let is_equal = composer.is_equal(a, b);
+                    composer.constrain_to_constant(
+                        is_equal,
+                        BlsScalar::from(*expected),
+                        None,
+                    );
+                }
+            },
+            64,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the values are different
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(5u64));
+                let b = composer.add_input(BlsScalar::from(7u64));
+                let is_equal = composer.is_equal(a, b);
+                composer.constrain_to_constant(
+                    is_equal,
+                    BlsScalar::one(),
+                    None,
+                );
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_field_division() {
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(6u64));
+                let b = composer.add_input(BlsScalar::from(3u64));
+                let q = composer.field_division(a, b).unwrap();
+                composer.constrain_to_constant(q, BlsScalar::from(2u64), None);
+
+                // The quotient of non exact divisions is a field element
+                let a = composer.add_input(BlsScalar::from(7u64));
+                let q = composer.field_division(a, b).unwrap();
+                let back = composer.mul(
+                    BlsScalar::one(),
+                    q,
+                    b,
+                    BlsScalar::zero(),
+                    None,
+                );
+                composer.assert_equal(back, a);
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(6u64));
+        let b = composer.add_input(BlsScalar::zero());
+        assert!(matches!(
+            composer.field_division(a, b),
+            Err(Error::DivisionByZero)
+        ));
+    }
+
+    #[test]
+    fn test_linear_combination() {
+        let res = gadget_tester(
+            |composer| {
+                let terms: Vec<(BlsScalar, Variable)> = (1..8u64)
+                    .map(|i| {
+                        let x = composer.add_input(BlsScalar::from(i * 10));
+                        (BlsScalar::from(i), x)
+                    })
+                    .collect();
+                let constant = BlsScalar::from(5u64);
+
+                let n = composer.circuit_size();
+                let sum = composer.linear_combination(&terms, constant);
+                assert_eq!(composer.circuit_size() - n, 3);
+
+                // Naive chain of one gate per term
+                let n = composer.circuit_size();
+                let naive = terms.iter().fold(
+                    composer.add_witness_to_circuit_description(constant),
+                    |acc, term| {
+                        composer.add(
+                            (BlsScalar::one(), acc),
+                            *term,
+                            BlsScalar::zero(),
+                            None,
+                        )
+                    },
+                );
+                assert_eq!(composer.circuit_size() - n, 8);
+
+                // 5 + sum i * 10i = 1405
+                composer.assert_equal(sum, naive);
+                composer.constrain_to_constant(
+                    sum,
+                    BlsScalar::from(1405u64),
+                    None,
+                );
+
+                // Up to three terms take a single gate
+                for len in 0..=3 {
+                    let n = composer.circuit_size();
+                    let sum =
+                        composer.linear_combination(&terms[..len], constant);
+                    assert_eq!(composer.circuit_size() - n, 1);
+
+                    let expected = (1..=len as u64).fold(constant, |acc, i| {
+                        acc + BlsScalar::from(i * i * 10)
+                    });
+                    composer.constrain_to_constant(sum, expected, None);
+                }
+            },
+            64,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the running sum is tampered with
+        let res = gadget_tester(
+            |composer| {
+                let terms: Vec<(BlsScalar, Variable)> = (1..6u64)
+                    .map(|i| (BlsScalar::one(), composer.add_input(i.into())))
+                    .collect();
+                let sum =
+                    composer.linear_combination(&terms, BlsScalar::zero());
+                composer.variables.insert(sum, BlsScalar::from(16u64));
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_constant_arithmetic() {
+        let res = gadget_tester(
+            |composer| {
+                let x = composer.add_input(BlsScalar::from(5u64));
+
+                let n = composer.circuit_size();
+                let vars = composer.num_variables();
+                let sum = composer.add_constant(x, BlsScalar::from(42u64));
+                let product = composer.mul_constant(x, BlsScalar::from(3u64));
+                assert_eq!(composer.circuit_size() - n, 2);
+                assert_eq!(composer.num_variables() - vars, 2);
+
+                let n = composer.circuit_size();
+                composer.constrain_to_constant(
+                    sum,
+                    BlsScalar::from(47u64),
+                    None,
+                );
+                composer.constrain_to_constant(
+                    product,
+                    BlsScalar::zero(),
+                    Some(-BlsScalar::from(15u64)),
+                );
+                assert_eq!(composer.circuit_size() - n, 2);
+                assert_eq!(composer.num_variables() - vars, 2);
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the witness doesn't hold the constant
+        let res = gadget_tester(
+            |composer| {
+                let x = composer.add_input(BlsScalar::from(41u64));
+                composer.constrain_to_constant(x, BlsScalar::from(42u64), None);
+            },
+            32,
+        );
+        assert!(res.is_err());
+
+        // Should fail since the witness of the sum is tampered with, as if
+        // the constant was 43
+        let res = gadget_tester(
+            |composer| {
+                let x = composer.add_input(BlsScalar::from(5u64));
+                let sum = composer.add_constant(x, BlsScalar::from(42u64));
+                composer.variables.insert(sum, BlsScalar::from(48u64));
+                composer.constrain_to_constant(
+                    sum,
+                    BlsScalar::from(48u64),
+                    None,
+                );
+            },
+            32,
+        );
+        assert!(res.is_err());
+
+        // Should fail since the witness of the product is tampered with, as
+        // if the constant was 4
+        let res = gadget_tester(
+            |composer| {
+                let x = composer.add_input(BlsScalar::from(5u64));
+                let product = composer.mul_constant(x, BlsScalar::from(3u64));
+                composer.variables.insert(product, BlsScalar::from(20u64));
+                composer.constrain_to_constant(
+                    product,
+                    BlsScalar::from(20u64),
+                    None,
+                );
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
 }
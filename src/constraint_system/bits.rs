@@ -0,0 +1,198 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::witness::BIT;
+use crate::constraint_system::{StandardComposer, Variable};
+use crate::error::Error;
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+
+impl StandardComposer {
+    /// Decomposes the value of a [`Variable`] into `num_bits` boolean
+    /// [`Variable`]s, returned in little-endian order.
+    ///
+    /// Each one of the bits is constrained with a
+    /// [`StandardComposer::boolean_gate`] and the bits are constrained to
+    /// accumulate to the original [`Variable`], so the circuit will only be
+    /// satisfied if its value fits in `num_bits`.
+    ///
+    /// This function adds `num_bits + ceil(num_bits / 2) + 1` gates to the
+    /// circuit description.
+    pub fn split_variable(
+        &mut self,
+        var: Variable,
+        num_bits: usize,
+    ) -> Vec<Variable> {
+        let value = self.variables[&var];
+        let bits: Vec<Variable> = (0..num_bits)
+            .map(|i| {
+                let bit = self
+                    .hint_values(BIT, &[value, BlsScalar::from(i as u64)])
+                    .expect("The bits are defined for any value");
+                self.boolean_gate(bit)
+            })
+            .collect();
+
+        let composed = self.compose_bits(&bits);
+        self.assert_equal(composed, var);
+
+        bits
+    }
+
+    /// Returns a [`Variable`] holding the value represented by the
+    /// little-endian `bits`.
+    ///
+    /// The bits are not constrained to be boolean, this is the responsibility
+    /// of the caller.
+    pub(crate) fn compose_bits(&mut self, bits: &[Variable]) -> Variable {
+        // Two bits are accumulated on each gate, using the fourth wire to
+        // carry the accumulator
+        bits.chunks(2)
+            .enumerate()
+            .fold(self.zero_var, |acc, (i, pair)| {
+                let low = BlsScalar::pow_of_2(2 * i as u64);
+                let high = BlsScalar::pow_of_2(2 * i as u64 + 1);
+                let high_bit = pair.get(1).copied().unwrap_or(self.zero_var);
+
+                self.big_add(
+                    (low, pair[0]),
+                    (high, high_bit),
+                    Some((BlsScalar::one(), acc)),
+                    BlsScalar::zero(),
+                    None,
+                )
+            })
+    }
+
+    /// Returns a [`Variable`] holding the value of `var`, interpreted as a
+    /// 32-bit integer, rotated `amount` bits to the left.
+    ///
+    /// The circuit will only be satisfied if the value of `var` fits in 32
+    /// bits. Returns an error if `amount` is not in the range `[0, 32)`.
+    ///
+    /// This function adds 65 gates to the circuit description.
+    pub fn rotate_left_32(
+        &mut self,
+        var: Variable,
+        amount: usize,
+    ) -> Result<Variable, Error> {
+        if amount >= 32 {
+            return Err(Error::InvalidRotationAmount { amount });
+        }
+
+        // The bit `i` of the result is the bit `i - amount` of the input
+        let bits = self.split_variable(var, 32);
+        let rotated: Vec<Variable> =
+            (0..32).map(|i| bits[(i + 32 - amount) % 32]).collect();
+
+        Ok(self.compose_bits(&rotated))
+    }
+
+    /// Returns a [`Variable`] holding the value of `var`, interpreted as a
+    /// 32-bit integer, rotated `amount` bits to the right.
+    ///
+    /// The circuit will only be satisfied if the value of `var` fits in 32
+    /// bits. Returns an error if `amount` is not in the range `[0, 32)`.
+    ///
+    /// This function adds 65 gates to the circuit description.
+    pub fn rotate_right_32(
+        &mut self,
+        var: Variable,
+        amount: usize,
+    ) -> Result<Variable, Error> {
+        if amount >= 32 {
+            return Err(Error::InvalidRotationAmount { amount });
+        }
+
+        self.rotate_left_32(var, (32 - amount) % 32)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use super::*;
+
+    #[test]
+    fn test_split_variable() {
+        let res = gadget_tester(
+            |composer| {
+                let value = 0b1011_0110u64;
+                let var = composer.add_input(BlsScalar::from(value));
+                let bits = composer.split_variable(var, 8);
+
+                for (i, bit) in bits.iter().enumerate() {
+                    let expected = (value >> i) & 1;
+                    composer.constrain_to_constant(
+                        *bit,
+                        BlsScalar::from(expected),
+                        None,
+                    );
+                }
+            },
+            64,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the value doesn't fit in 8 bits
+        let res = gadget_tester(
+            |composer| {
+                let var = composer.add_input(BlsScalar::from(256u64));
+                composer.split_variable(var, 8);
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_rotate_32() {
+        let res = gadget_tester(
+            |composer| {
+                let values = [1u32, 0x8000_0001, 0xdead_beef];
+                for value in values.iter() {
+                    let var =
+                        composer.add_input(BlsScalar::from(*value as u64));
+                    for amount in [0, 7, 31].iter() {
+                        let left =
+                            composer.rotate_left_32(var, *amount).unwrap();
+                        let right =
+                            composer.rotate_right_32(var, *amount).unwrap();
+
+                        let expected_left = value.rotate_left(*amount as u32);
+                        let expected_right = value.rotate_right(*amount as u32);
+                        assert_eq!(
+                            composer.variables[&left],
+                            BlsScalar::from(expected_left as u64)
+                        );
+                        composer.constrain_to_constant(
+                            left,
+                            BlsScalar::from(expected_left as u64),
+                            None,
+                        );
+                        composer.constrain_to_constant(
+                            right,
+                            BlsScalar::from(expected_right as u64),
+                            None,
+                        );
+                    }
+                }
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_rotate_32_invalid_amount() {
+        let mut composer = StandardComposer::new();
+        let var = composer.add_input(BlsScalar::from(5u64));
+
+        assert!(composer.rotate_left_32(var, 32).is_err());
+        assert!(composer.rotate_right_32(var, 33).is_err());
+    }
+}
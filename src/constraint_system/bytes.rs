@@ -0,0 +1,193 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::{StandardComposer, Variable};
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+
+impl StandardComposer {
+    /// Decomposes the value of `x` into the 32 [`Variable`]s holding the
+    /// bytes of its canonical little-endian representation.
+    ///
+    /// Each byte is range constrained to 8 bits, the bytes are constrained
+    /// to accumulate to `x` and to represent an integer smaller than the
+    /// scalar field modulus, so the decomposition is unique.
+    ///
+    /// This function adds around 200 gates to the circuit description.
+    pub fn scalar_to_bytes(&mut self, x: Variable) -> [Variable; 32] {
+        let value = self.variables[&x].to_bytes();
+
+        let mut bytes = [self.zero_var; 32];
+        bytes
+            .iter_mut()
+            .zip(value.iter())
+            .for_each(|(byte, value)| {
+                *byte = self.add_input(BlsScalar::from(*value as u64))
+            });
+
+        let packed = self.bytes_to_scalar(&bytes);
+        self.assert_equal(packed, x);
+
+        bytes
+    }
+
+    /// Returns a [`Variable`] holding the scalar represented by the
+    /// little-endian `bytes`.
+    ///
+    /// Each byte is range constrained to 8 bits and the bytes are
+    /// constrained to represent an integer smaller than the scalar field
+    /// modulus, so non-canonical encodings can't satisfy the circuit.
+    pub fn bytes_to_scalar(&mut self, bytes: &[Variable; 32]) -> Variable {
+        bytes.iter().for_each(|byte| self.range_gate(*byte, 8));
+        self.assert_canonical_bytes(bytes);
+
+        // Two bytes are accumulated on each gate, using the fourth wire to
+        // carry the accumulator
+        bytes
+            .chunks(2)
+            .enumerate()
+            .fold(self.zero_var, |acc, (i, pair)| {
+                self.big_add(
+                    (BlsScalar::pow_of_2(16 * i as u64), pair[0]),
+                    (BlsScalar::pow_of_2(16 * i as u64 + 8), pair[1]),
+                    Some((BlsScalar::one(), acc)),
+                    BlsScalar::zero(),
+                    None,
+                )
+            })
+    }
+
+    /// Constrains the little-endian `bytes`, which are expected to be range
+    /// constrained, to represent an integer smaller than the scalar field
+    /// modulus `p`.
+    ///
+    /// The byte-wise subtraction `(p - 1) - bytes` is computed with a borrow
+    /// chain, where each difference is range constrained to 8 bits and the
+    /// final borrow must be zero.
+    fn assert_canonical_bytes(&mut self, bytes: &[Variable; 32]) {
+        let max = (-BlsScalar::one()).to_bytes();
+        let base = BlsScalar::from(256u64);
+
+        let borrow = bytes.iter().zip(max.iter()).fold(
+            self.zero_var,
+            |borrow_in, (byte, max)| {
+                let max = *max as i64;
+                let byte_value = self.variables[byte].to_bytes()[0] as i64;
+                let borrow_value =
+                    self.variables[&borrow_in].to_bytes()[0] as i64;
+
+                let diff = max - byte_value - borrow_value;
+                let borrow_out = (diff < 0) as i64;
+                let diff = diff + 256 * borrow_out;
+
+                let diff = self.add_input(BlsScalar::from(diff as u64));
+                let borrow_out =
+                    self.add_input(BlsScalar::from(borrow_out as u64));
+
+                // max - byte - borrow_in - diff + 256 * borrow_out = 0
+                self.big_add_gate(
+                    *byte,
+                    borrow_in,
+                    diff,
+                    Some(borrow_out),
+                    -BlsScalar::one(),
+                    -BlsScalar::one(),
+                    -BlsScalar::one(),
+                    base,
+                    BlsScalar::from(max as u64),
+                    None,
+                );
+                self.range_gate(diff, 8);
+                self.boolean_gate(borrow_out);
+
+                borrow_out
+            },
+        );
+
+        self.constrain_to_constant(borrow, BlsScalar::zero(), None);
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_scalar_to_bytes() {
+        let res = gadget_tester(
+            |composer| {
+                let values = [
+                    BlsScalar::zero(),
+                    BlsScalar::one(),
+                    -BlsScalar::one(),
+                    BlsScalar::random(&mut OsRng),
+                ];
+                for value in values.iter() {
+                    let x = composer.add_input(*value);
+                    let bytes = composer.scalar_to_bytes(x);
+
+                    for (byte, expected) in
+                        bytes.iter().zip(value.to_bytes().iter())
+                    {
+                        composer.constrain_to_constant(
+                            *byte,
+                            BlsScalar::from(*expected as u64),
+                            None,
+                        );
+                    }
+                }
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_bytes_to_scalar() {
+        let res = gadget_tester(
+            |composer| {
+                let value = -BlsScalar::one();
+                let mut bytes = [composer.zero_var; 32];
+                bytes.iter_mut().zip(value.to_bytes().iter()).for_each(
+                    |(byte, value)| {
+                        *byte =
+                            composer.add_input(BlsScalar::from(*value as u64))
+                    },
+                );
+
+                let x = composer.bytes_to_scalar(&bytes);
+                composer.constrain_to_constant(x, value, None);
+            },
+            1024,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the bytes encode the modulus, which is a
+        // non-canonical encoding of zero
+        let res = gadget_tester(
+            |composer| {
+                let mut modulus = (-BlsScalar::one()).to_bytes();
+                modulus[0] += 1;
+
+                let mut bytes = [composer.zero_var; 32];
+                bytes.iter_mut().zip(modulus.iter()).for_each(
+                    |(byte, value)| {
+                        *byte =
+                            composer.add_input(BlsScalar::from(*value as u64))
+                    },
+                );
+
+                let x = composer.bytes_to_scalar(&bytes);
+                composer.constrain_to_constant(x, BlsScalar::zero(), None);
+            },
+            1024,
+        );
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,276 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Checkpoints of the state of a [`StandardComposer`], to speculatively add
+//! gates to the circuit and roll them back.
+
+use crate::constraint_system::{StandardComposer, Variable, WireData};
+
+/// State of a [`StandardComposer`] returned by
+/// [`StandardComposer::snapshot`], which the composer can be rolled back to
+/// with [`StandardComposer::restore`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComposerCheckpoint {
+    /// Number of gates of the circuit.
+    n: usize,
+    /// Number of [`Variable`]s of the circuit.
+    num_variables: usize,
+    /// Number of range gates of the circuit.
+    num_range_checks: usize,
+    /// Number of copy constraints added without a gate.
+    num_equalities: usize,
+}
+
+impl ComposerCheckpoint {
+    /// Returns the number of gates of the circuit at the checkpoint.
+    pub fn circuit_size(&self) -> usize {
+        self.n
+    }
+}
+
+impl StandardComposer {
+    /// Returns a [`ComposerCheckpoint`] of the current state of the circuit.
+    ///
+    /// Snapshots can be nested: restoring a checkpoint discards every gate
+    /// and [`Variable`] added after it, including the ones of the later
+    /// checkpoints, which must not be restored afterwards.
+    pub fn snapshot(&self) -> ComposerCheckpoint {
+        ComposerCheckpoint {
+            n: self.n,
+            num_variables: self.variables.len(),
+            num_range_checks: self.range_checks.len(),
+            num_equalities: self.perm.equalities.len(),
+        }
+    }
+
+    /// Rolls the circuit back to the state of the `checkpoint`.
+    ///
+    /// The selectors, the wires, the public inputs and the permutation are
+    /// truncated to the gates of the checkpoint, and the [`Variable`]s added
+    /// after it are removed from the circuit. These [`Variable`]s must not
+    /// be used afterwards, since the ones added later on will reuse their
+    /// indexes. The generation of the composer is bumped, so wiring one of
+    /// them into a gate panics in debug builds.
+    ///
+    /// # Panics
+    /// This function will panic if the circuit is smaller than it was at the
+    /// `checkpoint`, which happens when it was already rolled back to an
+    /// earlier one.
+    pub fn restore(&mut self, checkpoint: ComposerCheckpoint) {
+        let ComposerCheckpoint {
+            n,
+            num_variables,
+            num_range_checks,
+            num_equalities,
+        } = checkpoint;
+        assert!(
+            n <= self.n && num_variables <= self.variables.len(),
+            "The checkpoint is ahead of the circuit"
+        );
+
+        self.n = n;
+        [
+            &mut self.q_m,
+            &mut self.q_l,
+            &mut self.q_r,
+            &mut self.q_o,
+            &mut self.q_4,
+            &mut self.q_c,
+            &mut self.q_arith,
+            &mut self.q_range,
+            &mut self.q_logic,
+            &mut self.q_fixed_group_add,
+            &mut self.q_variable_group_add,
+        ]
+        .iter_mut()
+        .for_each(|selector| selector.truncate(n));
+        [&mut self.w_l, &mut self.w_r, &mut self.w_o, &mut self.w_4]
+            .iter_mut()
+            .for_each(|wire| wire.truncate(n));
+
+        self.public_inputs_sparse_store.retain(|pos, _| *pos < n);
+        self.range_checks.truncate(num_range_checks);
+        self.perm.equalities.truncate(num_equalities);
+
+        // The variables are indexed in order of creation
+        self.variables.retain(|var, _| var.0 < num_variables);
+        self.constants.retain(|_, var| var.0 < num_variables);
+        self.perm
+            .variable_map
+            .retain(|var, _| var.0 < num_variables);
+        self.perm.generations.truncate(num_variables);
+        self.perm.generation += 1;
+        self.perm.variable_map.values_mut().for_each(|wires| {
+            wires.retain(|wire| {
+                let gate_index = match wire {
+                    WireData::Left(i)
+                    | WireData::Right(i)
+                    | WireData::Output(i)
+                    | WireData::Fourth(i) => *i,
+                };
+                gate_index < n
+            })
+        });
+
+        debug_assert!(
+            [&self.w_l, &self.w_r, &self.w_o, &self.w_4]
+                .iter()
+                .flat_map(|wire| wire.iter())
+                .chain(self.range_checks.iter().map(|(var, _)| var))
+                .all(|var: &Variable| var.0 < num_variables),
+            "A variable created after the checkpoint is still referenced"
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint_system::helper::*;
+    use dusk_bls12_381::BlsScalar;
+
+    fn assert_same_circuit(a: &StandardComposer, b: &StandardComposer) {
+        assert_eq!(a.n, b.n);
+        assert_eq!(a.q_m, b.q_m);
+        assert_eq!(a.q_l, b.q_l);
+        assert_eq!(a.q_r, b.q_r);
+        assert_eq!(a.q_o, b.q_o);
+        assert_eq!(a.q_4, b.q_4);
+        assert_eq!(a.q_c, b.q_c);
+        assert_eq!(a.q_arith, b.q_arith);
+        assert_eq!(a.q_range, b.q_range);
+        assert_eq!(a.q_logic, b.q_logic);
+        assert_eq!(a.q_fixed_group_add, b.q_fixed_group_add);
+        assert_eq!(a.q_variable_group_add, b.q_variable_group_add);
+        assert_eq!(a.public_inputs_sparse_store, b.public_inputs_sparse_store);
+        assert_eq!(a.w_l, b.w_l);
+        assert_eq!(a.w_r, b.w_r);
+        assert_eq!(a.w_o, b.w_o);
+        assert_eq!(a.w_4, b.w_4);
+        assert_eq!(a.variables, b.variables);
+        assert_eq!(a.perm.variable_map, b.perm.variable_map);
+        assert_eq!(a.perm.equalities, b.perm.equalities);
+        assert_eq!(a.range_checks, b.range_checks);
+        assert_eq!(a.constants, b.constants);
+    }
+
+    /// Adds some gates of every kind, with a public input and a copy
+    /// constraint.
+    fn speculative_gadget(composer: &mut StandardComposer) {
+        let a = composer.add_input(BlsScalar::from(7u64));
+        let b = composer.add_input(BlsScalar::from(9u64));
+        composer.range_gate(a, 8);
+        composer.xor_gate(a, b, 8);
+        let c = composer.add_input(BlsScalar::from(7u64));
+        composer.range_gate(c, 8);
+        composer.assert_equal_wire(a, c);
+        composer.constrain_to_constant(
+            a,
+            BlsScalar::zero(),
+            Some(-BlsScalar::from(7u64)),
+        );
+    }
+
+    #[test]
+    fn test_restore_at_start() {
+        let expected = StandardComposer::new();
+
+        let mut composer = StandardComposer::new();
+        let checkpoint = composer.snapshot();
+        speculative_gadget(&mut composer);
+        assert!(composer.circuit_size() > checkpoint.circuit_size());
+
+        composer.restore(checkpoint);
+        assert_same_circuit(&composer, &expected);
+    }
+
+    #[test]
+    fn test_restore_in_the_middle() {
+        let mut expected = StandardComposer::new();
+        dummy_gadget(5, &mut expected);
+        dummy_gadget(3, &mut expected);
+
+        let mut composer = StandardComposer::new();
+        dummy_gadget(5, &mut composer);
+        let checkpoint = composer.snapshot();
+        speculative_gadget(&mut composer);
+        composer.restore(checkpoint);
+        dummy_gadget(3, &mut composer);
+
+        assert_same_circuit(&composer, &expected);
+    }
+
+    #[test]
+    fn test_nested_restore() {
+        let mut expected = StandardComposer::new();
+        dummy_gadget(2, &mut expected);
+        speculative_gadget(&mut expected);
+
+        let mut composer = StandardComposer::new();
+        dummy_gadget(2, &mut composer);
+        let outer = composer.snapshot();
+        speculative_gadget(&mut composer);
+        let inner = composer.snapshot();
+        speculative_gadget(&mut composer);
+        composer.restore(inner);
+        assert_same_circuit(&composer, &expected);
+
+        composer.restore(outer);
+        dummy_gadget(4, &mut composer);
+
+        let mut expected = StandardComposer::new();
+        dummy_gadget(2, &mut expected);
+        dummy_gadget(4, &mut expected);
+        assert_same_circuit(&composer, &expected);
+    }
+
+    #[test]
+    fn test_rolled_back_gates_are_not_proven() {
+        let res = gadget_tester(
+            |composer| {
+                let checkpoint = composer.snapshot();
+
+                // Unsatisfied gate, discarded with the checkpoint
+                let one = composer.add_input(BlsScalar::one());
+                composer.constrain_to_constant(one, BlsScalar::zero(), None);
+                composer.restore(checkpoint);
+
+                speculative_gadget(composer);
+            },
+            200,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "discarded by StandardComposer::restore")]
+    fn test_use_discarded_variable() {
+        let mut composer = StandardComposer::new();
+        let checkpoint = composer.snapshot();
+        let discarded = composer.add_input(BlsScalar::one());
+        composer.restore(checkpoint);
+
+        // The index of the discarded variable is taken by a new one
+        let fresh = composer.add_input(BlsScalar::one());
+        assert_eq!(discarded.0, fresh.0);
+        composer.constrain_to_constant(fresh, BlsScalar::one(), None);
+
+        composer.constrain_to_constant(discarded, BlsScalar::one(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_restore_stale_checkpoint() {
+        let mut composer = StandardComposer::new();
+        let outer = composer.snapshot();
+        dummy_gadget(2, &mut composer);
+        let inner = composer.snapshot();
+        composer.restore(outer);
+        composer.restore(inner);
+    }
+}
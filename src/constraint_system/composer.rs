@@ -18,12 +18,17 @@
 // it is intended to be like this in order to provide
 // maximum performance and minimum circuit sizes.
 
-use crate::constraint_system::Variable;
+use crate::constraint_system::witness::ComposerWitness;
+use crate::constraint_system::{ArithmeticGate, Variable, WireData};
+use crate::error::Error;
 use crate::permutation::Permutation;
 use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use dusk_jubjub::EDWARDS_D;
 use hashbrown::HashMap;
+use rand_core::{CryptoRng, RngCore};
 
 /// The StandardComposer is the circuit-builder tool that the `dusk-plonk`
 /// repository provides so that circuit descriptions can be written, stored and
@@ -104,6 +109,21 @@ pub struct StandardComposer {
 
     /// Permutation argument.
     pub(crate) perm: Permutation,
+
+    /// Witnesses constrained by a range gate along with their bit width,
+    /// kept to report the range gates that are not satisfied.
+    pub(crate) range_checks: Vec<(Variable, usize)>,
+
+    /// Gate budget of the circuit, see [`StandardComposer::set_max_gates`].
+    pub(crate) max_gates: usize,
+
+    /// Variables created by [`StandardComposer::constant`], indexed by the
+    /// bytes of their value.
+    pub(crate) constants: HashMap<[u8; 32], Variable>,
+
+    /// Provider of the hints, see
+    /// [`StandardComposer::set_witness_provider`].
+    pub(crate) witness_provider: ComposerWitness,
 }
 
 impl StandardComposer {
@@ -134,6 +154,60 @@ impl StandardComposer {
             .copied()
             .collect::<Vec<usize>>()
     }
+
+    /// Returns the number of Public Inputs added to this Composer instance
+    /// so far.
+    ///
+    /// Every gate holds at most one Public Input, so gadgets such as
+    /// [`StandardComposer::assert_equal_public_point`] add one per
+    /// coordinate.
+    pub fn num_public_inputs(&self) -> usize {
+        self.public_inputs_sparse_store.len()
+    }
+
+    /// Returns the number of distinct [`Variable`]s allocated in this
+    /// Composer instance so far, which is the size of the witness.
+    ///
+    /// Every call to [`StandardComposer::add_input`] allocates a new
+    /// variable, including the ones made by gates computing their output,
+    /// while constraining existing variables doesn't. The zero variable
+    /// reserved by the composer is counted as well.
+    pub fn num_variables(&self) -> usize {
+        self.variables.len()
+    }
+
+    /// Sets a budget of `max_gates` gates for the circuit, which is
+    /// unlimited by default.
+    ///
+    /// Gates can still be added past the budget, so the gadgets don't have
+    /// to handle the overflow, but [`StandardComposer::check_gate_budget`]
+    /// fails from then on. This allows to report a circuit too large for
+    /// the [`PublicParameters`](crate::prelude::PublicParameters) right
+    /// after it is built, instead of failing to
+    /// commit to its polynomials.
+    pub fn set_max_gates(&mut self, max_gates: usize) {
+        self.max_gates = max_gates;
+    }
+
+    /// Returns the gate budget of the circuit.
+    pub fn max_gates(&self) -> usize {
+        self.max_gates
+    }
+
+    /// Returns [`Error::CircuitTooLarge`] if the circuit has more gates
+    /// than its budget, see [`StandardComposer::set_max_gates`].
+    pub fn check_gate_budget(&self) -> Result<(), Error> {
+        match self.n > self.max_gates {
+            // The gates are indexed from zero, so the first gate past the
+            // budget is the one at the index of the maximum
+            true => Err(Error::CircuitTooLarge {
+                size: self.n,
+                max: self.max_gates,
+                at_gate: self.max_gates,
+            }),
+            false => Ok(()),
+        }
+    }
 }
 
 impl Default for StandardComposer {
@@ -166,6 +240,23 @@ impl StandardComposer {
         var
     }
 
+    /// Returns a [`Variable`] fixed to `value` in the circuit description.
+    ///
+    /// The first call for a value creates the variable with
+    /// [`StandardComposer::add_witness_to_circuit_description`], which adds
+    /// one gate, and the following ones return the same variable, so a
+    /// constant used many times costs a single gate.
+    pub fn constant(&mut self, value: BlsScalar) -> Variable {
+        let key = value.to_bytes();
+        if let Some(var) = self.constants.get(&key) {
+            return *var;
+        }
+
+        let var = self.add_witness_to_circuit_description(value);
+        self.constants.insert(key, var);
+        var
+    }
+
     /// Creates a new circuit with an expected circuit size.
     /// This will allow for less reallocations when building the circuit
     /// since the `Vec`s will already have an appropriate allocation at the
@@ -192,16 +283,23 @@ impl StandardComposer {
             w_o: Vec::with_capacity(expected_size),
             w_4: Vec::with_capacity(expected_size),
 
-            zero_var: Variable(0),
+            zero_var: Variable(0, 0),
 
             variables: HashMap::with_capacity(expected_size),
 
             perm: Permutation::new(),
+
+            range_checks: Vec::new(),
+
+            max_gates: usize::MAX,
+
+            constants: HashMap::new(),
+
+            witness_provider: ComposerWitness::default(),
         };
 
         // Reserve the first variable to be zero
-        composer.zero_var =
-            composer.add_witness_to_circuit_description(BlsScalar::zero());
+        composer.zero_var = composer.constant(BlsScalar::zero());
 
         // Add dummy constraints
         composer.add_dummy_constraints();
@@ -229,6 +327,36 @@ impl StandardComposer {
         var
     }
 
+    /// Adds each one of the `values` as an input, returning the allocated
+    /// [`Variable`]s in the same order.
+    pub fn add_input_vec(&mut self, values: &[BlsScalar]) -> Vec<Variable> {
+        values.iter().map(|value| self.add_input(*value)).collect()
+    }
+
+    /// Adds each one of the values of the `matrix` as an input, returning
+    /// the allocated [`Variable`]s with the same layout, row by row.
+    ///
+    /// # Panics
+    /// This function will panic if the rows of the `matrix` don't have the
+    /// same length.
+    pub fn add_input_matrix(
+        &mut self,
+        matrix: &[&[BlsScalar]],
+    ) -> Vec<Vec<Variable>> {
+        if let Some(first) = matrix.first() {
+            matrix.iter().enumerate().for_each(|(i, row)| {
+                assert_eq!(
+                    row.len(),
+                    first.len(),
+                    "Row {} of the matrix has a different length than the first",
+                    i
+                )
+            });
+        }
+
+        matrix.iter().map(|row| self.add_input_vec(row)).collect()
+    }
+
     /// Adds a width-3 poly gate.
     /// This gate gives total freedom to the end user to implement the
     /// corresponding circuits in the most optimized way possible because
@@ -238,6 +366,7 @@ impl StandardComposer {
     ///
     /// The final constraint added will force the following:
     /// `(a * b) * q_m + a * q_l + b * q_r + q_c + PI + q_o * c = 0`.
+    #[deprecated(note = "use `ArithmeticGate` instead")]
     pub fn poly_gate(
         &mut self,
         a: Variable,
@@ -250,17 +379,42 @@ impl StandardComposer {
         q_c: BlsScalar,
         pi: Option<BlsScalar>,
     ) -> (Variable, Variable, Variable) {
+        ArithmeticGate::new()
+            .left(a)
+            .right(b)
+            .output(c)
+            .q_m(q_m)
+            .q_l(q_l)
+            .q_r(q_r)
+            .q_o(q_o)
+            .q_c(q_c)
+            .pi(pi)
+            .apply(self)
+    }
+
+    /// Adds the arithmetic `gate` to the circuit description, returning its
+    /// left, right and output wires.
+    ///
+    /// See [`ArithmeticGate::apply`].
+    pub fn add_arithmetic_gate(
+        &mut self,
+        gate: ArithmeticGate,
+    ) -> (Variable, Variable, Variable) {
+        let a = gate.left.unwrap_or(self.zero_var);
+        let b = gate.right.unwrap_or(self.zero_var);
+        let c = gate.output.unwrap_or(self.zero_var);
+
         self.w_l.push(a);
         self.w_r.push(b);
         self.w_o.push(c);
         self.w_4.push(self.zero_var);
-        self.q_l.push(q_l);
-        self.q_r.push(q_r);
+        self.q_l.push(gate.q_l);
+        self.q_r.push(gate.q_r);
 
         // Add selector vectors
-        self.q_m.push(q_m);
-        self.q_o.push(q_o);
-        self.q_c.push(q_c);
+        self.q_m.push(gate.q_m);
+        self.q_o.push(gate.q_o);
+        self.q_c.push(gate.q_c);
         self.q_4.push(BlsScalar::zero());
         self.q_arith.push(BlsScalar::one());
 
@@ -269,7 +423,7 @@ impl StandardComposer {
         self.q_fixed_group_add.push(BlsScalar::zero());
         self.q_variable_group_add.push(BlsScalar::zero());
 
-        if let Some(pi) = pi {
+        if let Some(pi) = gate.pi {
             assert!(self
                 .public_inputs_sparse_store
                 .insert(self.n, pi)
@@ -293,33 +447,78 @@ impl StandardComposer {
         constant: BlsScalar,
         pi: Option<BlsScalar>,
     ) {
-        self.poly_gate(
-            a,
-            a,
-            a,
-            BlsScalar::zero(),
-            BlsScalar::one(),
-            BlsScalar::zero(),
-            BlsScalar::zero(),
-            -constant,
-            pi,
-        );
+        ArithmeticGate::new()
+            .left(a)
+            .right(a)
+            .output(a)
+            .q_l(BlsScalar::one())
+            .q_c(-constant)
+            .pi(pi)
+            .apply(self);
+    }
+
+    /// Constrain a [`Variable`] to be equal to zero.
+    ///
+    /// This function adds a single gate to the circuit description.
+    pub fn constrain_to_zero(&mut self, a: Variable) {
+        self.constrain_to_constant(a, BlsScalar::zero(), None);
     }
 
     /// Add a constraint into the circuit description that states that two
     /// [`Variable`]s are equal.
     pub fn assert_equal(&mut self, a: Variable, b: Variable) {
-        self.poly_gate(
-            a,
-            b,
-            self.zero_var,
-            BlsScalar::zero(),
-            BlsScalar::one(),
-            -BlsScalar::one(),
-            BlsScalar::zero(),
+        ArithmeticGate::new()
+            .left(a)
+            .right(b)
+            .q_l(BlsScalar::one())
+            .q_r(-BlsScalar::one())
+            .apply(self);
+    }
+
+    /// Add a copy constraint into the circuit description that states that
+    /// two [`Variable`]s are equal, as if the same [`Variable`] was used in
+    /// all of their wires.
+    ///
+    /// Unlike [`StandardComposer::assert_equal`], this doesn't add any gate
+    /// to the circuit description: the wires of both variables are linked in
+    /// the same cycle of the permutation argument. It only constrains the
+    /// variables used by some gate.
+    pub fn assert_equal_wire(&mut self, a: Variable, b: Variable) {
+        self.perm.add_equality(a, b);
+    }
+
+    /// Adds a constraint into the circuit description that states that two
+    /// [`Variable`]s are equal when `cond` is set. If:
+    /// cond == 1 => a == b,
+    /// cond == 0 => no constraint,
+    ///
+    /// Forces `cond * (a - b) = 0`, adding two gates to the circuit
+    /// description.
+    ///
+    /// # Note
+    /// The `cond` used as input which is a [`Variable`] should had previously
+    /// been constrained to be either 1 or 0 using a bool constrain. See:
+    /// [`StandardComposer::boolean_gate`].
+    pub fn conditional_assert_equal(
+        &mut self,
+        cond: Variable,
+        a: Variable,
+        b: Variable,
+    ) {
+        // a - b
+        let diff = self.add(
+            (BlsScalar::one(), a),
+            (-BlsScalar::one(), b),
             BlsScalar::zero(),
             None,
         );
+
+        // cond * (a - b) = 0
+        ArithmeticGate::new()
+            .left(cond)
+            .right(diff)
+            .q_m(BlsScalar::one())
+            .apply(self);
     }
 
     /// Conditionally selects a [`Variable`] based on an input bit.
@@ -407,17 +606,15 @@ impl StandardComposer {
             BlsScalar::one() - bit_scalar + (bit_scalar * value_scalar);
         let f_x = self.add_input(f_x_scalar);
 
-        self.poly_gate(
-            bit,
-            value,
-            f_x,
-            BlsScalar::one(),
-            -BlsScalar::one(),
-            BlsScalar::zero(),
-            -BlsScalar::one(),
-            BlsScalar::one(),
-            None,
-        );
+        ArithmeticGate::new()
+            .left(bit)
+            .right(value)
+            .output(f_x)
+            .q_m(BlsScalar::one())
+            .q_l(-BlsScalar::one())
+            .q_o(-BlsScalar::one())
+            .q_c(BlsScalar::one())
+            .apply(self);
 
         f_x
     }
@@ -481,39 +678,125 @@ impl StandardComposer {
         self.n += 1;
     }
 
-    /// Utility function that allows to check on the "front-end"
-    /// side of the PLONK implementation if the identity polynomial
-    /// is satisfied for each one of the [`StandardComposer`]'s gates.
+    /// Appends `rows` blinding gates to the circuit description.
     ///
-    /// The recommended usage is to derive the std output and the std error to a
-    /// text file and analyze there the gates.
+    /// Every selector of a blinding gate is zero, so the gate equation is
+    /// always satisfied, and each one of its four wires holds a fresh
+    /// [`Variable`] assigned to a random value. Since none of these
+    /// variables is copied anywhere else, they don't take part in any
+    /// permutation cycle.
     ///
-    /// # Panic
-    /// The function by itself will print each circuit gate info until one of
-    /// the gates does not satisfy the equation or there are no more gates. If
-    /// the cause is an unsatisfied gate equation, the function will panic.
-    #[cfg(feature = "trace")]
-    pub fn check_circuit_satisfied(&self) {
-        let w_l: Vec<&BlsScalar> = self
-            .w_l
+    /// The random evaluations blind the wire polynomials: as long as the
+    /// number of blinding rows is greater than the number of points each wire
+    /// polynomial is opened at, the openings reveal nothing about the witness.
+    ///
+    /// # Note
+    /// The blinding gates are part of the circuit description, so the same
+    /// number of them must be added to the circuit that is preprocessed by
+    /// the [`Verifier`](crate::proof_system::Verifier).
+    pub fn add_blinding_factors<R: RngCore + CryptoRng>(
+        &mut self,
+        rows: usize,
+        rng: &mut R,
+    ) {
+        for _ in 0..rows {
+            let a = self.add_input(BlsScalar::random(&mut *rng));
+            let b = self.add_input(BlsScalar::random(&mut *rng));
+            let c = self.add_input(BlsScalar::random(&mut *rng));
+            let d = self.add_input(BlsScalar::random(&mut *rng));
+
+            self.q_m.push(BlsScalar::zero());
+            self.q_l.push(BlsScalar::zero());
+            self.q_r.push(BlsScalar::zero());
+            self.q_o.push(BlsScalar::zero());
+            self.q_c.push(BlsScalar::zero());
+            self.q_4.push(BlsScalar::zero());
+            self.q_arith.push(BlsScalar::zero());
+            self.q_range.push(BlsScalar::zero());
+            self.q_logic.push(BlsScalar::zero());
+            self.q_fixed_group_add.push(BlsScalar::zero());
+            self.q_variable_group_add.push(BlsScalar::zero());
+
+            self.w_l.push(a);
+            self.w_r.push(b);
+            self.w_o.push(c);
+            self.w_4.push(d);
+            self.perm.add_variables_to_map(a, b, c, d, self.n);
+            self.n += 1;
+        }
+    }
+
+    /// Returns the index of the first gate whose equation is not satisfied by
+    /// the witness, or `None` if every gate is satisfied.
+    ///
+    /// The equations of every gate are evaluated outside of the circuit,
+    /// along with the public inputs. The copy constraints added with
+    /// [`StandardComposer::assert_equal_wire`] are checked as well: a
+    /// violated one is reported at the first gate using any of its
+    /// variables, see [`StandardComposer::unsatisfied_equalities`].
+    pub fn first_unsatisfied_gate(&self) -> Option<usize> {
+        let pi_vec = self.construct_dense_pi_vec();
+        let gate = (0..self.n)
+            .find(|i| self.gate_evaluation(*i, &pi_vec) != BlsScalar::zero());
+        let equality = self
+            .unsatisfied_equalities()
             .iter()
-            .map(|w_l_i| self.variables.get(&w_l_i).unwrap())
-            .collect();
-        let w_r: Vec<&BlsScalar> = self
-            .w_r
+            .filter_map(|(a, b)| self.first_gate_using(&[*a, *b]))
+            .min();
+
+        gate.into_iter().chain(equality).min()
+    }
+
+    /// Returns the pairs of [`Variable`]s linked with
+    /// [`StandardComposer::assert_equal_wire`] which hold different values,
+    /// in the order the copy constraints were added.
+    ///
+    /// Those variables are used in the wires of the same cycle of the
+    /// permutation, so the permutation argument fails for any of them which
+    /// is used by some gate.
+    pub fn unsatisfied_equalities(&self) -> Vec<(Variable, Variable)> {
+        self.perm
+            .equalities
             .iter()
-            .map(|w_r_i| self.variables.get(&w_r_i).unwrap())
-            .collect();
-        let w_o: Vec<&BlsScalar> = self
-            .w_o
+            .filter(|(a, b)| self.variables[a] != self.variables[b])
+            .copied()
+            .collect()
+    }
+
+    /// Returns the index of the first gate with a wire holding any of the
+    /// `variables`, or `None` if none of them is used.
+    fn first_gate_using(&self, variables: &[Variable]) -> Option<usize> {
+        variables
             .iter()
-            .map(|w_o_i| self.variables.get(&w_o_i).unwrap())
-            .collect();
-        let w_4: Vec<&BlsScalar> = self
-            .w_4
+            .flat_map(|var| self.perm.variable_map[var].iter())
+            .map(|wire| match *wire {
+                WireData::Left(i)
+                | WireData::Right(i)
+                | WireData::Output(i)
+                | WireData::Fourth(i) => i,
+            })
+            .min()
+    }
+
+    /// Evaluates the equation of the gate `i` on the witness, which is zero
+    /// when the gate is satisfied.
+    fn gate_evaluation(&self, i: usize, pi_vec: &[BlsScalar]) -> BlsScalar {
+        self.gate_identities(i, pi_vec)
             .iter()
-            .map(|w_4_i| self.variables.get(&w_4_i).unwrap())
-            .collect();
+            .fold(BlsScalar::zero(), |acc, (_, evaluation)| acc + evaluation)
+    }
+
+    /// Evaluates the arithmetic, logic, range, fixed base and variable base
+    /// identities of the gate `i` on the witness, scaled by their selectors,
+    /// along with their names. They are all zero when the gate is satisfied.
+    ///
+    /// The checks of the curve gates are summed up without the powers of
+    /// the separation challenge which weight them in the quotient.
+    pub(crate) fn gate_identities(
+        &self,
+        i: usize,
+        pi_vec: &[BlsScalar],
+    ) -> [(&'static str, BlsScalar); 5] {
         // Computes f(f-1)(f-2)(f-3)
         let delta = |f: BlsScalar| -> BlsScalar {
             let f_1 = f - BlsScalar::one();
@@ -521,30 +804,125 @@ impl StandardComposer {
             let f_3 = f - BlsScalar::from(3);
             f * f_1 * f_2 * f_3
         };
-        let pi_vec = self.construct_dense_pi_vec();
         let four = BlsScalar::from(4);
-        for i in 0..self.n {
-            let qm = self.q_m[i];
-            let ql = self.q_l[i];
-            let qr = self.q_r[i];
-            let qo = self.q_o[i];
-            let qc = self.q_c[i];
-            let q4 = self.q_4[i];
-            let qarith = self.q_arith[i];
-            let qrange = self.q_range[i];
-            let qlogic = self.q_logic[i];
-            let qfixed = self.q_fixed_group_add[i];
-            let qvar = self.q_variable_group_add[i];
-            let pi = pi_vec[i];
-
-            let a = w_l[i];
-            let a_next = w_l[(i + 1) % self.n];
-            let b = w_r[i];
-            let b_next = w_r[(i + 1) % self.n];
-            let c = w_o[i];
-            let d = w_4[i];
-            let d_next = w_4[(i + 1) % self.n];
+        let next = (i + 1) % self.n;
+
+        let qm = self.q_m[i];
+        let ql = self.q_l[i];
+        let qr = self.q_r[i];
+        let qo = self.q_o[i];
+        let qc = self.q_c[i];
+        let q4 = self.q_4[i];
+        let qarith = self.q_arith[i];
+        let qrange = self.q_range[i];
+        let qlogic = self.q_logic[i];
+        let qfixed = self.q_fixed_group_add[i];
+        let qvariable = self.q_variable_group_add[i];
+        let pi = pi_vec[i];
+
+        let a = self.variables[&self.w_l[i]];
+        let a_next = self.variables[&self.w_l[next]];
+        let b = self.variables[&self.w_r[i]];
+        let b_next = self.variables[&self.w_r[next]];
+        let c = self.variables[&self.w_o[i]];
+        let d = self.variables[&self.w_4[i]];
+        let d_next = self.variables[&self.w_4[next]];
+
+        let arithmetic = qarith
+            * ((qm * a * b)
+                + (ql * a)
+                + (qr * b)
+                + (qo * c)
+                + (q4 * d)
+                + pi
+                + qc);
+        let logic = qlogic
+            * (((delta(a_next - four * a) - delta(b_next - four * b)) * c)
+                + delta(a_next - four * a)
+                + delta(b_next - four * b)
+                + delta(d_next - four * d)
+                + match (
+                    qlogic == BlsScalar::one(),
+                    qlogic == -BlsScalar::one(),
+                ) {
+                    (true, false) => (&a & &b) - d,
+                    (false, true) => (&a ^ &b) - d,
+                    (false, false) => BlsScalar::zero(),
+                    _ => unreachable!(),
+                });
+        let range = qrange
+            * (delta(c - four * d)
+                + delta(b - four * c)
+                + delta(a - four * b)
+                + delta(d_next - four * a));
+
+        // The fixed base wires hold the accumulated point `(a, b)`, the
+        // product of the coordinates of the added point `c` and the
+        // accumulated scalar `d`, while `q_l` and `q_r` hold the coordinates
+        // of the power of the base and `q_c` their product
+        let bit = d_next - d - d;
+        let y_alpha = bit.square() * (qr - BlsScalar::one()) + BlsScalar::one();
+        let x_alpha = bit * ql;
+        let xy = a * b * c * EDWARDS_D;
+        let fixed_base = qfixed
+            * (bit * (bit - BlsScalar::one()) * (bit + BlsScalar::one())
+                + (bit * qc - c)
+                + (a_next + a_next * xy - (a * y_alpha + b * x_alpha))
+                + (b_next - b_next * xy - (b * y_alpha + a * x_alpha)));
+
+        // The variable base wires hold the points `(a, b)` and `(c, d)`,
+        // whose sum is held by the next `a` and `b`, and the product of `a`
+        // and `d` in the next `d`
+        let x1_y2 = d_next;
+        let y1_x2 = b * c;
+        let xy = EDWARDS_D * x1_y2 * y1_x2;
+        let variable_base = qvariable
+            * ((a * d - x1_y2)
+                + (x1_y2 + y1_x2 - (a_next + a_next * xy))
+                + (b * d + a * c - (b_next - b_next * xy)));
+
+        [
+            ("arithmetic", arithmetic),
+            ("logic", logic),
+            ("range", range),
+            ("fixed base", fixed_base),
+            ("variable base", variable_base),
+        ]
+    }
+
+    /// Utility function that allows to check on the "front-end"
+    /// side of the PLONK implementation if the identity polynomial
+    /// is satisfied for each one of the [`StandardComposer`]'s gates.
+    ///
+    /// The recommended usage is to derive the std output and the std error to a
+    /// text file and analyze there the gates.
+    ///
+    /// # Panic
+    /// The function will first panic reporting the offending witness value
+    /// and bit width if any range gate is not satisfied. See
+    /// [`StandardComposer::unsatisfied_range_gates`].
+    ///
+    /// It will then panic reporting the values of the [`Variable`]s if any
+    /// copy constraint added with [`StandardComposer::assert_equal_wire`]
+    /// is not satisfied. See [`StandardComposer::unsatisfied_equalities`].
+    ///
+    /// Otherwise, it will print each circuit gate info until one of the gates
+    /// does not satisfy the equation or there are no more gates. If the cause
+    /// is an unsatisfied gate equation, the function will panic.
+    #[cfg(feature = "trace")]
+    pub fn check_circuit_satisfied(&self) {
+        if let Some(failure) = self.unsatisfied_range_gates().first() {
+            panic!("{}", failure);
+        }
+        if let Some((a, b)) = self.unsatisfied_equalities().first() {
+            panic!(
+                "Copy constraint failed: {:?} = {:?} but {:?} = {:?}",
+                a, self.variables[a], b, self.variables[b]
+            );
+        }
 
+        let pi_vec = self.construct_dense_pi_vec();
+        for i in 0..self.n {
             #[cfg(all(feature = "trace-print", feature = "std"))]
             std::println!(
                 "--------------------------------------------\n
@@ -567,53 +945,29 @@ impl StandardComposer {
             - w_o -> {:?}\n
             - w_4 -> {:?}\n",
                 i,
-                qm,
-                ql,
-                qr,
-                q4,
-                qo,
-                qc,
-                qarith,
-                qrange,
-                qlogic,
-                qfixed,
-                qvar,
-                a,
-                b,
-                c,
-                d
+                self.q_m[i],
+                self.q_l[i],
+                self.q_r[i],
+                self.q_4[i],
+                self.q_o[i],
+                self.q_c[i],
+                self.q_arith[i],
+                self.q_range[i],
+                self.q_logic[i],
+                self.q_fixed_group_add[i],
+                self.q_variable_group_add[i],
+                self.variables[&self.w_l[i]],
+                self.variables[&self.w_r[i]],
+                self.variables[&self.w_o[i]],
+                self.variables[&self.w_4[i]]
             );
 
-            let k = qarith
-                * ((qm * a * b)
-                    + (ql * a)
-                    + (qr * b)
-                    + (qo * c)
-                    + (q4 * d)
-                    + pi
-                    + qc)
-                + qlogic
-                    * (((delta(a_next - four * a) - delta(b_next - four * b))
-                        * c)
-                        + delta(a_next - four * a)
-                        + delta(b_next - four * b)
-                        + delta(d_next - four * d)
-                        + match (
-                            qlogic == BlsScalar::one(),
-                            qlogic == -BlsScalar::one(),
-                        ) {
-                            (true, false) => (a & b) - d,
-                            (false, true) => (a ^ b) - d,
-                            (false, false) => BlsScalar::zero(),
-                            _ => unreachable!(),
-                        })
-                + qrange
-                    * (delta(c - four * d)
-                        + delta(b - four * c)
-                        + delta(a - four * b)
-                        + delta(d_next - four * a));
-
-            assert_eq!(k, BlsScalar::zero(), "Check failed at gate {}", i,);
+            assert_eq!(
+                self.gate_evaluation(i, &pi_vec),
+                BlsScalar::zero(),
+                "Check failed at gate {}",
+                i,
+            );
         }
     }
 }
@@ -641,6 +995,106 @@ mod tests {
         assert_eq!(3, composer.circuit_size())
     }
 
+    #[test]
+    fn test_add_input_matrix() {
+        let mut composer = StandardComposer::new();
+        let values: Vec<BlsScalar> =
+            (0..6u64).map(|i| BlsScalar::from(i * i)).collect();
+
+        let vars = composer.add_input_vec(&values);
+        assert_eq!(vars.len(), 6);
+        vars.iter().zip(values.iter()).for_each(|(var, value)| {
+            assert_eq!(composer.variables[var], *value)
+        });
+
+        let matrix = composer.add_input_matrix(&[&values[..3], &values[3..]]);
+        assert_eq!(matrix.len(), 2);
+        matrix.iter().enumerate().for_each(|(i, row)| {
+            assert_eq!(row.len(), 3);
+            row.iter().enumerate().for_each(|(j, var)| {
+                assert_eq!(composer.variables[var], values[3 * i + j])
+            });
+        });
+
+        // Inputs don't add gates and get distinct variables
+        assert_eq!(composer.circuit_size(), 3);
+        assert_ne!(matrix[0][0], vars[0]);
+
+        assert!(composer.add_input_matrix(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_input_matrix_ragged() {
+        let mut composer = StandardComposer::new();
+        let values = [BlsScalar::one(); 5];
+        composer.add_input_matrix(&[&values[..3], &values[3..]]);
+    }
+
+    #[test]
+    fn test_num_public_inputs() {
+        let mut composer = StandardComposer::new();
+        assert_eq!(composer.num_public_inputs(), 0);
+
+        let a = composer.add_input(BlsScalar::from(2u64));
+        let b = composer.add_input(BlsScalar::from(3u64));
+        composer.add(
+            (BlsScalar::one(), a),
+            (BlsScalar::one(), b),
+            BlsScalar::zero(),
+            None,
+        );
+        assert_eq!(composer.num_public_inputs(), 0);
+
+        composer.constrain_to_constant(
+            a,
+            BlsScalar::zero(),
+            Some(-BlsScalar::from(2u64)),
+        );
+        composer.mul(
+            BlsScalar::one(),
+            a,
+            b,
+            BlsScalar::zero(),
+            Some(BlsScalar::one()),
+        );
+        assert_eq!(composer.num_public_inputs(), 2);
+
+        let generator = dusk_jubjub::GENERATOR;
+        let point = composer.add_affine(generator);
+        composer.assert_equal_public_point(point, generator);
+        assert_eq!(composer.num_public_inputs(), 4);
+
+        composer.add_public_affine(generator);
+        assert_eq!(composer.num_public_inputs(), 6);
+        assert_eq!(composer.pi_positions().len(), 6);
+    }
+
+    #[test]
+    fn test_num_variables() {
+        let mut composer = StandardComposer::new();
+        let initial = composer.num_variables();
+        assert!(initial >= 1);
+
+        let a = composer.add_input(BlsScalar::from(2u64));
+        let b = composer.add_input(BlsScalar::from(3u64));
+        assert_eq!(composer.num_variables(), initial + 2);
+
+        // The output of the gate is a new variable
+        let c = composer.add(
+            (BlsScalar::one(), a),
+            (BlsScalar::one(), b),
+            BlsScalar::zero(),
+            None,
+        );
+        assert_eq!(composer.num_variables(), initial + 3);
+
+        // Constraining existing variables doesn't allocate
+        composer.constrain_to_constant(c, BlsScalar::from(5u64), None);
+        composer.assert_equal(a, a);
+        assert_eq!(composer.num_variables(), initial + 3);
+    }
+
     #[allow(unused_variables)]
     #[test]
     #[ignore]
@@ -655,6 +1109,192 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn test_constrain_to_zero() {
+        let res = gadget_tester(
+            |composer| {
+                let zero = composer.add_input(BlsScalar::zero());
+
+                let n = composer.circuit_size();
+                composer.constrain_to_zero(zero);
+                assert_eq!(composer.circuit_size() - n, 1);
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the value is not zero
+        let res = gadget_tester(
+            |composer| {
+                let one = composer.add_input(BlsScalar::one());
+                composer.constrain_to_zero(one);
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_constant() {
+        let mut composer = StandardComposer::new();
+        assert_eq!(composer.constant(BlsScalar::zero()), composer.zero_var());
+
+        let n = composer.circuit_size();
+        let one = composer.constant(BlsScalar::one());
+        assert_eq!(composer.circuit_size() - n, 1);
+
+        // The following calls reuse the variable without adding gates
+        assert_eq!(composer.constant(BlsScalar::one()), one);
+        assert_eq!(composer.constant(BlsScalar::one()), one);
+        assert_eq!(composer.circuit_size() - n, 1);
+        assert_ne!(composer.constant(BlsScalar::from(2u64)), one);
+
+        let res = gadget_tester(
+            |composer| {
+                let five = composer.constant(BlsScalar::from(5u64));
+                let ten = composer.add(
+                    (BlsScalar::one(), five),
+                    (BlsScalar::one(), five),
+                    BlsScalar::zero(),
+                    None,
+                );
+                let other_five = composer.constant(BlsScalar::from(5u64));
+                composer.mul(
+                    BlsScalar::one(),
+                    other_five,
+                    ten,
+                    BlsScalar::zero(),
+                    None,
+                );
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the constant is fixed by the circuit
+        let res = gadget_tester(
+            |composer| {
+                let one = composer.constant(BlsScalar::one());
+                composer.variables.insert(one, BlsScalar::from(2u64));
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_assert_equal_wire() {
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(5u64));
+                let b = composer.add_input(BlsScalar::from(5u64));
+                let c = composer.add_input(BlsScalar::from(5u64));
+                composer.constrain_to_constant(a, BlsScalar::from(5u64), None);
+                composer.constrain_to_constant(b, BlsScalar::from(5u64), None);
+                composer.constrain_to_constant(c, BlsScalar::from(5u64), None);
+
+                // No gate is needed, unlike with `assert_equal`
+                let n = composer.circuit_size();
+                composer.assert_equal_wire(a, b);
+                composer.assert_equal_wire(c, b);
+                assert_eq!(composer.circuit_size(), n);
+
+                composer.assert_equal(a, c);
+                assert_eq!(composer.circuit_size(), n + 1);
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the values are different, even though every
+        // gate is satisfied
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(5u64));
+                let b = composer.add_input(BlsScalar::from(6u64));
+                composer.range_gate(a, 8);
+                composer.range_gate(b, 8);
+                composer.assert_equal_wire(a, b);
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_unsatisfied_equalities() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(5u64));
+        let b = composer.add_input(BlsScalar::from(5u64));
+        let c = composer.add_input(BlsScalar::from(6u64));
+        composer.assert_equal_wire(a, b);
+        composer.range_gate(a, 8);
+        let gate = composer.circuit_size();
+        composer.constrain_to_constant(c, BlsScalar::from(6u64), None);
+        assert!(composer.unsatisfied_equalities().is_empty());
+        assert_eq!(composer.first_unsatisfied_gate(), None);
+
+        // Every gate is satisfied, but not the copy constraint, which is
+        // reported at the first gate using `c`
+        composer.assert_equal_wire(b, c);
+        assert_eq!(composer.unsatisfied_equalities(), vec![(b, c)]);
+        assert_eq!(composer.first_unsatisfied_gate(), Some(gate));
+
+        // A failing witness of a satisfied circuit is caught as well
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(5u64));
+        let b = composer.add_input(BlsScalar::from(5u64));
+        composer.range_gate(a, 8);
+        composer.range_gate(b, 8);
+        composer.assert_equal_wire(a, b);
+        assert_eq!(composer.first_unsatisfied_gate(), None);
+
+        composer.variables.insert(b, BlsScalar::from(7u64));
+        assert_eq!(composer.unsatisfied_equalities(), vec![(a, b)]);
+        assert!(composer.first_unsatisfied_gate().is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "trace")]
+    #[should_panic(expected = "Copy constraint failed")]
+    fn test_check_circuit_satisfied_equalities() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(5u64));
+        let b = composer.add_input(BlsScalar::from(6u64));
+        composer.range_gate(a, 8);
+        composer.range_gate(b, 8);
+        composer.assert_equal_wire(a, b);
+        composer.check_circuit_satisfied();
+    }
+
+    #[test]
+    fn test_gate_budget() {
+        let mut composer = StandardComposer::new();
+        assert_eq!(composer.max_gates(), usize::MAX);
+
+        let size = composer.circuit_size();
+        composer.set_max_gates(size + 5);
+        dummy_gadget(5, &mut composer);
+        assert!(composer.circuit_size() > size + 5);
+
+        match composer.check_gate_budget() {
+            Err(Error::CircuitTooLarge {
+                size: n,
+                max,
+                at_gate,
+            }) => {
+                assert_eq!(n, composer.circuit_size());
+                assert_eq!(max, size + 5);
+                assert_eq!(at_gate, size + 5);
+            }
+            _ => panic!("The circuit exceeds its budget"),
+        }
+
+        // The budget can be raised afterwards
+        composer.set_max_gates(composer.circuit_size());
+        assert!(composer.check_gate_budget().is_ok());
+    }
+
     #[test]
     fn test_conditional_select() {
         let res = gadget_tester(
@@ -678,6 +1318,44 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn test_conditional_assert_equal() {
+        let res = gadget_tester(
+            |composer| {
+                let cond_1 = composer.add_input(BlsScalar::one());
+                composer.boolean_gate(cond_1);
+                let cond_0 = composer.zero_var();
+
+                let a = composer.add_input(BlsScalar::from(10u64));
+                let b = composer.add_input(BlsScalar::from(10u64));
+                let c = composer.add_input(BlsScalar::from(20u64));
+
+                let n = composer.circuit_size();
+                composer.conditional_assert_equal(cond_1, a, b);
+                assert_eq!(composer.circuit_size() - n, 2);
+
+                // The assertion is vacuous when the condition is not set
+                composer.conditional_assert_equal(cond_0, a, c);
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the condition is set and the values differ
+        let res = gadget_tester(
+            |composer| {
+                let cond = composer.add_input(BlsScalar::one());
+                composer.boolean_gate(cond);
+
+                let a = composer.add_input(BlsScalar::from(10u64));
+                let b = composer.add_input(BlsScalar::from(20u64));
+                composer.conditional_assert_equal(cond, a, b);
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
     #[test]
     // XXX: Move this to integration tests
     fn test_multiple_proofs() {
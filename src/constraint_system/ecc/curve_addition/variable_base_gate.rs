@@ -14,6 +14,14 @@ impl StandardComposer {
     /// Note that since the points are not fixed the generator is not a part of
     /// the circuit description, however it is less efficient for a program
     /// width of 4.
+    ///
+    /// The addition is complete: the gate constrains the complete addition
+    /// formulas of the twisted Edwards curve, which hold for any pair of
+    /// points, including the doubling of a point, the addition of a point
+    /// and its negation, and the addition of the identity. No special case
+    /// has to be handled by the caller.
+    ///
+    /// This function adds 2 gates to the circuit description.
     pub fn point_addition_gate(
         &mut self,
         point_a: Point,
@@ -83,6 +91,34 @@ impl StandardComposer {
 
         Point { x: x_3, y: y_3 }
     }
+
+    /// Returns the negation of `point`, which is the point with the opposite
+    /// `x` coordinate on the twisted Edwards curve.
+    ///
+    /// This function adds 1 gate to the circuit description.
+    pub fn point_neg(&mut self, point: Point) -> Point {
+        let neg_x = self.add(
+            (-BlsScalar::one(), point.x),
+            (BlsScalar::zero(), self.zero_var),
+            BlsScalar::zero(),
+            None,
+        );
+
+        Point {
+            x: neg_x,
+            y: point.y,
+        }
+    }
+
+    /// Subtracts `point_b` from `point_a` by adding its
+    /// [`StandardComposer::point_neg`] with
+    /// [`StandardComposer::point_addition_gate`].
+    ///
+    /// This function adds 3 gates to the circuit description.
+    pub fn point_sub(&mut self, point_a: Point, point_b: Point) -> Point {
+        let neg_b = self.point_neg(point_b);
+        self.point_addition_gate(point_a, neg_b)
+    }
 }
 
 #[cfg(feature = "std")]
@@ -90,8 +126,8 @@ impl StandardComposer {
 mod test {
     use super::*;
     use crate::constraint_system::helper::*;
-    use dusk_jubjub::GENERATOR;
-    use dusk_jubjub::{JubJubAffine, JubJubExtended, EDWARDS_D};
+    use dusk_jubjub::{JubJubAffine, JubJubExtended, JubJubScalar, EDWARDS_D};
+    use dusk_jubjub::{GENERATOR, GENERATOR_EXTENDED};
 
     /// Adds two curve points together using the classical point addition
     /// algorithm. This method is slower than WNaf and is just meant to be the
@@ -240,4 +276,132 @@ mod test {
         );
         assert!(res.is_ok());
     }
+
+    #[test]
+    fn test_complete_point_addition() {
+        // G + G = 2G
+        let res = gadget_tester(
+            |composer| {
+                let g = composer.add_affine(GENERATOR);
+                let n = composer.circuit_size();
+                let sum = composer.point_addition_gate(g, g);
+                assert_eq!(composer.circuit_size() - n, 2);
+
+                composer.assert_equal_public_point(
+                    sum,
+                    GENERATOR_EXTENDED.double().into(),
+                );
+            },
+            64,
+        );
+        assert!(res.is_ok());
+
+        // O + G = G and G + (-G) = O
+        let res = gadget_tester(
+            |composer| {
+                let identity = Point::identity(composer);
+                let g = composer.add_affine(GENERATOR);
+                let neg_g = composer.add_affine((-GENERATOR_EXTENDED).into());
+
+                let sum = composer.point_addition_gate(identity, g);
+                composer.assert_equal_public_point(sum, GENERATOR);
+                let sum = composer.point_addition_gate(g, neg_g);
+                composer
+                    .assert_equal_public_point(sum, JubJubAffine::identity());
+            },
+            64,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since G + G is not G
+        let res = gadget_tester(
+            |composer| {
+                let g = composer.add_affine(GENERATOR);
+                let sum = composer.point_addition_gate(g, g);
+                composer.assert_equal_public_point(sum, GENERATOR);
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_point_neg() {
+        // P + (-P) = O and -(-P) = P
+        let res = gadget_tester(
+            |composer| {
+                let p = GENERATOR_EXTENDED * JubJubScalar::from(5u64);
+                let p = composer.add_affine(p.into());
+
+                let n = composer.circuit_size();
+                let neg_p = composer.point_neg(p);
+                assert_eq!(composer.circuit_size() - n, 1);
+
+                let sum = composer.point_addition_gate(p, neg_p);
+                composer
+                    .assert_equal_public_point(sum, JubJubAffine::identity());
+                let p_again = composer.point_neg(neg_p);
+                composer.assert_equal(*p_again.x(), *p.x());
+                composer.assert_equal(*p_again.y(), *p.y());
+            },
+            64,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the negation of G is not G
+        let res = gadget_tester(
+            |composer| {
+                let g = composer.add_affine(GENERATOR);
+                let neg_g = composer.point_neg(g);
+                composer.assert_equal_public_point(neg_g, GENERATOR);
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
+
+    fn test_subtraction(composer: &mut StandardComposer, expected: u64) {
+        let p = GENERATOR_EXTENDED * JubJubScalar::from(5u64);
+        let q = GENERATOR_EXTENDED * JubJubScalar::from(7u64);
+        let expected = GENERATOR_EXTENDED * JubJubScalar::from(expected);
+
+        let p = composer.add_affine(p.into());
+        let q = composer.add_affine(q.into());
+        let sum = composer.point_addition_gate(p, q);
+
+        let n = composer.circuit_size();
+        let diff = composer.point_sub(sum, q);
+        assert_eq!(composer.circuit_size() - n, 3);
+
+        composer.assert_equal_public_point(diff, expected.into());
+    }
+
+    #[test]
+    fn test_point_sub() {
+        // (P + Q) - Q = P
+        let res = gadget_tester(|composer| test_subtraction(composer, 5), 64);
+        assert!(res.is_ok());
+
+        // P - Q = P + (-Q)
+        let res = gadget_tester(
+            |composer| {
+                let p = GENERATOR_EXTENDED * JubJubScalar::from(5u64);
+                let q = GENERATOR_EXTENDED * JubJubScalar::from(7u64);
+                let p = composer.add_affine(p.into());
+                let q = composer.add_affine(q.into());
+
+                let diff = composer.point_sub(p, q);
+                let neg_q = composer.point_neg(q);
+                let sum = composer.point_addition_gate(p, neg_q);
+                composer.assert_equal(*diff.x(), *sum.x());
+                composer.assert_equal(*diff.y(), *sum.y());
+            },
+            64,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since (P + Q) - Q is not Q
+        let res = gadget_tester(|composer| test_subtraction(composer, 7), 64);
+        assert!(res.is_err());
+    }
 }
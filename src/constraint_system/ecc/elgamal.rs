@@ -0,0 +1,195 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! ElGamal encryption over JubJub.
+//!
+//! A message `m` is encrypted under the public key `PK = sk * G` with the
+//! randomness `r` as the ciphertext `(c1, c2) = (r * G, m * G + r * PK)`,
+//! where `G` is [`GENERATOR_EXTENDED`]. The owner of `sk` recovers `m * G`
+//! as `c2 - sk * c1`.
+
+use crate::constraint_system::ecc::Point;
+use crate::constraint_system::{StandardComposer, Variable};
+use dusk_jubjub::{
+    JubJubAffine, JubJubExtended, JubJubScalar, GENERATOR_EXTENDED,
+};
+
+/// Returns the ElGamal encryption of `message` under the `public_key` with
+/// the `randomness`, computed outside of the circuit as
+/// [`StandardComposer::elgamal_encrypt`] does.
+pub fn elgamal_encrypt(
+    message: JubJubScalar,
+    randomness: JubJubScalar,
+    public_key: JubJubExtended,
+) -> (JubJubAffine, JubJubAffine) {
+    let c1 = GENERATOR_EXTENDED * randomness;
+    let c2 = GENERATOR_EXTENDED * message + public_key * randomness;
+
+    (c1.into(), c2.into())
+}
+
+impl StandardComposer {
+    /// Returns the [`Point`]s `(c1, c2)` of the ElGamal encryption of
+    /// `message` under the `public_key` with the `randomness`, see
+    /// [`elgamal_encrypt`].
+    ///
+    /// Both `message` and `randomness` must hold JubJub scalars. The
+    /// ciphertext is computed with two fixed base scalar multiplications, a
+    /// variable base one and a curve addition.
+    pub fn elgamal_encrypt(
+        &mut self,
+        message: Variable,
+        randomness: Variable,
+        public_key: Point,
+    ) -> (Point, Point) {
+        let c1 = self.fixed_base_scalar_mul(randomness, GENERATOR_EXTENDED);
+
+        let message = self.fixed_base_scalar_mul(message, GENERATOR_EXTENDED);
+        let shared = self.variable_base_scalar_mul(randomness, public_key);
+        let c2 = self.point_addition_gate(message, shared);
+
+        (c1, c2)
+    }
+
+    /// Constrains the ElGamal encryption of `message` under the `public_key`
+    /// with the `randomness` to be equal to the public ciphertext
+    /// `(c1, c2)`.
+    ///
+    /// The coordinates of the ciphertext are added as public inputs, in the
+    /// order `c1.x`, `c1.y`, `c2.x`, `c2.y`.
+    pub fn assert_elgamal_ciphertext(
+        &mut self,
+        message: Variable,
+        randomness: Variable,
+        public_key: Point,
+        (c1, c2): (JubJubAffine, JubJubAffine),
+    ) {
+        let (c1_point, c2_point) =
+            self.elgamal_encrypt(message, randomness, public_key);
+
+        self.assert_equal_public_point(c1_point, c1);
+        self.assert_equal_public_point(c2_point, c2);
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::{self, Circuit, PublicInputValue};
+    use crate::commitment_scheme::kzg10::PublicParameters;
+    use crate::error::Error;
+    use rand_core::OsRng;
+
+    #[derive(Debug, Default)]
+    struct ElGamalCircuit {
+        message: JubJubScalar,
+        randomness: JubJubScalar,
+        public_key: JubJubAffine,
+        ciphertext: (JubJubAffine, JubJubAffine),
+    }
+
+    impl Circuit for ElGamalCircuit {
+        const CIRCUIT_ID: [u8; 32] = [0xee; 32];
+
+        fn gadget(
+            &mut self,
+            composer: &mut StandardComposer,
+        ) -> Result<(), Error> {
+            let public_key = composer.add_public_affine(self.public_key);
+            let message = composer.add_input(self.message.into());
+            let randomness = composer.add_input(self.randomness.into());
+
+            composer.assert_elgamal_ciphertext(
+                message,
+                randomness,
+                public_key,
+                self.ciphertext,
+            );
+            Ok(())
+        }
+
+        fn padded_circuit_size(&self) -> usize {
+            1 << 12
+        }
+    }
+
+    #[test]
+    fn test_elgamal_decryption() {
+        let secret_key = JubJubScalar::random(&mut OsRng);
+        let public_key = GENERATOR_EXTENDED * secret_key;
+        let message = JubJubScalar::from(42u64);
+
+        let (c1, c2) = elgamal_encrypt(
+            message,
+            JubJubScalar::random(&mut OsRng),
+            public_key,
+        );
+
+        let decrypted =
+            JubJubExtended::from(c2) - JubJubExtended::from(c1) * secret_key;
+        assert_eq!(
+            JubJubAffine::from(decrypted),
+            JubJubAffine::from(GENERATOR_EXTENDED * message)
+        );
+    }
+
+    #[test]
+    fn test_elgamal_encrypt() -> Result<(), Error> {
+        let pp = PublicParameters::setup(1 << 13, &mut OsRng)?;
+        let (pk, vd) = ElGamalCircuit::default().compile(&pp)?;
+
+        let public_key = GENERATOR_EXTENDED * JubJubScalar::random(&mut OsRng);
+        let message = JubJubScalar::from(1000u64);
+        let randomness = JubJubScalar::random(&mut OsRng);
+        let ciphertext = elgamal_encrypt(message, randomness, public_key);
+
+        let proof = ElGamalCircuit {
+            message,
+            randomness,
+            public_key: public_key.into(),
+            ciphertext,
+        }
+        .gen_proof(&pp, &pk, b"ElGamal")?;
+
+        let public_inputs = [
+            PublicInputValue::from(JubJubAffine::from(public_key)),
+            PublicInputValue::from(ciphertext.0),
+            PublicInputValue::from(ciphertext.1),
+        ];
+        circuit::verify_proof(
+            &pp,
+            vd.key(),
+            &proof,
+            &public_inputs,
+            vd.pi_pos(),
+            b"ElGamal",
+        )?;
+
+        // Should fail since the ciphertext encrypts another message
+        let (_, wrong_c2) = elgamal_encrypt(
+            JubJubScalar::from(1001u64),
+            randomness,
+            public_key,
+        );
+        let public_inputs = [
+            PublicInputValue::from(JubJubAffine::from(public_key)),
+            PublicInputValue::from(ciphertext.0),
+            PublicInputValue::from(wrong_c2),
+        ];
+        assert!(circuit::verify_proof(
+            &pp,
+            vd.key(),
+            &proof,
+            &public_inputs,
+            vd.pi_pos(),
+            b"ElGamal",
+        )
+        .is_err());
+
+        Ok(())
+    }
+}
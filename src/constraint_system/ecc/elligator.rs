@@ -0,0 +1,324 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::ecc::Point;
+use crate::constraint_system::{
+    ArithmeticGate, PoseidonParams, StandardComposer, Variable,
+};
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use dusk_jubjub::EDWARDS_D;
+
+/// Non-square used by the Elligator2 map. It's the multiplicative generator
+/// of the field, which is never a square.
+const Z: BlsScalar = BlsScalar::from_raw([7, 0, 0, 0]);
+
+/// Returns the constants `c1 = A / B` and `c2 = 1 / B^2` of the Elligator2
+/// map, where `B * v^2 = u^3 + A * u^2 + u` is the Montgomery curve
+/// birationally equivalent to JubJub, with `A = 2 * (d - 1) / (-1 - d)` and
+/// `B = 4 / (-1 - d)`.
+fn elligator_constants() -> (BlsScalar, BlsScalar) {
+    let one_plus_d = BlsScalar::one() + EDWARDS_D;
+    let c1 = (EDWARDS_D - BlsScalar::one())
+        * BlsScalar::from(2u64).invert().unwrap();
+    let c2 = one_plus_d.square() * BlsScalar::from(16u64).invert().unwrap();
+    (c1, c2)
+}
+
+/// Returns the parity of the canonical representation of a [`BlsScalar`].
+fn is_odd(scalar: &BlsScalar) -> bool {
+    scalar.to_bytes()[0] & 1 == 1
+}
+
+impl StandardComposer {
+    /// Maps the value of `u` to a JubJub [`Point`] of the prime order
+    /// subgroup, using the Elligator2 map followed by the clearing of the
+    /// cofactor.
+    ///
+    /// The map follows the straight-line Elligator2 of RFC 9380 over the
+    /// Montgomery form of JubJub: `x1 = -c1 / (1 + Z * u^2)` is taken as the
+    /// Montgomery coordinate if `g(x1)` is a square and `x2 = -x1 - c1`
+    /// otherwise, with the square root being even in the first case and odd
+    /// in the second. The resulting Montgomery point is converted into the
+    /// twisted Edwards form and multiplied by the cofactor 8 with three
+    /// doublings.
+    ///
+    /// To hash a message to the curve, `u` should be the output of a hash to
+    /// the field of the message, as done by
+    /// [`StandardComposer::hash_to_curve`].
+    pub fn map_to_curve(&mut self, u: Variable) -> Point {
+        let (c1, c2) = elligator_constants();
+        let k = -BlsScalar::from(4u64)
+            * (BlsScalar::one() + EDWARDS_D).invert().unwrap();
+
+        // tv1 = Z * u^2, which is never -1 since -1 is a square and Z is not
+        let tv1 = self.mul(Z, u, u, BlsScalar::zero(), None);
+
+        // x1 * (1 + tv1) + c1 = 0
+        let x1_value =
+            -c1 * (BlsScalar::one() + self.variables[&tv1]).invert().unwrap();
+        let x1 = self.add_input(x1_value);
+        ArithmeticGate::new()
+            .left(x1)
+            .right(tv1)
+            .q_m(BlsScalar::one())
+            .q_l(BlsScalar::one())
+            .q_c(c1)
+            .apply(self);
+
+        // Exactly one of g(x1) and g(x2) = Z * u^2 * g(x1) is a square, so
+        // the root constraint below forces the correct branch. For u = 0,
+        // g(x1) = -c1 * c2 is not a square and x2 = g(x2) = 0
+        let gx1 = x1_value * (x1_value.square() + c1 * x1_value + c2);
+        let gx1_root: Option<BlsScalar> = gx1.sqrt().into();
+        let is_square =
+            self.add_input(BlsScalar::from(gx1_root.is_some() as u64));
+        self.boolean_gate(is_square);
+
+        // x = x2 + is_square * (x1 - x2), which expands to
+        // 2 * is_square * x1 + c1 * is_square - x1 - c1 - x = 0
+        let x_value = match gx1_root {
+            Some(_) => x1_value,
+            None => -x1_value - c1,
+        };
+        let x = self.add_input(x_value);
+        ArithmeticGate::new()
+            .left(is_square)
+            .right(x1)
+            .output(x)
+            .q_m(BlsScalar::from(2u64))
+            .q_l(c1)
+            .q_r(-BlsScalar::one())
+            .q_o(-BlsScalar::one())
+            .q_c(-c1)
+            .apply(self);
+
+        // g(x) = (x^2 + c1 * x + c2) * x
+        let x_sq = self.mul(BlsScalar::one(), x, x, BlsScalar::zero(), None);
+        let t = self.add((BlsScalar::one(), x_sq), (c1, x), c2, None);
+        let gx = self.mul(BlsScalar::one(), t, x, BlsScalar::zero(), None);
+
+        // y is the root of g(x) which is even if g(x1) is a square and odd
+        // otherwise
+        let gx_value = self.variables[&gx];
+        let y_value: Option<BlsScalar> = gx_value.sqrt().into();
+        let y_value = y_value.unwrap_or(BlsScalar::zero());
+        let odd = gx1_root.is_none();
+        let y_value = match is_odd(&y_value) == odd {
+            true => y_value,
+            false => -y_value,
+        };
+        let y = self.add_input(y_value);
+
+        // y * y - g(x) = 0
+        ArithmeticGate::new()
+            .left(y)
+            .right(y)
+            .output(gx)
+            .q_m(BlsScalar::one())
+            .q_o(-BlsScalar::one())
+            .apply(self);
+
+        self.constrain_parity(y, is_square);
+
+        // The Montgomery point is (K * x, K * y), which is mapped to the
+        // twisted Edwards point (x / y, (K * x - 1) / (K * x + 1)). The
+        // exceptional cases are mapped as with an inverse of zero being zero
+        let (y_inv, _) = self.try_inverse(y);
+        let edwards_x =
+            self.mul(BlsScalar::one(), x, y_inv, BlsScalar::zero(), None);
+
+        let w = self.add(
+            (k, x),
+            (BlsScalar::zero(), self.zero_var),
+            BlsScalar::one(),
+            None,
+        );
+        let (w_inv, _) = self.try_inverse(w);
+        let edwards_y_value = (self.variables[&w] - BlsScalar::from(2u64))
+            * self.variables[&w_inv];
+        let edwards_y = self.add_input(edwards_y_value);
+
+        // w * w_inv - 2 * w_inv - edwards_y = 0
+        ArithmeticGate::new()
+            .left(w)
+            .right(w_inv)
+            .output(edwards_y)
+            .q_m(BlsScalar::one())
+            .q_r(-BlsScalar::from(2u64))
+            .q_o(-BlsScalar::one())
+            .apply(self);
+
+        // Clear the cofactor 8
+        let point = Point {
+            x: edwards_x,
+            y: edwards_y,
+        };
+        let point = self.point_addition_gate(point, point);
+        let point = self.point_addition_gate(point, point);
+        self.point_addition_gate(point, point)
+    }
+
+    /// Hashes the `msg` to a JubJub [`Point`] of the prime order subgroup,
+    /// by mapping its Poseidon sponge hash to the curve with
+    /// [`StandardComposer::map_to_curve`].
+    pub fn hash_to_curve(
+        &mut self,
+        params: &PoseidonParams,
+        msg: &[Variable],
+    ) -> Point {
+        let u = self.poseidon_sponge(params, msg);
+        self.map_to_curve(u)
+    }
+
+    /// Constrains the canonical representation of the value of `y` to be
+    /// even if `is_even` is one and odd if it's zero.
+    ///
+    /// The value is decomposed as `y = 2 * h + 1 - is_even` where
+    /// `h <= (p - 1) / 2`, so the decomposition is unique for any non-zero
+    /// value. Zero is accepted with both parities.
+    fn constrain_parity(&mut self, y: Variable, is_even: Variable) {
+        let half =
+            self.variables[&y] - BlsScalar::one() + self.variables[&is_even];
+        let half = half * BlsScalar::from(2u64).invert().unwrap();
+        let half = self.add_input(half);
+
+        // 2 * h - is_even + 1 - y = 0
+        self.big_add_gate(
+            half,
+            is_even,
+            y,
+            None,
+            BlsScalar::from(2u64),
+            -BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::zero(),
+            BlsScalar::one(),
+            None,
+        );
+
+        // (p - 1) / 2 = -1 / 2
+        let max_half = -BlsScalar::from(2u64).invert().unwrap();
+        let complement = self.add(
+            (-BlsScalar::one(), half),
+            (BlsScalar::zero(), self.zero_var),
+            max_half,
+            None,
+        );
+
+        self.range_gate(half, 254);
+        self.range_gate(complement, 254);
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint_system::helper::*;
+    use alloc::vec::Vec;
+    use dusk_jubjub::{JubJubAffine, JubJubExtended};
+    use rand_core::OsRng;
+
+    /// Native Elligator2 map used as the source of truth of the gadget.
+    fn native_map_to_curve(u: BlsScalar) -> JubJubAffine {
+        let (c1, c2) = elligator_constants();
+        let k = -BlsScalar::from(4u64)
+            * (BlsScalar::one() + EDWARDS_D).invert().unwrap();
+        let g = |x: BlsScalar| x * (x.square() + c1 * x + c2);
+
+        let x1 = -c1 * (BlsScalar::one() + Z * u.square()).invert().unwrap();
+        let x2 = -x1 - c1;
+
+        let gx1_root: Option<BlsScalar> = g(x1).sqrt().into();
+        let (x, y, odd) = match gx1_root {
+            Some(y) => (x1, y, false),
+            None => {
+                let y: Option<BlsScalar> = g(x2).sqrt().into();
+                (x2, y.unwrap(), true)
+            }
+        };
+        let y = if is_odd(&y) == odd { y } else { -y };
+
+        let inv0 = |v: BlsScalar| v.invert().unwrap_or(BlsScalar::zero());
+        let w = k * x + BlsScalar::one();
+        let point = JubJubAffine::from_raw_unchecked(
+            x * inv0(y),
+            (w - BlsScalar::from(2u64)) * inv0(w),
+        );
+
+        let point = JubJubExtended::from(point);
+        let point = point + point;
+        let point = point + point;
+        (point + point).into()
+    }
+
+    #[test]
+    fn test_map_to_curve() {
+        let res = gadget_tester(
+            |composer| {
+                let inputs = [0u64, 1, 2, 42, 0xdead_beef, 1 << 60];
+                for input in inputs.iter() {
+                    let u = BlsScalar::from(*input);
+                    let expected = native_map_to_curve(u);
+
+                    // The point lies on the curve
+                    let x = expected.get_x();
+                    let y = expected.get_y();
+                    assert_eq!(
+                        -x.square() + y.square(),
+                        BlsScalar::one() + EDWARDS_D * x.square() * y.square()
+                    );
+
+                    let u = composer.add_input(u);
+                    let point = composer.map_to_curve(u);
+                    composer.assert_equal_public_point(point, expected);
+                }
+            },
+            4096,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_hash_to_curve() {
+        let res = gadget_tester(
+            |composer| {
+                let params = PoseidonParams::default();
+                let msg: Vec<BlsScalar> =
+                    (0..3).map(|_| BlsScalar::random(&mut OsRng)).collect();
+
+                for len in 0..=msg.len() {
+                    let expected =
+                        native_map_to_curve(params.sponge(&msg[..len]));
+                    let vars: Vec<Variable> = msg[..len]
+                        .iter()
+                        .map(|x| composer.add_input(*x))
+                        .collect();
+
+                    let point = composer.hash_to_curve(&params, &vars);
+                    composer.assert_equal_public_point(point, expected);
+                }
+            },
+            8192,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_map_to_curve_wrong_point() {
+        let res = gadget_tester(
+            |composer| {
+                let u = composer.add_input(BlsScalar::from(42u64));
+                let point = composer.map_to_curve(u);
+                let expected = native_map_to_curve(BlsScalar::from(43u64));
+                composer.assert_equal_public_point(point, expected);
+            },
+            1024,
+        );
+        assert!(res.is_err());
+    }
+}
@@ -6,11 +6,22 @@
 
 /// Curve addition gate
 pub mod curve_addition;
+/// ElGamal encryption
+pub mod elgamal;
+/// Elligator2 map to the curve
+mod elligator;
+/// Pedersen commitments
+pub mod pedersen;
 /// Gates related to scalar multiplication
 pub mod scalar_mul;
+/// Vector commitments
+pub mod vector_commitment;
 
-use crate::constraint_system::{variable::Variable, StandardComposer};
+use crate::constraint_system::{
+    variable::Variable, ArithmeticGate, StandardComposer,
+};
 use dusk_bls12_381::BlsScalar;
+use dusk_jubjub::EDWARDS_D;
 
 /// Represents a JubJub point in the circuit
 #[derive(Debug, Clone, Copy)]
@@ -107,6 +118,52 @@ impl StandardComposer {
         self.assert_equal(point_b.y, point_b.y);
     }
 
+    /// Asserts that a [`Point`] in the circuit lies on the JubJub curve by
+    /// constraining its coordinates to satisfy the twisted Edwards equation
+    /// `-x^2 + y^2 = 1 + d * x^2 * y^2`.
+    ///
+    /// Points added with [`StandardComposer::add_public_affine`] or
+    /// [`StandardComposer::add_affine_to_circuit_description`] come from a
+    /// valid `JubJubAffine`, but points obtained from untrusted witnesses
+    /// should be checked with this gadget.
+    ///
+    /// # Note
+    /// The points of small order, such as `(0, -1)`, lie on the curve as
+    /// well, so this gadget alone doesn't guard against small-subgroup
+    /// attacks. The gadgets that need a point of the prime order subgroup
+    /// have to exclude them, for example by multiplying the point by the
+    /// cofactor.
+    ///
+    /// This function adds 3 gates to the circuit description.
+    pub fn assert_on_curve(&mut self, point: Point) {
+        // x^2
+        let x_sq = self.mul(
+            BlsScalar::one(),
+            point.x,
+            point.x,
+            BlsScalar::zero(),
+            None,
+        );
+        // y^2
+        let y_sq = self.mul(
+            BlsScalar::one(),
+            point.y,
+            point.y,
+            BlsScalar::zero(),
+            None,
+        );
+
+        // -d * x^2 * y^2 - x^2 + y^2 - 1 = 0
+        ArithmeticGate::new()
+            .left(x_sq)
+            .right(y_sq)
+            .q_m(-EDWARDS_D)
+            .q_l(-BlsScalar::one())
+            .q_r(BlsScalar::one())
+            .q_c(-BlsScalar::one())
+            .apply(self);
+    }
+
     /// Adds to the circuit description the conditional selection of the
     /// a point between two of them.
     /// bit == 1 => point_a,
@@ -181,4 +238,54 @@ mod tests {
         );
         assert!(res.is_ok());
     }
+
+    #[test]
+    fn test_assert_on_curve() {
+        // Should pass since the generator is a valid JubJub point
+        let res = gadget_tester(
+            |composer| {
+                let point = composer.add_affine(dusk_jubjub::GENERATOR);
+                composer.assert_on_curve(point);
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the point doesn't satisfy the curve equation
+        let res = gadget_tester(
+            |composer| {
+                let point = Point {
+                    x: composer.add_input(BlsScalar::from(10u64)),
+                    y: composer.add_input(BlsScalar::from(20u64)),
+                };
+                composer.assert_on_curve(point);
+            },
+            32,
+        );
+        assert!(res.is_err());
+
+        // Should fail since the witness of a valid point is tampered
+        let res = gadget_tester(
+            |composer| {
+                let point = composer.add_affine(dusk_jubjub::GENERATOR);
+                composer.assert_on_curve(point);
+                composer.variables.insert(point.y, BlsScalar::one());
+            },
+            32,
+        );
+        assert!(res.is_err());
+
+        // Should pass since the point of order two lies on the curve
+        let res = gadget_tester(
+            |composer| {
+                let point = Point {
+                    x: composer.zero_var(),
+                    y: composer.add_input(-BlsScalar::one()),
+                };
+                composer.assert_on_curve(point);
+            },
+            32,
+        );
+        assert!(res.is_ok());
+    }
 }
@@ -0,0 +1,132 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Pedersen commitments `C = m * G + r * H` over JubJub.
+//!
+//! The value generator `G` is [`GENERATOR_EXTENDED`] and the blinding
+//! generator `H` is [`GENERATOR_NUMS_EXTENDED`], the nothing-up-my-sleeve
+//! generator of `dusk-jubjub`, which is derived by hashing `G` so nobody
+//! knows the discrete logarithm of `H` with respect to `G`. Both belong to
+//! the prime order subgroup, as required by the fixed base scalar
+//! multiplication.
+
+use crate::constraint_system::ecc::Point;
+use crate::constraint_system::{StandardComposer, Variable};
+use dusk_jubjub::{
+    JubJubAffine, JubJubScalar, GENERATOR_EXTENDED, GENERATOR_NUMS_EXTENDED,
+};
+
+/// Returns the Pedersen commitment to `value` with the `blinder`, computed
+/// outside of the circuit with the same generators as
+/// [`StandardComposer::pedersen_commit`].
+pub fn pedersen_commit(
+    value: JubJubScalar,
+    blinder: JubJubScalar,
+) -> JubJubAffine {
+    (GENERATOR_EXTENDED * value + GENERATOR_NUMS_EXTENDED * blinder).into()
+}
+
+impl StandardComposer {
+    /// Returns the [`Point`] holding the Pedersen commitment to `value` with
+    /// the `blinder`, see [`pedersen_commit`].
+    ///
+    /// Both `value` and `blinder` must hold JubJub scalars. The commitment is
+    /// computed with two fixed base scalar multiplications and a curve
+    /// addition.
+    pub fn pedersen_commit(
+        &mut self,
+        value: Variable,
+        blinder: Variable,
+    ) -> Point {
+        let value = self.fixed_base_scalar_mul(value, GENERATOR_EXTENDED);
+        let blinder =
+            self.fixed_base_scalar_mul(blinder, GENERATOR_NUMS_EXTENDED);
+
+        self.point_addition_gate(value, blinder)
+    }
+
+    /// Constrains the Pedersen commitment to `value` with the `blinder` to
+    /// be equal to the public `commitment`.
+    ///
+    /// The coordinates of the commitment are added as public inputs, in the
+    /// order `x`, `y`, so the verifier must provide the commitment computed
+    /// with [`pedersen_commit`] at those positions.
+    pub fn assert_pedersen_commitment(
+        &mut self,
+        value: Variable,
+        blinder: Variable,
+        commitment: JubJubAffine,
+    ) {
+        let point = self.pedersen_commit(value, blinder);
+        self.assert_equal_public_point(point, commitment);
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint_system::helper::*;
+    use dusk_bls12_381::BlsScalar;
+    use dusk_bytes::Serializable;
+    use rand_core::OsRng;
+
+    fn to_bls(scalar: JubJubScalar) -> BlsScalar {
+        BlsScalar::from_bytes(&scalar.to_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_pedersen_commit() {
+        let res = gadget_tester(
+            |composer| {
+                let value = JubJubScalar::from(100u64);
+                let blinder = JubJubScalar::random(&mut OsRng);
+                let commitment = pedersen_commit(value, blinder);
+
+                let value = composer.add_input(to_bls(value));
+                let blinder = composer.add_input(to_bls(blinder));
+                composer.assert_pedersen_commitment(value, blinder, commitment);
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the blinder is wrong
+        let res = gadget_tester(
+            |composer| {
+                let value = JubJubScalar::from(100u64);
+                let commitment = pedersen_commit(value, JubJubScalar::one());
+
+                let value = composer.add_input(to_bls(value));
+                let blinder = composer.add_input(BlsScalar::from(2u64));
+                composer.assert_pedersen_commitment(value, blinder, commitment);
+            },
+            2048,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_pedersen_commit_is_binding_to_the_generators() {
+        // Swapping the value and the blinder changes the commitment
+        let value = JubJubScalar::from(7u64);
+        let blinder = JubJubScalar::from(11u64);
+        assert_ne!(
+            pedersen_commit(value, blinder),
+            pedersen_commit(blinder, value)
+        );
+
+        // And so does committing with no blinding
+        assert_ne!(
+            pedersen_commit(value, blinder),
+            pedersen_commit(value, JubJubScalar::zero())
+        );
+        assert_eq!(
+            pedersen_commit(value, JubJubScalar::zero()),
+            JubJubAffine::from(GENERATOR_EXTENDED * value)
+        );
+    }
+}
@@ -11,6 +11,7 @@ use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
 use dusk_bytes::Serializable;
 use dusk_jubjub::{JubJubAffine, JubJubExtended, JubJubScalar};
+use subtle::{ConditionallySelectable, ConstantTimeEq};
 
 fn compute_wnaf_point_multiples(
     generator: JubJubExtended,
@@ -27,6 +28,37 @@ fn compute_wnaf_point_multiples(
     dusk_jubjub::batch_normalize(&mut multiples).collect()
 }
 
+/// Computes the non-adjacent form, or WNAF of width 2, of the `scalar`, whose
+/// `i`th entry is the digit of `2^i`.
+///
+/// The scalar is a witness, so unlike
+/// [`JubJubScalar::compute_windowed_naf`] the digits are computed from its
+/// bits without branching on them.
+fn compute_naf(scalar: &JubJubScalar) -> [i8; 256] {
+    let bytes = scalar.to_bytes();
+    let bit = |i: usize| match i {
+        256 => 0,
+        _ => (bytes[i >> 3] >> (i & 7)) & 1,
+    };
+
+    let mut naf = [0i8; 256];
+    let mut carry = 0u8;
+    for i in 0..256 {
+        // The two lowest bits of the remaining scalar are `sum & 1` and
+        // `next`, when the first one is set
+        let sum = bit(i) + carry;
+        let next = bit(i + 1);
+        let odd = sum & 1;
+
+        // Odd values are rounded to the closest multiple of 4, subtracting
+        // 1 from `x = 1 mod 4` and adding 1 to `x = 3 mod 4`
+        naf[i] = odd as i8 * (1 - 2 * next as i8);
+        carry = (sum >> 1) | (odd & next);
+    }
+
+    naf
+}
+
 impl StandardComposer {
     /// Adds an elliptic curve Scalar multiplication gate to the circuit
     /// description.
@@ -59,7 +91,7 @@ impl StandardComposer {
             JubJubScalar::from_bytes(&raw_bls_scalar.to_bytes()).unwrap();
 
         // Convert scalar to wnaf_2(k)
-        let wnaf_entries = raw_jubjub_scalar.compute_windowed_naf(2);
+        let wnaf_entries = compute_naf(&raw_jubjub_scalar);
         assert_eq!(wnaf_entries.len(), num_bits);
 
         // Initialise the accumulators
@@ -71,13 +103,33 @@ impl StandardComposer {
 
         // Load values into accumulators based on wnaf entries
         for (i, entry) in wnaf_entries.iter().rev().enumerate() {
-            // Based on the WNAF, we decide what scalar and point to add
-            let (scalar_to_add, point_to_add) = match entry {
-            0 => { (BlsScalar::zero(), JubJubAffine::identity())},
-            -1 => {(BlsScalar::one().neg(), -point_multiples[i])},
-            1 => {(BlsScalar::one(), point_multiples[i])},
-            _ => unreachable!("Currently WNAF_2(k) is supported. The possible values are 1, -1 and 0. Current entry is {}", entry),
-        };
+            // Based on the WNAF, we decide what scalar and point to add. The
+            // entries depend on the witness, so the choice is made in
+            // constant time.
+            let is_one = entry.ct_eq(&1);
+            let is_minus_one = entry.ct_eq(&-1);
+
+            let scalar_to_add = BlsScalar::conditional_select(
+                &BlsScalar::zero(),
+                &BlsScalar::one(),
+                is_one,
+            );
+            let scalar_to_add = BlsScalar::conditional_select(
+                &scalar_to_add,
+                &-BlsScalar::one(),
+                is_minus_one,
+            );
+
+            let point_to_add = JubJubAffine::conditional_select(
+                &JubJubAffine::identity(),
+                &point_multiples[i],
+                is_one,
+            );
+            let point_to_add = JubJubAffine::conditional_select(
+                &point_to_add,
+                &-point_multiples[i],
+                is_minus_one,
+            );
 
             let prev_accumulator = BlsScalar::from(2u64) * scalar_acc[i];
             scalar_acc.push(prev_accumulator + scalar_to_add);
@@ -165,6 +217,7 @@ mod tests {
     use super::*;
     use crate::constraint_system::helper::*;
     use dusk_jubjub::GENERATOR_EXTENDED;
+    use rand_core::OsRng;
 
     #[test]
     fn test_ecc_constraint() {
@@ -380,4 +433,69 @@ mod tests {
         );
         assert!(res.is_ok());
     }
+
+    #[test]
+    fn test_compute_naf() {
+        let mut scalars = vec![
+            JubJubScalar::zero(),
+            JubJubScalar::one(),
+            -JubJubScalar::one(),
+        ];
+        scalars.extend((0..100).map(|_| JubJubScalar::random(&mut OsRng)));
+
+        for scalar in scalars {
+            assert_eq!(
+                compute_naf(&scalar)[..],
+                scalar.compute_windowed_naf(2)[..]
+            );
+        }
+    }
+
+    /// Dudect-style check that the time spent computing the NAF of a fixed
+    /// scalar can't be told apart from the one spent on random scalars with
+    /// Welch's t-test.
+    ///
+    /// Timings are noisy on shared hosts, so it's ignored by default. Run it
+    /// with `cargo test --release -- --ignored test_compute_naf_timing`.
+    #[test]
+    #[ignore]
+    fn test_compute_naf_timing() {
+        use rand_core::RngCore;
+        use std::time::Instant;
+
+        const SAMPLES: usize = 100_000;
+        const THRESHOLD: f64 = 4.5;
+
+        let fixed = JubJubScalar::zero();
+        let mut timings = [Vec::new(), Vec::new()];
+        let mut sink = 0i8;
+        for _ in 0..SAMPLES {
+            let class = (OsRng.next_u32() & 1) as usize;
+            let scalar = match class {
+                0 => fixed,
+                _ => JubJubScalar::random(&mut OsRng),
+            };
+
+            let start = Instant::now();
+            let naf = compute_naf(&scalar);
+            let elapsed = start.elapsed().as_nanos() as f64;
+
+            sink ^= naf[0];
+            timings[class].push(elapsed);
+        }
+
+        let stats = |timings: &[f64]| {
+            let n = timings.len() as f64;
+            let mean = timings.iter().sum::<f64>() / n;
+            let var =
+                timings.iter().map(|t| (t - mean) * (t - mean)).sum::<f64>()
+                    / (n - 1.0);
+            (mean, var, n)
+        };
+        let (mean_0, var_0, n_0) = stats(&timings[0]);
+        let (mean_1, var_1, n_1) = stats(&timings[1]);
+        let t = (mean_0 - mean_1) / (var_0 / n_0 + var_1 / n_1).sqrt();
+
+        assert!(t.abs() < THRESHOLD, "t = {} (sink {})", t, sink);
+    }
 }
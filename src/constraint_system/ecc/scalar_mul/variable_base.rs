@@ -9,6 +9,7 @@ use crate::constraint_system::{variable::Variable, StandardComposer};
 use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
 use dusk_bytes::Serializable;
+use dusk_jubjub::JubJubScalar;
 
 impl StandardComposer {
     /// Adds a variable-base scalar multiplication to the circuit description.
@@ -47,6 +48,86 @@ impl StandardComposer {
         result
     }
 
+    /// Adds the multi-scalar multiplication `sum s_i * P_i` of the `pairs`
+    /// of scalars and variable base points to the circuit description.
+    ///
+    /// The scalars are decomposed into bits as in
+    /// [`StandardComposer::variable_base_scalar_mul`], then the
+    /// double-and-add runs once over all the bits, one at a time from the
+    /// most significant one: the accumulator is doubled once per bit for all
+    /// the pairs, and each point is added when the bit of its scalar is set.
+    /// A wider window would trade these additions for lookups in a table of
+    /// multiples, which cost more gates than they save with the selections
+    /// available.
+    ///
+    /// For `n` pairs, this function adds `1513 * n + 503` gates to the
+    /// circuit description, against `2020 * n - 2` to sum independent
+    /// scalar multiplications.
+    pub fn variable_base_msm(&mut self, pairs: &[(Variable, Point)]) -> Point {
+        let scalars_bits: Vec<Vec<Variable>> = pairs
+            .iter()
+            .map(|(scalar, _)| {
+                let raw_bls_scalar = self.variables[scalar];
+                self.scalar_decomposition(*scalar, raw_bls_scalar)
+            })
+            .collect();
+
+        let identity = Point::identity(self);
+        (0..252).rev().fold(identity, |result, i| {
+            // Doubling the identity is skipped on the first bit
+            let result = match i {
+                251 => result,
+                _ => self.point_addition_gate(result, result),
+            };
+
+            scalars_bits.iter().zip(pairs.iter()).fold(
+                result,
+                |result, (bits, (_, point))| {
+                    let point_to_add =
+                        self.conditional_select_identity(bits[i], *point);
+                    self.point_addition_gate(result, point_to_add)
+                },
+            )
+        })
+    }
+
+    /// Adds the multiplication of a variable base `point` by the known
+    /// `scalar` to the circuit description.
+    ///
+    /// Since the bits of the scalar are known when the circuit is built, they
+    /// are neither added as witnesses nor constrained to be boolean. The
+    /// double-and-add starts at the most significant set bit and only adds
+    /// the point on the set bits, so this function adds one point addition
+    /// per bit after the leading one plus one per set bit after it.
+    ///
+    /// # Note
+    /// The scalar is part of the circuit description, so circuits built with
+    /// different scalars have different keys. Use
+    /// [`StandardComposer::variable_base_scalar_mul`] if the scalar is a
+    /// witness.
+    pub fn mul_by_public_scalar(
+        &mut self,
+        scalar: JubJubScalar,
+        point: Point,
+    ) -> Point {
+        // The JubJub scalar field is smaller than the BLS12-381 one
+        let scalar = BlsScalar::from_bytes(&scalar.to_bytes())
+            .expect("JubJub scalars are valid BLS12-381 scalars");
+        let scalar_bits = scalar_to_bits(&scalar);
+        let mut bits = scalar_bits.iter().rev().skip_while(|bit| **bit == 0);
+
+        match bits.next() {
+            None => Point::identity(self),
+            Some(_) => bits.fold(point, |acc, bit| {
+                let acc = self.point_addition_gate(acc, acc);
+                match bit {
+                    1 => self.point_addition_gate(acc, point),
+                    _ => acc,
+                }
+            }),
+        }
+    }
+
     fn scalar_decomposition(
         &mut self,
         witness_var: Variable,
@@ -106,7 +187,9 @@ mod tests {
     use crate::constraint_system::helper::*;
     use dusk_bls12_381::BlsScalar;
     use dusk_jubjub::GENERATOR;
-    use dusk_jubjub::{JubJubAffine, JubJubExtended, JubJubScalar};
+    use dusk_jubjub::{JubJubAffine, JubJubExtended};
+    use rand_core::OsRng;
+
     #[test]
     fn test_var_base_scalar_mul() {
         let res = gadget_tester(
@@ -137,4 +220,139 @@ mod tests {
         );
         assert!(res.is_ok());
     }
+
+    #[test]
+    fn test_variable_base_msm() {
+        let res = gadget_tester(
+            |composer| {
+                let pairs: Vec<(JubJubScalar, JubJubExtended)> = (0..3)
+                    .map(|_| {
+                        (
+                            JubJubScalar::random(&mut OsRng),
+                            JubJubExtended::from(GENERATOR)
+                                * JubJubScalar::random(&mut OsRng),
+                        )
+                    })
+                    .collect();
+                let expected: JubJubAffine = pairs
+                    .iter()
+                    .fold(JubJubExtended::identity(), |acc, (s, p)| acc + p * s)
+                    .into();
+
+                let pairs: Vec<(Variable, Point)> = pairs
+                    .iter()
+                    .map(|(scalar, point)| {
+                        let scalar =
+                            BlsScalar::from_bytes(&scalar.to_bytes()).unwrap();
+                        (
+                            composer.add_input(scalar),
+                            composer.add_affine((*point).into()),
+                        )
+                    })
+                    .collect();
+
+                let n = composer.circuit_size();
+                let msm = composer.variable_base_msm(&pairs);
+                let msm_gates = composer.circuit_size() - n;
+                assert_eq!(msm_gates, 1513 * 3 + 503);
+
+                // Matches the naive sum of the scalar multiplications
+                let n = composer.circuit_size();
+                let identity = Point::identity(composer);
+                let naive = pairs.iter().fold(identity, |acc, (s, p)| {
+                    let product = composer.variable_base_scalar_mul(*s, *p);
+                    composer.point_addition_gate(acc, product)
+                });
+                let naive_gates = composer.circuit_size() - n - 1;
+                assert_eq!(naive_gates, 2020 * 3);
+                composer.assert_equal(*msm.x(), *naive.x());
+                composer.assert_equal(*msm.y(), *naive.y());
+
+                composer.assert_equal_public_point(msm, expected);
+            },
+            16384,
+        );
+        assert!(res.is_ok());
+
+        // The empty sum is the identity
+        let res = gadget_tester(
+            |composer| {
+                let msm = composer.variable_base_msm(&[]);
+                composer
+                    .assert_equal_public_point(msm, JubJubAffine::identity());
+            },
+            1024,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the scalars are swapped
+        let res = gadget_tester(
+            |composer| {
+                let a = JubJubScalar::from(3u64);
+                let b = JubJubScalar::from(5u64);
+                let p = JubJubExtended::from(GENERATOR);
+                let q = p * JubJubScalar::from(7u64);
+                let expected: JubJubAffine = (p * a + q * b).into();
+
+                let a = composer.add_input(BlsScalar::from(3u64));
+                let b = composer.add_input(BlsScalar::from(5u64));
+                let p = composer.add_affine(p.into());
+                let q = composer.add_affine(q.into());
+                let msm = composer.variable_base_msm(&[(b, p), (a, q)]);
+                composer.assert_equal_public_point(msm, expected);
+            },
+            4096,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_mul_by_public_scalar() {
+        let res = gadget_tester(
+            |composer| {
+                let scalars = [
+                    JubJubScalar::zero(),
+                    JubJubScalar::one(),
+                    JubJubScalar::from(6u64),
+                    JubJubScalar::random(&mut OsRng),
+                ];
+                let base = JubJubExtended::from(GENERATOR)
+                    * JubJubScalar::random(&mut OsRng);
+                let point = composer.add_affine(base.into());
+
+                for scalar in scalars.iter() {
+                    let expected: JubJubAffine = (base * scalar).into();
+                    let public = composer.mul_by_public_scalar(*scalar, point);
+
+                    // Matches the general gadget for the same inputs
+                    let bls_scalar =
+                        BlsScalar::from_bytes(&scalar.to_bytes()).unwrap();
+                    let scalar = composer.add_input(bls_scalar);
+                    let general =
+                        composer.variable_base_scalar_mul(scalar, point);
+                    composer.assert_equal(*public.x(), *general.x());
+                    composer.assert_equal(*public.y(), *general.y());
+
+                    composer.assert_equal_public_point(public, expected);
+                }
+            },
+            16384,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the scalar is not the expected one
+        let res = gadget_tester(
+            |composer| {
+                let point = composer.add_affine(GENERATOR);
+                let public = composer
+                    .mul_by_public_scalar(JubJubScalar::from(5u64), point);
+                let expected: JubJubAffine = (JubJubExtended::from(GENERATOR)
+                    * JubJubScalar::from(6u64))
+                .into();
+                composer.assert_equal_public_point(public, expected);
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
 }
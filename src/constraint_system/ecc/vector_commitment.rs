@@ -0,0 +1,258 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Vector commitments over JubJub.
+//!
+//! The values of the vector are the leaves of a Merkle tree hashed with the
+//! default [`PoseidonParams`], padded with zeros up to a power of two. The
+//! commitment is the Pedersen commitment to the root, split in its low and
+//! high 128 bits, `C = lo * G + hi * H` as for [`pedersen_commit`].
+//!
+//! An opening of the value at some index is the list of siblings along the
+//! path from the leaf to the root, and the bits of the index give whether
+//! the current node is the right child at each level, starting from the
+//! least significant one.
+
+use crate::constraint_system::ecc::pedersen::pedersen_commit;
+use crate::constraint_system::ecc::Point;
+use crate::constraint_system::hashing::merkle;
+use crate::constraint_system::modular::to_limbs;
+use crate::constraint_system::witness::{QUOTIENT, REMAINDER};
+use crate::constraint_system::{
+    NativeWitness, PoseidonParams, StandardComposer, Variable, WitnessProvider,
+};
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+use dusk_jubjub::{JubJubAffine, JubJubScalar};
+
+/// Committed vector of [`BlsScalar`]s, holding every level of its tree to
+/// produce the openings.
+#[derive(Debug, Clone)]
+pub struct VectorCommitment {
+    levels: Vec<Vec<BlsScalar>>,
+}
+
+impl VectorCommitment {
+    /// Builds the tree of the `values`, padded with zeros to the next power
+    /// of two.
+    pub fn new(values: &[BlsScalar]) -> Self {
+        let hasher = PoseidonParams::default();
+
+        let mut leaves = values.to_vec();
+        leaves.resize(values.len().next_power_of_two(), BlsScalar::zero());
+
+        let mut levels = vec![leaves];
+        while levels[levels.len() - 1].len() > 1 {
+            let level = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| hasher.hash(pair[0], pair[1]))
+                .collect();
+            levels.push(level);
+        }
+
+        Self { levels }
+    }
+
+    /// Returns the depth of the tree, which is the length of the openings.
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> BlsScalar {
+        self.levels[self.depth()][0]
+    }
+
+    /// Returns the commitment to the vector, see [`commit_root`].
+    pub fn commitment(&self) -> JubJubAffine {
+        commit_root(self.root())
+    }
+
+    /// Returns the siblings along the path of the value at `index`, or
+    /// `None` if the index is out of the padded vector.
+    pub fn opening(&self, index: usize) -> Option<Vec<BlsScalar>> {
+        if index >= self.levels[0].len() {
+            return None;
+        }
+
+        let path = self.levels[..self.depth()]
+            .iter()
+            .enumerate()
+            .map(|(height, level)| level[(index >> height) ^ 1])
+            .collect();
+
+        Some(path)
+    }
+
+    /// Returns `true` if `value` is held at `index` by the vector committed
+    /// in `commitment`, according to the siblings of the `path`.
+    pub fn verify(
+        commitment: JubJubAffine,
+        index: usize,
+        value: BlsScalar,
+        path: &[BlsScalar],
+    ) -> bool {
+        let path: Vec<(BlsScalar, bool)> = path
+            .iter()
+            .enumerate()
+            .map(|(height, sibling)| (*sibling, (index >> height) & 1 == 1))
+            .collect();
+        let root =
+            merkle::merkle_root(&PoseidonParams::default(), value, &path);
+
+        let fits = index.checked_shr(path.len() as u32).unwrap_or(0) == 0;
+        fits && commit_root(root) == commitment
+    }
+}
+
+/// Returns the Pedersen commitment to the low and high 128 bits of `root`.
+pub fn commit_root(root: BlsScalar) -> JubJubAffine {
+    let (hi, lo) = split_root(root);
+    let hi = JubJubScalar::from_raw(to_limbs(&hi));
+    let lo = JubJubScalar::from_raw(to_limbs(&lo));
+
+    pedersen_commit(lo, hi)
+}
+
+/// Returns the high and low 128 bits of `root`.
+fn split_root(root: BlsScalar) -> (BlsScalar, BlsScalar) {
+    let inputs = [root, BlsScalar::pow_of_2(128)];
+    let hi = NativeWitness
+        .compute(QUOTIENT, &inputs)
+        .expect("The modulus is not zero");
+    let lo = NativeWitness
+        .compute(REMAINDER, &inputs)
+        .expect("The modulus is not zero");
+
+    (hi, lo)
+}
+
+impl StandardComposer {
+    /// Constrains `value` to be held at `index` by the vector committed in
+    /// `commitment`, according to the siblings of the `path`, see
+    /// [`VectorCommitment::verify`].
+    ///
+    /// The index is split into `path.len()` bits, so the circuit is only
+    /// satisfied if it fits in the depth of the tree. The root is computed
+    /// with [`StandardComposer::merkle_root`] and split in two 128-bit
+    /// limbs, which are committed to with
+    /// [`StandardComposer::pedersen_commit`]. Both coordinates of the result
+    /// are constrained to be equal to the ones of `commitment`.
+    pub fn vector_opening(
+        &mut self,
+        commitment: Point,
+        index: Variable,
+        value: Variable,
+        path: &[Variable],
+    ) {
+        let bits = self.split_variable(index, path.len());
+        let path: Vec<(Variable, Variable)> =
+            path.iter().copied().zip(bits.into_iter()).collect();
+        let root = self.merkle_root(&PoseidonParams::default(), value, &path);
+
+        let (hi, lo) = split_root(self.variables[&root]);
+        let hi = self.add_input(hi);
+        let lo = self.add_input(lo);
+        self.range_gate(hi, 128);
+        self.range_gate(lo, 128);
+
+        // hi * 2^128 + lo - root = 0
+        self.big_add(
+            (BlsScalar::pow_of_2(128), hi),
+            (BlsScalar::one(), lo),
+            Some((-BlsScalar::one(), root)),
+            BlsScalar::zero(),
+            None,
+        );
+
+        let computed = self.pedersen_commit(lo, hi);
+        self.assert_equal(*computed.x(), *commitment.x());
+        self.assert_equal(*computed.y(), *commitment.y());
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint_system::helper::*;
+
+    fn values() -> Vec<BlsScalar> {
+        (0..7u64).map(|i| BlsScalar::from(1000 + i)).collect()
+    }
+
+    #[test]
+    fn test_vector_commitment_native() {
+        let vc = VectorCommitment::new(&values());
+        assert_eq!(vc.depth(), 3);
+
+        let path = vc.opening(4).unwrap();
+        assert!(VectorCommitment::verify(
+            vc.commitment(),
+            4,
+            BlsScalar::from(1004u64),
+            &path
+        ));
+        assert!(!VectorCommitment::verify(
+            vc.commitment(),
+            5,
+            BlsScalar::from(1004u64),
+            &path
+        ));
+
+        // The padding is opened as zero
+        let path = vc.opening(7).unwrap();
+        assert!(VectorCommitment::verify(
+            vc.commitment(),
+            7,
+            BlsScalar::zero(),
+            &path
+        ));
+        assert!(vc.opening(8).is_none());
+
+        assert_eq!(VectorCommitment::new(&[]).depth(), 0);
+    }
+
+    fn test_opening(
+        composer: &mut StandardComposer,
+        index: u64,
+        value: BlsScalar,
+    ) {
+        let vc = VectorCommitment::new(&values());
+        let path = vc.opening(2).unwrap();
+
+        let commitment = composer.add_affine(vc.commitment());
+        let index = composer.add_input(BlsScalar::from(index));
+        let value = composer.add_input(value);
+        let path: Vec<Variable> =
+            path.iter().map(|s| composer.add_input(*s)).collect();
+
+        composer.vector_opening(commitment, index, value, &path);
+    }
+
+    #[test]
+    fn test_vector_opening() {
+        let res = gadget_tester(
+            |composer| test_opening(composer, 2, BlsScalar::from(1002u64)),
+            4096,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the value is held at another index
+        let res = gadget_tester(
+            |composer| test_opening(composer, 2, BlsScalar::from(1003u64)),
+            4096,
+        );
+        assert!(res.is_err());
+
+        // Should fail since the index doesn't fit in the depth of the tree
+        let res = gadget_tester(
+            |composer| test_opening(composer, 10, BlsScalar::from(1002u64)),
+            4096,
+        );
+        assert!(res.is_err());
+    }
+}
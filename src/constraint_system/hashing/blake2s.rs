@@ -0,0 +1,373 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::{StandardComposer, Variable};
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+
+/// Initialization vector of Blake2s, as defined in RFC 7693.
+const IV: [u32; 8] = [
+    0x6a09_e667,
+    0xbb67_ae85,
+    0x3c6e_f372,
+    0xa54f_f53a,
+    0x510e_527f,
+    0x9b05_688c,
+    0x1f83_d9ab,
+    0x5be0_cd19,
+];
+
+/// Message word permutations of each one of the ten rounds.
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Size in bits of a Blake2s message block.
+const BLOCK_BITS: usize = 512;
+
+impl StandardComposer {
+    /// Returns the 256 bits of the Blake2s digest of the message represented
+    /// by `input_bits`, without a key.
+    ///
+    /// The message is given as a sequence of bytes, each one of them as 8
+    /// bits in little-endian order, and the digest is returned in the same
+    /// representation. The bits are not constrained to be boolean, this is
+    /// the responsibility of the caller. The length of the message is part of
+    /// the circuit description.
+    ///
+    /// Each block of 64 bytes of the padded message adds 30,488 gates to the
+    /// circuit description, with 400 more gates to set up the chaining value
+    /// and decompose the digest. An empty message is hashed as one block.
+    ///
+    /// # Panics
+    /// This function will panic if the number of bits is not a multiple of 8.
+    pub fn blake2s(&mut self, input_bits: &[Variable]) -> [Variable; 256] {
+        assert_eq!(
+            input_bits.len() % 8,
+            0,
+            "The Blake2s message must be made of whole bytes"
+        );
+        let len = (input_bits.len() / 8) as u64;
+        let num_blocks =
+            1usize.max((input_bits.len() + BLOCK_BITS - 1) / BLOCK_BITS);
+        let zero = self.zero_var;
+
+        // Parameter block for a 32 bytes digest without key, salt or
+        // personalization
+        let mut params = IV;
+        params[0] ^= 0x0101_0000 ^ 32;
+
+        let mut h = [zero; 8];
+        h.iter_mut().zip(params.iter()).for_each(|(word, param)| {
+            *word = self.add_witness_to_circuit_description(BlsScalar::from(
+                *param as u64,
+            ))
+        });
+
+        for i in 0..num_blocks {
+            // The last block is padded with zeros
+            let mut m = [zero; 16];
+            m.iter_mut().enumerate().for_each(|(j, word)| {
+                let offset = i * BLOCK_BITS + 32 * j;
+                let bits: Vec<Variable> = (offset..offset + 32)
+                    .map(|k| input_bits.get(k).copied().unwrap_or(zero))
+                    .collect();
+                *word = self.compose_bits(&bits);
+            });
+
+            let last = i + 1 == num_blocks;
+            let t = match last {
+                true => len,
+                false => 64 * (i as u64 + 1),
+            };
+            h = self.blake2s_compression(&h, &m, t, last);
+        }
+
+        let mut digest = [zero; 256];
+        digest
+            .chunks_mut(32)
+            .zip(h.iter())
+            .for_each(|(bits, word)| {
+                bits.copy_from_slice(&self.split_variable(*word, 32))
+            });
+
+        digest
+    }
+
+    /// Applies the Blake2s compression function to the chaining value `h`
+    /// and the message block `m`, returning the new chaining value.
+    ///
+    /// Every [`Variable`] holds a 32-bit word. The byte counter `t` and
+    /// whether `m` is the last block of the message are part of the circuit
+    /// description. The values of `h` and `m` are expected to be 32-bit
+    /// integers: constraining them is the responsibility of the caller.
+    ///
+    /// This function adds 30,232 gates to the circuit description.
+    pub fn blake2s_compression(
+        &mut self,
+        h: &[Variable; 8],
+        m: &[Variable; 16],
+        t: u64,
+        last: bool,
+    ) -> [Variable; 8] {
+        let mut iv = IV;
+        iv[4] ^= t as u32;
+        iv[5] ^= (t >> 32) as u32;
+        if last {
+            iv[6] ^= 0xffff_ffff;
+        }
+
+        let mut v = [self.zero_var; 16];
+        v[..8].copy_from_slice(h);
+        v[8..].iter_mut().zip(iv.iter()).for_each(|(word, iv)| {
+            *word = self
+                .add_witness_to_circuit_description(BlsScalar::from(*iv as u64))
+        });
+
+        for s in SIGMA.iter() {
+            self.blake2s_mix(&mut v, [0, 4, 8, 12], m[s[0]], m[s[1]]);
+            self.blake2s_mix(&mut v, [1, 5, 9, 13], m[s[2]], m[s[3]]);
+            self.blake2s_mix(&mut v, [2, 6, 10, 14], m[s[4]], m[s[5]]);
+            self.blake2s_mix(&mut v, [3, 7, 11, 15], m[s[6]], m[s[7]]);
+            self.blake2s_mix(&mut v, [0, 5, 10, 15], m[s[8]], m[s[9]]);
+            self.blake2s_mix(&mut v, [1, 6, 11, 12], m[s[10]], m[s[11]]);
+            self.blake2s_mix(&mut v, [2, 7, 8, 13], m[s[12]], m[s[13]]);
+            self.blake2s_mix(&mut v, [3, 4, 9, 14], m[s[14]], m[s[15]]);
+        }
+
+        let mut output = [self.zero_var; 8];
+        output.iter_mut().enumerate().for_each(|(i, out)| {
+            let mixed = self.xor_32(v[i], v[i + 8]);
+            *out = self.xor_32(h[i], mixed);
+        });
+
+        output
+    }
+
+    /// Applies the mixing function `G` of Blake2s to the words of `v` at the
+    /// given `indexes`, with the message words `x` and `y`.
+    ///
+    /// This function adds 374 gates to the circuit description.
+    fn blake2s_mix(
+        &mut self,
+        v: &mut [Variable; 16],
+        [a, b, c, d]: [usize; 4],
+        x: Variable,
+        y: Variable,
+    ) {
+        v[a] = self.mod_2_32_add3(v[a], v[b], x);
+        v[d] = self.blake2s_xor_rotate(v[d], v[a], 16);
+        v[c] = self.mod_2_32_add(v[c], v[d]);
+        v[b] = self.blake2s_xor_rotate(v[b], v[c], 12);
+
+        v[a] = self.mod_2_32_add3(v[a], v[b], y);
+        v[d] = self.blake2s_xor_rotate(v[d], v[a], 8);
+        v[c] = self.mod_2_32_add(v[c], v[d]);
+        v[b] = self.blake2s_xor_rotate(v[b], v[c], 7);
+    }
+
+    /// Returns a [`Variable`] holding `(a ^ b) >>> amount`.
+    fn blake2s_xor_rotate(
+        &mut self,
+        a: Variable,
+        b: Variable,
+        amount: usize,
+    ) -> Variable {
+        let xor = self.xor_32(a, b);
+        self.rotate_right_32(xor, amount)
+            .expect("The rotation amounts of Blake2s are smaller than 32")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint_system::helper::*;
+
+    /// Returns the chaining value of Blake2s with a 32 bytes digest and no
+    /// key.
+    fn initial_chaining_value() -> [u32; 8] {
+        let mut h = IV;
+        h[0] ^= 0x0101_0000 ^ 32;
+        h
+    }
+
+    /// Compresses a single block holding the `message` words and returns
+    /// the values of the resulting chaining value.
+    fn compress_single_block(message: [u32; 16], len: u64) -> [BlsScalar; 8] {
+        let mut composer = StandardComposer::new();
+
+        let mut h = [composer.zero_var; 8];
+        h.iter_mut().zip(initial_chaining_value().iter()).for_each(
+            |(h, value)| {
+                *h = composer.add_input(BlsScalar::from(*value as u64))
+            },
+        );
+
+        let mut m = [composer.zero_var; 16];
+        m.iter_mut().zip(message.iter()).for_each(|(m, value)| {
+            *m = composer.add_input(BlsScalar::from(*value as u64))
+        });
+
+        let size = composer.circuit_size();
+        let output = composer.blake2s_compression(&h, &m, len, true);
+        assert_eq!(composer.circuit_size() - size, 30_232);
+
+        let mut values = [BlsScalar::zero(); 8];
+        values
+            .iter_mut()
+            .zip(output.iter())
+            .for_each(|(value, out)| *value = composer.variables[out]);
+        values
+    }
+
+    /// Returns the given words as [`BlsScalar`]s.
+    fn to_scalars(words: [u32; 8]) -> [BlsScalar; 8] {
+        let mut scalars = [BlsScalar::zero(); 8];
+        scalars
+            .iter_mut()
+            .zip(words.iter())
+            .for_each(|(scalar, word)| *scalar = BlsScalar::from(*word as u64));
+        scalars
+    }
+
+    #[test]
+    fn test_blake2s_compression_empty() {
+        let output = compress_single_block([0; 16], 0);
+        let expected = to_scalars([
+            0x307a_2169,
+            0x9480_9079,
+            0xd021_11e1,
+            0x7c4a_3542,
+            0x48b6_551f,
+            0x1ea5_a12c,
+            0xfd0d_251b,
+            0xf9ee_d01e,
+        ]);
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_blake2s_compression_abc() {
+        let mut message = [0; 16];
+        message[0] = u32::from_le_bytes([b'a', b'b', b'c', 0]);
+
+        let output = compress_single_block(message, 3);
+        let expected = to_scalars([
+            0x8c5e_8c50,
+            0xe214_7c32,
+            0xa32b_a7e1,
+            0x2f45_eb4e,
+            0x208b_4537,
+            0x293a_d69e,
+            0x4c9b_994d,
+            0x8259_6786,
+        ]);
+        assert_eq!(output, expected);
+    }
+
+    /// Returns the bits of the `bytes`, each byte in little-endian order, as
+    /// [`Variable`]s.
+    fn add_bytes(
+        composer: &mut StandardComposer,
+        bytes: &[u8],
+    ) -> Vec<Variable> {
+        bytes
+            .iter()
+            .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1))
+            .map(|bit| composer.add_input(BlsScalar::from(bit as u64)))
+            .collect()
+    }
+
+    /// Returns the bytes represented by the values of the digest bits.
+    fn digest_bytes(
+        composer: &StandardComposer,
+        digest: &[Variable; 256],
+    ) -> Vec<u8> {
+        digest
+            .chunks(8)
+            .map(|bits| {
+                bits.iter().enumerate().fold(0u8, |byte, (i, bit)| {
+                    let bit = composer.variables[bit] == BlsScalar::one();
+                    byte | ((bit as u8) << i)
+                })
+            })
+            .collect()
+    }
+
+    const EMPTY_DIGEST: [u8; 32] = [
+        0x69, 0x21, 0x7a, 0x30, 0x79, 0x90, 0x80, 0x94, 0xe1, 0x11, 0x21, 0xd0,
+        0x42, 0x35, 0x4a, 0x7c, 0x1f, 0x55, 0xb6, 0x48, 0x2c, 0xa1, 0xa5, 0x1e,
+        0x1b, 0x25, 0x0d, 0xfd, 0x1e, 0xd0, 0xee, 0xf9,
+    ];
+
+    const ABC_DIGEST: [u8; 32] = [
+        0x50, 0x8c, 0x5e, 0x8c, 0x32, 0x7c, 0x14, 0xe2, 0xe1, 0xa7, 0x2b, 0xa3,
+        0x4e, 0xeb, 0x45, 0x2f, 0x37, 0x45, 0x8b, 0x20, 0x9e, 0xd6, 0x3a, 0x29,
+        0x4d, 0x99, 0x9b, 0x4c, 0x86, 0x67, 0x59, 0x82,
+    ];
+
+    /// Digest of the 65 bytes `0, 1, ..., 64`.
+    const TWO_BLOCKS_DIGEST: [u8; 32] = [
+        0x1b, 0x53, 0xee, 0x94, 0xaa, 0xf3, 0x4e, 0x4b, 0x15, 0x9d, 0x48, 0xde,
+        0x35, 0x2c, 0x7f, 0x06, 0x61, 0xd0, 0xa4, 0x0e, 0xdf, 0xf9, 0x5a, 0x0b,
+        0x16, 0x39, 0xb4, 0x09, 0x0e, 0x97, 0x44, 0x72,
+    ];
+
+    #[test]
+    fn test_blake2s_digest() {
+        let messages: [(Vec<u8>, [u8; 32], usize); 3] = [
+            (vec![], EMPTY_DIGEST, 1),
+            (b"abc".to_vec(), ABC_DIGEST, 1),
+            ((0..65).collect(), TWO_BLOCKS_DIGEST, 2),
+        ];
+
+        for (message, expected, num_blocks) in messages.iter() {
+            let mut composer = StandardComposer::new();
+            let bits = add_bytes(&mut composer, message);
+
+            let size = composer.circuit_size();
+            let digest = composer.blake2s(&bits);
+            assert_eq!(
+                composer.circuit_size() - size,
+                30_488 * num_blocks + 400
+            );
+
+            assert_eq!(&digest_bytes(&composer, &digest)[..], &expected[..]);
+        }
+    }
+
+    #[test]
+    fn test_blake2s_proof() {
+        let res = gadget_tester(
+            |composer| {
+                let bits = add_bytes(composer, b"abc");
+                let digest = composer.blake2s(&bits);
+
+                for (i, bit) in digest.iter().enumerate() {
+                    let expected = (ABC_DIGEST[i / 8] >> (i % 8)) & 1;
+                    composer.constrain_to_constant(
+                        *bit,
+                        BlsScalar::from(expected as u64),
+                        None,
+                    );
+                }
+            },
+            1 << 15,
+        );
+        assert!(res.is_ok());
+    }
+}
@@ -0,0 +1,181 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Merkle tree openings, generic over the [`HashGadget`] hashing the nodes.
+//!
+//! A path holds one level per height of the tree, starting from the leaf.
+//! Each level is the sibling of the current node along with whether the
+//! current node is the right child, so the parent is `hash(sibling, node)`
+//! when it is and `hash(node, sibling)` otherwise.
+
+use crate::constraint_system::hashing::HashGadget;
+use crate::constraint_system::{StandardComposer, Variable};
+use dusk_bls12_381::BlsScalar;
+
+/// Returns the root of the tree holding the `leaf` at the given `path`,
+/// computed outside of the circuit as [`StandardComposer::merkle_root`] does.
+///
+/// Each level of the path is the sibling of the current node and whether
+/// the current node is the right child.
+pub fn merkle_root<H: HashGadget>(
+    hasher: &H,
+    leaf: BlsScalar,
+    path: &[(BlsScalar, bool)],
+) -> BlsScalar {
+    path.iter()
+        .fold(leaf, |node, (sibling, is_right)| match is_right {
+            true => hasher.hash_two_native(*sibling, node),
+            false => hasher.hash_two_native(node, *sibling),
+        })
+}
+
+impl StandardComposer {
+    /// Returns a [`Variable`] holding the root of the tree holding the
+    /// `leaf` at the given `path`, see [`merkle_root`].
+    ///
+    /// Each level of the path is the sibling of the current node and a bit
+    /// set when the current node is the right child, which is constrained
+    /// to be boolean. Ordering the children takes four gates per level,
+    /// on top of the gates of [`HashGadget::hash_two`].
+    pub fn merkle_root<H: HashGadget>(
+        &mut self,
+        hasher: &H,
+        leaf: Variable,
+        path: &[(Variable, Variable)],
+    ) -> Variable {
+        path.iter().fold(leaf, |node, (sibling, is_right)| {
+            self.boolean_gate(*is_right);
+
+            // left = node + is_right * (sibling - node)
+            let diff = self.add(
+                (BlsScalar::one(), *sibling),
+                (-BlsScalar::one(), node),
+                BlsScalar::zero(),
+                None,
+            );
+            let left = self.big_mul(
+                BlsScalar::one(),
+                *is_right,
+                diff,
+                Some((BlsScalar::one(), node)),
+                BlsScalar::zero(),
+                None,
+            );
+
+            // right = node + sibling - left
+            let right = self.big_add(
+                (BlsScalar::one(), node),
+                (BlsScalar::one(), *sibling),
+                Some((-BlsScalar::one(), left)),
+                BlsScalar::zero(),
+                None,
+            );
+
+            hasher.hash_two(self, left, right)
+        })
+    }
+
+    /// Constrains the `leaf` to be held by the tree with the public `root`
+    /// at the given `path`, see [`StandardComposer::merkle_root`].
+    ///
+    /// The root is added as a public input.
+    pub fn assert_merkle_opening<H: HashGadget>(
+        &mut self,
+        hasher: &H,
+        leaf: Variable,
+        path: &[(Variable, Variable)],
+        root: BlsScalar,
+    ) {
+        let computed = self.merkle_root(hasher, leaf, path);
+        self.constrain_to_constant(computed, BlsScalar::zero(), Some(-root));
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint_system::helper::*;
+    use crate::constraint_system::{MimcParams, PoseidonParams};
+    use alloc::vec::Vec;
+
+    fn test_opening<H: HashGadget + Default>(
+        composer: &mut StandardComposer,
+        corrupt: bool,
+    ) {
+        let hasher = H::default();
+
+        let leaf = BlsScalar::from(42u64);
+        let path: Vec<(BlsScalar, bool)> = (0..3u64)
+            .map(|i| (BlsScalar::from(100 + i), i % 2 == 0))
+            .collect();
+        let root = merkle_root(&hasher, leaf, &path);
+
+        // A corrupted opening claims the leaf is the other child at the
+        // first level
+        let leaf = composer.add_input(leaf);
+        let path: Vec<(Variable, Variable)> = path
+            .iter()
+            .enumerate()
+            .map(|(i, (sibling, is_right))| {
+                let is_right = *is_right ^ (corrupt && i == 0);
+                (
+                    composer.add_input(*sibling),
+                    composer.add_input(BlsScalar::from(is_right as u64)),
+                )
+            })
+            .collect();
+
+        composer.assert_merkle_opening(&hasher, leaf, &path, root);
+    }
+
+    #[test]
+    fn test_merkle_opening_poseidon() {
+        let res = gadget_tester(
+            |composer| test_opening::<PoseidonParams>(composer, false),
+            2048,
+        );
+        assert!(res.is_ok());
+
+        let res = gadget_tester(
+            |composer| test_opening::<PoseidonParams>(composer, true),
+            2048,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_merkle_opening_mimc() {
+        let res = gadget_tester(
+            |composer| test_opening::<MimcParams>(composer, false),
+            8192,
+        );
+        assert!(res.is_ok());
+
+        let res = gadget_tester(
+            |composer| test_opening::<MimcParams>(composer, true),
+            8192,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_merkle_root_native() {
+        let hasher = PoseidonParams::default();
+        let leaf = BlsScalar::from(1u64);
+        let sibling = BlsScalar::from(2u64);
+
+        assert_eq!(merkle_root(&hasher, leaf, &[]), leaf);
+        assert_eq!(
+            merkle_root(&hasher, leaf, &[(sibling, false)]),
+            hasher.hash(leaf, sibling)
+        );
+        assert_eq!(
+            merkle_root(&hasher, leaf, &[(sibling, true)]),
+            hasher.hash(sibling, leaf)
+        );
+    }
+}
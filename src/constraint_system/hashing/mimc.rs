@@ -0,0 +1,431 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! MiMC-Feistel permutation over [`BlsScalar`] with the `x^5` round
+//! function.
+//!
+//! Each round maps the state `(xl, xr)` to `(xr + (xl + c_i)^5, xl)`. The
+//! cube isn't a permutation of the scalar field, since `3` divides `p - 1`,
+//! so the exponent is `5` as for Poseidon. The bundled parameters have 220
+//! rounds, twice `ceil(log_5(p))` as required by the Feistel construction.
+//!
+//! The round constants are derived from the BLAKE2b-512 hash of
+//! [`CONSTANTS_SEED`] followed by the little-endian round index, reduced
+//! modulo `p`.
+//!
+//! The two-to-one hash is the sponge of the inputs with a rate of one
+//! element, starting from the zero state: `l` is added to `xl`, the state is
+//! permuted, then `r` is added to `xl`, the state is permuted again and the
+//! output is `xl`.
+//!
+//! The permutation can also be keyed with `k`, mapping each round to
+//! `(xr + (xl + k + c_i)^5, xl)`, which is the permutation itself for the
+//! zero key. The keyed hash absorbs any number of inputs the same way,
+//! permuting the state with the key after each one, so the two-to-one hash
+//! is the keyed hash of `[l, r]` with the zero key.
+//!
+//! The construction is specific to this crate: its round count, its round
+//! constants and its exponent differ from the ones of circomlib's
+//! `MiMCSponge` and of gnark's MiMC, so its hashes don't match theirs.
+
+use crate::constraint_system::hashing::HashGadget;
+use crate::constraint_system::{StandardComposer, Variable};
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+
+/// Seed of the generation of the round constants.
+pub const CONSTANTS_SEED: &[u8] = b"dusk-plonk-mimc-feistel";
+
+/// Number of rounds of the bundled parameters.
+const ROUNDS: usize = 220;
+
+/// Round constants of a MiMC-Feistel permutation.
+///
+/// The [`Default`] parameters have 220 rounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MimcParams {
+    round_constants: Vec<BlsScalar>,
+}
+
+impl Default for MimcParams {
+    fn default() -> Self {
+        Self::generate(ROUNDS)
+    }
+}
+
+impl MimcParams {
+    /// Loads the parameters of a permutation from the constant added on
+    /// each round, in order.
+    pub fn new(round_constants: Vec<BlsScalar>) -> Self {
+        Self { round_constants }
+    }
+
+    /// Generates the constants of a permutation with the given number of
+    /// `rounds` from [`CONSTANTS_SEED`].
+    pub fn generate(rounds: usize) -> Self {
+        Self {
+            round_constants: derive_constants(CONSTANTS_SEED, rounds),
+        }
+    }
+
+    /// Returns the number of rounds of the permutation.
+    pub fn rounds(&self) -> usize {
+        self.round_constants.len()
+    }
+
+    /// Returns the constants added on each round, in order.
+    pub fn round_constants(&self) -> &[BlsScalar] {
+        &self.round_constants
+    }
+
+    /// Applies the permutation to the state `(xl, xr)`.
+    pub fn permute(&self, state: &mut (BlsScalar, BlsScalar)) {
+        self.permute_keyed(state, BlsScalar::zero())
+    }
+
+    /// Applies the permutation keyed with `key` to the state `(xl, xr)`.
+    pub fn permute_keyed(
+        &self,
+        state: &mut (BlsScalar, BlsScalar),
+        key: BlsScalar,
+    ) {
+        self.round_constants.iter().for_each(|c| {
+            let t = state.0 + key + c;
+            *state = (state.1 + t.square().square() * t, state.0);
+        });
+    }
+
+    /// Returns the two-to-one hash of `left` and `right`.
+    pub fn hash(&self, left: BlsScalar, right: BlsScalar) -> BlsScalar {
+        let mut state = (left, BlsScalar::zero());
+        self.permute(&mut state);
+
+        state.0 += right;
+        self.permute(&mut state);
+
+        state.0
+    }
+
+    /// Returns the hash of the `inputs` keyed with `key`. The hash of no
+    /// input is zero.
+    pub fn keyed_hash(
+        &self,
+        inputs: &[BlsScalar],
+        key: BlsScalar,
+    ) -> BlsScalar {
+        let mut state = (BlsScalar::zero(), BlsScalar::zero());
+        inputs.iter().for_each(|input| {
+            state.0 += input;
+            self.permute_keyed(&mut state, key);
+        });
+
+        state.0
+    }
+}
+
+/// Derives `rounds` constants from the BLAKE2b-512 hash of the `seed`
+/// followed by the little-endian round index, reduced modulo `p`.
+pub(crate) fn derive_constants(seed: &[u8], rounds: usize) -> Vec<BlsScalar> {
+    (0..rounds as u64)
+        .map(|i| {
+            let hash = blake2b_simd::Params::new()
+                .hash_length(64)
+                .to_state()
+                .update(seed)
+                .update(&i.to_le_bytes())
+                .finalize();
+
+            let mut wide = [0u8; 64];
+            wide.copy_from_slice(hash.as_bytes());
+            BlsScalar::from_bytes_wide(&wide)
+        })
+        .collect()
+}
+
+/// Two-to-one MiMC-Feistel hash, adding 1761 gates to the circuit
+/// description for the default parameters.
+impl HashGadget for MimcParams {
+    fn hash_two_native(&self, l: BlsScalar, r: BlsScalar) -> BlsScalar {
+        self.hash(l, r)
+    }
+
+    fn hash_two(
+        &self,
+        composer: &mut StandardComposer,
+        l: Variable,
+        r: Variable,
+    ) -> Variable {
+        composer.mimc_hash(self, l, r)
+    }
+}
+
+impl StandardComposer {
+    /// Applies the MiMC-Feistel permutation described by `params` to the
+    /// state `(xl, xr)`, returning the resulting state.
+    ///
+    /// Each round takes three gates for the `x^5` S-box, with the round
+    /// constant folded into them, and one gate for the addition. For the
+    /// default parameters, this function adds 880 gates to the circuit
+    /// description.
+    pub fn mimc_permutation(
+        &mut self,
+        params: &MimcParams,
+        state: (Variable, Variable),
+    ) -> (Variable, Variable) {
+        params.round_constants.iter().fold(state, |(xl, xr), c| {
+            let t = self.poseidon_sbox(xl, *c);
+            let xl_next = self.add(
+                (BlsScalar::one(), xr),
+                (BlsScalar::one(), t),
+                BlsScalar::zero(),
+                None,
+            );
+
+            (xl_next, xl)
+        })
+    }
+
+    /// Returns a [`Variable`] holding the two-to-one MiMC-Feistel hash of
+    /// `left` and `right`, see [`MimcParams::hash`].
+    ///
+    /// For the default parameters, this function adds 1761 gates to the
+    /// circuit description.
+    pub fn mimc_hash(
+        &mut self,
+        params: &MimcParams,
+        left: Variable,
+        right: Variable,
+    ) -> Variable {
+        let (xl, xr) = self.mimc_permutation(params, (left, self.zero_var));
+        let xl = self.add(
+            (BlsScalar::one(), xl),
+            (BlsScalar::one(), right),
+            BlsScalar::zero(),
+            None,
+        );
+
+        self.mimc_permutation(params, (xl, xr)).0
+    }
+
+    /// Applies the MiMC-Feistel rounds keyed with `key` to the state
+    /// `(xl, xr)`, one per constant of `round_constants`, returning the
+    /// resulting state. See [`MimcParams::permute_keyed`].
+    ///
+    /// Each round takes one gate for the addition of the key and the round
+    /// constant, and three for the `x^5` S-box added to `xr`. For the
+    /// constants of the default parameters, this function adds 880 gates to
+    /// the circuit description.
+    pub fn mimc_keyed_rounds(
+        &mut self,
+        state: (Variable, Variable),
+        key: Variable,
+        round_constants: &[BlsScalar],
+    ) -> (Variable, Variable) {
+        round_constants.iter().fold(state, |(xl, xr), c| {
+            let t = self.add(
+                (BlsScalar::one(), xl),
+                (BlsScalar::one(), key),
+                *c,
+                None,
+            );
+            let t_2 = self.mul(BlsScalar::one(), t, t, BlsScalar::zero(), None);
+            let t_4 =
+                self.mul(BlsScalar::one(), t_2, t_2, BlsScalar::zero(), None);
+            let xl_next = self.big_mul(
+                BlsScalar::one(),
+                t_4,
+                t,
+                Some((BlsScalar::one(), xr)),
+                BlsScalar::zero(),
+                None,
+            );
+
+            (xl_next, xl)
+        })
+    }
+
+    /// Returns a [`Variable`] holding the hash of the `inputs` keyed with
+    /// `key`, see [`MimcParams::keyed_hash`].
+    ///
+    /// For the default parameters, this function adds 881 gates to the
+    /// circuit description per input.
+    pub fn mimc_keyed_hash(
+        &mut self,
+        params: &MimcParams,
+        inputs: &[Variable],
+        key: Variable,
+    ) -> Variable {
+        let state = (self.zero_var, self.zero_var);
+        let (xl, _) = inputs.iter().fold(state, |(xl, xr), input| {
+            let xl = self.add(
+                (BlsScalar::one(), xl),
+                (BlsScalar::one(), *input),
+                BlsScalar::zero(),
+                None,
+            );
+
+            self.mimc_keyed_rounds((xl, xr), key, &params.round_constants)
+        });
+
+        xl
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint_system::helper::*;
+    use alloc::vec::Vec;
+    use dusk_bytes::Serializable;
+    use rand_core::OsRng;
+
+    /// Parses a big-endian hex string into a [`BlsScalar`].
+    fn from_hex(hex: &str) -> BlsScalar {
+        let mut bytes = [0u8; 32];
+        bytes.iter_mut().rev().enumerate().for_each(|(i, byte)| {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap()
+        });
+        BlsScalar::from_bytes(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_mimc_vector() {
+        // Computed with an independent implementation of the constant
+        // generation and the sponge
+        let params = MimcParams::default();
+        assert_eq!(params.rounds(), 220);
+        assert_eq!(
+            params.round_constants[0],
+            from_hex(
+                "70501d02553213246d0199670c47ba4b7d8a4d7702b31df70bea5ee46781c190"
+            )
+        );
+        assert_eq!(
+            params.hash(BlsScalar::one(), BlsScalar::from(2u64)),
+            from_hex(
+                "035726ec144c009bfba9e45389b058ef6f3467fa297e0c13daf791509897125e"
+            )
+        );
+    }
+
+    #[test]
+    fn test_mimc_hash() {
+        let res = gadget_tester(
+            |composer| {
+                let params = MimcParams::default();
+                let left = BlsScalar::random(&mut OsRng);
+                let right = BlsScalar::random(&mut OsRng);
+                let expected = params.hash_two_native(left, right);
+
+                let left = composer.add_input(left);
+                let right = composer.add_input(right);
+
+                let n = composer.circuit_size();
+                let hash = params.hash_two(composer, left, right);
+                assert_eq!(composer.circuit_size() - n, 1761);
+
+                composer.constrain_to_constant(hash, expected, None);
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the inputs are swapped
+        let res = gadget_tester(
+            |composer| {
+                let params = MimcParams::default();
+                let left = BlsScalar::from(1u64);
+                let right = BlsScalar::from(2u64);
+                let expected = params.hash(right, left);
+
+                let left = composer.add_input(left);
+                let right = composer.add_input(right);
+                let hash = composer.mimc_hash(&params, left, right);
+                composer.constrain_to_constant(hash, expected, None);
+            },
+            2048,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_mimc_keyed_vector() {
+        // Computed with an independent implementation of the constant
+        // generation and the sponge
+        let params = MimcParams::default();
+        assert_eq!(
+            params.keyed_hash(
+                &[BlsScalar::one(), BlsScalar::from(2u64)],
+                BlsScalar::from(3u64)
+            ),
+            from_hex(
+                "6c0480b27ee33ee688d402f9d01518ac2e8e77b0b71d04afff95b161a42ca579"
+            )
+        );
+        assert_eq!(
+            params.keyed_hash(&[BlsScalar::from(5u64)], BlsScalar::zero()),
+            from_hex(
+                "42f208fd698820ba40aa0b17f53593c2a7e08414ed751b9d6a3f97c62fdecb51"
+            )
+        );
+        assert_eq!(params.keyed_hash(&[], BlsScalar::one()), BlsScalar::zero());
+
+        // The two-to-one hash is the keyed hash with the zero key
+        assert_eq!(
+            params.keyed_hash(
+                &[BlsScalar::one(), BlsScalar::from(2u64)],
+                BlsScalar::zero()
+            ),
+            params.hash(BlsScalar::one(), BlsScalar::from(2u64))
+        );
+    }
+
+    #[test]
+    fn test_mimc_keyed_hash() {
+        let res = gadget_tester(
+            |composer| {
+                let params = MimcParams::default();
+                let inputs = [
+                    BlsScalar::random(&mut OsRng),
+                    BlsScalar::random(&mut OsRng),
+                ];
+                let key = BlsScalar::random(&mut OsRng);
+                let expected = params.keyed_hash(&inputs, key);
+
+                let inputs: Vec<Variable> =
+                    inputs.iter().map(|i| composer.add_input(*i)).collect();
+                let key = composer.add_input(key);
+
+                let n = composer.circuit_size();
+                let hash = composer.mimc_keyed_hash(&params, &inputs, key);
+                assert_eq!(composer.circuit_size() - n, 1762);
+
+                composer.constrain_to_constant(hash, expected, None);
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the hash is keyed with another key
+        let res = gadget_tester(
+            |composer| {
+                let params = MimcParams::default();
+                let inputs = [BlsScalar::one(), BlsScalar::from(2u64)];
+                let expected =
+                    params.keyed_hash(&inputs, BlsScalar::from(3u64));
+
+                let inputs: Vec<Variable> =
+                    inputs.iter().map(|i| composer.add_input(*i)).collect();
+                let key = composer.add_input(BlsScalar::from(4u64));
+                let hash = composer.mimc_keyed_hash(&params, &inputs, key);
+                composer.constrain_to_constant(hash, expected, None);
+            },
+            2048,
+        );
+        assert!(res.is_err());
+    }
+}
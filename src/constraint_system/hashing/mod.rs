@@ -0,0 +1,48 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Hash function gates.
+//!
+//! The two-to-one hashes implementing [`HashGadget`] can be plugged into the
+//! gadgets that are generic over the hash, such as the [`merkle`] opening.
+//! Their cost for the bundled parameters is:
+//!
+//! | Hash                              | Gates per `hash_two` |
+//! |-----------------------------------|----------------------|
+//! | Poseidon, [`PoseidonParams`]      | 439                  |
+//! | MiMC-Feistel, [`MimcParams`]      | 1761                 |
+//!
+//! [`PoseidonParams`]: poseidon::PoseidonParams
+//! [`MimcParams`]: mimc::MimcParams
+
+/// Blake2s hash gates
+mod blake2s;
+/// Merkle tree opening gates
+pub mod merkle;
+/// MiMC-Feistel hash gates
+pub mod mimc;
+/// Poseidon hash gates
+pub mod poseidon;
+/// SHA-256 hash gates
+mod sha256;
+
+use crate::constraint_system::{StandardComposer, Variable};
+use dusk_bls12_381::BlsScalar;
+
+/// Two-to-one hash over [`BlsScalar`] with a matching gadget.
+pub trait HashGadget {
+    /// Returns the hash of `l` and `r`, computed outside of the circuit.
+    fn hash_two_native(&self, l: BlsScalar, r: BlsScalar) -> BlsScalar;
+
+    /// Returns a [`Variable`] holding the hash of `l` and `r`, constrained
+    /// to be equal to [`HashGadget::hash_two_native`] of their values.
+    fn hash_two(
+        &self,
+        composer: &mut StandardComposer,
+        l: Variable,
+        r: Variable,
+    ) -> Variable;
+}
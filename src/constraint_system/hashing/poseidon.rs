@@ -0,0 +1,705 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Poseidon permutation over [`BlsScalar`] with the `x^5` S-box.
+//!
+//! The permutation is parametrised by a [`PoseidonParams`], which holds the
+//! round constants and the MDS matrix, so the gadgets can match any off
+//! circuit implementation using the same constants. The bundled parameters
+//! are generated with the Grain LFSR of the reference implementation of the
+//! Poseidon paper, for a width of 3 with 8 full and 57 partial rounds.
+//!
+//! The first element of the state is the capacity of the sponge, which is
+//! initialized with the length of the input, while the remaining ones are
+//! the rate. The input is absorbed by adding it to the rate, with the last
+//! block padded with zeros, and the output is the first element of the rate.
+
+use crate::constraint_system::hashing::HashGadget;
+use crate::constraint_system::{ArithmeticGate, StandardComposer, Variable};
+use crate::error::Error;
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+
+/// Number of bits of the scalar field modulus, used by the generation of
+/// the constants.
+const FIELD_BITS: usize = 255;
+
+/// Width of the bundled parameters.
+const WIDTH: usize = 3;
+/// Number of full rounds of the bundled parameters.
+const FULL_ROUNDS: usize = 8;
+/// Number of partial rounds of the bundled parameters.
+const PARTIAL_ROUNDS: usize = 57;
+
+/// Round constants and MDS matrix of a Poseidon permutation.
+///
+/// The [`Default`] parameters have a width of 3, with 8 full and 57 partial
+/// rounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoseidonParams {
+    width: usize,
+    full_rounds: usize,
+    partial_rounds: usize,
+    round_constants: Vec<BlsScalar>,
+    mds: Vec<Vec<BlsScalar>>,
+}
+
+impl Default for PoseidonParams {
+    fn default() -> Self {
+        Self::generate(WIDTH, FULL_ROUNDS, PARTIAL_ROUNDS)
+    }
+}
+
+impl PoseidonParams {
+    /// Loads the parameters of a permutation of `width` elements from the
+    /// `width` constants added on each round, in order, and the `width x
+    /// width` MDS matrix, given by rows.
+    ///
+    /// Returns [`Error::InvalidPoseidonParameters`] if the width is smaller
+    /// than 2, the number of full rounds is odd or the sizes of the constants
+    /// and the matrix don't match the width and the number of rounds.
+    pub fn new(
+        width: usize,
+        full_rounds: usize,
+        partial_rounds: usize,
+        round_constants: Vec<BlsScalar>,
+        mds: Vec<Vec<BlsScalar>>,
+    ) -> Result<Self, Error> {
+        let valid = width >= 2
+            && full_rounds % 2 == 0
+            && round_constants.len() == (full_rounds + partial_rounds) * width
+            && mds.len() == width
+            && mds.iter().all(|row| row.len() == width);
+
+        if !valid {
+            return Err(Error::InvalidPoseidonParameters);
+        }
+
+        Ok(Self {
+            width,
+            full_rounds,
+            partial_rounds,
+            round_constants,
+            mds,
+        })
+    }
+
+    /// Generates the round constants and the Cauchy MDS matrix of a
+    /// permutation with the Grain LFSR, as done by the reference
+    /// implementation of the Poseidon paper.
+    ///
+    /// The number of rounds isn't checked against the security requirements
+    /// of the paper, and neither is the matrix checked against the invariant
+    /// subspace attacks, so they must be picked from the reference scripts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width is smaller than 2 or the number of full rounds is
+    /// odd.
+    pub fn generate(
+        width: usize,
+        full_rounds: usize,
+        partial_rounds: usize,
+    ) -> Self {
+        assert!(width >= 2, "the width must be at least 2");
+        assert!(
+            full_rounds % 2 == 0,
+            "the number of full rounds must be even"
+        );
+
+        let mut grain = Grain::new(width, full_rounds, partial_rounds);
+
+        // Samples above the modulus are rejected
+        let count = (full_rounds + partial_rounds) * width;
+        let mut round_constants = Vec::with_capacity(count);
+        while round_constants.len() < count {
+            if let Ok(constant) = BlsScalar::from_bytes(&grain.next_bytes()) {
+                round_constants.push(constant);
+            }
+        }
+
+        // M[i][j] = 1 / (x_i + y_j), where the samples are reduced modulo p
+        // and redrawn until they are distinct and no sum is zero
+        let mds = loop {
+            let samples: Vec<BlsScalar> = (0..2 * width)
+                .map(|_| {
+                    let mut wide = [0u8; 64];
+                    wide[..32].copy_from_slice(&grain.next_bytes());
+                    BlsScalar::from_bytes_wide(&wide)
+                })
+                .collect();
+            let (xs, ys) = samples.split_at(width);
+
+            let distinct = samples
+                .iter()
+                .enumerate()
+                .all(|(i, s)| samples[i + 1..].iter().all(|t| s != t));
+            let sums: Vec<Vec<BlsScalar>> = xs
+                .iter()
+                .map(|x| ys.iter().map(|y| x + y).collect())
+                .collect();
+            let invertible = sums
+                .iter()
+                .all(|row| row.iter().all(|sum| sum != &BlsScalar::zero()));
+
+            if distinct && invertible {
+                break sums
+                    .iter()
+                    .map(|row| {
+                        row.iter().map(|sum| sum.invert().unwrap()).collect()
+                    })
+                    .collect();
+            }
+        };
+
+        Self {
+            width,
+            full_rounds,
+            partial_rounds,
+            round_constants,
+            mds,
+        }
+    }
+
+    /// Returns the number of elements of the state of the permutation.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Applies the permutation to the `state`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the state doesn't match the width.
+    pub fn permute(&self, state: &mut [BlsScalar]) {
+        assert_eq!(state.len(), self.width, "the state must match the width");
+
+        self.round_constants
+            .chunks(self.width)
+            .enumerate()
+            .for_each(|(round, constants)| {
+                state.iter_mut().zip(constants.iter()).for_each(|(s, c)| {
+                    *s += c;
+                });
+
+                let sboxes = match self.is_full_round(round) {
+                    true => self.width,
+                    false => 1,
+                };
+                state
+                    .iter_mut()
+                    .take(sboxes)
+                    .for_each(|s| *s = s.square().square() * *s);
+
+                let mixed: Vec<BlsScalar> = self
+                    .mds
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .zip(state.iter())
+                            .fold(BlsScalar::zero(), |acc, (m, s)| acc + m * s)
+                    })
+                    .collect();
+                state.copy_from_slice(&mixed);
+            });
+    }
+
+    /// Returns the two-to-one hash of `left` and `right`, which is the sponge
+    /// of both.
+    pub fn hash(&self, left: BlsScalar, right: BlsScalar) -> BlsScalar {
+        self.sponge(&[left, right])
+    }
+
+    /// Returns the sponge hash of the variable length `inputs`.
+    pub fn sponge(&self, inputs: &[BlsScalar]) -> BlsScalar {
+        let mut state = vec![BlsScalar::zero(); self.width];
+        state[0] = BlsScalar::from(inputs.len() as u64);
+
+        match inputs.is_empty() {
+            true => self.permute(&mut state),
+            false => inputs.chunks(self.width - 1).for_each(|block| {
+                state[1..].iter_mut().zip(block.iter()).for_each(|(s, x)| {
+                    *s += x;
+                });
+                self.permute(&mut state);
+            }),
+        }
+
+        state[1]
+    }
+
+    /// Returns whether the S-box is applied to the whole state on the given
+    /// round. The full rounds are split evenly around the partial ones.
+    fn is_full_round(&self, round: usize) -> bool {
+        let half = self.full_rounds / 2;
+        round < half || round >= half + self.partial_rounds
+    }
+}
+
+/// Grain LFSR in self-shrinking mode, as used by the reference
+/// implementation to generate the constants.
+struct Grain {
+    state: [bool; 80],
+    head: usize,
+}
+
+impl Grain {
+    /// Initializes the LFSR with the description of a permutation over a
+    /// prime field with the `x^5` S-box, discarding the first 160 bits.
+    fn new(width: usize, full_rounds: usize, partial_rounds: usize) -> Self {
+        let fields: [(usize, usize); 7] = [
+            (1, 2),
+            (0, 4),
+            (FIELD_BITS, 12),
+            (width, 12),
+            (full_rounds, 10),
+            (partial_rounds, 10),
+            ((1 << 30) - 1, 30),
+        ];
+
+        let mut state = [false; 80];
+        fields
+            .iter()
+            .flat_map(|(value, bits)| {
+                (0..*bits).rev().map(move |i| (value >> i) & 1 == 1)
+            })
+            .zip(state.iter_mut())
+            .for_each(|(bit, s)| *s = bit);
+
+        let mut grain = Self { state, head: 0 };
+        (0..160).for_each(|_| {
+            grain.next_raw_bit();
+        });
+
+        grain
+    }
+
+    fn next_raw_bit(&mut self) -> bool {
+        let s = |i: usize| self.state[(self.head + i) % 80];
+        let bit = s(62) ^ s(51) ^ s(38) ^ s(23) ^ s(13) ^ s(0);
+
+        self.state[self.head] = bit;
+        self.head = (self.head + 1) % 80;
+
+        bit
+    }
+
+    /// Returns the next output bit: bits are taken in pairs and the second
+    /// one is output only if the first one is set.
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.next_raw_bit();
+            let bit = self.next_raw_bit();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    /// Returns the little-endian bytes of the integer made of the next
+    /// `FIELD_BITS` bits, most significant first.
+    fn next_bytes(&mut self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        (0..FIELD_BITS).rev().for_each(|i| {
+            if self.next_bit() {
+                bytes[i / 8] |= 1 << (i % 8);
+            }
+        });
+
+        bytes
+    }
+}
+
+/// Two-to-one Poseidon hash, adding 439 gates to the circuit description for
+/// the default parameters.
+impl HashGadget for PoseidonParams {
+    fn hash_two_native(&self, l: BlsScalar, r: BlsScalar) -> BlsScalar {
+        self.hash(l, r)
+    }
+
+    fn hash_two(
+        &self,
+        composer: &mut StandardComposer,
+        l: Variable,
+        r: Variable,
+    ) -> Variable {
+        composer.poseidon_hash(self, l, r)
+    }
+}
+
+impl StandardComposer {
+    /// Applies the Poseidon permutation described by `params` to the
+    /// `state`, returning the resulting state.
+    ///
+    /// No dedicated S-box gate is available, so each `x^5` takes three
+    /// multiplication gates, with the round constant folded into them. The
+    /// constants of the elements skipped by the S-box on partial rounds are
+    /// folded into the matrix multiplication, where each output takes one
+    /// gate for widths up to 3. For the default parameters, this function
+    /// adds 438 gates to the circuit description.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the state doesn't match the width.
+    pub fn poseidon_permutation(
+        &mut self,
+        params: &PoseidonParams,
+        state: &[Variable],
+    ) -> Vec<Variable> {
+        assert_eq!(state.len(), params.width, "the state must match the width");
+
+        // Each element is the value of the variable plus a constant that
+        // wasn't constrained yet
+        let mut state: Vec<(Variable, BlsScalar)> =
+            state.iter().map(|v| (*v, BlsScalar::zero())).collect();
+
+        for (round, constants) in
+            params.round_constants.chunks(params.width).enumerate()
+        {
+            state
+                .iter_mut()
+                .zip(constants.iter())
+                .for_each(|((_, offset), c)| *offset += c);
+
+            let sboxes = match params.is_full_round(round) {
+                true => params.width,
+                false => 1,
+            };
+            state.iter_mut().take(sboxes).for_each(|(x, offset)| {
+                *x = self.poseidon_sbox(*x, *offset);
+                *offset = BlsScalar::zero();
+            });
+
+            state = params
+                .mds
+                .iter()
+                .map(|row| {
+                    let constant =
+                        row.iter().zip(state.iter()).fold(
+                            BlsScalar::zero(),
+                            |acc, (m, (_, offset))| acc + m * offset,
+                        );
+                    let terms: Vec<(BlsScalar, Variable)> = row
+                        .iter()
+                        .zip(state.iter())
+                        .map(|(m, (x, _))| (*m, *x))
+                        .collect();
+
+                    let x = self.poseidon_linear_combination(&terms, constant);
+                    (x, BlsScalar::zero())
+                })
+                .collect();
+        }
+
+        state.into_iter().map(|(x, _)| x).collect()
+    }
+
+    /// Returns a [`Variable`] holding the two-to-one Poseidon hash of `left`
+    /// and `right`, which is the sponge of both.
+    ///
+    /// For the default parameters, this function adds 439 gates to the
+    /// circuit description.
+    pub fn poseidon_hash(
+        &mut self,
+        params: &PoseidonParams,
+        left: Variable,
+        right: Variable,
+    ) -> Variable {
+        self.poseidon_sponge(params, &[left, right])
+    }
+
+    /// Returns a [`Variable`] holding the Poseidon sponge hash of the
+    /// variable length `inputs`.
+    ///
+    /// The length of the input is a constant of the circuit description, so
+    /// the capacity is initialized with one gate. Each block of `width - 1`
+    /// inputs adds one permutation, plus one gate per input after the first
+    /// block.
+    pub fn poseidon_sponge(
+        &mut self,
+        params: &PoseidonParams,
+        inputs: &[Variable],
+    ) -> Variable {
+        let mut state = vec![self.zero_var; params.width];
+        state[0] = self.add_witness_to_circuit_description(BlsScalar::from(
+            inputs.len() as u64,
+        ));
+
+        if inputs.is_empty() {
+            state = self.poseidon_permutation(params, &state);
+        }
+
+        for (i, block) in inputs.chunks(params.width - 1).enumerate() {
+            // The rate is zero before the first permutation
+            state[1..].iter_mut().zip(block.iter()).for_each(|(s, x)| {
+                *s = match i {
+                    0 => *x,
+                    _ => self.add(
+                        (BlsScalar::one(), *s),
+                        (BlsScalar::one(), *x),
+                        BlsScalar::zero(),
+                        None,
+                    ),
+                };
+            });
+            state = self.poseidon_permutation(params, &state);
+        }
+
+        state[1]
+    }
+
+    /// Returns a [`Variable`] holding `(x + c)^5`, adding three gates to the
+    /// circuit description.
+    pub(super) fn poseidon_sbox(
+        &mut self,
+        x: Variable,
+        c: BlsScalar,
+    ) -> Variable {
+        let y = self.variables[&x] + c;
+
+        // (x + c)^2 = x * x + 2c * x + c^2
+        let square = self.add_input(y.square());
+        ArithmeticGate::new()
+            .left(x)
+            .right(x)
+            .output(square)
+            .q_m(BlsScalar::one())
+            .q_l(c + c)
+            .q_o(-BlsScalar::one())
+            .q_c(c.square())
+            .apply(self);
+
+        let quad =
+            self.mul(BlsScalar::one(), square, square, BlsScalar::zero(), None);
+
+        // (x + c)^5 = quad * x + c * quad
+        let quint = self.add_input(y.square().square() * y);
+        ArithmeticGate::new()
+            .left(quad)
+            .right(x)
+            .output(quint)
+            .q_m(BlsScalar::one())
+            .q_l(c)
+            .q_o(-BlsScalar::one())
+            .apply(self);
+
+        quint
+    }
+
+    /// Returns a [`Variable`] holding the sum of the scaled `terms` plus the
+    /// `constant`.
+    ///
+    /// The first three terms are added on one gate, and each following gate
+    /// adds two more terms, using the fourth wire to carry the accumulator.
+    fn poseidon_linear_combination(
+        &mut self,
+        terms: &[(BlsScalar, Variable)],
+        constant: BlsScalar,
+    ) -> Variable {
+        let zero = (BlsScalar::zero(), self.zero_var);
+        let term = |i: usize| terms.get(i).copied().unwrap_or(zero);
+
+        let acc = self.big_add(term(0), term(1), Some(term(2)), constant, None);
+
+        terms.iter().skip(3).collect::<Vec<_>>().chunks(2).fold(
+            acc,
+            |acc, pair| {
+                let second = pair.get(1).map(|t| **t).unwrap_or(zero);
+                self.big_add(
+                    *pair[0],
+                    second,
+                    Some((BlsScalar::one(), acc)),
+                    BlsScalar::zero(),
+                    None,
+                )
+            },
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint_system::helper::*;
+    use rand_core::OsRng;
+
+    /// Parses a big-endian hex string into a [`BlsScalar`].
+    fn from_hex(hex: &str) -> BlsScalar {
+        let mut bytes = [0u8; 32];
+        bytes.iter_mut().rev().enumerate().for_each(|(i, byte)| {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap()
+        });
+        BlsScalar::from_bytes(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_permutation_vector() {
+        // Test vector of the reference implementation for the permutation
+        // over the BLS12-381 scalar field with width 3
+        let params = PoseidonParams::default();
+        let mut state =
+            [BlsScalar::zero(), BlsScalar::one(), BlsScalar::from(2u64)];
+        params.permute(&mut state);
+
+        let expected = [
+            "28ce19420fc246a05553ad1e8c98f5c9d67166be2c18e9e4cb4b4e317dd2a78a",
+            "51f3e312c95343a896cfd8945ea82ba956c1118ce9b9859b6ea56637b4b1ddc4",
+            "3b2b69139b235626a0bfb56c9527ae66a7bf486ad8c11c14d1da0c69bbe0f79a",
+        ];
+        state
+            .iter()
+            .zip(expected.iter())
+            .for_each(|(s, e)| assert_eq!(*s, from_hex(e)));
+    }
+
+    #[test]
+    fn test_params_new() {
+        let params = PoseidonParams::default();
+        let loaded = PoseidonParams::new(
+            3,
+            8,
+            57,
+            params.round_constants.clone(),
+            params.mds.clone(),
+        );
+        assert_eq!(loaded.unwrap(), params);
+
+        let res = PoseidonParams::new(
+            3,
+            8,
+            56,
+            params.round_constants.clone(),
+            params.mds.clone(),
+        );
+        assert!(matches!(res, Err(Error::InvalidPoseidonParameters)));
+
+        let res = PoseidonParams::new(
+            3,
+            7,
+            58,
+            params.round_constants.clone(),
+            params.mds[..2].to_vec(),
+        );
+        assert!(matches!(res, Err(Error::InvalidPoseidonParameters)));
+    }
+
+    #[test]
+    fn test_poseidon_permutation() {
+        let res = gadget_tester(
+            |composer| {
+                let params = PoseidonParams::default();
+                let mut values = [
+                    BlsScalar::random(&mut OsRng),
+                    BlsScalar::random(&mut OsRng),
+                    BlsScalar::random(&mut OsRng),
+                ];
+                let state: Vec<Variable> =
+                    values.iter().map(|v| composer.add_input(*v)).collect();
+
+                let gates = composer.circuit_size();
+                let state = composer.poseidon_permutation(&params, &state);
+                assert_eq!(composer.circuit_size() - gates, 438);
+
+                params.permute(&mut values);
+                state.iter().zip(values.iter()).for_each(|(s, v)| {
+                    composer.constrain_to_constant(*s, *v, None);
+                });
+            },
+            1024,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_poseidon_permutation_wide() {
+        // Width 5 exercises the chained matrix multiplication
+        let res = gadget_tester(
+            |composer| {
+                let params = PoseidonParams::generate(5, 8, 60);
+                let mut values: Vec<BlsScalar> =
+                    (0..5).map(|_| BlsScalar::random(&mut OsRng)).collect();
+                let state: Vec<Variable> =
+                    values.iter().map(|v| composer.add_input(*v)).collect();
+
+                let state = composer.poseidon_permutation(&params, &state);
+
+                params.permute(&mut values);
+                state.iter().zip(values.iter()).for_each(|(s, v)| {
+                    composer.constrain_to_constant(*s, *v, None);
+                });
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_poseidon_hash() {
+        let res = gadget_tester(
+            |composer| {
+                let params = PoseidonParams::default();
+                let left = BlsScalar::random(&mut OsRng);
+                let right = BlsScalar::random(&mut OsRng);
+                let expected = params.hash(left, right);
+
+                let left = composer.add_input(left);
+                let right = composer.add_input(right);
+                let hash = composer.poseidon_hash(&params, left, right);
+                composer.constrain_to_constant(hash, expected, None);
+            },
+            1024,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the inputs are swapped
+        let res = gadget_tester(
+            |composer| {
+                let params = PoseidonParams::default();
+                let left = BlsScalar::from(1u64);
+                let right = BlsScalar::from(2u64);
+                let expected = params.hash(right, left);
+
+                let left = composer.add_input(left);
+                let right = composer.add_input(right);
+                let hash = composer.poseidon_hash(&params, left, right);
+                composer.constrain_to_constant(hash, expected, None);
+            },
+            1024,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_poseidon_sponge() {
+        let res = gadget_tester(
+            |composer| {
+                let params = PoseidonParams::default();
+                let inputs: Vec<BlsScalar> =
+                    (0..5).map(|_| BlsScalar::random(&mut OsRng)).collect();
+
+                for len in 0..=inputs.len() {
+                    let expected = params.sponge(&inputs[..len]);
+                    let vars: Vec<Variable> = inputs[..len]
+                        .iter()
+                        .map(|x| composer.add_input(*x))
+                        .collect();
+
+                    let hash = composer.poseidon_sponge(&params, &vars);
+                    composer.constrain_to_constant(hash, expected, None);
+                }
+            },
+            8192,
+        );
+        assert!(res.is_ok());
+
+        // Inputs differing only by trailing zeros have different hashes
+        let params = PoseidonParams::default();
+        let x = BlsScalar::random(&mut OsRng);
+        assert_ne!(params.sponge(&[x]), params.sponge(&[x, BlsScalar::zero()]));
+    }
+}
@@ -0,0 +1,403 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::{StandardComposer, Variable};
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+
+/// Initial hash value of SHA-256, as defined in FIPS 180-4.
+const H0: [u32; 8] = [
+    0x6a09_e667,
+    0xbb67_ae85,
+    0x3c6e_f372,
+    0xa54f_f53a,
+    0x510e_527f,
+    0x9b05_688c,
+    0x1f83_d9ab,
+    0x5be0_cd19,
+];
+
+/// Round constants of SHA-256, as defined in FIPS 180-4.
+const K: [u32; 64] = [
+    0x428a_2f98,
+    0x7137_4491,
+    0xb5c0_fbcf,
+    0xe9b5_dba5,
+    0x3956_c25b,
+    0x59f1_11f1,
+    0x923f_82a4,
+    0xab1c_5ed5,
+    0xd807_aa98,
+    0x1283_5b01,
+    0x2431_85be,
+    0x550c_7dc3,
+    0x72be_5d74,
+    0x80de_b1fe,
+    0x9bdc_06a7,
+    0xc19b_f174,
+    0xe49b_69c1,
+    0xefbe_4786,
+    0x0fc1_9dc6,
+    0x240c_a1cc,
+    0x2de9_2c6f,
+    0x4a74_84aa,
+    0x5cb0_a9dc,
+    0x76f9_88da,
+    0x983e_5152,
+    0xa831_c66d,
+    0xb003_27c8,
+    0xbf59_7fc7,
+    0xc6e0_0bf3,
+    0xd5a7_9147,
+    0x06ca_6351,
+    0x1429_2967,
+    0x27b7_0a85,
+    0x2e1b_2138,
+    0x4d2c_6dfc,
+    0x5338_0d13,
+    0x650a_7354,
+    0x766a_0abb,
+    0x81c2_c92e,
+    0x9272_2c85,
+    0xa2bf_e8a1,
+    0xa81a_664b,
+    0xc24b_8b70,
+    0xc76c_51a3,
+    0xd192_e819,
+    0xd699_0624,
+    0xf40e_3585,
+    0x106a_a070,
+    0x19a4_c116,
+    0x1e37_6c08,
+    0x2748_774c,
+    0x34b0_bcb5,
+    0x391c_0cb3,
+    0x4ed8_aa4a,
+    0x5b9c_ca4f,
+    0x682e_6ff3,
+    0x748f_82ee,
+    0x78a5_636f,
+    0x84c8_7814,
+    0x8cc7_0208,
+    0x90be_fffa,
+    0xa450_6ceb,
+    0xbef9_a3f7,
+    0xc671_78f2,
+];
+
+impl StandardComposer {
+    /// Returns the 32 bytes of the SHA-256 digest of `message_bytes`.
+    ///
+    /// Every [`Variable`] holds a byte. The bytes of the message are not
+    /// range constrained, this is the responsibility of the caller, while
+    /// the bytes of the digest are. The length of the message is part of the
+    /// circuit description, and so is the padding.
+    ///
+    /// Each block of 64 bytes of the padded message adds 42,480 gates to the
+    /// circuit description, with around 130 more gates to set up the initial
+    /// hash value and the padding and to decompose the digest into bytes.
+    pub fn sha256(&mut self, message_bytes: &[Variable]) -> [Variable; 32] {
+        let len = message_bytes.len() as u64;
+
+        // The message is followed by the byte `0x80`, zeros up to 56 bytes
+        // modulo 64 and the length of the message in bits as a big-endian
+        // 64-bit integer
+        let mut padding = [0u8; 72];
+        padding[0] = 0x80;
+        let zeros = (55 + 64 - message_bytes.len() % 64) % 64;
+        padding[1 + zeros..9 + zeros].copy_from_slice(&(8 * len).to_be_bytes());
+
+        let padding: Vec<Variable> = padding[..9 + zeros]
+            .iter()
+            .map(|byte| match byte {
+                0 => self.zero_var,
+                _ => self.add_witness_to_circuit_description(BlsScalar::from(
+                    *byte as u64,
+                )),
+            })
+            .collect();
+        let padded: Vec<Variable> = message_bytes
+            .iter()
+            .chain(padding.iter())
+            .copied()
+            .collect();
+
+        let mut state = [self.zero_var; 8];
+        state.iter_mut().zip(H0.iter()).for_each(|(word, h)| {
+            *word = self
+                .add_witness_to_circuit_description(BlsScalar::from(*h as u64))
+        });
+
+        for chunk in padded.chunks(64) {
+            let mut block = [self.zero_var; 16];
+            block
+                .iter_mut()
+                .zip(chunk.chunks(4))
+                .for_each(|(word, bytes)| *word = self.sha256_pack_word(bytes));
+
+            state = self.sha256_compression(&state, &block);
+        }
+
+        let mut digest = [self.zero_var; 32];
+        digest
+            .chunks_mut(4)
+            .zip(state.iter())
+            .for_each(|(bytes, word)| {
+                bytes.copy_from_slice(&self.sha256_unpack_word(*word))
+            });
+
+        digest
+    }
+
+    /// Applies the SHA-256 compression function to the `state` and the
+    /// message `block`, returning the new state.
+    ///
+    /// Every [`Variable`] holds a 32-bit word. The values of `state` and
+    /// `block` are expected to be 32-bit integers: constraining them is the
+    /// responsibility of the caller.
+    ///
+    /// This function adds 42,448 gates to the circuit description: 13,584
+    /// for the message schedule and 450 for each one of the 64 rounds.
+    pub fn sha256_compression(
+        &mut self,
+        state: &[Variable; 8],
+        block: &[Variable; 16],
+    ) -> [Variable; 8] {
+        let mut w = [self.zero_var; 64];
+        w[..16].copy_from_slice(block);
+        for t in 16..64 {
+            let s0 = self.sha256_sigma(w[t - 15], [7, 18, 3], true);
+            let s1 = self.sha256_sigma(w[t - 2], [17, 19, 10], true);
+            let sum = self.mod_2_32_add3(s1, w[t - 7], s0);
+            w[t] = self.mod_2_32_add(sum, w[t - 16]);
+        }
+
+        let mut v = *state;
+        for (w, k) in w.iter().zip(K.iter()) {
+            let [a, b, c, d, e, f, g, h] = v;
+
+            // ch = (e & f) ^ (!e & g) = g ^ (e & (f ^ g))
+            let s1 = self.sha256_sigma(e, [6, 11, 25], false);
+            let ch = self.xor_32(f, g);
+            let ch = self.and_32(e, ch);
+            let ch = self.xor_32(g, ch);
+
+            let k = self
+                .add_witness_to_circuit_description(BlsScalar::from(*k as u64));
+            let t1 = self.mod_2_32_add3(h, s1, ch);
+            let t1 = self.mod_2_32_add3(t1, *w, k);
+
+            // maj = (a & b) ^ (a & c) ^ (b & c) = (a & (b ^ c)) ^ (b & c)
+            let s0 = self.sha256_sigma(a, [2, 13, 22], false);
+            let maj = self.xor_32(b, c);
+            let maj = self.and_32(a, maj);
+            let b_and_c = self.and_32(b, c);
+            let maj = self.xor_32(maj, b_and_c);
+            let t2 = self.mod_2_32_add(s0, maj);
+
+            let new_e = self.mod_2_32_add(d, t1);
+            let new_a = self.mod_2_32_add(t1, t2);
+            v = [new_a, a, b, c, new_e, e, f, g];
+        }
+
+        let mut output = [self.zero_var; 8];
+        output.iter_mut().enumerate().for_each(|(i, out)| {
+            *out = self.mod_2_32_add(state[i], v[i]);
+        });
+
+        output
+    }
+
+    /// Returns a [`Variable`] holding the XOR of the value of `x` rotated to
+    /// the right by each one of the `amounts`, where the last one is a
+    /// logical shift instead if `shift_last` is `true`.
+    ///
+    /// The bits of `x` are decomposed once and composed back for each one of
+    /// the amounts, so this function adds at most 135 gates to the circuit
+    /// description.
+    fn sha256_sigma(
+        &mut self,
+        x: Variable,
+        amounts: [usize; 3],
+        shift_last: bool,
+    ) -> Variable {
+        let bits = self.split_variable(x, 32);
+
+        let mut words = [self.zero_var; 3];
+        words.iter_mut().zip(amounts.iter()).enumerate().for_each(
+            |(i, (word, amount))| {
+                // The bit `j` of the result is the bit `j + amount` of `x`
+                let rotated: Vec<Variable> = match shift_last && i == 2 {
+                    true => bits[*amount..].to_vec(),
+                    false => (0..32).map(|j| bits[(j + amount) % 32]).collect(),
+                };
+                *word = self.compose_bits(&rotated);
+            },
+        );
+
+        let xor = self.xor_32(words[0], words[1]);
+        self.xor_32(xor, words[2])
+    }
+
+    /// Returns a [`Variable`] holding the 32-bit word represented by the
+    /// big-endian `bytes`, adding two gates to the circuit description.
+    fn sha256_pack_word(&mut self, bytes: &[Variable]) -> Variable {
+        let high = self.big_add(
+            (BlsScalar::pow_of_2(24), bytes[0]),
+            (BlsScalar::pow_of_2(16), bytes[1]),
+            Some((BlsScalar::pow_of_2(8), bytes[2])),
+            BlsScalar::zero(),
+            None,
+        );
+        self.add(
+            (BlsScalar::one(), high),
+            (BlsScalar::one(), bytes[3]),
+            BlsScalar::zero(),
+            None,
+        )
+    }
+
+    /// Returns the range constrained big-endian bytes of the 32-bit `word`,
+    /// adding 15 gates to the circuit description.
+    fn sha256_unpack_word(&mut self, word: Variable) -> [Variable; 4] {
+        let value = self.variables[&word].to_bytes();
+
+        let mut bytes = [self.zero_var; 4];
+        bytes.iter_mut().enumerate().for_each(|(i, byte)| {
+            *byte = self.add_input(BlsScalar::from(value[3 - i] as u64));
+            self.range_gate(*byte, 8);
+        });
+
+        let packed = self.sha256_pack_word(&bytes);
+        self.assert_equal(packed, word);
+
+        bytes
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint_system::helper::*;
+
+    const EMPTY_DIGEST: [u8; 32] = [
+        0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4, 0xc8,
+        0x99, 0x6f, 0xb9, 0x24, 0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c,
+        0xa4, 0x95, 0x99, 0x1b, 0x78, 0x52, 0xb8, 0x55,
+    ];
+
+    const ABC_DIGEST: [u8; 32] = [
+        0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde,
+        0x5d, 0xae, 0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c,
+        0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00, 0x15, 0xad,
+    ];
+
+    const MESSAGE_448: &[u8] =
+        b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+
+    const MESSAGE_448_DIGEST: [u8; 32] = [
+        0x24, 0x8d, 0x6a, 0x61, 0xd2, 0x06, 0x38, 0xb8, 0xe5, 0xc0, 0x26, 0x93,
+        0x0c, 0x3e, 0x60, 0x39, 0xa3, 0x3c, 0xe4, 0x59, 0x64, 0xff, 0x21, 0x67,
+        0xf6, 0xec, 0xed, 0xd4, 0x19, 0xdb, 0x06, 0xc1,
+    ];
+
+    /// Digest of the 64 bytes `0, 1, ..., 63`.
+    const FULL_BLOCK_DIGEST: [u8; 32] = [
+        0xfd, 0xea, 0xb9, 0xac, 0xf3, 0x71, 0x03, 0x62, 0xbd, 0x26, 0x58, 0xcd,
+        0xc9, 0xa2, 0x9e, 0x8f, 0x9c, 0x75, 0x7f, 0xcf, 0x98, 0x11, 0x60, 0x3a,
+        0x8c, 0x44, 0x7c, 0xd1, 0xd9, 0x15, 0x11, 0x08,
+    ];
+
+    /// Hashes `message` and constrains the digest to be `expected`.
+    fn hash_and_check(
+        composer: &mut StandardComposer,
+        message: &[u8],
+        expected: &[u8; 32],
+    ) -> [Variable; 32] {
+        let bytes: Vec<Variable> = message
+            .iter()
+            .map(|byte| composer.add_input(BlsScalar::from(*byte as u64)))
+            .collect();
+        let digest = composer.sha256(&bytes);
+
+        for (byte, expected) in digest.iter().zip(expected.iter()) {
+            composer.constrain_to_constant(
+                *byte,
+                BlsScalar::from(*expected as u64),
+                None,
+            );
+        }
+
+        digest
+    }
+
+    #[test]
+    fn test_sha256_digest() {
+        let full_block: Vec<u8> = (0..64).collect();
+        let vectors: [(&[u8], &[u8; 32]); 4] = [
+            (b"", &EMPTY_DIGEST),
+            (b"abc", &ABC_DIGEST),
+            (MESSAGE_448, &MESSAGE_448_DIGEST),
+            (&full_block, &FULL_BLOCK_DIGEST),
+        ];
+
+        for (message, expected) in vectors.iter() {
+            let mut composer = StandardComposer::new();
+            let digest = hash_and_check(&mut composer, message, expected);
+
+            for (byte, expected) in digest.iter().zip(expected.iter()) {
+                assert_eq!(
+                    composer.variables[byte],
+                    BlsScalar::from(*expected as u64)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_sha256_compression_gate_count() {
+        let mut composer = StandardComposer::new();
+        let mut state = [composer.zero_var; 8];
+        state.iter_mut().zip(H0.iter()).for_each(|(word, h)| {
+            *word = composer.add_input(BlsScalar::from(*h as u64))
+        });
+        let block = [composer.zero_var; 16];
+
+        let size = composer.circuit_size();
+        composer.sha256_compression(&state, &block);
+        assert_eq!(composer.circuit_size() - size, 42_448);
+    }
+
+    #[test]
+    fn test_sha256_proof() {
+        let res = gadget_tester(
+            |composer| {
+                hash_and_check(composer, b"abc", &ABC_DIGEST);
+            },
+            1 << 16,
+        );
+        assert!(res.is_ok());
+
+        let res = gadget_tester(
+            |composer| {
+                hash_and_check(composer, b"", &EMPTY_DIGEST);
+            },
+            1 << 16,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the digest is not the one of the message
+        let res = gadget_tester(
+            |composer| {
+                hash_and_check(composer, b"abd", &ABC_DIGEST);
+            },
+            1 << 16,
+        );
+        assert!(res.is_err());
+    }
+}
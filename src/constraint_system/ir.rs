@@ -0,0 +1,329 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Intermediate representation of a circuit description.
+//!
+//! The [`CircuitIR`] is a faithful dump of the PLONK arithmetization held by a
+//! [`StandardComposer`]: for every gate it stores the value of each selector
+//! and the index of the [`Variable`](super::Variable) sitting on each one of
+//! the four wires. Together with the public input positions and the
+//! equalities added with
+//! [`StandardComposer::assert_equal_wire`], this is all of the information
+//! needed by external tools to reconstruct the constraints.
+//!
+//! It's not an R1CS conversion of the circuit, the gates are exported exactly
+//! as they're defined by the composer.
+
+use crate::constraint_system::StandardComposer;
+use crate::error::Error;
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::{DeserializableSlice, Serializable, Write};
+
+/// Selector values and wiring of a single gate of a [`CircuitIR`].
+///
+/// The wires hold the index of the [`Variable`](super::Variable) they're
+/// assigned to, so two wires holding the same index are copy-constrained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GateIR {
+    /// Multiplier selector
+    pub q_m: BlsScalar,
+    /// Left wire selector
+    pub q_l: BlsScalar,
+    /// Right wire selector
+    pub q_r: BlsScalar,
+    /// Output wire selector
+    pub q_o: BlsScalar,
+    /// Fourth wire selector
+    pub q_4: BlsScalar,
+    /// Constant wire selector
+    pub q_c: BlsScalar,
+    /// Arithmetic wire selector
+    pub q_arith: BlsScalar,
+    /// Range selector
+    pub q_range: BlsScalar,
+    /// Logic selector
+    pub q_logic: BlsScalar,
+    /// Fixed base group addition selector
+    pub q_fixed_group_add: BlsScalar,
+    /// Variable base group addition selector
+    pub q_variable_group_add: BlsScalar,
+    /// Index of the variable assigned to the left wire
+    pub w_l: u64,
+    /// Index of the variable assigned to the right wire
+    pub w_r: u64,
+    /// Index of the variable assigned to the output wire
+    pub w_o: u64,
+    /// Index of the variable assigned to the fourth wire
+    pub w_4: u64,
+}
+
+impl Serializable<{ 11 * BlsScalar::SIZE + 4 * u64::SIZE }> for GateIR {
+    type Error = dusk_bytes::Error;
+
+    #[allow(unused_must_use)]
+    fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut buf = [0u8; Self::SIZE];
+        let mut writer = &mut buf[..];
+
+        writer.write(&self.q_m.to_bytes());
+        writer.write(&self.q_l.to_bytes());
+        writer.write(&self.q_r.to_bytes());
+        writer.write(&self.q_o.to_bytes());
+        writer.write(&self.q_4.to_bytes());
+        writer.write(&self.q_c.to_bytes());
+        writer.write(&self.q_arith.to_bytes());
+        writer.write(&self.q_range.to_bytes());
+        writer.write(&self.q_logic.to_bytes());
+        writer.write(&self.q_fixed_group_add.to_bytes());
+        writer.write(&self.q_variable_group_add.to_bytes());
+        writer.write(&self.w_l.to_bytes());
+        writer.write(&self.w_r.to_bytes());
+        writer.write(&self.w_o.to_bytes());
+        writer.write(&self.w_4.to_bytes());
+
+        buf
+    }
+
+    fn from_bytes(buf: &[u8; Self::SIZE]) -> Result<GateIR, Self::Error> {
+        let mut buffer = &buf[..];
+
+        Ok(GateIR {
+            q_m: BlsScalar::from_reader(&mut buffer)?,
+            q_l: BlsScalar::from_reader(&mut buffer)?,
+            q_r: BlsScalar::from_reader(&mut buffer)?,
+            q_o: BlsScalar::from_reader(&mut buffer)?,
+            q_4: BlsScalar::from_reader(&mut buffer)?,
+            q_c: BlsScalar::from_reader(&mut buffer)?,
+            q_arith: BlsScalar::from_reader(&mut buffer)?,
+            q_range: BlsScalar::from_reader(&mut buffer)?,
+            q_logic: BlsScalar::from_reader(&mut buffer)?,
+            q_fixed_group_add: BlsScalar::from_reader(&mut buffer)?,
+            q_variable_group_add: BlsScalar::from_reader(&mut buffer)?,
+            w_l: u64::from_reader(&mut buffer)?,
+            w_r: u64::from_reader(&mut buffer)?,
+            w_o: u64::from_reader(&mut buffer)?,
+            w_4: u64::from_reader(&mut buffer)?,
+        })
+    }
+}
+
+/// Serializable dump of the gates, wiring, public input positions and
+/// equalities of a circuit built with a [`StandardComposer`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CircuitIR {
+    /// Gates of the circuit in the order they were added to the composer.
+    pub gates: Vec<GateIR>,
+    /// Indexes of the gates that hold a Public Input.
+    pub pi_positions: Vec<u64>,
+    /// Pairs of indexes of the variables copy-constrained without a gate,
+    /// in the order they were added to the composer.
+    pub equalities: Vec<(u64, u64)>,
+}
+
+impl CircuitIR {
+    /// Serializes the `CircuitIR` into a vector of bytes.
+    ///
+    /// The layout is the number of gates followed by each one of the gates,
+    /// the number of public input positions followed by each position, and
+    /// the number of equalities followed by both indexes of each one.
+    #[allow(unused_must_use)]
+    pub fn to_var_bytes(&self) -> Vec<u8> {
+        let mut buff = vec![
+            0u8;
+            3 * u64::SIZE
+                + self.gates.len() * GateIR::SIZE
+                + self.pi_positions.len() * u64::SIZE
+                + self.equalities.len() * 2 * u64::SIZE
+        ];
+        let mut writer = &mut buff[..];
+
+        writer.write(&(self.gates.len() as u64).to_bytes());
+        self.gates.iter().for_each(|gate| {
+            let _ = writer.write(&gate.to_bytes());
+        });
+        writer.write(&(self.pi_positions.len() as u64).to_bytes());
+        self.pi_positions.iter().for_each(|pos| {
+            let _ = writer.write(&pos.to_bytes());
+        });
+        writer.write(&(self.equalities.len() as u64).to_bytes());
+        self.equalities.iter().for_each(|(a, b)| {
+            let _ = writer.write(&a.to_bytes());
+            let _ = writer.write(&b.to_bytes());
+        });
+
+        buff
+    }
+
+    /// Deserializes a `CircuitIR` from a slice of bytes.
+    ///
+    /// The capacity reserved for the gates, the positions and the equalities
+    /// is bounded by the number of entries the remaining bytes can hold, so a
+    /// forged count can't trigger a huge allocation.
+    pub fn from_slice(mut buf: &[u8]) -> Result<CircuitIR, Error> {
+        let gates_num = u64::from_reader(&mut buf)? as usize;
+        let mut gates =
+            Vec::with_capacity(gates_num.min(buf.len() / GateIR::SIZE));
+        for _ in 0..gates_num {
+            gates.push(GateIR::from_reader(&mut buf)?);
+        }
+
+        let pos_num = u64::from_reader(&mut buf)? as usize;
+        let mut pi_positions =
+            Vec::with_capacity(pos_num.min(buf.len() / u64::SIZE));
+        for _ in 0..pos_num {
+            pi_positions.push(u64::from_reader(&mut buf)?);
+        }
+
+        let eq_num = u64::from_reader(&mut buf)? as usize;
+        let mut equalities =
+            Vec::with_capacity(eq_num.min(buf.len() / (2 * u64::SIZE)));
+        for _ in 0..eq_num {
+            let a = u64::from_reader(&mut buf)?;
+            let b = u64::from_reader(&mut buf)?;
+            equalities.push((a, b));
+        }
+
+        Ok(CircuitIR {
+            gates,
+            pi_positions,
+            equalities,
+        })
+    }
+}
+
+impl StandardComposer {
+    /// Exports the circuit description held by the composer into a
+    /// [`CircuitIR`].
+    ///
+    /// Only the circuit description is exported, the witness values assigned
+    /// to the variables are not part of the IR.
+    pub fn export_ir(&self) -> CircuitIR {
+        let gates = (0..self.n)
+            .map(|i| GateIR {
+                q_m: self.q_m[i],
+                q_l: self.q_l[i],
+                q_r: self.q_r[i],
+                q_o: self.q_o[i],
+                q_4: self.q_4[i],
+                q_c: self.q_c[i],
+                q_arith: self.q_arith[i],
+                q_range: self.q_range[i],
+                q_logic: self.q_logic[i],
+                q_fixed_group_add: self.q_fixed_group_add[i],
+                q_variable_group_add: self.q_variable_group_add[i],
+                w_l: self.w_l[i].0 as u64,
+                w_r: self.w_r[i].0 as u64,
+                w_o: self.w_o[i].0 as u64,
+                w_4: self.w_4[i].0 as u64,
+            })
+            .collect();
+
+        let pi_positions = self
+            .pi_positions()
+            .into_iter()
+            .map(|pos| pos as u64)
+            .collect();
+
+        let equalities = self
+            .perm
+            .equalities
+            .iter()
+            .map(|(a, b)| (a.0 as u64, b.0 as u64))
+            .collect();
+
+        CircuitIR {
+            gates,
+            pi_positions,
+            equalities,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_ir() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(3u64));
+        let b = composer.add_input(BlsScalar::from(4u64));
+        let c = composer.add(
+            (BlsScalar::one(), a),
+            (BlsScalar::one(), b),
+            BlsScalar::zero(),
+            Some(-BlsScalar::from(7u64)),
+        );
+        composer.range_gate(c, 4);
+
+        let ir = composer.export_ir();
+        assert_eq!(ir.gates.len(), composer.circuit_size());
+        assert_eq!(ir.pi_positions, vec![3]);
+
+        let gate = ir.gates[3];
+        assert_eq!(gate.q_arith, BlsScalar::one());
+        assert_eq!(gate.w_l, a.0 as u64);
+        assert_eq!(gate.w_r, b.0 as u64);
+        assert_eq!(gate.w_o, c.0 as u64);
+        assert_eq!(gate.w_4, composer.zero_var().0 as u64);
+    }
+
+    #[test]
+    fn test_circuit_ir_serde() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(25u64));
+        let b = composer.add_input(BlsScalar::from(5u64));
+        composer.mul(BlsScalar::one(), b, b, BlsScalar::zero(), None);
+        composer.constrain_to_constant(a, BlsScalar::from(25u64), None);
+        composer.xor_gate(a, b, 8);
+
+        let ir = composer.export_ir();
+        let bytes = ir.to_var_bytes();
+        let obtained_ir =
+            CircuitIR::from_slice(&bytes).expect("Deserialization error");
+        assert_eq!(ir, obtained_ir);
+    }
+
+    #[test]
+    fn test_circuit_ir_equalities() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(5u64));
+        let b = composer.add_input(BlsScalar::from(5u64));
+        let c = composer.add_input(BlsScalar::from(5u64));
+        composer.mul(BlsScalar::one(), a, b, BlsScalar::zero(), None);
+        composer.range_gate(c, 4);
+        composer.assert_equal_wire(a, b);
+        composer.assert_equal_wire(b, c);
+
+        let ir = composer.export_ir();
+        assert_eq!(
+            ir.equalities,
+            vec![(a.0 as u64, b.0 as u64), (b.0 as u64, c.0 as u64)]
+        );
+
+        let obtained_ir = CircuitIR::from_slice(&ir.to_var_bytes())
+            .expect("Deserialization error");
+        assert_eq!(ir, obtained_ir);
+    }
+
+    #[test]
+    fn test_circuit_ir_forged_count() {
+        // Should fail without reserving room for the forged counts
+        let mut bytes = u64::MAX.to_bytes().to_vec();
+        bytes.extend_from_slice(&[0u8; 16]);
+        assert!(CircuitIR::from_slice(&bytes).is_err());
+
+        let mut bytes = 0u64.to_bytes().to_vec();
+        bytes.extend_from_slice(&u64::MAX.to_bytes());
+        assert!(CircuitIR::from_slice(&bytes).is_err());
+
+        let mut bytes = [0u8; 16].to_vec();
+        bytes.extend_from_slice(&u64::MAX.to_bytes());
+        assert!(CircuitIR::from_slice(&bytes).is_err());
+    }
+}
@@ -13,20 +13,47 @@ pub(crate) mod variable;
 
 /// Simple Arithmetic gates
 mod arithmetic;
+mod bits;
 /// Boolean gate
 mod boolean;
+mod bytes;
+mod checkpoint;
 /// Elliptic Curve Crypto gates
 pub mod ecc;
 
+/// Hash function gates
+pub mod hashing;
 #[cfg(feature = "std")]
 #[cfg(test)]
 pub(crate) mod helper;
+/// Circuit intermediate representation
+pub mod ir;
 /// XOR and AND gates
 pub mod logic;
+mod modular;
+/// Constant folding of the circuit description
+mod optimize;
+mod pow;
+/// Human-readable listing of the circuit gates
+#[cfg(feature = "std")]
+pub mod printer;
 /// Range gate
 pub mod range;
+mod select;
+mod set;
+mod uint32;
+/// Witness values computed outside of the constraints
+pub mod witness;
 
+pub use arithmetic::ArithmeticGate;
+pub use checkpoint::ComposerCheckpoint;
 pub use composer::StandardComposer;
 pub use ecc::Point;
+pub use hashing::mimc::MimcParams;
+pub use hashing::poseidon::PoseidonParams;
+pub use hashing::HashGadget;
+#[cfg(feature = "std")]
+pub use printer::{CircuitPrinter, GateType};
 pub use variable::Variable;
 pub(crate) use variable::WireData;
+pub use witness::{NativeWitness, WitnessProvider};
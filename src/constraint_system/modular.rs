@@ -0,0 +1,461 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::witness::{QUOTIENT, REMAINDER};
+use crate::constraint_system::{StandardComposer, Variable};
+use crate::error::Error;
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+
+/// Maximum number of bits of `quotient * modulus`, which keeps the
+/// reduction equation from wrapping around the field modulus.
+const MAX_REDUCTION_BITS: usize = 252;
+
+/// Returns the canonical little-endian limbs of a [`BlsScalar`].
+pub(crate) fn to_limbs(scalar: &BlsScalar) -> [u64; 4] {
+    let bytes = scalar.to_bytes();
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(chunk);
+        *limb = u64::from_le_bytes(buf);
+    }
+    limbs
+}
+
+/// Returns the number of bits needed to represent the `limbs`.
+fn bit_length(limbs: &[u64; 4]) -> usize {
+    limbs
+        .iter()
+        .rposition(|limb| *limb != 0)
+        .map(|i| 64 * i + 64 - limbs[i].leading_zeros() as usize)
+        .unwrap_or(0)
+}
+
+/// Computes the quotient and remainder of the integer division of the
+/// `dividend` by the non-zero `divisor`, by binary long division.
+pub(crate) fn div_rem_limbs(
+    dividend: &[u64; 4],
+    divisor: &[u64; 4],
+) -> ([u64; 4], [u64; 4]) {
+    let mut quotient = [0u64; 4];
+    let mut remainder = [0u64; 4];
+
+    for i in (0..bit_length(dividend)).rev() {
+        // remainder = 2 * remainder + bit
+        for j in (1..4).rev() {
+            remainder[j] = (remainder[j] << 1) | (remainder[j - 1] >> 63);
+        }
+        remainder[0] =
+            (remainder[0] << 1) | ((dividend[i / 64] >> (i % 64)) & 1);
+
+        let ge = remainder
+            .iter()
+            .rev()
+            .zip(divisor.iter().rev())
+            .find(|(r, d)| r != d)
+            .map(|(r, d)| r > d)
+            .unwrap_or(true);
+        if ge {
+            let mut borrow = false;
+            for (r, d) in remainder.iter_mut().zip(divisor.iter()) {
+                let (diff, b1) = r.overflowing_sub(*d);
+                let (diff, b2) = diff.overflowing_sub(borrow as u64);
+                *r = diff;
+                borrow = b1 || b2;
+            }
+            quotient[i / 64] |= 1 << (i % 64);
+        }
+    }
+
+    (quotient, remainder)
+}
+
+impl StandardComposer {
+    /// Constrains the value of `var` to fit in exactly `bits` bits.
+    ///
+    /// The range gate only takes an even number of bits, so for an odd
+    /// `bits` both `var` and `2 * var` are range constrained to `bits + 1`
+    /// bits. The former keeps the doubling from wrapping around the field
+    /// modulus, which would let the values above `p / 2` pass the latter.
+    fn range_gate_exact(&mut self, var: Variable, bits: usize) {
+        match bits {
+            0 => self.constrain_to_constant(var, BlsScalar::zero(), None),
+            bits if bits % 2 == 0 => self.range_gate(var, bits),
+            bits => {
+                let doubled = self.add(
+                    (BlsScalar::from(2u64), var),
+                    (BlsScalar::zero(), self.zero_var),
+                    BlsScalar::zero(),
+                    None,
+                );
+                self.range_gate(var, bits + 1);
+                self.range_gate(doubled, bits + 1);
+            }
+        }
+    }
+
+    /// Returns a [`Variable`] holding `a mod modulus` for a public
+    /// `modulus`, where the value of `a` is expected to fit in `bits` bits.
+    ///
+    /// The quotient and the remainder are added to the witness and
+    /// constrained to satisfy `a = quotient * modulus + remainder`, with the
+    /// quotient range constrained to the bits left by the modulus and the
+    /// remainder to be less than the modulus. When the modulus is a power of
+    /// two, the latter only requires a single range constraint.
+    ///
+    /// # Panics
+    /// This function will panic if `modulus` is zero, or if the reduction
+    /// could wrap around the field modulus, which happens when `bits` is
+    /// larger than 250 or `modulus` is larger than `2^252`.
+    pub fn mod_reduce(
+        &mut self,
+        a: Variable,
+        modulus: BlsScalar,
+        bits: usize,
+    ) -> Variable {
+        let modulus_limbs = to_limbs(&modulus);
+        let modulus_bits = bit_length(&modulus_limbs);
+        assert!(modulus_bits > 0, "the modulus can't be zero");
+
+        // Since the modulus is at least 2^(modulus_bits - 1), the quotient
+        // fits in the remaining bits of `a`
+        let quotient_bits = bits.saturating_sub(modulus_bits - 1);
+        assert!(quotient_bits + modulus_bits <= MAX_REDUCTION_BITS);
+
+        let inputs = [self.variables[&a], modulus];
+        let quotient = self
+            .hint_values(QUOTIENT, &inputs)
+            .expect("The modulus is not zero");
+        let remainder = self
+            .hint_values(REMAINDER, &inputs)
+            .expect("The modulus is not zero");
+
+        self.range_gate_exact(quotient, quotient_bits);
+
+        // a - quotient * modulus - remainder = 0
+        self.big_add_gate(
+            quotient,
+            remainder,
+            a,
+            None,
+            modulus,
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::zero(),
+            BlsScalar::zero(),
+            None,
+        );
+
+        let is_power_of_two =
+            modulus_limbs.iter().map(|l| l.count_ones()).sum::<u32>() == 1;
+
+        if is_power_of_two {
+            // remainder < 2^k
+            self.range_gate_exact(remainder, modulus_bits - 1);
+        } else {
+            // Both remainder and modulus - 1 - remainder are non-negative
+            // and fit in the bits of the modulus
+            let range_bits = modulus_bits + modulus_bits % 2;
+            let complement = self.add(
+                (-BlsScalar::one(), remainder),
+                (BlsScalar::zero(), self.zero_var),
+                modulus - BlsScalar::one(),
+                None,
+            );
+            self.range_gate(remainder, range_bits);
+            self.range_gate(complement, range_bits);
+        }
+
+        remainder
+    }
+
+    /// Returns the [`Variable`]s holding the quotient and the remainder of
+    /// the integer division of `a` by `b`, whose values are expected to fit
+    /// in 64 bits.
+    ///
+    /// The quotient `q` and the remainder `r` are constrained to satisfy
+    /// `a = q * b + r`, with `q` fitting in exactly `bits` bits and `r < b`,
+    /// the latter being enforced by range constraining both `r` and
+    /// `b - r - 1` to 64 bits. The circuit can therefore only be satisfied if
+    /// `b` is not zero.
+    ///
+    /// Neither `a` nor `b` are range constrained: `b` is only bounded
+    /// indirectly, to less than `2^65`, as the sum of `r + 1` and
+    /// `b - r - 1`. This keeps `q * b + r` from wrapping around the field
+    /// modulus, so the division is the one of the integers, but a caller
+    /// relying on `b` fitting in 64 bits has to range constrain it.
+    ///
+    /// Returns [`Error::DivisionByZero`] if the value of `b` is zero, since
+    /// the witness can't be computed.
+    ///
+    /// # Panics
+    /// This function will panic if `bits` is larger than 64.
+    pub fn div_rem(
+        &mut self,
+        a: Variable,
+        b: Variable,
+        bits: usize,
+    ) -> Result<(Variable, Variable), Error> {
+        assert!(bits <= 64, "the quotient can't be larger than 64 bits");
+
+        let quotient = self.hint(QUOTIENT, &[a, b])?;
+        let remainder = self.hint(REMAINDER, &[a, b])?;
+
+        // q * b + r - a = 0
+        self.big_mul_gate(
+            quotient,
+            b,
+            a,
+            Some(remainder),
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::zero(),
+            BlsScalar::one(),
+            None,
+        );
+
+        // b - r - 1 >= 0
+        let complement = self.add(
+            (BlsScalar::one(), b),
+            (-BlsScalar::one(), remainder),
+            -BlsScalar::one(),
+            None,
+        );
+
+        self.range_gate_exact(quotient, bits);
+        self.range_gate(remainder, 64);
+        self.range_gate(complement, 64);
+
+        Ok((quotient, remainder))
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use super::*;
+
+    #[test]
+    fn test_div_rem_limbs() {
+        let (q, r) = div_rem_limbs(&[100, 0, 0, 0], &[7, 0, 0, 0]);
+        assert_eq!((q, r), ([14, 0, 0, 0], [2, 0, 0, 0]));
+
+        let (q, r) = div_rem_limbs(&[5, 3, 0, 1], &[0, 1, 0, 0]);
+        assert_eq!((q, r), ([3, 0, 1, 0], [5, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_mod_reduce() {
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(100u64));
+                let r = composer.mod_reduce(a, BlsScalar::from(7u64), 8);
+                composer.constrain_to_constant(r, BlsScalar::from(2u64), None);
+
+                // Powers of two with even and odd exponents
+                let a = composer.add_input(BlsScalar::from(1000u64));
+                let r = composer.mod_reduce(a, BlsScalar::from(16u64), 10);
+                composer.constrain_to_constant(r, BlsScalar::from(8u64), None);
+                let r = composer.mod_reduce(a, BlsScalar::from(32u64), 10);
+                composer.constrain_to_constant(r, BlsScalar::from(8u64), None);
+
+                // Values spanning several limbs
+                let a = composer.add_input(BlsScalar::from_raw([5, 3, 0, 1]));
+                let r = composer.mod_reduce(
+                    a,
+                    BlsScalar::from_raw([0, 1, 0, 0]),
+                    194,
+                );
+                composer.constrain_to_constant(r, BlsScalar::from(5u64), None);
+            },
+            1024,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the value doesn't fit in the given bits
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(1000u64));
+                composer.mod_reduce(a, BlsScalar::from(7u64), 4);
+            },
+            128,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mod_reduce_zero_modulus() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(100u64));
+        composer.mod_reduce(a, BlsScalar::zero(), 8);
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let res = gadget_tester(
+            |composer| {
+                let values = [
+                    (100u64, 7u64),
+                    (7, 100),
+                    (0, 3),
+                    (u64::MAX, 1),
+                    (u64::MAX, u64::MAX),
+                    (0xdead_beef_cafe_babe, 0x1234_5678),
+                ];
+                for (a, b) in values.iter() {
+                    let a_var = composer.add_input(BlsScalar::from(*a));
+                    let b_var = composer.add_input(BlsScalar::from(*b));
+                    let (q, r) = composer.div_rem(a_var, b_var, 64).unwrap();
+
+                    composer.constrain_to_constant(
+                        q,
+                        BlsScalar::from(a / b),
+                        None,
+                    );
+                    composer.constrain_to_constant(
+                        r,
+                        BlsScalar::from(a % b),
+                        None,
+                    );
+                }
+            },
+            1024,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_div_rem_malicious_witness() {
+        // A quotient too small leaves a remainder larger than the divisor
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(100u64));
+                let b = composer.add_input(BlsScalar::from(7u64));
+                let (q, r) = composer.div_rem(a, b, 8).unwrap();
+                composer.variables.insert(q, BlsScalar::from(13u64));
+                composer.variables.insert(r, BlsScalar::from(9u64));
+            },
+            128,
+        );
+        assert!(res.is_err());
+
+        // A quotient too big makes the remainder negative
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(100u64));
+                let b = composer.add_input(BlsScalar::from(7u64));
+                let (q, r) = composer.div_rem(a, b, 8).unwrap();
+                composer.variables.insert(q, BlsScalar::from(15u64));
+                composer.variables.insert(r, -BlsScalar::from(5u64));
+            },
+            128,
+        );
+        assert!(res.is_err());
+
+        // The quotient doesn't fit in the given bits
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(100u64));
+                let b = composer.add_input(BlsScalar::from(7u64));
+                composer.div_rem(a, b, 2).unwrap();
+            },
+            128,
+        );
+        assert!(res.is_err());
+
+        // The quotient is 2^3, which fits in the 4 bits of the range gate
+        // but not in the 3 bits given
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(16u64));
+                let b = composer.add_input(BlsScalar::from(2u64));
+                composer.div_rem(a, b, 3).unwrap();
+            },
+            128,
+        );
+        assert!(res.is_err());
+
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(15u64));
+                let b = composer.add_input(BlsScalar::from(2u64));
+                let (q, _) = composer.div_rem(a, b, 3).unwrap();
+                composer.constrain_to_constant(q, BlsScalar::from(7u64), None);
+            },
+            128,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_range_gate_exact() {
+        let res = gadget_tester(
+            |composer| {
+                for bits in 0..6 {
+                    let max = (1u64 << bits) - 1;
+                    let var = composer.add_input(BlsScalar::from(max));
+                    composer.range_gate_exact(var, bits);
+                }
+            },
+            128,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the values need one more bit
+        let res = gadget_tester(
+            |composer| {
+                let var = composer.add_input(BlsScalar::one());
+                composer.range_gate_exact(var, 0);
+            },
+            128,
+        );
+        assert!(res.is_err());
+
+        let res = gadget_tester(
+            |composer| {
+                let var = composer.add_input(BlsScalar::from(8u64));
+                composer.range_gate_exact(var, 3);
+            },
+            128,
+        );
+        assert!(res.is_err());
+
+        let res = gadget_tester(
+            |composer| {
+                let var = composer.add_input(BlsScalar::from(16u64));
+                composer.range_gate_exact(var, 4);
+            },
+            128,
+        );
+        assert!(res.is_err());
+
+        // Should fail although the doubled value, 1, fits in the bits
+        let res = gadget_tester(
+            |composer| {
+                let half = BlsScalar::from(2u64).invert().unwrap();
+                let var = composer.add_input(half);
+                composer.range_gate_exact(var, 3);
+            },
+            128,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_div_rem_by_zero() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(100u64));
+        let b = composer.add_input(BlsScalar::zero());
+
+        assert!(matches!(
+            composer.div_rem(a, b, 64),
+            Err(Error::DivisionByZero)
+        ));
+    }
+}
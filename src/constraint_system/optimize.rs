@@ -0,0 +1,434 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::{StandardComposer, Variable};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use hashbrown::hash_map::Entry;
+use hashbrown::HashMap;
+
+/// Result of evaluating an arithmetic gate against the set of [`Variable`]s
+/// whose value is fixed by the circuit description.
+enum GateFolding {
+    /// Every variable of the gate is a constant, the value is the result of
+    /// evaluating the gate equation.
+    Known(BlsScalar),
+    /// The gate fixes the value of the single non-constant variable it holds.
+    Linear(Variable, BlsScalar),
+    /// The gate can't be folded.
+    Unknown,
+}
+
+impl StandardComposer {
+    /// Returns `true` if the `i`th gate has any of the selectors that read the
+    /// wires of the next gate enabled.
+    fn reads_next_gate(&self, i: usize) -> bool {
+        self.q_range[i] != BlsScalar::zero()
+            || self.q_logic[i] != BlsScalar::zero()
+            || self.q_fixed_group_add[i] != BlsScalar::zero()
+            || self.q_variable_group_add[i] != BlsScalar::zero()
+    }
+
+    /// Returns `true` if the `i`th gate is a plain arithmetic gate without a
+    /// public input, whose wires are not read by the previous gate.
+    fn is_foldable(&self, i: usize) -> bool {
+        self.q_arith[i] == BlsScalar::one()
+            && !self.reads_next_gate(i)
+            && (i == 0 || !self.reads_next_gate(i - 1))
+            && !self.public_inputs_sparse_store.contains_key(&i)
+    }
+
+    /// Evaluates the equation of the `i`th gate using the known constants.
+    fn fold_gate(
+        &self,
+        i: usize,
+        constants: &HashMap<Variable, BlsScalar>,
+    ) -> GateFolding {
+        let mut unknown: Option<Variable> = None;
+        let mut coeff = BlsScalar::zero();
+        let mut known = self.q_c[i];
+
+        // Tracks the only non-constant variable allowed in the gate
+        let mut track = |var: Variable| match unknown {
+            Some(unknown) => unknown == var,
+            None => {
+                unknown = Some(var);
+                true
+            }
+        };
+
+        let wires = [
+            (self.w_l[i], self.q_l[i]),
+            (self.w_r[i], self.q_r[i]),
+            (self.w_o[i], self.q_o[i]),
+            (self.w_4[i], self.q_4[i]),
+        ];
+        for (var, q) in wires.iter() {
+            if q == &BlsScalar::zero() {
+                continue;
+            }
+            match constants.get(var) {
+                Some(value) => known += q * value,
+                None if track(*var) => coeff += q,
+                None => return GateFolding::Unknown,
+            }
+        }
+
+        let q_m = self.q_m[i];
+        if q_m != BlsScalar::zero() {
+            let (a, b) = (self.w_l[i], self.w_r[i]);
+            match (constants.get(&a), constants.get(&b)) {
+                (Some(a), Some(b)) => known += q_m * a * b,
+                (Some(a), None) if track(b) => coeff += q_m * a,
+                (None, Some(b)) if track(a) => coeff += q_m * b,
+                _ => return GateFolding::Unknown,
+            }
+        }
+
+        match unknown {
+            None => GateFolding::Known(known),
+            Some(var) if coeff != BlsScalar::zero() => {
+                GateFolding::Linear(var, -known * coeff.invert().unwrap())
+            }
+            Some(_) => GateFolding::Unknown,
+        }
+    }
+
+    /// Performs a constant folding pass over the circuit description.
+    ///
+    /// A [`Variable`] is considered a constant when an arithmetic gate fixes
+    /// its value from the selectors and other constants, as it's done for
+    /// example by [`StandardComposer::constrain_to_constant`] or
+    /// [`StandardComposer::add_witness_to_circuit_description`]. The pass:
+    /// - Replaces all of the constant variables holding the same value with a
+    ///   single variable, which is the first one whose value was fixed.
+    /// - Removes the arithmetic gates whose variables are all constants and
+    ///   that are satisfied by them, since they don't constrain anything.
+    ///
+    /// Gates holding public inputs and gates involved in range, logic or
+    /// elliptic curve constraints are never removed, so the statement that is
+    /// proven remains the same.
+    ///
+    /// # Note
+    /// The optimization changes the circuit description, so it must be
+    /// performed by both the [`Prover`](crate::proof_system::Prover) and the
+    /// [`Verifier`](crate::proof_system::Verifier) before preprocessing.
+    pub fn optimize(&mut self) {
+        let mut constants: HashMap<Variable, BlsScalar> = HashMap::new();
+        // Variable chosen to represent each one of the constant values.
+        let mut representatives: HashMap<[u8; 32], Variable> = HashMap::new();
+        let mut substitutions: HashMap<Variable, Variable> = HashMap::new();
+        // Gates fixing the value of a representative variable.
+        let mut defining = vec![false; self.n];
+
+        // A gate may use a constant that is fixed by a later gate, so iterate
+        // until there is nothing else to fold.
+        let mut folded = true;
+        while folded {
+            folded = false;
+            for i in (0..self.n).filter(|i| self.is_foldable(*i)) {
+                if let GateFolding::Linear(var, value) =
+                    self.fold_gate(i, &constants)
+                {
+                    constants.insert(var, value);
+                    match representatives.entry(value.to_bytes()) {
+                        Entry::Occupied(rep) => {
+                            substitutions.insert(var, *rep.get());
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(var);
+                            defining[i] = true;
+                        }
+                    }
+                    folded = true;
+                }
+            }
+        }
+
+        let keep: Vec<bool> = (0..self.n)
+            .map(|i| {
+                let redundant = self.is_foldable(i)
+                    && !defining[i]
+                    && matches!(
+                        self.fold_gate(i, &constants),
+                        GateFolding::Known(value) if value == BlsScalar::zero()
+                    );
+                !redundant
+            })
+            .collect();
+
+        // Positions of the remaining gates in the optimized circuit
+        let mut positions = Vec::with_capacity(self.n);
+        let mut n = 0;
+        keep.iter().for_each(|keep| {
+            positions.push(n);
+            if *keep {
+                n += 1;
+            }
+        });
+
+        let retain = |values: &mut Vec<BlsScalar>| {
+            let mut i = 0;
+            values.retain(|_| {
+                i += 1;
+                keep[i - 1]
+            });
+        };
+        retain(&mut self.q_m);
+        retain(&mut self.q_l);
+        retain(&mut self.q_r);
+        retain(&mut self.q_o);
+        retain(&mut self.q_4);
+        retain(&mut self.q_c);
+        retain(&mut self.q_arith);
+        retain(&mut self.q_range);
+        retain(&mut self.q_logic);
+        retain(&mut self.q_fixed_group_add);
+        retain(&mut self.q_variable_group_add);
+
+        let substitute = |wires: &[Variable]| -> Vec<Variable> {
+            wires
+                .iter()
+                .zip(keep.iter())
+                .filter(|(_, keep)| **keep)
+                .map(|(var, _)| *substitutions.get(var).unwrap_or(var))
+                .collect()
+        };
+        self.w_l = substitute(&self.w_l);
+        self.w_r = substitute(&self.w_r);
+        self.w_o = substitute(&self.w_o);
+        self.w_4 = substitute(&self.w_4);
+        self.constants
+            .values_mut()
+            .for_each(|var| *var = *substitutions.get(var).unwrap_or(var));
+
+        self.public_inputs_sparse_store = self
+            .public_inputs_sparse_store
+            .iter()
+            .map(|(pos, value)| (positions[*pos], *value))
+            .collect::<BTreeMap<usize, BlsScalar>>();
+
+        // Rebuild the permutation from the remaining wires
+        self.perm.equalities.iter_mut().for_each(|(a, b)| {
+            *a = *substitutions.get(a).unwrap_or(a);
+            *b = *substitutions.get(b).unwrap_or(b);
+        });
+        self.perm
+            .variable_map
+            .values_mut()
+            .for_each(|wire_data| wire_data.clear());
+        for i in 0..n {
+            self.perm.add_variables_to_map(
+                self.w_l[i],
+                self.w_r[i],
+                self.w_o[i],
+                self.w_4[i],
+                i,
+            );
+        }
+
+        self.n = n;
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use super::*;
+    use crate::commitment_scheme::kzg10::PublicParameters;
+    use crate::error::Error;
+    use crate::proof_system::Prover;
+    use rand_core::{CryptoRng, RngCore};
+
+    /// Deterministic xorshift generator, so the public parameters and with
+    /// them the proofs of the tests are the same on every run.
+    struct TestRng(u64);
+
+    impl RngCore for TestRng {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            rand_core::impls::fill_bytes_via_next(self, dest)
+        }
+
+        fn try_fill_bytes(
+            &mut self,
+            dest: &mut [u8],
+        ) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for TestRng {}
+
+    /// Returns the bytes of the proof of the `gadget`, optimizing the
+    /// circuit before preprocessing it if `optimize` is set.
+    fn proof_bytes(
+        gadget: fn(&mut StandardComposer),
+        optimize: bool,
+    ) -> Result<Vec<u8>, Error> {
+        let pp = PublicParameters::setup(1 << 7, &mut TestRng(0x5eed))?;
+
+        let mut prover = Prover::new(b"optimize");
+        gadget(prover.mut_cs());
+        if optimize {
+            prover.mut_cs().optimize();
+        }
+
+        let (ck, _) =
+            pp.trim(2 * prover.cs.circuit_size().next_power_of_two())?;
+        prover.preprocess(&ck)?;
+
+        Ok(prover.prove(&ck)?.to_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_optimize_gate_count() {
+        let mut composer = StandardComposer::new();
+        let one = composer.add_witness_to_circuit_description(BlsScalar::one());
+        let other_one =
+            composer.add_witness_to_circuit_description(BlsScalar::one());
+        let two = composer.add(
+            (BlsScalar::one(), one),
+            (BlsScalar::one(), other_one),
+            BlsScalar::zero(),
+            None,
+        );
+        composer.constrain_to_constant(two, BlsScalar::from(2u64), None);
+
+        let witness = composer.add_input(BlsScalar::from(5u64));
+        composer.range_gate(witness, 4);
+
+        let size = composer.circuit_size();
+        composer.optimize();
+
+        // The second binding of one and the constraint on two are removed
+        assert_eq!(composer.circuit_size(), size - 2);
+    }
+
+    #[test]
+    fn test_optimize_proof() {
+        // Should pass since the statement is satisfied before and after
+        // the optimization
+        let res = gadget_tester(
+            |composer| {
+                let three = composer
+                    .add_witness_to_circuit_description(BlsScalar::from(3u64));
+                let four = composer
+                    .add_witness_to_circuit_description(BlsScalar::from(4u64));
+                let seven = composer.add(
+                    (BlsScalar::one(), three),
+                    (BlsScalar::one(), four),
+                    BlsScalar::zero(),
+                    None,
+                );
+                composer.constrain_to_constant(
+                    seven,
+                    BlsScalar::from(7u64),
+                    None,
+                );
+
+                let a = composer.add_input(BlsScalar::from(12u64));
+                let b = composer.add(
+                    (BlsScalar::one(), a),
+                    (BlsScalar::one(), seven),
+                    BlsScalar::zero(),
+                    Some(-BlsScalar::from(19u64)),
+                );
+                composer.range_gate(b, 8);
+                composer.optimize();
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the constant gate is not satisfied, so it can't
+        // be removed by the optimization
+        let res = gadget_tester(
+            |composer| {
+                let three = composer
+                    .add_witness_to_circuit_description(BlsScalar::from(3u64));
+                let four = composer
+                    .add_witness_to_circuit_description(BlsScalar::from(4u64));
+                let seven = composer.add(
+                    (BlsScalar::one(), three),
+                    (BlsScalar::one(), four),
+                    BlsScalar::zero(),
+                    None,
+                );
+                composer.constrain_to_constant(
+                    seven,
+                    BlsScalar::from(8u64),
+                    None,
+                );
+                composer.optimize();
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_optimize_proof_bytes() -> Result<(), Error> {
+        // The proof is the same since there is nothing to fold
+        let witness_gadget = |composer: &mut StandardComposer| {
+            let a = composer.add_input(BlsScalar::from(12u64));
+            let b = composer.add_input(BlsScalar::from(7u64));
+            let c = composer.add(
+                (BlsScalar::one(), a),
+                (BlsScalar::one(), b),
+                BlsScalar::zero(),
+                Some(-BlsScalar::from(19u64)),
+            );
+            composer.range_gate(c, 8);
+        };
+        assert_eq!(
+            proof_bytes(witness_gadget, false)?,
+            proof_bytes(witness_gadget, true)?
+        );
+
+        // The proof changes with the circuit description, but the one of
+        // the optimized circuit is the same on every run
+        let constant_gadget = |composer: &mut StandardComposer| {
+            let three = composer
+                .add_witness_to_circuit_description(BlsScalar::from(3u64));
+            let other_three = composer
+                .add_witness_to_circuit_description(BlsScalar::from(3u64));
+            let a = composer.add_input(BlsScalar::from(12u64));
+            let b = composer.add(
+                (BlsScalar::one(), a),
+                (BlsScalar::one(), three),
+                BlsScalar::zero(),
+                None,
+            );
+            let c = composer.add(
+                (BlsScalar::one(), b),
+                (BlsScalar::one(), other_three),
+                BlsScalar::zero(),
+                Some(-BlsScalar::from(18u64)),
+            );
+            composer.range_gate(c, 8);
+        };
+        let optimized = proof_bytes(constant_gadget, true)?;
+        assert_ne!(proof_bytes(constant_gadget, false)?, optimized);
+        assert_eq!(proof_bytes(constant_gadget, true)?, optimized);
+
+        Ok(())
+    }
+}
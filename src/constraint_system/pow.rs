@@ -0,0 +1,170 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::{StandardComposer, Variable};
+use dusk_bls12_381::BlsScalar;
+
+impl StandardComposer {
+    /// Returns a [`Variable`] holding the value of `x` raised to the constant
+    /// exponent `exp`.
+    ///
+    /// The power is computed with the left-to-right square-and-multiply
+    /// method, so this function adds `floor(log2(exp))` squarings plus one
+    /// multiplication per set bit of `exp` after the leading one to the
+    /// circuit description. For `exp = 0` the result is the constant one.
+    pub fn pow_constant(&mut self, x: Variable, exp: u64) -> Variable {
+        if exp == 0 {
+            return self.add_witness_to_circuit_description(BlsScalar::one());
+        }
+
+        let leading = 63 - exp.leading_zeros();
+        (0..leading).rev().fold(x, |acc, i| {
+            let acc =
+                self.mul(BlsScalar::one(), acc, acc, BlsScalar::zero(), None);
+            match (exp >> i) & 1 {
+                1 => {
+                    self.mul(BlsScalar::one(), acc, x, BlsScalar::zero(), None)
+                }
+                _ => acc,
+            }
+        })
+    }
+
+    /// Returns a [`Variable`] holding the value of `x` raised to the exponent
+    /// represented by the little-endian `exp_bits`.
+    ///
+    /// For each bit, the accumulated power is multiplied by either the
+    /// current square of `x` or one, adding three gates per bit to the
+    /// circuit description.
+    ///
+    /// The bits are not constrained to be boolean, this is the responsibility
+    /// of the caller. See [`StandardComposer::boolean_gate`] and
+    /// [`StandardComposer::split_variable`].
+    pub fn pow(&mut self, x: Variable, exp_bits: &[Variable]) -> Variable {
+        let one = self.add_witness_to_circuit_description(BlsScalar::one());
+
+        let (acc, _) = exp_bits.iter().enumerate().fold(
+            (one, x),
+            |(acc, square), (i, bit)| {
+                // factor = bit * square - bit + 1
+                let factor = self.big_mul(
+                    BlsScalar::one(),
+                    *bit,
+                    square,
+                    Some((-BlsScalar::one(), *bit)),
+                    BlsScalar::one(),
+                    None,
+                );
+                let acc = self.mul(
+                    BlsScalar::one(),
+                    acc,
+                    factor,
+                    BlsScalar::zero(),
+                    None,
+                );
+
+                // The square of the last bit is never used
+                let square = match i + 1 < exp_bits.len() {
+                    true => self.mul(
+                        BlsScalar::one(),
+                        square,
+                        square,
+                        BlsScalar::zero(),
+                        None,
+                    ),
+                    false => square,
+                };
+
+                (acc, square)
+            },
+        );
+
+        acc
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use super::*;
+    use rand_core::OsRng;
+
+    const EXPONENTS: [u64; 6] = [0, 1, 2, 5, 65537, 0xffff];
+
+    #[test]
+    fn test_pow_constant() {
+        let res = gadget_tester(
+            |composer| {
+                let base = BlsScalar::random(&mut OsRng);
+                let x = composer.add_input(base);
+
+                for exp in EXPONENTS.iter() {
+                    let power = composer.pow_constant(x, *exp);
+                    let expected = base.pow(&[*exp, 0, 0, 0]);
+                    assert_eq!(composer.variables[&power], expected);
+                    composer.constrain_to_constant(power, expected, None);
+                }
+            },
+            256,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the power is wrong
+        let res = gadget_tester(
+            |composer| {
+                let x = composer.add_input(BlsScalar::from(3u64));
+                let power = composer.pow_constant(x, 5);
+                composer.constrain_to_constant(
+                    power,
+                    BlsScalar::from(81u64),
+                    None,
+                );
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_pow() {
+        let res = gadget_tester(
+            |composer| {
+                let base = BlsScalar::random(&mut OsRng);
+                let x = composer.add_input(base);
+
+                for exp in EXPONENTS.iter() {
+                    let exp_var = composer.add_input(BlsScalar::from(*exp));
+                    let bits = composer.split_variable(exp_var, 17);
+                    let power = composer.pow(x, &bits);
+
+                    let expected = base.pow(&[*exp, 0, 0, 0]);
+                    assert_eq!(composer.variables[&power], expected);
+                    composer.constrain_to_constant(power, expected, None);
+                }
+            },
+            1024,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the power is wrong
+        let res = gadget_tester(
+            |composer| {
+                let x = composer.add_input(BlsScalar::from(3u64));
+                let exp = composer.add_input(BlsScalar::from(5u64));
+                let bits = composer.split_variable(exp, 4);
+                let power = composer.pow(x, &bits);
+                composer.constrain_to_constant(
+                    power,
+                    BlsScalar::from(81u64),
+                    None,
+                );
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
+}
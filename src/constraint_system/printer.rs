@@ -0,0 +1,272 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Human-readable listing of the gates of a circuit.
+//!
+//! The [`CircuitPrinter`] writes one line per gate of a [`StandardComposer`]
+//! with the index of the gate, its [`GateType`], the variables sitting on
+//! each wire along with their witness values, the non-zero selectors and the
+//! public input of the gate, if any. For instance:
+//!
+//! ```text
+//! 3 arithmetic w_l=v2(0x..03) w_r=v3(0x..04) w_o=v4(0x..07) w_4=v0(0x..00) \
+//!   q_l=0x..01 q_r=0x..01 q_o=0x73..00 q_arith=0x..01 pi=0x73..fa
+//! ```
+//!
+//! The full listing ends with one line per equality added with
+//! [`StandardComposer::assert_equal_wire`], with the variables it links:
+//!
+//! ```text
+//! eq v2 v5
+//! ```
+//!
+//! It's meant to inspect circuits while debugging, the format of the listing
+//! is not stable and shouldn't be parsed.
+
+use crate::constraint_system::{StandardComposer, Variable};
+use dusk_bls12_381::BlsScalar;
+use std::io::{self, Write};
+
+/// Kind of a gate, given by the non-zero gate selector of its row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GateType {
+    /// Gate with the arithmetic selector set
+    Arithmetic,
+    /// Gate with the range selector set
+    Range,
+    /// Gate with the logic selector set
+    Logic,
+    /// Gate with the fixed base group addition selector set
+    FixedBaseAddition,
+    /// Gate with the variable base group addition selector set
+    VariableBaseAddition,
+    /// Gate with every gate selector set to zero, which only holds wires
+    /// read by the gate right before it
+    Empty,
+}
+
+impl GateType {
+    fn of(composer: &StandardComposer, gate: usize) -> GateType {
+        let zero = BlsScalar::zero();
+
+        if composer.q_arith[gate] != zero {
+            GateType::Arithmetic
+        } else if composer.q_range[gate] != zero {
+            GateType::Range
+        } else if composer.q_logic[gate] != zero {
+            GateType::Logic
+        } else if composer.q_fixed_group_add[gate] != zero {
+            GateType::FixedBaseAddition
+        } else if composer.q_variable_group_add[gate] != zero {
+            GateType::VariableBaseAddition
+        } else {
+            GateType::Empty
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            GateType::Arithmetic => "arithmetic",
+            GateType::Range => "range",
+            GateType::Logic => "logic",
+            GateType::FixedBaseAddition => "fixed_base_add",
+            GateType::VariableBaseAddition => "variable_base_add",
+            GateType::Empty => "empty",
+        }
+    }
+}
+
+/// Writes the gates of a [`StandardComposer`] in a human-readable format.
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitPrinter;
+
+impl CircuitPrinter {
+    /// Writes every gate of the `composer` into the `writer`, one per line,
+    /// followed by its equalities.
+    pub fn print(
+        composer: &StandardComposer,
+        mut writer: impl Write,
+    ) -> Result<(), io::Error> {
+        Self::print_gates(composer, &mut writer, None)?;
+        Self::print_equalities(composer, writer)
+    }
+
+    /// Writes the pairs of variables copy-constrained by
+    /// [`StandardComposer::assert_equal_wire`] into the `writer`, one per
+    /// line, in the order they were added.
+    pub fn print_equalities(
+        composer: &StandardComposer,
+        mut writer: impl Write,
+    ) -> Result<(), io::Error> {
+        for (a, b) in composer.perm.equalities.iter() {
+            writeln!(writer, "eq v{} v{}", a.0, b.0)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the gates of the `composer` into the `writer`, one per line,
+    /// skipping the ones whose type is not in `gate_types` when it's given.
+    ///
+    /// The gates keep the index they have in the circuit, so the listing
+    /// can be matched with the gates of the full circuit.
+    pub fn print_gates(
+        composer: &StandardComposer,
+        mut writer: impl Write,
+        gate_types: Option<&[GateType]>,
+    ) -> Result<(), io::Error> {
+        for gate in 0..composer.n {
+            let gate_type = GateType::of(composer, gate);
+            if let Some(gate_types) = gate_types {
+                if !gate_types.contains(&gate_type) {
+                    continue;
+                }
+            }
+
+            write!(writer, "{} {}", gate, gate_type.name())?;
+
+            let wires = [
+                ("w_l", composer.w_l[gate]),
+                ("w_r", composer.w_r[gate]),
+                ("w_o", composer.w_o[gate]),
+                ("w_4", composer.w_4[gate]),
+            ];
+            for (name, var) in wires.iter() {
+                Self::print_wire(composer, &mut writer, name, *var)?;
+            }
+
+            let selectors = [
+                ("q_m", composer.q_m[gate]),
+                ("q_l", composer.q_l[gate]),
+                ("q_r", composer.q_r[gate]),
+                ("q_o", composer.q_o[gate]),
+                ("q_4", composer.q_4[gate]),
+                ("q_c", composer.q_c[gate]),
+                ("q_arith", composer.q_arith[gate]),
+                ("q_range", composer.q_range[gate]),
+                ("q_logic", composer.q_logic[gate]),
+                ("q_fixed_group_add", composer.q_fixed_group_add[gate]),
+                ("q_variable_group_add", composer.q_variable_group_add[gate]),
+            ];
+            for (name, value) in selectors.iter() {
+                if *value != BlsScalar::zero() {
+                    write!(writer, " {}={:?}", name, value)?;
+                }
+            }
+
+            if let Some(pi) = composer.public_inputs_sparse_store.get(&gate) {
+                write!(writer, " pi={:?}", pi)?;
+            }
+
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn print_wire(
+        composer: &StandardComposer,
+        writer: &mut impl Write,
+        name: &str,
+        var: Variable,
+    ) -> Result<(), io::Error> {
+        match composer.variables.get(&var) {
+            Some(value) => write!(writer, " {}=v{}({:?})", name, var.0, value),
+            None => write!(writer, " {}=v{}", name, var.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::String;
+    use std::vec::Vec;
+
+    fn listing(
+        composer: &StandardComposer,
+        gate_types: Option<&[GateType]>,
+    ) -> Vec<String> {
+        let mut buf = Vec::new();
+        CircuitPrinter::print_gates(composer, &mut buf, gate_types)
+            .expect("Writing to a vector can't fail");
+
+        String::from_utf8(buf)
+            .expect("The listing is valid UTF-8")
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_print_circuit() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(3u64));
+        let b = composer.add_input(BlsScalar::from(4u64));
+        let c = composer.add(
+            (BlsScalar::one(), a),
+            (BlsScalar::one(), b),
+            BlsScalar::zero(),
+            Some(-BlsScalar::from(7u64)),
+        );
+        composer.range_gate(c, 4);
+
+        let lines = listing(&composer, None);
+        assert_eq!(lines.len(), composer.circuit_size());
+
+        let gate = &lines[3];
+        assert!(gate.starts_with("3 arithmetic "));
+        assert!(gate.contains(&format!(" w_l=v{}(", a.0)));
+        assert!(gate.contains(&format!(" w_r=v{}(", b.0)));
+        assert!(gate.contains(&format!(" w_o=v{}(", c.0)));
+        assert!(gate.contains(" q_arith="));
+        assert!(!gate.contains(" q_m="));
+        assert!(gate.contains(&format!(" pi={:?}", -BlsScalar::from(7u64))));
+
+        let mut buf = Vec::new();
+        CircuitPrinter::print(&composer, &mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap().lines().count(),
+            lines.len()
+        );
+    }
+
+    #[test]
+    fn test_print_filtered_gates() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(3u64));
+        composer.range_gate(a, 8);
+
+        let lines = listing(&composer, Some(&[GateType::Range]));
+        assert!(!lines.is_empty());
+        assert!(lines.iter().all(|line| line.contains(" range ")));
+
+        // The gates keep their index in the circuit
+        let first_range = (0..composer.n)
+            .find(|i| composer.q_range[*i] != BlsScalar::zero())
+            .unwrap();
+        assert!(lines[0].starts_with(&format!("{} range ", first_range)));
+
+        assert!(listing(&composer, Some(&[])).is_empty());
+    }
+
+    #[test]
+    fn test_print_equalities() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(3u64));
+        let b = composer.add_input(BlsScalar::from(3u64));
+        composer.mul(BlsScalar::one(), a, b, BlsScalar::zero(), None);
+        composer.assert_equal_wire(a, b);
+
+        let mut buf = Vec::new();
+        CircuitPrinter::print(&composer, &mut buf).unwrap();
+        let listing = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = listing.lines().collect();
+
+        assert_eq!(lines.len(), composer.circuit_size() + 1);
+        assert_eq!(lines[lines.len() - 1], format!("eq v{} v{}", a.0, b.0));
+    }
+}
@@ -8,9 +8,41 @@ use crate::bit_iterator::*;
 use crate::constraint_system::StandardComposer;
 use crate::constraint_system::{Variable, WireData};
 use alloc::vec::Vec;
+use core::fmt;
 use dusk_bls12_381::BlsScalar;
 use dusk_bytes::Serializable;
 
+/// Report of a range gate whose witness doesn't fit in the constrained
+/// number of bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeGateFailure {
+    /// The [`Variable`] constrained by the range gate.
+    pub variable: Variable,
+    /// The value assigned to the [`Variable`].
+    pub value: BlsScalar,
+    /// The number of bits the value is expected to fit in.
+    pub num_bits: usize,
+}
+
+impl fmt::Display for RangeGateFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Range gate failed: {:?} holds {:?} which doesn't fit in {} bits",
+            self.variable, self.value, self.num_bits
+        )
+    }
+}
+
+/// Returns `true` if the canonical representation of `value` fits in
+/// `num_bits` bits.
+fn fits_in_bits(value: &BlsScalar, num_bits: usize) -> bool {
+    value.to_bytes().iter().enumerate().all(|(i, byte)| {
+        let low_bits = num_bits.saturating_sub(8 * i);
+        low_bits >= 8 || byte >> low_bits == 0
+    })
+}
+
 impl StandardComposer {
     /// Adds a range-constraint gate that checks and constrains a
     /// [`Variable`] to be inside of the range \[0,num_bits\].
@@ -190,13 +222,171 @@ impl StandardComposer {
         let last_accumulator = accumulators.len() - 1;
         self.assert_equal(accumulators[last_accumulator], witness);
         accumulators[last_accumulator] = witness;
+
+        self.range_checks.push((witness, num_bits));
+    }
+
+    /// Returns a [`RangeGateFailure`] for each range gate of the circuit
+    /// whose witness value doesn't fit in the constrained number of bits, in
+    /// the order the gates were added.
+    ///
+    /// An over-range witness makes the accumulators of the range gate differ
+    /// from the witness, so the unsatisfied gate is the arithmetic one
+    /// linking them. This reports the witness itself, which is what's needed
+    /// to track down faulty decompositions.
+    pub fn unsatisfied_range_gates(&self) -> Vec<RangeGateFailure> {
+        self.range_checks
+            .iter()
+            .filter_map(|(variable, num_bits)| {
+                let value = self.variables[variable];
+                match fits_in_bits(&value, *num_bits) {
+                    true => None,
+                    false => Some(RangeGateFailure {
+                        variable: *variable,
+                        value,
+                        num_bits: *num_bits,
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns a boolean [`Variable`] which is `1` if the value of `a` is
+    /// less than `bound` and `0` otherwise.
+    ///
+    /// Unlike [`StandardComposer::range_gate`], the circuit remains satisfied
+    /// when `a` is not less than `bound`. The comparison is performed by
+    /// decomposing `a - bound + 2^252` into a range constrained 252-bit value
+    /// and a boolean carry, which is `1` iff `a >= bound`.
+    ///
+    /// The value of `a` is expected to be in the range `[0, 2^252)`:
+    /// constraining it is the responsibility of the caller, otherwise the
+    /// result is meaningless.
+    ///
+    /// # Panics
+    /// This function will panic if `bound` is greater than `2^252`.
+    pub fn less_than(&mut self, a: Variable, bound: BlsScalar) -> Variable {
+        let offset = BlsScalar::pow_of_2(LESS_THAN_BITS as u64);
+
+        // The bits of the bound are all below the 252th bit, except for the
+        // bound `2^252` itself
+        let bound_bytes = bound.to_bytes();
+        assert!(
+            bound == offset
+                || (bound_bytes[LESS_THAN_BITS / 8] >> (LESS_THAN_BITS % 8)
+                    == 0
+                    && bound_bytes[LESS_THAN_BITS / 8 + 1..]
+                        .iter()
+                        .all(|byte| *byte == 0))
+        );
+
+        let diff = self.variables[&a] - bound + offset;
+        let diff_bytes = diff.to_bytes();
+        let carry =
+            (diff_bytes[LESS_THAN_BITS / 8] >> (LESS_THAN_BITS % 8)) & 1;
+        let carry = BlsScalar::from(carry as u64);
+        let low = diff - carry * offset;
+
+        let low = self.add_input(low);
+        let carry = self.add_input(carry);
+
+        self.range_gate(low, LESS_THAN_BITS);
+        self.boolean_gate(carry);
+
+        // a - bound + 2^252 - low - 2^252 * carry = 0
+        self.big_add_gate(
+            a,
+            self.zero_var,
+            low,
+            Some(carry),
+            BlsScalar::one(),
+            BlsScalar::zero(),
+            -BlsScalar::one(),
+            -offset,
+            offset - bound,
+            None,
+        );
+
+        // The result is the negation of the carry
+        self.add(
+            (-BlsScalar::one(), carry),
+            (BlsScalar::zero(), self.zero_var),
+            BlsScalar::one(),
+            None,
+        )
+    }
+
+    /// Constrains the value of `a` to be less than or equal to the value of
+    /// `b`, by range constraining `b - a` to [`LESS_THAN_BITS`] bits.
+    ///
+    /// The values of `a` and `b` are expected to be in the range
+    /// `[0, 2^252)`: constraining them is the responsibility of the caller,
+    /// otherwise the constraint is meaningless. In that range, `b - a` wraps
+    /// around the modulus whenever `a > b`, so it doesn't fit in 252 bits.
+    ///
+    /// This function adds one gate to the circuit description on top of the
+    /// ones of the [`StandardComposer::range_gate`].
+    pub fn assert_less_than_or_equal(&mut self, a: Variable, b: Variable) {
+        self.assert_difference_in_range(a, b, BlsScalar::zero());
+    }
+
+    /// Constrains the value of `a` to be strictly less than the value of
+    /// `b`, by range constraining `b - a - 1` to [`LESS_THAN_BITS`] bits.
+    ///
+    /// The same assumptions as for
+    /// [`StandardComposer::assert_less_than_or_equal`] apply to the values
+    /// of `a` and `b`.
+    ///
+    /// This function adds one gate to the circuit description on top of the
+    /// ones of the [`StandardComposer::range_gate`].
+    pub fn assert_less_than(&mut self, a: Variable, b: Variable) {
+        self.assert_difference_in_range(a, b, -BlsScalar::one());
+    }
+
+    /// Constrains the values of `vars` to be sorted in non-decreasing order,
+    /// with a [`StandardComposer::assert_less_than_or_equal`] between each
+    /// pair of consecutive values.
+    ///
+    /// Along with [`StandardComposer::enforce_permutation`], this proves
+    /// that a list is the sorted version of another one.
+    pub fn assert_sorted(&mut self, vars: &[Variable]) {
+        vars.windows(2)
+            .for_each(|pair| self.assert_less_than_or_equal(pair[0], pair[1]));
+    }
+
+    /// Constrains the values of `vars` to be sorted in strictly increasing
+    /// order, with a [`StandardComposer::assert_less_than`] between each pair
+    /// of consecutive values. This also proves that the values are distinct.
+    pub fn assert_sorted_strict(&mut self, vars: &[Variable]) {
+        vars.windows(2)
+            .for_each(|pair| self.assert_less_than(pair[0], pair[1]));
+    }
+
+    /// Range constrains `b - a + constant` to [`LESS_THAN_BITS`] bits.
+    fn assert_difference_in_range(
+        &mut self,
+        a: Variable,
+        b: Variable,
+        constant: BlsScalar,
+    ) {
+        let diff = self.add(
+            (BlsScalar::one(), b),
+            (-BlsScalar::one(), a),
+            constant,
+            None,
+        );
+        self.range_gate(diff, LESS_THAN_BITS);
     }
 }
 
+/// Number of bits of the values compared by [`StandardComposer::less_than`].
+const LESS_THAN_BITS: usize = 252;
+
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod tests {
     use super::super::helper::*;
+    use super::*;
     use dusk_bls12_381::BlsScalar;
 
     #[test]
@@ -235,6 +425,161 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn test_less_than() {
+        let res = gadget_tester(
+            |composer| {
+                let bound = BlsScalar::from(10u64);
+                for (value, expected) in
+                    [(0u64, 1u64), (9, 1), (10, 0), (11, 0), (u64::MAX, 0)]
+                        .iter()
+                {
+                    let a = composer.add_input(BlsScalar::from(*value));
+                    let lt = composer.less_than(a, bound);
+                    composer.constrain_to_constant(
+                        lt,
+                        BlsScalar::from(*expected),
+                        None,
+                    );
+                }
+
+                // Values close to the maximum supported bound
+                let bound = BlsScalar::pow_of_2(252);
+                let a = composer.add_input(bound - BlsScalar::one());
+                let lt = composer.less_than(a, bound);
+                composer.constrain_to_constant(lt, BlsScalar::one(), None);
+            },
+            512,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the result is not the expected one
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(10u64));
+                let lt = composer.less_than(a, BlsScalar::from(10u64));
+                composer.constrain_to_constant(lt, BlsScalar::one(), None);
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
+
+    fn test_sorted(composer: &mut StandardComposer, values: &[u64]) {
+        let vars: Vec<Variable> = values
+            .iter()
+            .map(|v| composer.add_input(BlsScalar::from(*v)))
+            .collect();
+        composer.assert_sorted(&vars);
+    }
+
+    fn test_sorted_strict(composer: &mut StandardComposer, values: &[u64]) {
+        let vars: Vec<Variable> = values
+            .iter()
+            .map(|v| composer.add_input(BlsScalar::from(*v)))
+            .collect();
+        composer.assert_sorted_strict(&vars);
+    }
+
+    #[test]
+    fn test_assert_sorted() {
+        let res =
+            gadget_tester(|composer| test_sorted(composer, &[1, 3, 3, 7]), 512);
+        assert!(res.is_ok());
+
+        let res = gadget_tester(
+            |composer| {
+                let values: Vec<u64> = (0..16).map(|i| i * i).collect();
+                test_sorted(composer, &values);
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the values are in reverse order
+        let res =
+            gadget_tester(|composer| test_sorted(composer, &[7, 3, 3, 1]), 512);
+        assert!(res.is_err());
+
+        let res = gadget_tester(
+            |composer| {
+                let values: Vec<u64> = (0..16).rev().collect();
+                test_sorted(composer, &values);
+            },
+            2048,
+        );
+        assert!(res.is_err());
+
+        // Should fail since a single pair is out of order
+        let res =
+            gadget_tester(|composer| test_sorted(composer, &[1, 3, 2, 7]), 512);
+        assert!(res.is_err());
+
+        let res = gadget_tester(
+            |composer| {
+                let mut values: Vec<u64> = (0..16).collect();
+                values.swap(9, 10);
+                test_sorted(composer, &values);
+            },
+            2048,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_assert_sorted_strict() {
+        let res = gadget_tester(
+            |composer| test_sorted_strict(composer, &[1, 3, 4, 7]),
+            512,
+        );
+        assert!(res.is_ok());
+
+        let res = gadget_tester(
+            |composer| {
+                let values: Vec<u64> = (0..16).map(|i| i * i).collect();
+                test_sorted_strict(composer, &values);
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since two consecutive values are equal
+        let res = gadget_tester(
+            |composer| test_sorted_strict(composer, &[1, 3, 3, 7]),
+            512,
+        );
+        assert!(res.is_err());
+
+        // Should fail since the values are in reverse order
+        let res = gadget_tester(
+            |composer| {
+                let values: Vec<u64> = (0..16).rev().collect();
+                test_sorted_strict(composer, &values);
+            },
+            2048,
+        );
+        assert!(res.is_err());
+
+        // Should fail since a single pair is out of order
+        let res = gadget_tester(
+            |composer| {
+                let mut values: Vec<u64> = (0..16).collect();
+                values.swap(3, 4);
+                test_sorted_strict(composer, &values);
+            },
+            2048,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_less_than_bound_too_large() {
+        let mut composer = crate::constraint_system::StandardComposer::new();
+        let a = composer.add_input(BlsScalar::one());
+        composer.less_than(a, BlsScalar::pow_of_2(252) + BlsScalar::one());
+    }
+
     #[test]
     #[should_panic]
     fn test_odd_bit_range() {
@@ -248,4 +593,39 @@ mod tests {
             200,
         );
     }
+
+    #[test]
+    fn test_unsatisfied_range_gates() {
+        let mut composer = StandardComposer::new();
+        let in_range = composer.add_input(BlsScalar::from(255u64));
+        let over_range = composer.add_input(BlsScalar::from(300u64));
+        let max = composer.add_input(-BlsScalar::one());
+
+        composer.range_gate(in_range, 8);
+        composer.range_gate(over_range, 8);
+        composer.range_gate(over_range, 10);
+        composer.range_gate(max, 256);
+        composer.range_gate(max, 254);
+
+        let failures = composer.unsatisfied_range_gates();
+        assert_eq!(
+            failures,
+            vec![
+                RangeGateFailure {
+                    variable: over_range,
+                    value: BlsScalar::from(300u64),
+                    num_bits: 8,
+                },
+                RangeGateFailure {
+                    variable: max,
+                    value: -BlsScalar::one(),
+                    num_bits: 254,
+                },
+            ]
+        );
+
+        let report = format!("{}", failures[0]);
+        assert!(report.contains(&format!("{:?}", BlsScalar::from(300u64))));
+        assert!(report.contains("8 bits"));
+    }
 }
@@ -0,0 +1,160 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::{StandardComposer, Variable};
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+
+impl StandardComposer {
+    /// Returns `len` boolean [`Variable`]s where only the one at position
+    /// `index` is set.
+    ///
+    /// The bits are constrained to be boolean, to sum to one and to sum to
+    /// `index` when weighted by their position, so the circuit is only
+    /// satisfied if the value of `index` is in `[0, len)`.
+    ///
+    /// This function adds `3 * len + 2` gates to the circuit description.
+    pub fn one_hot(&mut self, index: Variable, len: usize) -> Vec<Variable> {
+        let value = self.variables[&index];
+
+        let bits: Vec<Variable> = (0..len)
+            .map(|i| {
+                let bit = BlsScalar::from(
+                    (value == BlsScalar::from(i as u64)) as u64,
+                );
+                let bit = self.add_input(bit);
+                self.boolean_gate(bit)
+            })
+            .collect();
+
+        let (sum, weighted_sum) = bits.iter().enumerate().fold(
+            (self.zero_var, self.zero_var),
+            |(sum, weighted_sum), (i, bit)| {
+                let sum = self.add(
+                    (BlsScalar::one(), sum),
+                    (BlsScalar::one(), *bit),
+                    BlsScalar::zero(),
+                    None,
+                );
+                let weighted_sum = self.add(
+                    (BlsScalar::one(), weighted_sum),
+                    (BlsScalar::from(i as u64), *bit),
+                    BlsScalar::zero(),
+                    None,
+                );
+
+                (sum, weighted_sum)
+            },
+        );
+
+        self.constrain_to_constant(sum, BlsScalar::one(), None);
+        self.assert_equal(weighted_sum, index);
+
+        bits
+    }
+
+    /// Returns a [`Variable`] holding the value of `options[index]`.
+    ///
+    /// The output is the inner product of the `options` with the
+    /// [`StandardComposer::one_hot`] decomposition of `index`, so the circuit
+    /// is only satisfied if the value of `index` is in `[0, options.len())`.
+    ///
+    /// This function adds `4 * options.len() + 2` gates to the circuit
+    /// description.
+    pub fn select_index(
+        &mut self,
+        index: Variable,
+        options: &[Variable],
+    ) -> Variable {
+        let bits = self.one_hot(index, options.len());
+
+        bits.iter().zip(options.iter()).fold(
+            self.zero_var,
+            |acc, (bit, option)| {
+                self.big_mul(
+                    BlsScalar::one(),
+                    *bit,
+                    *option,
+                    Some((BlsScalar::one(), acc)),
+                    BlsScalar::zero(),
+                    None,
+                )
+            },
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use super::*;
+
+    fn options(composer: &mut StandardComposer) -> Vec<Variable> {
+        (0..4u64)
+            .map(|i| composer.add_input(BlsScalar::from(10 * (i + 1))))
+            .collect()
+    }
+
+    #[test]
+    fn test_select_index() {
+        let res = gadget_tester(
+            |composer| {
+                let options = options(composer);
+                let index = composer.add_input(BlsScalar::from(2u64));
+
+                let n = composer.circuit_size();
+                let selected = composer.select_index(index, &options);
+                assert_eq!(composer.circuit_size() - n, 18);
+
+                composer.constrain_to_constant(
+                    selected,
+                    BlsScalar::from(30u64),
+                    None,
+                );
+            },
+            64,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the index is out of the options
+        let res = gadget_tester(
+            |composer| {
+                let options = options(composer);
+                let index = composer.add_input(BlsScalar::from(4u64));
+                composer.select_index(index, &options);
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_invalid_one_hot() {
+        // Should fail since the one-hot has two bits set
+        let res = gadget_tester(
+            |composer| {
+                let index = composer.add_input(BlsScalar::from(2u64));
+                let bits = composer.one_hot(index, 4);
+                composer.variables.insert(bits[0], BlsScalar::one());
+            },
+            64,
+        );
+        assert!(res.is_err());
+
+        // Should fail since the bit set is not the one of the index
+        let res = gadget_tester(
+            |composer| {
+                let index = composer.add_input(BlsScalar::from(2u64));
+                let bits = composer.one_hot(index, 4);
+                composer.variables.insert(bits[2], BlsScalar::zero());
+                composer.variables.insert(bits[3], BlsScalar::one());
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,241 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::{
+    ArithmeticGate, PoseidonParams, StandardComposer, Variable,
+};
+use crate::error::Error;
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+
+impl StandardComposer {
+    /// Constrains the value of the [`Variable`] `a` to be one of the
+    /// `allowed` values, by enforcing that the product of `a - v` for each of
+    /// the allowed values `v` is zero.
+    ///
+    /// This function adds as many gates as allowed values to the circuit
+    /// description. Returns an error if `allowed` is empty, since no value
+    /// can satisfy the constraint.
+    pub fn assert_in_set(
+        &mut self,
+        a: Variable,
+        allowed: &[BlsScalar],
+    ) -> Result<(), Error> {
+        let (last, rest) = allowed.split_last().ok_or(Error::EmptySet)?;
+
+        // Accumulates the product of `a - v` for the values in `rest`
+        let mut acc: Option<Variable> = None;
+        for v in rest {
+            acc = Some(match acc {
+                None => self.add(
+                    (BlsScalar::one(), a),
+                    (BlsScalar::zero(), self.zero_var),
+                    -v,
+                    None,
+                ),
+                Some(acc) => {
+                    let value = self.variables[&acc] * (self.variables[&a] - v);
+                    let next = self.add_input(value);
+
+                    // acc * a - v * acc - next = 0
+                    ArithmeticGate::new()
+                        .left(acc)
+                        .right(a)
+                        .output(next)
+                        .q_m(BlsScalar::one())
+                        .q_l(-v)
+                        .q_o(-BlsScalar::one())
+                        .apply(self);
+                    next
+                }
+            });
+        }
+
+        match acc {
+            None => self.constrain_to_constant(a, *last, None),
+            Some(acc) => {
+                // acc * a - last * acc = 0
+                ArithmeticGate::new()
+                    .left(acc)
+                    .right(a)
+                    .q_m(BlsScalar::one())
+                    .q_l(-last)
+                    .apply(self);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Constrains the [`Variable`]s of `a` to hold the same multiset of
+    /// values as the ones of `b`, in any order.
+    ///
+    /// The challenge `alpha` is the Poseidon sponge of `a` followed by `b`
+    /// with the default [`PoseidonParams`], so it's bound to the values
+    /// of both slices. The products of `alpha - a_i` and `alpha - b_i` are
+    /// then constrained to be equal, which only holds for different
+    /// multisets with negligible probability.
+    ///
+    /// On top of the gates of the sponge, this function adds `4 * n - 1`
+    /// gates to the circuit description for slices of length `n`.
+    ///
+    /// # Panics
+    /// This function will panic if the slices don't have the same length.
+    pub fn enforce_permutation(&mut self, a: &[Variable], b: &[Variable]) {
+        assert_eq!(a.len(), b.len(), "the slices must have the same length");
+        if a.is_empty() {
+            return;
+        }
+
+        let inputs = [a, b].concat();
+        let alpha = self.poseidon_sponge(&PoseidonParams::default(), &inputs);
+
+        let a_product = self.product_of_differences(alpha, a);
+        let b_product = self.product_of_differences(alpha, b);
+        self.assert_equal(a_product, b_product);
+    }
+
+    /// Returns a [`Variable`] holding the product of `alpha - x` for each
+    /// `x` of the non-empty `values`.
+    fn product_of_differences(
+        &mut self,
+        alpha: Variable,
+        values: &[Variable],
+    ) -> Variable {
+        let diffs: Vec<Variable> = values
+            .iter()
+            .map(|x| {
+                self.add(
+                    (BlsScalar::one(), alpha),
+                    (-BlsScalar::one(), *x),
+                    BlsScalar::zero(),
+                    None,
+                )
+            })
+            .collect();
+
+        diffs[1..].iter().fold(diffs[0], |acc, diff| {
+            self.mul(BlsScalar::one(), acc, *diff, BlsScalar::zero(), None)
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use super::*;
+
+    #[test]
+    fn test_assert_in_set() {
+        // Should pass since 2 is one of the allowed values
+        let res = gadget_tester(
+            |composer| {
+                let allowed = [
+                    BlsScalar::from(1u64),
+                    BlsScalar::from(2u64),
+                    BlsScalar::from(3u64),
+                ];
+                let a = composer.add_input(BlsScalar::from(2u64));
+                composer.assert_in_set(a, &allowed).unwrap();
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since 4 is not an allowed value
+        let res = gadget_tester(
+            |composer| {
+                let allowed = [
+                    BlsScalar::from(1u64),
+                    BlsScalar::from(2u64),
+                    BlsScalar::from(3u64),
+                ];
+                let a = composer.add_input(BlsScalar::from(4u64));
+                composer.assert_in_set(a, &allowed).unwrap();
+            },
+            32,
+        );
+        assert!(res.is_err());
+
+        // A single value is equivalent to an equality constraint
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(7u64));
+                composer.assert_in_set(a, &[BlsScalar::from(7u64)]).unwrap();
+            },
+            32,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_assert_in_empty_set() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(2u64));
+
+        assert!(composer.assert_in_set(a, &[]).is_err());
+    }
+
+    #[test]
+    fn test_assert_in_set_gate_count() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(2u64));
+
+        for k in 1..10u64 {
+            let allowed: Vec<BlsScalar> = (0..k).map(BlsScalar::from).collect();
+            let size = composer.circuit_size();
+            composer.assert_in_set(a, &allowed).unwrap();
+            assert_eq!(composer.circuit_size() - size, k as usize);
+        }
+    }
+
+    fn test_permutation(composer: &mut StandardComposer, a: &[u64], b: &[u64]) {
+        let a: Vec<Variable> = a
+            .iter()
+            .map(|v| composer.add_input(BlsScalar::from(*v)))
+            .collect();
+        let b: Vec<Variable> = b
+            .iter()
+            .map(|v| composer.add_input(BlsScalar::from(*v)))
+            .collect();
+
+        composer.enforce_permutation(&a, &b);
+    }
+
+    #[test]
+    fn test_enforce_permutation() {
+        // Should pass since the sorted slice is a permutation of the other
+        let res = gadget_tester(
+            |composer| {
+                test_permutation(composer, &[5, 1, 4, 1, 3], &[1, 1, 3, 4, 5])
+            },
+            4096,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the multiplicities of 1 and 4 don't match
+        let res = gadget_tester(
+            |composer| {
+                test_permutation(composer, &[5, 1, 4, 1, 3], &[1, 3, 4, 4, 5])
+            },
+            4096,
+        );
+        assert!(res.is_err());
+
+        // Empty slices are trivially permutations of each other
+        let res =
+            gadget_tester(|composer| test_permutation(composer, &[], &[]), 32);
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_enforce_permutation_length_mismatch() {
+        let mut composer = StandardComposer::new();
+        test_permutation(&mut composer, &[1, 2], &[1, 2, 3]);
+    }
+}
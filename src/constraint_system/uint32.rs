@@ -0,0 +1,549 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::{StandardComposer, Variable};
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+
+/// Returns the lowest 64 bits of a [`BlsScalar`].
+fn scalar_to_u64(scalar: &BlsScalar) -> u64 {
+    let bytes = scalar.to_bytes();
+    let mut low = [0u8; 8];
+    low.copy_from_slice(&bytes[..8]);
+    u64::from_le_bytes(low)
+}
+
+impl StandardComposer {
+    /// Adds the constraints needed to prove that
+    /// `q_a * a + q_b * b + q_c = low + 2^32 * carry` where `low` is a 32-bit
+    /// value and `carry` is a boolean, returning `low`.
+    fn mod_2_32_decompose_sum(
+        &mut self,
+        (q_a, a): (BlsScalar, Variable),
+        (q_b, b): (BlsScalar, Variable),
+        q_c: BlsScalar,
+    ) -> Variable {
+        let value = scalar_to_u64(
+            &(q_a * self.variables[&a] + q_b * self.variables[&b] + q_c),
+        );
+
+        let low = self.add_input(BlsScalar::from(value & 0xffff_ffff));
+        let carry = self.add_input(BlsScalar::from(value >> 32));
+
+        self.range_gate(low, 32);
+        self.boolean_gate(carry);
+
+        // q_a * a + q_b * b + q_c - low - 2^32 * carry = 0
+        self.big_add_gate(
+            a,
+            b,
+            low,
+            Some(carry),
+            q_a,
+            q_b,
+            -BlsScalar::one(),
+            -BlsScalar::pow_of_2(32),
+            q_c,
+            None,
+        );
+
+        low
+    }
+
+    /// Returns a [`Variable`] holding `(a + b) mod 2^32`.
+    ///
+    /// The sum is decomposed into its lowest 32 bits, which are range
+    /// constrained, and a boolean carry. The values of `a` and `b` are
+    /// expected to be 32-bit integers: constraining them is the
+    /// responsibility of the caller.
+    ///
+    /// This function adds 8 gates to the circuit description.
+    pub fn mod_2_32_add(&mut self, a: Variable, b: Variable) -> Variable {
+        self.mod_2_32_decompose_sum(
+            (BlsScalar::one(), a),
+            (BlsScalar::one(), b),
+            BlsScalar::zero(),
+        )
+    }
+
+    /// Returns a [`Variable`] holding `(a - b) mod 2^32`.
+    ///
+    /// The difference is offset by `2^32` so it's never negative and then
+    /// decomposed as in [`StandardComposer::mod_2_32_add`]. The values of `a`
+    /// and `b` are expected to be 32-bit integers: constraining them is the
+    /// responsibility of the caller.
+    ///
+    /// This function adds 8 gates to the circuit description.
+    pub fn mod_2_32_sub(&mut self, a: Variable, b: Variable) -> Variable {
+        self.mod_2_32_decompose_sum(
+            (BlsScalar::one(), a),
+            (-BlsScalar::one(), b),
+            BlsScalar::pow_of_2(32),
+        )
+    }
+
+    /// Returns a [`Variable`] holding `(a * b) mod 2^32`.
+    ///
+    /// The product is decomposed into two range constrained 32-bit halves
+    /// and the lowest one is returned. The values of `a` and `b` are
+    /// expected to be 32-bit integers: constraining them is the
+    /// responsibility of the caller.
+    ///
+    /// This function adds 13 gates to the circuit description.
+    pub fn mod_2_32_mul(&mut self, a: Variable, b: Variable) -> Variable {
+        let value = scalar_to_u64(&(self.variables[&a] * self.variables[&b]));
+
+        let low = self.add_input(BlsScalar::from(value & 0xffff_ffff));
+        let high = self.add_input(BlsScalar::from(value >> 32));
+
+        self.range_gate(low, 32);
+        self.range_gate(high, 32);
+
+        // a * b - low - 2^32 * high = 0
+        self.big_mul_gate(
+            a,
+            b,
+            low,
+            Some(high),
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::zero(),
+            -BlsScalar::pow_of_2(32),
+            None,
+        );
+
+        low
+    }
+
+    /// Returns a [`Variable`] holding `(a + b + c) mod 2^32`.
+    ///
+    /// The sum is decomposed into its lowest 32 bits and a carry in the range
+    /// `[0, 3]`, both range constrained. The values of `a`, `b` and `c` are
+    /// expected to be 32-bit integers: constraining them is the
+    /// responsibility of the caller.
+    ///
+    /// This function adds 11 gates to the circuit description.
+    pub fn mod_2_32_add3(
+        &mut self,
+        a: Variable,
+        b: Variable,
+        c: Variable,
+    ) -> Variable {
+        let partial = self.add(
+            (BlsScalar::one(), a),
+            (BlsScalar::one(), b),
+            BlsScalar::zero(),
+            None,
+        );
+
+        let value =
+            scalar_to_u64(&(self.variables[&partial] + self.variables[&c]));
+
+        let low = self.add_input(BlsScalar::from(value & 0xffff_ffff));
+        let carry = self.add_input(BlsScalar::from(value >> 32));
+
+        self.range_gate(low, 32);
+        self.range_gate(carry, 2);
+
+        // a + b + c - low - 2^32 * carry = 0
+        self.big_add_gate(
+            partial,
+            c,
+            low,
+            Some(carry),
+            BlsScalar::one(),
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            -BlsScalar::pow_of_2(32),
+            BlsScalar::zero(),
+            None,
+        );
+
+        low
+    }
+
+    /// Returns a [`Variable`] holding the value of `var`, interpreted as a
+    /// 32-bit integer, logically shifted `amount` bits to the right.
+    ///
+    /// The circuit will only be satisfied if the value of `var` fits in 32
+    /// bits. Shifting by 32 bits or more results in zero.
+    ///
+    /// This function adds `49 + ceil((32 - amount) / 2)` gates to the circuit
+    /// description.
+    pub fn shift_right_32(&mut self, var: Variable, amount: usize) -> Variable {
+        let bits = self.split_variable(var, 32);
+        let shifted = &bits[amount.min(32)..];
+
+        self.compose_bits(shifted)
+    }
+
+    /// Returns a [`Variable`] holding the value of `var`, interpreted as a
+    /// 32-bit integer, logically shifted `amount` bits to the left and
+    /// truncated to 32 bits.
+    ///
+    /// The circuit will only be satisfied if the value of `var` fits in 32
+    /// bits. Shifting by 32 bits or more results in zero.
+    ///
+    /// This function adds 65 gates to the circuit description.
+    pub fn shift_left_32(&mut self, var: Variable, amount: usize) -> Variable {
+        let amount = amount.min(32);
+        let bits = self.split_variable(var, 32);
+
+        // The bit `i` of the result is the bit `i - amount` of the input
+        let shifted: Vec<Variable> = (0..32)
+            .map(|i| match i < amount {
+                true => self.zero_var,
+                false => bits[i - amount],
+            })
+            .collect();
+
+        self.compose_bits(&shifted)
+    }
+
+    /// Returns a [`Variable`] holding the bitwise XOR of the values of `a`
+    /// and `b`, interpreted as 32-bit integers.
+    ///
+    /// Unlike [`StandardComposer::xor_gate`], the inputs are constrained to
+    /// be equal to the values accumulated by the logic gate, so the circuit
+    /// will only be satisfied if both of them fit in 32 bits.
+    ///
+    /// This function adds 19 gates to the circuit description.
+    pub fn xor_32(&mut self, a: Variable, b: Variable) -> Variable {
+        let result = self.xor_gate(a, b, 32);
+        self.assert_logic_inputs(a, b);
+
+        result
+    }
+
+    /// Returns a [`Variable`] holding the bitwise AND of the values of `a`
+    /// and `b`, interpreted as 32-bit integers.
+    ///
+    /// Unlike [`StandardComposer::and_gate`], the inputs are constrained to
+    /// be equal to the values accumulated by the logic gate, so the circuit
+    /// will only be satisfied if both of them fit in 32 bits.
+    ///
+    /// This function adds 19 gates to the circuit description.
+    pub fn and_32(&mut self, a: Variable, b: Variable) -> Variable {
+        let result = self.and_gate(a, b, 32);
+        self.assert_logic_inputs(a, b);
+
+        result
+    }
+
+    /// Constrains `a` and `b` to be equal to the left and right accumulators
+    /// of the last gate, which hold the inputs of the last logic gate.
+    fn assert_logic_inputs(&mut self, a: Variable, b: Variable) {
+        let left = self.w_l[self.n - 1];
+        let right = self.w_r[self.n - 1];
+
+        self.assert_equal(left, a);
+        self.assert_equal(right, b);
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use super::*;
+    use rand_core::{OsRng, RngCore};
+
+    const VALUES: [(u32, u32); 4] = [
+        (0, 0),
+        (7, 12),
+        (0xffff_ffff, 1),
+        (0xdead_beef, 0xcafe_babe),
+    ];
+
+    #[test]
+    fn test_mod_2_32_add() {
+        let res = gadget_tester(
+            |composer| {
+                for (a, b) in VALUES.iter() {
+                    let a_var = composer.add_input(BlsScalar::from(*a as u64));
+                    let b_var = composer.add_input(BlsScalar::from(*b as u64));
+                    let sum = composer.mod_2_32_add(a_var, b_var);
+
+                    let expected = BlsScalar::from(a.wrapping_add(*b) as u64);
+                    assert_eq!(composer.variables[&sum], expected);
+                    composer.constrain_to_constant(sum, expected, None);
+                }
+            },
+            256,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_mod_2_32_sub() {
+        let res = gadget_tester(
+            |composer| {
+                for (a, b) in VALUES.iter() {
+                    let a_var = composer.add_input(BlsScalar::from(*a as u64));
+                    let b_var = composer.add_input(BlsScalar::from(*b as u64));
+                    let diff = composer.mod_2_32_sub(a_var, b_var);
+                    let rev_diff = composer.mod_2_32_sub(b_var, a_var);
+
+                    let expected = BlsScalar::from(a.wrapping_sub(*b) as u64);
+                    let rev_expected =
+                        BlsScalar::from(b.wrapping_sub(*a) as u64);
+                    assert_eq!(composer.variables[&diff], expected);
+                    assert_eq!(composer.variables[&rev_diff], rev_expected);
+                    composer.constrain_to_constant(diff, expected, None);
+                    composer.constrain_to_constant(
+                        rev_diff,
+                        rev_expected,
+                        None,
+                    );
+                }
+            },
+            256,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_mod_2_32_mul() {
+        let res = gadget_tester(
+            |composer| {
+                for (a, b) in VALUES.iter() {
+                    let a_var = composer.add_input(BlsScalar::from(*a as u64));
+                    let b_var = composer.add_input(BlsScalar::from(*b as u64));
+                    let prod = composer.mod_2_32_mul(a_var, b_var);
+
+                    let expected = BlsScalar::from(a.wrapping_mul(*b) as u64);
+                    assert_eq!(composer.variables[&prod], expected);
+                    composer.constrain_to_constant(prod, expected, None);
+                }
+            },
+            256,
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_mod_2_32_wrong_result() {
+        // Should fail since the result is not the wrapped sum
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(0xffff_ffffu64));
+                let b = composer.add_input(BlsScalar::from(2u64));
+                let sum = composer.mod_2_32_add(a, b);
+                composer.constrain_to_constant(
+                    sum,
+                    BlsScalar::from(0x1_0000_0001u64),
+                    None,
+                );
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
+
+    /// Returns the edge case values along with random ones.
+    fn test_values() -> Vec<u32> {
+        vec![
+            0,
+            1,
+            0xffff_ffff,
+            0x8000_0000,
+            OsRng.next_u32(),
+            OsRng.next_u32(),
+        ]
+    }
+
+    #[test]
+    fn test_mod_2_32_add3() {
+        let res = gadget_tester(
+            |composer| {
+                let values = test_values();
+                for a in values.iter() {
+                    for b in values.iter() {
+                        let c = values[(*a as usize ^ *b as usize) % 6];
+
+                        let a_var =
+                            composer.add_input(BlsScalar::from(*a as u64));
+                        let b_var =
+                            composer.add_input(BlsScalar::from(*b as u64));
+                        let c_var =
+                            composer.add_input(BlsScalar::from(c as u64));
+                        let sum = composer.mod_2_32_add3(a_var, b_var, c_var);
+
+                        let expected = BlsScalar::from(
+                            a.wrapping_add(*b).wrapping_add(c) as u64,
+                        );
+                        assert_eq!(composer.variables[&sum], expected);
+                        composer.constrain_to_constant(sum, expected, None);
+                    }
+                }
+            },
+            1024,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the result is not the wrapped sum
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from(0xffff_ffffu64));
+                let sum = composer.mod_2_32_add3(a, a, a);
+                composer.constrain_to_constant(
+                    sum,
+                    BlsScalar::from(3 * 0xffff_ffffu64),
+                    None,
+                );
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_shift_32() {
+        let res = gadget_tester(
+            |composer| {
+                for value in test_values().iter() {
+                    let var =
+                        composer.add_input(BlsScalar::from(*value as u64));
+
+                    for amount in [0usize, 1, 7, 16, 31, 32, 40].iter() {
+                        let right = composer.shift_right_32(var, *amount);
+                        let left = composer.shift_left_32(var, *amount);
+
+                        let (expected_right, expected_left) = match *amount {
+                            0..=31 => (value >> *amount, value << *amount),
+                            _ => (0, 0),
+                        };
+                        let expected_right =
+                            BlsScalar::from(expected_right as u64);
+                        let expected_left =
+                            BlsScalar::from(expected_left as u64);
+
+                        assert_eq!(composer.variables[&right], expected_right);
+                        assert_eq!(composer.variables[&left], expected_left);
+                        composer.constrain_to_constant(
+                            right,
+                            expected_right,
+                            None,
+                        );
+                        composer.constrain_to_constant(
+                            left,
+                            expected_left,
+                            None,
+                        );
+                    }
+                }
+            },
+            4096,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the value doesn't fit in 32 bits
+        let res = gadget_tester(
+            |composer| {
+                let var = composer.add_input(BlsScalar::from(1u64 << 32));
+                let shifted = composer.shift_right_32(var, 1);
+                composer.constrain_to_constant(
+                    shifted,
+                    BlsScalar::from(1u64 << 31),
+                    None,
+                );
+            },
+            128,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_xor_and_32() {
+        let res = gadget_tester(
+            |composer| {
+                let values = test_values();
+                for a in values.iter() {
+                    for b in values.iter() {
+                        let a_var =
+                            composer.add_input(BlsScalar::from(*a as u64));
+                        let b_var =
+                            composer.add_input(BlsScalar::from(*b as u64));
+
+                        let xor = composer.xor_32(a_var, b_var);
+                        let and = composer.and_32(a_var, b_var);
+
+                        let expected_xor = BlsScalar::from((a ^ b) as u64);
+                        let expected_and = BlsScalar::from((a & b) as u64);
+                        assert_eq!(composer.variables[&xor], expected_xor);
+                        assert_eq!(composer.variables[&and], expected_and);
+                        composer.constrain_to_constant(xor, expected_xor, None);
+                        composer.constrain_to_constant(and, expected_and, None);
+                    }
+                }
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the input doesn't fit in 32 bits, even if the
+        // logic gate only accumulates its lowest 32 bits
+        let res = gadget_tester(
+            |composer| {
+                let a = composer.add_input(BlsScalar::from((1u64 << 32) + 5));
+                let b = composer.add_input(BlsScalar::from(3u64));
+                let xor = composer.xor_32(a, b);
+                composer.constrain_to_constant(
+                    xor,
+                    BlsScalar::from(6u64),
+                    None,
+                );
+            },
+            128,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_uint32_gate_count() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(0xdead_beefu64));
+        let b = composer.add_input(BlsScalar::from(0xcafe_babeu64));
+        let c = composer.add_input(BlsScalar::from(0xffff_ffffu64));
+
+        let size = composer.circuit_size();
+        composer.mod_2_32_add(a, b);
+        assert_eq!(composer.circuit_size() - size, 8);
+
+        let size = composer.circuit_size();
+        composer.mod_2_32_sub(a, b);
+        assert_eq!(composer.circuit_size() - size, 8);
+
+        let size = composer.circuit_size();
+        composer.mod_2_32_mul(a, b);
+        assert_eq!(composer.circuit_size() - size, 13);
+
+        let size = composer.circuit_size();
+        composer.mod_2_32_add3(a, b, c);
+        assert_eq!(composer.circuit_size() - size, 11);
+
+        let size = composer.circuit_size();
+        composer.rotate_right_32(a, 7).unwrap();
+        assert_eq!(composer.circuit_size() - size, 65);
+
+        let size = composer.circuit_size();
+        composer.shift_right_32(a, 7);
+        assert_eq!(composer.circuit_size() - size, 49 + 13);
+
+        let size = composer.circuit_size();
+        composer.shift_left_32(a, 7);
+        assert_eq!(composer.circuit_size() - size, 65);
+
+        let size = composer.circuit_size();
+        composer.xor_32(a, b);
+        assert_eq!(composer.circuit_size() - size, 19);
+
+        let size = composer.circuit_size();
+        composer.and_32(a, b);
+        assert_eq!(composer.circuit_size() - size, 19);
+    }
+}
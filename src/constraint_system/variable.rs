@@ -7,10 +7,33 @@
 //! This module holds the components needed in the Constraint System.
 //! The two components used are Variables and Wires.
 
+use core::hash::{Hash, Hasher};
+
 /// The value is a reference to the actual value that was added to the
 /// constraint system
-#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
-pub struct Variable(pub(crate) usize);
+///
+/// Along with its index, a variable holds the generation of the composer it
+/// was created in, which tells it apart from the variables reusing its
+/// index after the composer was rolled back with
+/// [`StandardComposer::restore`](super::StandardComposer::restore). The
+/// generation is not part of the identity of the variable, it's only
+/// checked in debug builds.
+#[derive(Debug, Clone, Copy)]
+pub struct Variable(pub(crate) usize, pub(crate) u32);
+
+impl PartialEq for Variable {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Variable {}
+
+impl Hash for Variable {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
 
 /// Stores the data for a specific wire in an arithmetic circuit
 /// This data is the gate index and the type of wire
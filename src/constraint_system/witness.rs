@@ -0,0 +1,416 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Witness values computed outside of the constraints, known as hints.
+//!
+//! Some gadgets need witness values that are cheap to check but expensive or
+//! impossible to compute with gates, such as inverses, square roots, the
+//! results of divisions or bit decompositions. Such values are requested
+//! from the [`WitnessProvider`] of the composer with
+//! [`StandardComposer::hint`], by a label naming the computation and the
+//! values of its input [`Variable`]s, and are then constrained by the
+//! gadget.
+//!
+//! The gadgets of this crate request their hints under the labels defined in
+//! this module, from [`NativeWitness`] unless the composer is given another
+//! provider with [`StandardComposer::set_witness_provider`]. Applications
+//! can implement their own provider to derive their witness values from
+//! their high-level inputs in one place, falling back to [`NativeWitness`]
+//! for the labels they don't handle:
+//!
+//! ```ignore
+//! struct Amounts;
+//!
+//! impl WitnessProvider for Amounts {
+//!     fn compute(
+//!         &self,
+//!         label: &str,
+//!         inputs: &[BlsScalar],
+//!     ) -> Result<BlsScalar, Error> {
+//!         match label {
+//!             "fee" => Ok(inputs[0] * BlsScalar::from(3u64)),
+//!             _ => NativeWitness.compute(label, inputs),
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Hints are only ever witness values: they are not part of the circuit
+//! description, so the verifier never computes them, and a gadget using a
+//! hint must constrain it since the prover is free to assign any value to
+//! it.
+
+use crate::constraint_system::modular::{div_rem_limbs, to_limbs};
+use crate::constraint_system::{StandardComposer, Variable};
+use crate::error::Error;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use dusk_bls12_381::BlsScalar;
+
+/// Label of the multiplicative inverse of `x`, or zero if `x` is zero.
+///
+/// Inputs: `[x]`.
+pub const INVERSE: &str = "inverse";
+
+/// Label of a square root of `x`, failing if `x` is not a quadratic residue.
+///
+/// Inputs: `[x]`.
+pub const SQRT: &str = "sqrt";
+
+/// Label of the field division `a / b`, failing if `b` is zero.
+///
+/// Inputs: `[a, b]`.
+pub const DIVISION: &str = "division";
+
+/// Label of the quotient of the integer division of `a` by `b`, failing if
+/// `b` is zero.
+///
+/// Inputs: `[a, b]`.
+pub const QUOTIENT: &str = "quotient";
+
+/// Label of the remainder of the integer division of `a` by `b`, failing if
+/// `b` is zero.
+///
+/// Inputs: `[a, b]`.
+pub const REMAINDER: &str = "remainder";
+
+/// Label of the bit of `x` at the little-endian position `i`, which is zero
+/// past the 256th bit.
+///
+/// Inputs: `[x, i]`.
+pub const BIT: &str = "bit";
+
+/// Source of the witness values requested by a circuit with
+/// [`StandardComposer::hint`].
+pub trait WitnessProvider {
+    /// Returns the witness value named by the `label`, computed from the
+    /// values of the `inputs`.
+    ///
+    /// Returns [`Error::UnknownWitnessLabel`] for the labels the provider
+    /// doesn't handle.
+    fn compute(
+        &self,
+        label: &str,
+        inputs: &[BlsScalar],
+    ) -> Result<BlsScalar, Error>;
+}
+
+/// [`WitnessProvider`] of the hints used by the gadgets of this crate,
+/// computed natively from their inputs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NativeWitness;
+
+impl NativeWitness {
+    fn inputs<const N: usize>(
+        inputs: &[BlsScalar],
+    ) -> Result<[BlsScalar; N], Error> {
+        let mut array = [BlsScalar::zero(); N];
+        match inputs.len() == N {
+            true => {
+                array.copy_from_slice(inputs);
+                Ok(array)
+            }
+            false => Err(Error::InvalidWitnessInputs {
+                found: inputs.len(),
+                expected: N,
+            }),
+        }
+    }
+
+    fn div_rem(
+        a: &BlsScalar,
+        b: &BlsScalar,
+    ) -> Result<([u64; 4], [u64; 4]), Error> {
+        let divisor = to_limbs(b);
+        if divisor == [0; 4] {
+            return Err(Error::DivisionByZero);
+        }
+
+        Ok(div_rem_limbs(&to_limbs(a), &divisor))
+    }
+}
+
+impl WitnessProvider for NativeWitness {
+    fn compute(
+        &self,
+        label: &str,
+        inputs: &[BlsScalar],
+    ) -> Result<BlsScalar, Error> {
+        match label {
+            INVERSE => {
+                let [x] = Self::inputs(inputs)?;
+                Ok(x.invert().unwrap_or(BlsScalar::zero()))
+            }
+            SQRT => {
+                let [x] = Self::inputs(inputs)?;
+                let root: Option<BlsScalar> = x.sqrt().into();
+                root.ok_or(Error::NoSquareRoot)
+            }
+            DIVISION => {
+                let [a, b] = Self::inputs(inputs)?;
+                let inv: Option<BlsScalar> = b.invert().into();
+                Ok(a * inv.ok_or(Error::DivisionByZero)?)
+            }
+            QUOTIENT => {
+                let [a, b] = Self::inputs(inputs)?;
+                let (quotient, _) = Self::div_rem(&a, &b)?;
+                Ok(BlsScalar::from_raw(quotient))
+            }
+            REMAINDER => {
+                let [a, b] = Self::inputs(inputs)?;
+                let (_, remainder) = Self::div_rem(&a, &b)?;
+                Ok(BlsScalar::from_raw(remainder))
+            }
+            BIT => {
+                let [x, i] = Self::inputs(inputs)?;
+                let i = to_limbs(&i);
+                let bit = match i {
+                    [i, 0, 0, 0] if i < 256 => {
+                        (to_limbs(&x)[i as usize / 64] >> (i % 64)) & 1
+                    }
+                    _ => 0,
+                };
+                Ok(BlsScalar::from(bit))
+            }
+            _ => Err(Error::UnknownWitnessLabel),
+        }
+    }
+}
+
+/// [`WitnessProvider`] held by a [`StandardComposer`].
+pub(crate) struct ComposerWitness(
+    pub(crate) Box<dyn WitnessProvider + Send + Sync>,
+);
+
+impl Default for ComposerWitness {
+    fn default() -> Self {
+        ComposerWitness(Box::new(NativeWitness))
+    }
+}
+
+impl fmt::Debug for ComposerWitness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ComposerWitness")
+    }
+}
+
+impl StandardComposer {
+    /// Sets the [`WitnessProvider`] of the hints requested by the composer,
+    /// which is [`NativeWitness`] by default.
+    ///
+    /// The provider computes the hints of the gadgets of this crate as well,
+    /// so it must compute the labels defined in the [`witness`](self) module
+    /// as [`NativeWitness`] does, or the circuit won't be satisfied.
+    pub fn set_witness_provider<W>(&mut self, provider: W)
+    where
+        W: WitnessProvider + Send + Sync + 'static,
+    {
+        self.witness_provider = ComposerWitness(Box::new(provider));
+    }
+
+    /// Returns a [`Variable`] holding the witness value named by the
+    /// `label`, computed by the [`WitnessProvider`] of the composer from the
+    /// values of the `inputs`.
+    ///
+    /// The value is added to the witness without any constraint, so it's up
+    /// to the caller to constrain it.
+    pub fn hint(
+        &mut self,
+        label: &str,
+        inputs: &[Variable],
+    ) -> Result<Variable, Error> {
+        let values: Vec<BlsScalar> =
+            inputs.iter().map(|input| self.variables[input]).collect();
+
+        self.hint_values(label, &values)
+    }
+
+    /// Returns a [`Variable`] holding the witness value named by the
+    /// `label`, computed by the [`WitnessProvider`] of the composer from the
+    /// `values`, for the hints taking inputs that are not held by a
+    /// [`Variable`], such as the constants of the circuit description.
+    ///
+    /// As for [`StandardComposer::hint`], the value is added to the witness
+    /// without any constraint.
+    pub fn hint_values(
+        &mut self,
+        label: &str,
+        values: &[BlsScalar],
+    ) -> Result<Variable, Error> {
+        let value = self.witness_provider.0.compute(label, values)?;
+
+        Ok(self.add_input(value))
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint_system::helper::*;
+
+    /// Provider deriving a fee from an amount, delegating the other labels
+    /// to [`NativeWitness`].
+    struct FeeWitness;
+
+    impl WitnessProvider for FeeWitness {
+        fn compute(
+            &self,
+            label: &str,
+            inputs: &[BlsScalar],
+        ) -> Result<BlsScalar, Error> {
+            match label {
+                "fee" => Ok(inputs[0] * BlsScalar::from(3u64)),
+                _ => NativeWitness.compute(label, inputs),
+            }
+        }
+    }
+
+    #[test]
+    fn test_native_witness() {
+        let x = BlsScalar::from(10u64);
+        let y = BlsScalar::from(3u64);
+
+        let inverse = NativeWitness.compute(INVERSE, &[x]).unwrap();
+        assert_eq!(inverse * x, BlsScalar::one());
+        assert_eq!(
+            NativeWitness
+                .compute(INVERSE, &[BlsScalar::zero()])
+                .unwrap(),
+            BlsScalar::zero()
+        );
+
+        let root = NativeWitness.compute(SQRT, &[BlsScalar::from(9u64)]);
+        assert_eq!(root.unwrap().square(), BlsScalar::from(9u64));
+
+        let division = NativeWitness.compute(DIVISION, &[x, y]).unwrap();
+        assert_eq!(division * y, x);
+        assert!(matches!(
+            NativeWitness.compute(DIVISION, &[x, BlsScalar::zero()]),
+            Err(Error::DivisionByZero)
+        ));
+
+        assert_eq!(
+            NativeWitness.compute(QUOTIENT, &[x, y]).unwrap(),
+            BlsScalar::from(3u64)
+        );
+        assert_eq!(
+            NativeWitness.compute(REMAINDER, &[x, y]).unwrap(),
+            BlsScalar::one()
+        );
+
+        // 10 = 0b1010
+        let bits: Vec<BlsScalar> = (0..5u64)
+            .map(|i| {
+                NativeWitness
+                    .compute(BIT, &[x, BlsScalar::from(i)])
+                    .unwrap()
+            })
+            .collect();
+        let expected: Vec<BlsScalar> = [0u64, 1, 0, 1, 0]
+            .iter()
+            .map(|b| BlsScalar::from(*b))
+            .collect();
+        assert_eq!(bits, expected);
+
+        assert!(matches!(
+            NativeWitness.compute(INVERSE, &[x, y]),
+            Err(Error::InvalidWitnessInputs {
+                found: 2,
+                expected: 1
+            })
+        ));
+        assert!(matches!(
+            NativeWitness.compute("fee", &[x]),
+            Err(Error::UnknownWitnessLabel)
+        ));
+    }
+
+    #[test]
+    fn test_custom_witness_provider() {
+        let res = gadget_tester(
+            |composer| {
+                composer.set_witness_provider(FeeWitness);
+                let amount = composer.add_input(BlsScalar::from(100u64));
+
+                let n = composer.circuit_size();
+                let fee = composer.hint("fee", &[amount]).unwrap();
+                assert_eq!(composer.circuit_size(), n);
+
+                // The hint is constrained by the circuit
+                let expected = composer.mul_constant(amount, 3u64.into());
+                composer.assert_equal(fee, expected);
+
+                // The labels of the gadgets are still provided
+                let inverse = composer.hint(INVERSE, &[fee]).unwrap();
+                let one = composer.mul(
+                    BlsScalar::one(),
+                    fee,
+                    inverse,
+                    BlsScalar::zero(),
+                    None,
+                );
+                composer.constrain_to_constant(one, BlsScalar::one(), None);
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        // Should fail since the provider computes a fee the circuit rejects
+        let res = gadget_tester(
+            |composer| {
+                composer.set_witness_provider(FeeWitness);
+                let amount = composer.add_input(BlsScalar::from(100u64));
+                let fee = composer.hint("fee", &[amount]).unwrap();
+
+                let expected = composer.mul_constant(amount, 2u64.into());
+                composer.assert_equal(fee, expected);
+            },
+            32,
+        );
+        assert!(res.is_err());
+
+        // The labels are only known to the provider that handles them
+        let mut composer = StandardComposer::new();
+        let amount = composer.add_input(BlsScalar::from(100u64));
+        assert!(matches!(
+            composer.hint("fee", &[amount]),
+            Err(Error::UnknownWitnessLabel)
+        ));
+    }
+
+    #[test]
+    fn test_gadgets_use_witness_provider() {
+        /// Provider returning the other square root than [`NativeWitness`].
+        struct NegatedRoot;
+
+        impl WitnessProvider for NegatedRoot {
+            fn compute(
+                &self,
+                label: &str,
+                inputs: &[BlsScalar],
+            ) -> Result<BlsScalar, Error> {
+                let value = NativeWitness.compute(label, inputs)?;
+                match label {
+                    SQRT => Ok(-value),
+                    _ => Ok(value),
+                }
+            }
+        }
+
+        let nine = BlsScalar::from(9u64);
+        let root = NativeWitness.compute(SQRT, &[nine]).unwrap();
+
+        let mut composer = StandardComposer::new();
+        composer.set_witness_provider(NegatedRoot);
+        let var = composer.add_input(nine);
+        let (r, r_neg) = composer.sqrt(var).unwrap();
+
+        assert_eq!(composer.variables[&r], -root);
+        assert_eq!(composer.variables[&r_neg], root);
+    }
+}
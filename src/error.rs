@@ -6,7 +6,7 @@
 
 //! A collection of all possible errors encountered in PLONK.
 
-use dusk_bytes::Error as DuskBytesError;
+use dusk_bytes::{BadLength, Error as DuskBytesError, InvalidChar};
 
 /// Defines all possible errors that can be encountered in PLONK.
 #[derive(core::fmt::Debug)]
@@ -35,6 +35,82 @@ pub enum Error {
     /// This error occurs when the Prover structure already contains a
     /// preprocessed circuit inside, but you call preprocess again.
     CircuitAlreadyPreprocessed,
+    /// This error occurs when the number of blinding gates of the hiding mode
+    /// is set below
+    /// [`BLINDING_ROWS`](crate::proof_system::prover::BLINDING_ROWS).
+    InsufficientBlindingRows {
+        /// Requested number of blinding gates
+        rows: usize,
+        /// Minimum number of blinding gates
+        min: usize,
+    },
+    /// This error occurs when a circuit is compiled or proved with more gates
+    /// than its budget, see [`Circuit::MAX_GATES`](crate::circuit::Circuit)
+    /// and the `set_max_gates` method of the composer.
+    CircuitTooLarge {
+        /// Number of gates of the circuit
+        size: usize,
+        /// Maximum number of gates allowed
+        max: usize,
+        /// Index of the first gate past the maximum
+        at_gate: usize,
+    },
+    /// This error occurs when the thread pool proving a batch of circuits
+    /// can't be created.
+    #[cfg(feature = "std")]
+    ThreadPoolCreation,
+    /// This error occurs when a proof is requested for a witness that
+    /// doesn't satisfy some gate of the circuit.
+    UnsatisfiedConstraint {
+        /// Index of the first unsatisfied gate
+        gate_index: usize,
+    },
+
+    // Composer errors
+    /// This error occurs when a 32-bit rotation is requested by an amount
+    /// outside of the range `[0, 32)`.
+    InvalidRotationAmount {
+        /// Requested rotation amount
+        amount: usize,
+    },
+    /// This error occurs when a variable is constrained to be a member of an
+    /// empty set of values.
+    EmptySet,
+    /// This error occurs when the witness of a division is computed with a
+    /// divisor equal to zero.
+    DivisionByZero,
+    /// This error occurs when the round constants or the MDS matrix of a
+    /// Poseidon permutation don't match its width and number of rounds.
+    InvalidPoseidonParameters,
+    /// This error occurs when two public inputs are placed at the same
+    /// position of a circuit.
+    DuplicatePublicInputPosition {
+        /// Repeated position
+        position: usize,
+    },
+    /// This error occurs when a public input is placed at a position outside
+    /// of the padded circuit.
+    PublicInputPositionOutOfRange {
+        /// Position of the public input
+        position: usize,
+        /// Padded size of the circuit
+        size: usize,
+    },
+    /// This error occurs when a witness value is requested from a
+    /// [`WitnessProvider`](crate::constraint_system::WitnessProvider) with a
+    /// label it doesn't handle.
+    UnknownWitnessLabel,
+    /// This error occurs when a witness value is requested with the wrong
+    /// number of inputs.
+    InvalidWitnessInputs {
+        /// Number of inputs given
+        found: usize,
+        /// Number of inputs expected by the label
+        expected: usize,
+    },
+    /// This error occurs when the square root of a value that is not a
+    /// quadratic residue is requested.
+    NoSquareRoot,
 
     // Preprocessing errors
     /// This error occurs when an error triggers during the preprocessing
@@ -51,6 +127,14 @@ pub enum Error {
     /// This error occurs when the user tries to trim PublicParameters
     /// down to a degree that is zero.
     TruncatedDegreeIsZero,
+    /// This error occurs when PublicParameters are loaded from a Powers of
+    /// Tau ceremony that doesn't hold enough powers for the requested degree.
+    PpotDegreeTooLarge {
+        /// Requested max degree
+        degree: usize,
+        /// Largest degree supported by the ceremony
+        max: usize,
+    },
     /// This error occurs when the user tries to commit to a polynomial whose
     /// degree is larger than the supported degree for that proving key.
     PolynomialDegreeTooLarge,
@@ -61,6 +145,19 @@ pub enum Error {
     /// Identity point.
     PairingCheckFailure,
 
+    // Verifier key errors
+    /// This error occurs when a VerifierKey is built from a number of
+    /// commitments that doesn't match the one of the key.
+    InvalidCommitmentCount {
+        /// Number of commitments provided
+        found: usize,
+        /// Number of commitments expected
+        expected: usize,
+    },
+    /// This error occurs when the fingerprint of a VerifierKey doesn't match
+    /// the digest it's expected to have.
+    VerifierKeyDigestMismatch,
+
     // Serialization errors
     /// Dusk-bytes serialization error
     BytesError(DuskBytesError),
@@ -72,6 +169,12 @@ pub enum Error {
     /// This error occurs when a malformed BLS scalar is decoded from a byte
     /// array.
     BlsScalarMalformed,
+    /// This error occurs when the bytes of a proof or a key were produced by
+    /// a version of the library with a different serialization format.
+    VersionMismatch,
+    /// This error occurs when a file can't be opened or read.
+    #[cfg(feature = "std")]
+    IoError(std::io::Error),
 }
 
 #[cfg(feature = "std")]
@@ -105,6 +208,55 @@ impl std::fmt::Display for Error {
             Self::CircuitAlreadyPreprocessed => {
                 write!(f, "circuit has already been preprocessed")
             }
+            Self::InsufficientBlindingRows { rows, min } => write!(
+                f,
+                "{} blinding gates are fewer than the minimum of {}",
+                rows, min
+            ),
+            Self::CircuitTooLarge { size, max, at_gate } => write!(
+                f,
+                "circuit has {} gates, past the maximum of {} at gate {}",
+                size, max, at_gate
+            ),
+            Self::ThreadPoolCreation => {
+                write!(f, "failed to create the thread pool")
+            }
+            Self::UnsatisfiedConstraint { gate_index } => write!(
+                f,
+                "the witness doesn't satisfy the constraint of gate {}",
+                gate_index
+            ),
+            Self::InvalidRotationAmount { amount } => {
+                write!(f, "cannot rotate a 32-bit integer by {} bits", amount)
+            }
+            Self::EmptySet => {
+                write!(f, "cannot constrain a variable to an empty set")
+            }
+            Self::DivisionByZero => write!(f, "division by zero"),
+            Self::UnknownWitnessLabel => {
+                write!(f, "unknown witness label")
+            }
+            Self::InvalidWitnessInputs { found, expected } => write!(
+                f,
+                "expected {} inputs to compute the witness, found {}",
+                expected, found
+            ),
+            Self::NoSquareRoot => {
+                write!(f, "the value has no square root in the field")
+            }
+            Self::DuplicatePublicInputPosition { position } => write!(
+                f,
+                "more than one public input placed at position {}",
+                position
+            ),
+            Self::PublicInputPositionOutOfRange { position, size } => write!(
+                f,
+                "public input placed at position {} of a circuit of size {}",
+                position, size
+            ),
+            Self::InvalidPoseidonParameters => {
+                write!(f, "invalid Poseidon round constants or MDS matrix")
+            }
             Self::DegreeIsZero => {
                 write!(f, "cannot create PublicParameters with max degree 0")
             }
@@ -115,6 +267,11 @@ impl std::fmt::Display for Error {
                 f,
                 "cannot trim PublicParameters to a maximum size of zero"
             ),
+            Self::PpotDegreeTooLarge { degree, max } => write!(
+                f,
+                "the Powers of Tau only support degree {}, not {}",
+                max, degree
+            ),
             Self::PolynomialDegreeTooLarge => write!(
                 f,
                 "proving key is not large enough to commit to said polynomial"
@@ -123,10 +280,22 @@ impl std::fmt::Display for Error {
                 write!(f, "cannot commit to polynomial of zero degree")
             }
             Self::PairingCheckFailure => write!(f, "pairing check failed"),
+            Self::InvalidCommitmentCount { found, expected } => write!(
+                f,
+                "expected {} commitments to build the verifier key, found {}",
+                expected, found
+            ),
+            Self::VerifierKeyDigestMismatch => {
+                write!(f, "the verifier key doesn't match the expected digest")
+            }
             Self::NotEnoughBytes => write!(f, "not enough bytes left to read"),
             Self::PointMalformed => write!(f, "BLS point bytes malformed"),
             Self::BlsScalarMalformed => write!(f, "BLS scalar bytes malformed"),
+            Self::VersionMismatch => {
+                write!(f, "serialization version mismatch")
+            }
             Self::BytesError(err) => write!(f, "{:?}", err),
+            Self::IoError(err) => write!(f, "I/O error: {}", err),
         }
     }
 }
@@ -137,5 +306,24 @@ impl From<DuskBytesError> for Error {
     }
 }
 
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(io_err: std::io::Error) -> Self {
+        Self::IoError(io_err)
+    }
+}
+
+impl BadLength for Error {
+    fn bad_length(found: usize, expected: usize) -> Self {
+        Self::BytesError(DuskBytesError::bad_length(found, expected))
+    }
+}
+
+impl InvalidChar for Error {
+    fn invalid_char(ch: char, index: usize) -> Self {
+        Self::BytesError(DuskBytesError::invalid_char(ch, index))
+    }
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
@@ -168,7 +168,10 @@ pub(crate) mod alloc {
             evals.par_iter_mut().for_each(|val| *val *= &self.size_inv);
         }
 
-        fn distribute_powers(coeffs: &mut [BlsScalar], g: BlsScalar) {
+        pub(crate) fn distribute_powers(
+            coeffs: &mut [BlsScalar],
+            g: BlsScalar,
+        ) {
             let mut pow = BlsScalar::one();
             coeffs.iter_mut().for_each(|c| {
                 *c *= &pow;
@@ -176,34 +179,6 @@ pub(crate) mod alloc {
             })
         }
 
-        /// Compute a FFT over a coset of the domain.
-        pub(crate) fn coset_fft(&self, coeffs: &[BlsScalar]) -> Vec<BlsScalar> {
-            let mut coeffs = coeffs.to_vec();
-            self.coset_fft_in_place(&mut coeffs);
-            coeffs
-        }
-
-        /// Compute a FFT over a coset of the domain, modifying the input vector
-        /// in place.
-        fn coset_fft_in_place(&self, coeffs: &mut Vec<BlsScalar>) {
-            Self::distribute_powers(coeffs, GENERATOR);
-            self.fft_in_place(coeffs);
-        }
-
-        /// Compute an IFFT over a coset of the domain.
-        pub(crate) fn coset_ifft(&self, evals: &[BlsScalar]) -> Vec<BlsScalar> {
-            let mut evals = evals.to_vec();
-            self.coset_ifft_in_place(&mut evals);
-            evals
-        }
-
-        /// Compute an IFFT over a coset of the domain, modifying the input
-        /// vector in place.
-        fn coset_ifft_in_place(&self, evals: &mut Vec<BlsScalar>) {
-            self.ifft_in_place(evals);
-            Self::distribute_powers(evals, self.generator_inv);
-        }
-
         #[allow(clippy::needless_range_loop)]
         /// Evaluate all the lagrange polynomials defined by this domain at the
         /// point `tau`.
@@ -300,8 +275,43 @@ pub(crate) mod alloc {
         }
     }
 
+    /// Compute in place the FFT of `evals` over the multiplicative subgroup
+    /// generated by `group_gen`, whose order is the length of `evals`, or the
+    /// IFFT if `inverse` is set.
+    pub(crate) fn fft_slice_in_place(
+        evals: &mut [BlsScalar],
+        group_gen: BlsScalar,
+        inverse: bool,
+    ) {
+        let log_size = evals.len().trailing_zeros();
+        if !inverse {
+            best_fft(evals, group_gen, log_size);
+            return;
+        }
+
+        best_fft(evals, group_gen.invert().unwrap(), log_size);
+
+        let size_inv = BlsScalar::from(evals.len() as u64).invert().unwrap();
+
+        #[cfg(not(feature = "std"))]
+        evals.iter_mut().for_each(|val| *val *= &size_inv);
+
+        #[cfg(feature = "std")]
+        evals.par_iter_mut().for_each(|val| *val *= &size_inv);
+    }
+
+    /// Domains below this `log_2` size are always transformed serially, since
+    /// the cost of spawning the parallel tasks outweighs the gain.
+    #[cfg(feature = "std")]
+    const MIN_PARALLEL_FFT_LOG_SIZE: u32 = 10;
+
     #[cfg(feature = "alloc")]
     fn best_fft(a: &mut [BlsScalar], omega: BlsScalar, log_n: u32) {
+        #[cfg(feature = "std")]
+        if log_n >= MIN_PARALLEL_FFT_LOG_SIZE {
+            return parallel_fft(a, omega, log_n);
+        }
+
         serial_fft(a, omega, log_n)
     }
 
@@ -356,6 +366,60 @@ pub(crate) mod alloc {
         }
     }
 
+    /// Radix-2 FFT computing the butterflies of each stage in parallel.
+    ///
+    /// The result is exactly the same as the one of [`serial_fft`].
+    #[cfg(feature = "std")]
+    pub(crate) fn parallel_fft(
+        a: &mut [BlsScalar],
+        omega: BlsScalar,
+        log_n: u32,
+    ) {
+        let n = a.len();
+        assert_eq!(n, 1 << log_n);
+
+        for k in 0..n {
+            let rk = bitreverse(k as u32, log_n) as usize;
+            if k < rk {
+                a.swap(rk, k);
+            }
+        }
+
+        // Powers of omega, the twiddle factors of every stage are a subset
+        // of them
+        let mut twiddles = Vec::with_capacity(n / 2);
+        let mut w = BlsScalar::one();
+        for _ in 0..n / 2 {
+            twiddles.push(w);
+            w.mul_assign(&omega);
+        }
+
+        let mut m = 1;
+        for _ in 0..log_n {
+            let stride = n / (2 * m);
+
+            // Early stages hold many small independent groups of butterflies
+            // while the latest ones hold a few big groups, so both the groups
+            // and the butterflies within them are processed in parallel.
+            a.par_chunks_mut(2 * m).for_each(|group| {
+                let (lo, hi) = group.split_at_mut(m);
+                lo.par_iter_mut()
+                    .zip(hi.par_iter_mut())
+                    .enumerate()
+                    .for_each(|(j, (lo, hi))| {
+                        let mut t = *hi;
+                        t *= &twiddles[j * stride];
+                        let mut tmp = *lo;
+                        tmp -= &t;
+                        *hi = tmp;
+                        *lo += &t;
+                    });
+            });
+
+            m *= 2;
+        }
+    }
+
     /// An iterator over the elements of the domain.
     #[derive(Debug)]
     pub(crate) struct Elements {
@@ -404,6 +468,31 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn parallel_fft_matches_serial() {
+        use super::alloc::{parallel_fft, serial_fft};
+        use ::alloc::vec::Vec;
+        use rand_core::OsRng;
+
+        for log_n in [1, 4, 10, 13].iter() {
+            let domain = EvaluationDomain::new(1 << log_n).unwrap();
+            let coeffs: Vec<BlsScalar> = (0..domain.size())
+                .map(|_| BlsScalar::random(&mut OsRng))
+                .collect();
+
+            let mut serial = coeffs.clone();
+            serial_fft(&mut serial, domain.group_gen, *log_n);
+            let mut parallel = coeffs.clone();
+            parallel_fft(&mut parallel, domain.group_gen, *log_n);
+            assert_eq!(serial, parallel);
+
+            serial_fft(&mut serial, domain.group_gen_inv, *log_n);
+            parallel_fft(&mut parallel, domain.group_gen_inv, *log_n);
+            assert_eq!(serial, parallel);
+        }
+    }
+
     #[test]
     fn dusk_bytes_evaluation_domain_serde() {
         let eval_domain = EvaluationDomain::new(1 << 13 - 1)
@@ -43,6 +43,11 @@ impl Polynomial {
     }
 
     /// Checks if the given polynomial is zero.
+    ///
+    /// XXX: This check, as well as the degree computations and the arithmetic
+    /// shortcuts relying on it, is variable-time on the coefficients, which
+    /// hold witness data when operating on the wire polynomials. It only
+    /// reveals the number of leading zero coefficients.
     pub(crate) fn is_zero(&self) -> bool {
         self.coeffs.is_empty()
             || self.coeffs.iter().all(|coeff| coeff == &BlsScalar::zero())
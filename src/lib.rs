@@ -57,13 +57,52 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+/// Marks the start of a phase measured by the `PlonkTimer`, which ends the
+/// previous one. Expands to nothing without the `timing` feature.
+macro_rules! timing_phase {
+    ($phase:ident) => {
+        #[cfg(feature = "timing")]
+        crate::timer::enter(crate::timer::$phase);
+    };
+}
+
+/// Enters a `tracing` span with the given name and fields, held by `$guard`
+/// until it's passed to `trace_exit!` or dropped at the end of the scope.
+/// Expands to nothing without the `trace` feature.
+macro_rules! trace_enter {
+    ($guard:ident, $name:literal $(, $($fields:tt)*)?) => {
+        #[cfg(feature = "trace")]
+        let $guard = tracing::info_span!($name $(, $($fields)*)?).entered();
+    };
+}
+
+/// Exits the span held by `$guard`, entered with `trace_enter!`. Expands to
+/// nothing without the `trace` feature.
+macro_rules! trace_exit {
+    ($guard:ident) => {
+        #[cfg(feature = "trace")]
+        drop($guard);
+    };
+}
+
 cfg_if::cfg_if!(
 if #[cfg(feature = "alloc")] {
     #[macro_use]
     extern crate alloc;
+    pub mod backend;
     pub mod constraint_system;
     mod bit_iterator;
     pub mod circuit;
+    #[cfg(feature = "timing")]
+    pub mod timer;
+    #[cfg(feature = "arkworks")]
+    pub mod arkworks;
+    #[cfg(feature = "codegen")]
+    pub mod codegen;
+    #[cfg(any(test, feature = "testing"))]
+    pub mod test_circuits;
+    #[cfg(all(test, feature = "std"))]
+    mod alloc_counter;
     mod util;
     mod permutation;
 });
@@ -73,7 +112,7 @@ pub mod error;
 mod fft;
 pub mod prelude;
 pub mod proof_system;
-mod transcript;
+pub mod transcript;
 
 #[doc = include_str!("../docs/notes-intro.md")]
 pub mod notes {
@@ -5,6 +5,7 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use super::constants::{K1, K2, K3};
+use crate::backend::{fft, ifft, ComputeBackend};
 use crate::constraint_system::{Variable, WireData};
 use crate::fft::{EvaluationDomain, Polynomial};
 use alloc::vec::Vec;
@@ -19,6 +20,13 @@ use itertools::izip;
 pub(crate) struct Permutation {
     // Maps a variable to the wires that it is associated to.
     pub(crate) variable_map: HashMap<Variable, Vec<WireData>>,
+    // Pairs of distinct variables whose wires are in the same cycle.
+    pub(crate) equalities: Vec<(Variable, Variable)>,
+    // Generation of the variables created from now on, bumped each time the
+    // composer is rolled back to a checkpoint.
+    pub(crate) generation: u32,
+    // Generation of each one of the variables, by index.
+    pub(crate) generations: Vec<u32>,
 }
 
 impl Permutation {
@@ -31,6 +39,9 @@ impl Permutation {
     pub(crate) fn with_capacity(expected_size: usize) -> Permutation {
         Permutation {
             variable_map: HashMap::with_capacity(expected_size),
+            equalities: Vec::new(),
+            generation: 0,
+            generations: Vec::with_capacity(expected_size),
         }
     }
 
@@ -39,7 +50,8 @@ impl Permutation {
     /// into the system It is always allocated in the `variable_map`.
     pub(crate) fn new_variable(&mut self) -> Variable {
         // Generate the Variable
-        let var = Variable(self.variable_map.keys().len());
+        let var = Variable(self.variable_map.keys().len(), self.generation);
+        self.generations.push(self.generation);
 
         // Allocate space for the Variable on the variable_map
         // Each vector is initialised with a capacity of 16.
@@ -49,6 +61,17 @@ impl Permutation {
         var
     }
 
+    /// Checks, in debug builds, that the [`Variable`] wasn't discarded by
+    /// rolling the composer back to a checkpoint taken before its creation,
+    /// in which case its index may now be held by another variable.
+    fn debug_assert_current(&self, var: Variable) {
+        debug_assert!(
+            self.generations.get(var.0) == Some(&var.1),
+            "The variable {} was discarded by StandardComposer::restore",
+            var.0
+        );
+    }
+
     /// Checks that the [`Variable`]s are valid by determining if they have been
     /// added to the system
     fn valid_variables(&self, variables: &[Variable]) -> bool {
@@ -89,6 +112,7 @@ impl Permutation {
         var: Variable,
         wire_data: WireData,
     ) {
+        self.debug_assert_current(var);
         assert!(self.valid_variables(&[var]));
 
         // Since we always allocate space for the Vec of WireData when a
@@ -97,6 +121,55 @@ impl Permutation {
         vec_wire_data.push(wire_data);
     }
 
+    /// Links the wires of the [`Variable`]s `a` and `b` in the same cycle of
+    /// the permutation, so they must hold the same value.
+    pub(crate) fn add_equality(&mut self, a: Variable, b: Variable) {
+        self.debug_assert_current(a);
+        self.debug_assert_current(b);
+        assert!(self.valid_variables(&[a, b]));
+        self.equalities.push((a, b));
+    }
+
+    /// Returns the wires of each class of [`Variable`]s holding the same
+    /// value, which are the variables linked by the equalities.
+    ///
+    /// The classes are ordered by their first variable, and the wires of a
+    /// class are the ones of its variables in order of creation, so the
+    /// permutation only depends on the circuit description.
+    fn copy_classes(&self) -> Vec<Vec<WireData>> {
+        // The variables are indexed in order of creation
+        let num_variables = self.variable_map.len();
+        let mut parent: Vec<usize> = (0..num_variables).collect();
+        let find = |parent: &mut Vec<usize>, mut i: usize| {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        };
+
+        for (a, b) in self.equalities.iter() {
+            let root_a = find(&mut parent, a.0);
+            let root_b = find(&mut parent, b.0);
+            let (root, child) = (root_a.min(root_b), root_a.max(root_b));
+            parent[child] = root;
+        }
+
+        let mut classes: Vec<Vec<WireData>> = Vec::new();
+        let mut class_of: HashMap<usize, usize> = HashMap::new();
+        for i in 0..num_variables {
+            let root = find(&mut parent, i);
+            let class = *class_of.entry(root).or_insert_with(|| {
+                classes.push(Vec::new());
+                classes.len() - 1
+            });
+            let var = Variable(i, self.generations[i]);
+            classes[class].extend(&self.variable_map[&var]);
+        }
+
+        classes
+    }
+
     #[allow(clippy::redundant_closure)]
     // Performs shift by one permutation and computes sigma_1, sigma_2 and
     // sigma_3, sigma_4 permutations from the variable maps
@@ -111,7 +184,7 @@ impl Permutation {
 
         let mut sigmas = [sigma_1, sigma_2, sigma_3, sigma_4];
 
-        for (_, wire_data) in self.variable_map.iter() {
+        for wire_data in self.copy_classes().iter() {
             // Gets the data for each wire assosciated with this variable
             for (wire_index, current_wire) in wire_data.iter().enumerate() {
                 // Fetch index of the next wire, if it is the last element
@@ -175,6 +248,7 @@ impl Permutation {
         &mut self,
         n: usize,
         domain: &EvaluationDomain,
+        backend: &dyn ComputeBackend,
     ) -> (Polynomial, Polynomial, Polynomial, Polynomial) {
         // Compute sigma mappings
         let sigmas = self.compute_sigma_permutations(n);
@@ -191,14 +265,24 @@ impl Permutation {
         let fourth_sigma =
             self.compute_permutation_lagrange(&sigmas[3], domain);
 
-        let left_sigma_poly =
-            Polynomial::from_coefficients_vec(domain.ifft(&left_sigma));
-        let right_sigma_poly =
-            Polynomial::from_coefficients_vec(domain.ifft(&right_sigma));
-        let out_sigma_poly =
-            Polynomial::from_coefficients_vec(domain.ifft(&out_sigma));
-        let fourth_sigma_poly =
-            Polynomial::from_coefficients_vec(domain.ifft(&fourth_sigma));
+        let left_sigma_poly = Polynomial::from_coefficients_vec(ifft(
+            backend,
+            domain,
+            &left_sigma,
+        ));
+        let right_sigma_poly = Polynomial::from_coefficients_vec(ifft(
+            backend,
+            domain,
+            &right_sigma,
+        ));
+        let out_sigma_poly = Polynomial::from_coefficients_vec(ifft(
+            backend, domain, &out_sigma,
+        ));
+        let fourth_sigma_poly = Polynomial::from_coefficients_vec(ifft(
+            backend,
+            domain,
+            &fourth_sigma,
+        ));
 
         (
             left_sigma_poly,
@@ -629,6 +713,7 @@ impl Permutation {
     // in the numerator_irreducible and denominator_irreducible functions
     pub(crate) fn compute_permutation_poly(
         &self,
+        backend: &dyn ComputeBackend,
         domain: &EvaluationDomain,
         wires: (&[BlsScalar], &[BlsScalar], &[BlsScalar], &[BlsScalar]),
         beta: &BlsScalar,
@@ -641,10 +726,10 @@ impl Permutation {
         let ks = vec![BlsScalar::one(), K1, K2, K3];
 
         let sigma_mappings = (
-            domain.fft(sigma_polys.0),
-            domain.fft(sigma_polys.1),
-            domain.fft(sigma_polys.2),
-            domain.fft(sigma_polys.3),
+            fft(backend, domain, sigma_polys.0),
+            fft(backend, domain, sigma_polys.1),
+            fft(backend, domain, sigma_polys.2),
+            fft(backend, domain, sigma_polys.3),
         );
 
         // Transpose wires and sigma values to get "rows" in the form [wl_i,
@@ -721,7 +806,7 @@ impl Permutation {
 
         assert_eq!(n, z.len());
 
-        Polynomial::from_coefficients_vec(domain.ifft(&z))
+        Polynomial::from_coefficients_vec(ifft(backend, domain, &z))
     }
 }
 
@@ -729,7 +814,8 @@ impl Permutation {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::constraint_system::StandardComposer;
+    use crate::backend::CpuBackend;
+    use crate::constraint_system::{ArithmeticGate, StandardComposer};
     use crate::fft::Polynomial;
     use dusk_bls12_381::BlsScalar;
     use rand_core::OsRng;
@@ -747,16 +833,42 @@ mod test {
         let two = BlsScalar::from_raw([2, 0, 0, 0]);
 
         // x1 * x4 = x2
-        cs.poly_gate(x1, x4, x2, one, zero, zero, -one, zero, None);
+        ArithmeticGate::new()
+            .left(x1)
+            .right(x4)
+            .output(x2)
+            .q_m(one)
+            .q_o(-one)
+            .apply(&mut cs);
 
         // x1 + x3 = x2
-        cs.poly_gate(x1, x3, x2, zero, one, one, -one, zero, None);
+        ArithmeticGate::new()
+            .left(x1)
+            .right(x3)
+            .output(x2)
+            .q_l(one)
+            .q_r(one)
+            .q_o(-one)
+            .apply(&mut cs);
 
         // x1 + x2 = 2*x3
-        cs.poly_gate(x1, x2, x3, zero, one, one, -two, zero, None);
+        ArithmeticGate::new()
+            .left(x1)
+            .right(x2)
+            .output(x3)
+            .q_l(one)
+            .q_r(one)
+            .q_o(-two)
+            .apply(&mut cs);
 
         // x3 * x4 = 2*x2
-        cs.poly_gate(x3, x4, x2, one, zero, zero, -two, zero, None);
+        ArithmeticGate::new()
+            .left(x3)
+            .right(x4)
+            .output(x2)
+            .q_m(one)
+            .q_o(-two)
+            .apply(&mut cs);
 
         let domain = EvaluationDomain::new(cs.circuit_size()).unwrap();
         let pad = vec![BlsScalar::zero(); domain.size() - cs.w_l.len()];
@@ -790,6 +902,7 @@ mod test {
             .collect();
 
         let mz = cs.perm.compute_permutation_poly(
+            &CpuBackend,
             &domain,
             (&w_l_scalar, &w_r_scalar, &w_o_scalar, &w_4_scalar),
             &beta,
@@ -1169,7 +1282,7 @@ mod test {
             right_sigma_poly,
             out_sigma_poly,
             fourth_sigma_poly,
-        ) = perm.compute_sigma_polynomials(n, &domain);
+        ) = perm.compute_sigma_polynomials(n, &domain, &CpuBackend);
         let (z_vec, numerator_components, denominator_components) = perm
             .compute_slow_permutation_poly(
                 domain,
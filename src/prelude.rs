@@ -11,13 +11,16 @@
 
 #[cfg(feature = "alloc")]
 pub use crate::{
-    circuit::{self, Circuit, PublicInputValue, VerifierData},
+    circuit::{
+        self, Circuit, PlonkVerifier, PublicInputValue, PublicInputs,
+        VerifierContext, VerifierData,
+    },
     commitment_scheme::kzg10::{
         key::{CommitKey, OpeningKey},
         PublicParameters,
     },
-    constraint_system::{Point, StandardComposer, Variable},
-    proof_system::{Prover, ProverKey, Verifier},
+    constraint_system::{ArithmeticGate, Point, StandardComposer, Variable},
+    proof_system::{aggregate, AggregatedProof, Prover, ProverKey, Verifier},
 };
 
 pub use crate::proof_system::{Proof, VerifierKey};
@@ -0,0 +1,97 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Aggregation of [`Proof`]s of the same circuit.
+//!
+//! Each [`Proof`] is verified with a pairing check of two polynomial
+//! openings. The openings of all the aggregated proofs are instead combined
+//! with random powers of a challenge into a single pairing check, so
+//! verifying `N` proofs costs two Miller loops and one final exponentiation
+//! instead of `N` of each.
+
+use crate::commitment_scheme::kzg10::OpeningKey;
+use crate::error::Error;
+use crate::proof_system::widget::VerifierKey;
+use crate::proof_system::{Proof, VerifierScratch};
+use crate::transcript::{TranscriptLabels, TranscriptProtocol, U_LABEL};
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+use merlin::Transcript;
+
+/// Collection of [`Proof`]s of the same circuit, verified at once with
+/// [`verify_aggregated`].
+///
+/// The aggregate keeps every proof, so it's not smaller than the proofs
+/// themselves: only its verification is cheaper.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AggregatedProof {
+    proofs: Vec<Proof>,
+}
+
+impl AggregatedProof {
+    /// Returns the aggregated [`Proof`]s, in order.
+    pub fn proofs(&self) -> &[Proof] {
+        &self.proofs
+    }
+}
+
+/// Aggregates the `proofs` of a circuit so they can be verified at once.
+pub fn aggregate(proofs: &[Proof]) -> AggregatedProof {
+    AggregatedProof {
+        proofs: proofs.to_vec(),
+    }
+}
+
+/// Verifies every [`Proof`] of the `aggregated` proof against the public
+/// inputs at the same index of `pub_inputs`.
+///
+/// The `transcript` must be the one the proofs were created with, before
+/// any proof was appended to it. It's cloned for each one of the proofs.
+///
+/// Fails with [`Error::ProofVerificationError`] if there are no proofs, if
+/// the number of proofs and public inputs differ or if any of the proofs is
+/// invalid.
+pub fn verify_aggregated(
+    aggregated: &AggregatedProof,
+    verifier_key: &VerifierKey,
+    opening_key: &OpeningKey,
+    transcript: &Transcript,
+    pub_inputs: &[&[BlsScalar]],
+) -> Result<(), Error> {
+    let proofs = aggregated.proofs();
+    if proofs.is_empty() || proofs.len() != pub_inputs.len() {
+        return Err(Error::ProofVerificationError);
+    }
+
+    let mut aggregation_transcript = Transcript::new(b"plonk-aggregation");
+    let mut points = Vec::with_capacity(2 * proofs.len());
+    let mut openings = Vec::with_capacity(2 * proofs.len());
+    let mut scratch = VerifierScratch::default();
+
+    for (proof, pub_inputs) in proofs.iter().zip(pub_inputs.iter()) {
+        let mut transcript = transcript.clone();
+        let (proof_points, proof_openings) = proof.opening_claims(
+            verifier_key,
+            &mut transcript,
+            pub_inputs,
+            &TranscriptLabels::DEFAULT,
+            &mut scratch,
+        )?;
+
+        // The transcript of each proof binds all of its elements and public
+        // inputs, so the challenge combining the openings depends on every
+        // one of them
+        let binding = transcript.challenge_scalar(b"aggregation");
+        aggregation_transcript.append_scalar(b"proof", &binding);
+
+        points.extend_from_slice(&proof_points);
+        openings.extend_from_slice(&proof_openings);
+    }
+
+    opening_key
+        .batch_check(&points, &openings, &mut aggregation_transcript, U_LABEL)
+        .map_err(|_| Error::ProofVerificationError)
+}
@@ -8,6 +8,7 @@
 
 cfg_if::cfg_if!(
     if #[cfg(feature = "alloc")] {
+        pub mod aggregation;
         mod preprocess;
         /// Represents a PLONK Prover
         pub mod prover;
@@ -15,7 +16,8 @@ cfg_if::cfg_if!(
         pub(crate) mod quotient_poly;
         /// Represents a PLONK Verifier
         pub mod verifier;
-        pub use prover::Prover;
+        pub use aggregation::{aggregate, verify_aggregated, AggregatedProof};
+        pub use prover::{Prover, ProverStage};
         pub use verifier::Verifier;
         pub use widget::alloc::*;
     }
@@ -25,5 +27,6 @@ cfg_if::cfg_if!(
 pub mod proof;
 pub(crate) mod widget;
 pub use proof::Proof;
-pub use widget::VerifierKey;
+pub use widget::{VerificationCost, VerifierKey};
 pub(crate) mod linearisation_poly;
+pub mod version;
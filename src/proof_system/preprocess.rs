@@ -6,6 +6,7 @@
 
 //! Methods to preprocess the constraint system for use in a proof
 
+use crate::backend::{coset_fft, ifft};
 use crate::commitment_scheme::kzg10::CommitKey;
 use crate::constraint_system::StandardComposer;
 
@@ -103,76 +104,45 @@ impl StandardComposer {
         commit_key: &CommitKey,
         transcript: &mut Transcript,
     ) -> Result<ProverKey, Error> {
+        trace_enter!(
+            _preprocess,
+            "preprocess",
+            size = self.circuit_size().next_power_of_two()
+        );
         let (_, selectors, domain) =
             self.preprocess_shared(commit_key, transcript)?;
 
+        timing_phase!(FFT);
         let domain_4n = EvaluationDomain::new(4 * domain.size())?;
-        let q_m_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_m),
-            domain_4n,
-        );
-        let q_l_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_l),
-            domain_4n,
-        );
-        let q_r_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_r),
-            domain_4n,
-        );
-        let q_o_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_o),
-            domain_4n,
-        );
-        let q_c_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_c),
-            domain_4n,
-        );
-        let q_4_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_4),
-            domain_4n,
-        );
-        let q_arith_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_arith),
-            domain_4n,
-        );
-        let q_range_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_range),
-            domain_4n,
-        );
-        let q_logic_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_logic),
-            domain_4n,
-        );
-        let q_fixed_group_add_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_fixed_group_add),
-            domain_4n,
-        );
-        let q_variable_group_add_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_variable_group_add),
-            domain_4n,
-        );
-
-        let left_sigma_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.left_sigma),
-            domain_4n,
-        );
-        let right_sigma_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.right_sigma),
-            domain_4n,
-        );
-        let out_sigma_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.out_sigma),
-            domain_4n,
-        );
-        let fourth_sigma_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.fourth_sigma),
-            domain_4n,
-        );
+        let backend = commit_key.backend();
+        let coset_fft_4n = |coeffs: &[BlsScalar]| {
+            Evaluations::from_vec_and_domain(
+                coset_fft(backend, &domain_4n, coeffs),
+                domain_4n,
+            )
+        };
+        trace_enter!(_fft, "selector coset fft", size = domain_4n.size());
+        let q_m_eval_4n = coset_fft_4n(&selectors.q_m);
+        let q_l_eval_4n = coset_fft_4n(&selectors.q_l);
+        let q_r_eval_4n = coset_fft_4n(&selectors.q_r);
+        let q_o_eval_4n = coset_fft_4n(&selectors.q_o);
+        let q_c_eval_4n = coset_fft_4n(&selectors.q_c);
+        let q_4_eval_4n = coset_fft_4n(&selectors.q_4);
+        let q_arith_eval_4n = coset_fft_4n(&selectors.q_arith);
+        let q_range_eval_4n = coset_fft_4n(&selectors.q_range);
+        let q_logic_eval_4n = coset_fft_4n(&selectors.q_logic);
+        let q_fixed_group_add_eval_4n =
+            coset_fft_4n(&selectors.q_fixed_group_add);
+        let q_variable_group_add_eval_4n =
+            coset_fft_4n(&selectors.q_variable_group_add);
+
+        let left_sigma_eval_4n = coset_fft_4n(&selectors.left_sigma);
+        let right_sigma_eval_4n = coset_fft_4n(&selectors.right_sigma);
+        let out_sigma_eval_4n = coset_fft_4n(&selectors.out_sigma);
+        let fourth_sigma_eval_4n = coset_fft_4n(&selectors.fourth_sigma);
         // XXX: Remove this and compute it on the fly
-        let linear_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&[BlsScalar::zero(), BlsScalar::one()]),
-            domain_4n,
-        );
+        let linear_eval_4n =
+            coset_fft_4n(&[BlsScalar::zero(), BlsScalar::one()]);
 
         // Prover Key for arithmetic circuits
         let arithmetic_prover_key = widget::arithmetic::ProverKey {
@@ -249,6 +219,11 @@ impl StandardComposer {
         commit_key: &CommitKey,
         transcript: &mut Transcript,
     ) -> Result<widget::VerifierKey, Error> {
+        trace_enter!(
+            _preprocess,
+            "preprocess",
+            size = self.circuit_size().next_power_of_two()
+        );
         let (verifier_key, _, _) =
             self.preprocess_shared(commit_key, transcript)?;
         Ok(verifier_key)
@@ -266,7 +241,9 @@ impl StandardComposer {
         (widget::VerifierKey, SelectorPolynomials, EvaluationDomain),
         Error,
     > {
+        timing_phase!(FFT);
         let domain = EvaluationDomain::new(self.circuit_size())?;
+        trace_enter!(fft, "selector ifft", size = domain.size());
 
         // Check that the length of the wires is consistent.
         self.check_poly_same_len()?;
@@ -274,30 +251,22 @@ impl StandardComposer {
         // 1. Pad circuit to a power of two
         self.pad(domain.size as usize - self.n);
 
-        let q_m_poly =
-            Polynomial::from_coefficients_slice(&domain.ifft(&self.q_m));
-        let q_l_poly =
-            Polynomial::from_coefficients_slice(&domain.ifft(&self.q_l));
-        let q_r_poly =
-            Polynomial::from_coefficients_slice(&domain.ifft(&self.q_r));
-        let q_o_poly =
-            Polynomial::from_coefficients_slice(&domain.ifft(&self.q_o));
-        let q_c_poly =
-            Polynomial::from_coefficients_slice(&domain.ifft(&self.q_c));
-        let q_4_poly =
-            Polynomial::from_coefficients_slice(&domain.ifft(&self.q_4));
-        let q_arith_poly =
-            Polynomial::from_coefficients_slice(&domain.ifft(&self.q_arith));
-        let q_range_poly =
-            Polynomial::from_coefficients_slice(&domain.ifft(&self.q_range));
-        let q_logic_poly =
-            Polynomial::from_coefficients_slice(&domain.ifft(&self.q_logic));
-        let q_fixed_group_add_poly = Polynomial::from_coefficients_slice(
-            &domain.ifft(&self.q_fixed_group_add),
-        );
-        let q_variable_group_add_poly = Polynomial::from_coefficients_slice(
-            &domain.ifft(&self.q_variable_group_add),
-        );
+        let backend = commit_key.backend();
+        let interpolate = |evals: &[BlsScalar]| {
+            Polynomial::from_coefficients_vec(ifft(backend, &domain, evals))
+        };
+
+        let q_m_poly = interpolate(&self.q_m);
+        let q_l_poly = interpolate(&self.q_l);
+        let q_r_poly = interpolate(&self.q_r);
+        let q_o_poly = interpolate(&self.q_o);
+        let q_c_poly = interpolate(&self.q_c);
+        let q_4_poly = interpolate(&self.q_4);
+        let q_arith_poly = interpolate(&self.q_arith);
+        let q_range_poly = interpolate(&self.q_range);
+        let q_logic_poly = interpolate(&self.q_logic);
+        let q_fixed_group_add_poly = interpolate(&self.q_fixed_group_add);
+        let q_variable_group_add_poly = interpolate(&self.q_variable_group_add);
 
         // 2. Compute the sigma polynomials
         let (
@@ -305,8 +274,14 @@ impl StandardComposer {
             right_sigma_poly,
             out_sigma_poly,
             fourth_sigma_poly,
-        ) = self.perm.compute_sigma_polynomials(self.n, &domain);
+        ) = self
+            .perm
+            .compute_sigma_polynomials(self.n, &domain, backend);
+
+        trace_exit!(fft);
 
+        timing_phase!(MSM);
+        trace_enter!(msm, "selector msm", size = domain.size());
         let q_m_poly_commit = commit_key.commit(&q_m_poly).unwrap_or_default();
         let q_l_poly_commit = commit_key.commit(&q_l_poly).unwrap_or_default();
         let q_r_poly_commit = commit_key.commit(&q_r_poly).unwrap_or_default();
@@ -330,6 +305,7 @@ impl StandardComposer {
         let right_sigma_poly_commit = commit_key.commit(&right_sigma_poly)?;
         let out_sigma_poly_commit = commit_key.commit(&out_sigma_poly)?;
         let fourth_sigma_poly_commit = commit_key.commit(&fourth_sigma_poly)?;
+        trace_exit!(msm);
 
         // Verifier Key for arithmetic circuits
         let arithmetic_verifier_key = widget::arithmetic::VerifierKey {
@@ -399,6 +375,7 @@ impl StandardComposer {
         };
 
         // Add the circuit description to the transcript
+        timing_phase!(TRANSCRIPT);
         verifier_key.seed_transcript(transcript);
 
         Ok((verifier_key, selectors, domain))
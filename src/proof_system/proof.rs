@@ -11,9 +11,21 @@
 //! `Proof` structure and it's methods.
 
 use super::linearisation_poly::ProofEvaluations;
+use super::version::{self, HEADER, HEADER_SIZE};
 use crate::commitment_scheme::kzg10::Commitment;
+use crate::error::Error;
+use dusk_bls12_381::G1Affine;
 use dusk_bytes::{DeserializableSlice, Serializable};
 
+/// Number of [`Commitment`]s held by a [`Proof`].
+pub(crate) const PROOF_COMMITMENTS: usize = 11;
+
+/// Number of [`Commitment`]s of a [`Proof`] to the witnesses of its openings.
+pub(crate) const OPENING_WITNESSES: usize = 2;
+
+/// Number of parts the quotient polynomial is split in.
+pub(crate) const QUOTIENT_PARTS: usize = 4;
+
 /// A Proof is a composition of `Commitment`s to the Witness, Permutation,
 /// Quotient, Shifted and Opening polynomials as well as the
 /// `ProofEvaluations`.
@@ -55,10 +67,16 @@ pub struct Proof {
     pub(crate) evaluations: ProofEvaluations,
 }
 
-impl Serializable<{ 11 * Commitment::SIZE + ProofEvaluations::SIZE }>
-    for Proof
+impl
+    Serializable<
+        {
+            HEADER_SIZE
+                + PROOF_COMMITMENTS * Commitment::SIZE
+                + ProofEvaluations::SIZE
+        },
+    > for Proof
 {
-    type Error = dusk_bytes::Error;
+    type Error = Error;
 
     #[allow(unused_must_use)]
     fn to_bytes(&self) -> [u8; Self::SIZE] {
@@ -66,6 +84,7 @@ impl Serializable<{ 11 * Commitment::SIZE + ProofEvaluations::SIZE }>
 
         let mut buf = [0u8; Self::SIZE];
         let mut writer = &mut buf[..];
+        writer.write(&HEADER);
         writer.write(&self.a_comm.to_bytes());
         writer.write(&self.b_comm.to_bytes());
         writer.write(&self.c_comm.to_bytes());
@@ -84,6 +103,7 @@ impl Serializable<{ 11 * Commitment::SIZE + ProofEvaluations::SIZE }>
 
     fn from_bytes(buf: &[u8; Self::SIZE]) -> Result<Self, Self::Error> {
         let mut buffer = &buf[..];
+        version::read_header(&mut buffer)?;
 
         let a_comm = Commitment::from_reader(&mut buffer)?;
         let b_comm = Commitment::from_reader(&mut buffer)?;
@@ -115,15 +135,46 @@ impl Serializable<{ 11 * Commitment::SIZE + ProofEvaluations::SIZE }>
     }
 }
 
+impl Proof {
+    /// Size in bytes of a serialized [`Proof`], which doesn't depend on the
+    /// circuit.
+    pub const SERIALIZED_SIZE: usize = Self::SIZE;
+
+    /// Returns the commitments to the witness polynomials of the left, right,
+    /// output and fourth wires, in this order.
+    ///
+    /// The witness polynomial of a wire interpolates the values assigned to
+    /// it on each gate of the circuit, padded with zeros up to the size of
+    /// the domain. These commitments are the first elements appended to the
+    /// transcript, so every challenge of the proof depends on them and a
+    /// valid [`Proof`] binds the prover to the committed witness.
+    ///
+    /// This allows an external protocol to link a proof to others, or to a
+    /// commitment it already trusts, by checking that the commitments to the
+    /// shared witness columns are the same. The circuits must then place the
+    /// shared witnesses in the same wires and gates, and have every other
+    /// value of those wires fixed.
+    ///
+    /// # Note
+    /// Unless the [`Prover`](super::Prover) runs in hiding mode, the
+    /// commitments are deterministic and reveal whether two proofs were
+    /// created with the same witness values on a wire.
+    pub fn wire_commitments(&self) -> [G1Affine; 4] {
+        [self.a_comm.0, self.b_comm.0, self.c_comm.0, self.d_comm.0]
+    }
+}
+
 #[cfg(feature = "alloc")]
 pub(crate) mod alloc {
     use super::*;
     use crate::{
-        commitment_scheme::kzg10::{AggregateProof, OpeningKey},
+        commitment_scheme::kzg10::{
+            proof::Proof as OpeningProof, AggregateProof, OpeningKey,
+        },
         error::Error,
         fft::EvaluationDomain,
         proof_system::widget::VerifierKey,
-        transcript::TranscriptProtocol,
+        transcript::{TranscriptLabels, TranscriptProtocol},
         util::batch_inversion,
     };
     use ::alloc::vec::Vec;
@@ -134,6 +185,42 @@ pub(crate) mod alloc {
     #[cfg(feature = "std")]
     use rayon::prelude::*;
 
+    /// Buffers used while verifying a [`Proof`], which can be kept to be
+    /// reused by the following verifications instead of being allocated
+    /// again.
+    #[derive(Debug, Clone, Default)]
+    pub(crate) struct VerifierScratch {
+        pi_indices: Vec<usize>,
+        denominators: Vec<BlsScalar>,
+        /// Scalars of the last linearisation commitment.
+        pub(crate) scalars: Vec<BlsScalar>,
+        /// Points of the last linearisation commitment.
+        pub(crate) points: Vec<G1Affine>,
+    }
+
+    /// Challenges of a [`Proof`], in the order they're drawn from its
+    /// transcript by the verifier.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Challenges {
+        /// Permutation challenge `beta`.
+        pub beta: BlsScalar,
+        /// Permutation challenge `gamma`.
+        pub gamma: BlsScalar,
+        /// Quotient challenge `alpha`.
+        pub alpha: BlsScalar,
+        /// Separation challenge of the range gates.
+        pub range_sep: BlsScalar,
+        /// Separation challenge of the logic gates.
+        pub logic_sep: BlsScalar,
+        /// Separation challenge of the fixed base scalar multiplication
+        /// gates.
+        pub fixed_base_sep: BlsScalar,
+        /// Separation challenge of the variable base curve addition gates.
+        pub var_base_sep: BlsScalar,
+        /// Evaluation challenge `z`.
+        pub z: BlsScalar,
+    }
+
     impl Proof {
         /// Performs the verification of a [`Proof`] returning a boolean result.
         pub(crate) fn verify(
@@ -143,51 +230,77 @@ pub(crate) mod alloc {
             opening_key: &OpeningKey,
             pub_inputs: &[BlsScalar],
         ) -> Result<(), Error> {
-            let domain = EvaluationDomain::new(verifier_key.n)?;
-
-            // Subgroup checks are done when the proof is deserialised.
-
-            // In order for the Verifier and Prover to have the same view in the
-            // non-interactive setting Both parties must commit the same
-            // elements into the transcript Below the verifier will simulate
-            // an interaction with the prover by adding the same elements
-            // that the prover added into the transcript, hence generating the
-            // same challenges
-            //
-            // Add commitment to witness polynomials to transcript
-            transcript.append_commitment(b"w_l", &self.a_comm);
-            transcript.append_commitment(b"w_r", &self.b_comm);
-            transcript.append_commitment(b"w_o", &self.c_comm);
-            transcript.append_commitment(b"w_4", &self.d_comm);
+            self.verify_with_scratch(
+                verifier_key,
+                transcript,
+                opening_key,
+                pub_inputs,
+                &TranscriptLabels::DEFAULT,
+                &mut VerifierScratch::default(),
+            )
+        }
 
-            // Compute beta and gamma challenges
-            let beta = transcript.challenge_scalar(b"beta");
-            transcript.append_scalar(b"beta", &beta);
-            let gamma = transcript.challenge_scalar(b"gamma");
-            // Add commitment to permutation polynomial to transcript
-            transcript.append_commitment(b"z", &self.z_comm);
+        /// Performs the verification of a [`Proof`] as [`Proof::verify`]
+        /// does, drawing the challenges with the given `labels` and using the
+        /// buffers of the `scratch`.
+        pub(crate) fn verify_with_scratch(
+            &self,
+            verifier_key: &VerifierKey,
+            transcript: &mut Transcript,
+            opening_key: &OpeningKey,
+            pub_inputs: &[BlsScalar],
+            labels: &TranscriptLabels,
+            scratch: &mut VerifierScratch,
+        ) -> Result<(), Error> {
+            let (points, openings) = self.opening_claims(
+                verifier_key,
+                transcript,
+                pub_inputs,
+                labels,
+                scratch,
+            )?;
 
-            // Compute quotient challenge
-            let alpha = transcript.challenge_scalar(b"alpha");
-            let range_sep_challenge =
-                transcript.challenge_scalar(b"range separation challenge");
-            let logic_sep_challenge =
-                transcript.challenge_scalar(b"logic separation challenge");
-            let fixed_base_sep_challenge =
-                transcript.challenge_scalar(b"fixed base separation challenge");
-            let var_base_sep_challenge = transcript
-                .challenge_scalar(b"variable base separation challenge");
+            // Batch check
+            if opening_key
+                .batch_check(&points, &openings, transcript, labels.u)
+                .is_err()
+            {
+                return Err(Error::ProofVerificationError);
+            }
+            Ok(())
+        }
 
-            // Add commitment to quotient polynomial to transcript
-            transcript.append_commitment(b"t_1", &self.t_1_comm);
-            transcript.append_commitment(b"t_2", &self.t_2_comm);
-            transcript.append_commitment(b"t_3", &self.t_3_comm);
-            transcript.append_commitment(b"t_4", &self.t_4_comm);
+        /// Replays the transcript of the [`Proof`] and returns the two
+        /// polynomial openings, along with the points they are evaluated at,
+        /// whose validity implies the validity of the proof.
+        ///
+        /// The pairing check of the openings is left to the caller, so the
+        /// openings of several proofs can be checked at once.
+        pub(crate) fn opening_claims(
+            &self,
+            verifier_key: &VerifierKey,
+            transcript: &mut Transcript,
+            pub_inputs: &[BlsScalar],
+            labels: &TranscriptLabels,
+            scratch: &mut VerifierScratch,
+        ) -> Result<([BlsScalar; 2], [OpeningProof; 2]), Error> {
+            let domain = EvaluationDomain::new(verifier_key.n)?;
 
-            // Compute evaluation challenge
-            let z_challenge = transcript.challenge_scalar(b"z");
+            // Subgroup checks are done when the proof is deserialised.
+            timing_phase!(TRANSCRIPT);
+            let Challenges {
+                beta,
+                gamma,
+                alpha,
+                range_sep,
+                logic_sep,
+                fixed_base_sep,
+                var_base_sep,
+                z: z_challenge,
+            } = self.challenges(transcript, labels);
 
             // Compute zero polynomial evaluated at `z_challenge`
+            timing_phase!(OPENING);
             let z_h_eval = domain.evaluate_vanishing_polynomial(&z_challenge);
 
             // Compute first lagrange polynomial evaluated at `z_challenge`
@@ -208,15 +321,18 @@ pub(crate) mod alloc {
                 &z_h_eval,
                 &l1_eval,
                 &self.evaluations.perm_eval,
+                scratch,
             );
 
             // Compute commitment to quotient polynomial
             // This method is necessary as we pass the `un-splitted` variation
             // to our commitment scheme
+            timing_phase!(MSM);
             let t_comm =
                 self.compute_quotient_commitment(&z_challenge, domain.size());
 
             // Add evaluations to transcript
+            timing_phase!(TRANSCRIPT);
             transcript.append_scalar(b"a_eval", &self.evaluations.a_eval);
             transcript.append_scalar(b"b_eval", &self.evaluations.b_eval);
             transcript.append_scalar(b"c_eval", &self.evaluations.c_eval);
@@ -250,19 +366,16 @@ pub(crate) mod alloc {
                 .append_scalar(b"r_eval", &self.evaluations.lin_poly_eval);
 
             // Compute linearisation commitment
-            let r_comm = self.compute_linearisation_commitment(
+            timing_phase!(MSM);
+            let r_comm = self.linearisation_commitment(
                 &alpha,
                 &beta,
                 &gamma,
-                (
-                    &range_sep_challenge,
-                    &logic_sep_challenge,
-                    &fixed_base_sep_challenge,
-                    &var_base_sep_challenge,
-                ),
+                (&range_sep, &logic_sep, &fixed_base_sep, &var_base_sep),
                 &z_challenge,
                 l1_eval,
-                &verifier_key,
+                verifier_key,
+                scratch,
             );
 
             // Commitment Scheme
@@ -275,8 +388,37 @@ pub(crate) mod alloc {
             // permutation polynomial evaluated at the shifted root of unity is
             // correct
 
-            // Compose the Aggregated Proof
-            //
+            // Compose the Aggregated Proof and the shifted one
+            timing_phase!(OPENING);
+            let [aggregate_proof, shifted_aggregate_proof] =
+                self.aggregate_proofs(t_eval, t_comm, r_comm, verifier_key);
+
+            // Flatten proof with opening challenge
+            let flattened_proof_a =
+                aggregate_proof.flatten(transcript, labels.v);
+            let flattened_proof_b =
+                shifted_aggregate_proof.flatten(transcript, labels.v);
+
+            // Add commitment to openings to transcript
+            transcript.append_commitment(b"w_z", &self.w_z_comm);
+            transcript.append_commitment(b"w_z_w", &self.w_zw_comm);
+
+            Ok((
+                [z_challenge, (z_challenge * domain.group_gen)],
+                [flattened_proof_a, flattened_proof_b],
+            ))
+        }
+
+        /// Returns the [`AggregateProof`]s of the polynomials opened at the
+        /// evaluation challenge and at its shift, given the evaluation and
+        /// the commitment of the quotient and linearisation polynomials.
+        fn aggregate_proofs(
+            &self,
+            t_eval: BlsScalar,
+            t_comm: Commitment,
+            r_comm: Commitment,
+            verifier_key: &VerifierKey,
+        ) -> [AggregateProof; OPENING_WITNESSES] {
             let mut aggregate_proof =
                 AggregateProof::with_witness(self.w_z_comm);
             aggregate_proof.add_part((t_eval, t_comm));
@@ -297,10 +439,7 @@ pub(crate) mod alloc {
                 self.evaluations.out_sigma_eval,
                 verifier_key.permutation.out_sigma,
             ));
-            // Flatten proof with opening challenge
-            let flattened_proof_a = aggregate_proof.flatten(transcript);
 
-            // Compose the shifted aggregate proof
             let mut shifted_aggregate_proof =
                 AggregateProof::with_witness(self.w_zw_comm);
             shifted_aggregate_proof
@@ -311,24 +450,106 @@ pub(crate) mod alloc {
                 .add_part((self.evaluations.b_next_eval, self.b_comm));
             shifted_aggregate_proof
                 .add_part((self.evaluations.d_next_eval, self.d_comm));
-            let flattened_proof_b = shifted_aggregate_proof.flatten(transcript);
 
-            // Add commitment to openings to transcript
-            transcript.append_commitment(b"w_z", &self.w_z_comm);
-            transcript.append_commitment(b"w_z_w", &self.w_zw_comm);
+            [aggregate_proof, shifted_aggregate_proof]
+        }
 
-            // Batch check
-            if opening_key
-                .batch_check(
-                    &[z_challenge, (z_challenge * domain.group_gen)],
-                    &[flattened_proof_a, flattened_proof_b],
-                    transcript,
-                )
-                .is_err()
-            {
-                return Err(Error::ProofVerificationError);
+        /// Returns the number of terms of the multi-scalar multiplication
+        /// computing the linearisation commitment of the proofs of the
+        /// circuit of the `verifier_key`, along with the number of
+        /// polynomials of each opening.
+        ///
+        /// The terms are counted by building both for a dummy proof, so they
+        /// always match what the verifier computes.
+        pub(crate) fn msm_terms(
+            verifier_key: &VerifierKey,
+        ) -> (usize, [usize; OPENING_WITNESSES]) {
+            let proof = Proof::default();
+            let zero = BlsScalar::zero();
+            let mut scratch = VerifierScratch::default();
+            proof.linearisation_terms(
+                &zero,
+                &zero,
+                &zero,
+                (&zero, &zero, &zero, &zero),
+                &zero,
+                zero,
+                verifier_key,
+                &mut scratch,
+            );
+
+            let commitment = Commitment::default();
+            let aggregate_proofs = proof.aggregate_proofs(
+                zero,
+                commitment,
+                commitment,
+                verifier_key,
+            );
+            let mut opened = [0; OPENING_WITNESSES];
+            opened.iter_mut().zip(aggregate_proofs.iter()).for_each(
+                |(opened, aggregate_proof)| {
+                    *opened = aggregate_proof.commitments_to_polynomials.len()
+                },
+            );
+
+            (scratch.points.len(), opened)
+        }
+
+        /// Replays the transcript of the [`Proof`] up to the evaluation
+        /// challenge, returning the [`Challenges`] drawn with the `labels`.
+        ///
+        /// In the non-interactive setting, the verifier and the prover only
+        /// draw the same challenges if they append the same elements to the
+        /// transcript. The verifier simulates the interaction by appending
+        /// the commitments of the proof in the order the prover did.
+        pub(crate) fn challenges(
+            &self,
+            transcript: &mut Transcript,
+            labels: &TranscriptLabels,
+        ) -> Challenges {
+            // Add commitment to witness polynomials to transcript
+            transcript.append_commitment(b"w_l", &self.a_comm);
+            transcript.append_commitment(b"w_r", &self.b_comm);
+            transcript.append_commitment(b"w_o", &self.c_comm);
+            transcript.append_commitment(b"w_4", &self.d_comm);
+
+            // Compute beta and gamma challenges
+            let beta = transcript.challenge_scalar(labels.beta);
+            transcript.append_scalar(b"beta", &beta);
+            let gamma = transcript.challenge_scalar(labels.gamma);
+            // Add commitment to permutation polynomial to transcript
+            transcript.append_commitment(b"z", &self.z_comm);
+
+            // Compute quotient challenge
+            let alpha = transcript.challenge_scalar(labels.alpha);
+            let range_sep =
+                transcript.challenge_scalar(b"range separation challenge");
+            let logic_sep =
+                transcript.challenge_scalar(b"logic separation challenge");
+            let fixed_base_sep =
+                transcript.challenge_scalar(b"fixed base separation challenge");
+            let var_base_sep = transcript
+                .challenge_scalar(b"variable base separation challenge");
+
+            // Add commitment to quotient polynomial to transcript
+            transcript.append_commitment(b"t_1", &self.t_1_comm);
+            transcript.append_commitment(b"t_2", &self.t_2_comm);
+            transcript.append_commitment(b"t_3", &self.t_3_comm);
+            transcript.append_commitment(b"t_4", &self.t_4_comm);
+
+            // Compute evaluation challenge
+            let z = transcript.challenge_scalar(labels.zeta);
+
+            Challenges {
+                beta,
+                gamma,
+                alpha,
+                range_sep,
+                logic_sep,
+                fixed_base_sep,
+                var_base_sep,
+                z,
             }
-            Ok(())
         }
 
         fn compute_quotient_evaluation(
@@ -342,10 +563,15 @@ pub(crate) mod alloc {
             z_h_eval: &BlsScalar,
             l1_eval: &BlsScalar,
             z_hat_eval: &BlsScalar,
+            scratch: &mut VerifierScratch,
         ) -> BlsScalar {
             // Compute the public input polynomial evaluated at `z_challenge`
-            let pi_eval =
-                compute_barycentric_eval(pub_inputs, z_challenge, domain);
+            let pi_eval = compute_barycentric_eval(
+                pub_inputs,
+                z_challenge,
+                domain,
+                scratch,
+            );
 
             let alpha_sq = alpha.square();
             // r + PI(z)
@@ -392,7 +618,43 @@ pub(crate) mod alloc {
         }
 
         // Commitment to [r]_1
-        fn compute_linearisation_commitment(
+        fn linearisation_commitment(
+            &self,
+            alpha: &BlsScalar,
+            beta: &BlsScalar,
+            gamma: &BlsScalar,
+            separation_challenges: (
+                &BlsScalar,
+                &BlsScalar,
+                &BlsScalar,
+                &BlsScalar,
+            ),
+            z_challenge: &BlsScalar,
+            l1_eval: BlsScalar,
+            verifier_key: &VerifierKey,
+            scratch: &mut VerifierScratch,
+        ) -> Commitment {
+            self.linearisation_terms(
+                alpha,
+                beta,
+                gamma,
+                separation_challenges,
+                z_challenge,
+                l1_eval,
+                verifier_key,
+                scratch,
+            );
+
+            Commitment::from(msm_variable_base(
+                &scratch.points,
+                &scratch.scalars,
+            ))
+        }
+
+        /// Fills the `scratch` with the points and scalars of the
+        /// multi-scalar multiplication computing the linearisation
+        /// commitment.
+        fn linearisation_terms(
             &self,
             alpha: &BlsScalar,
             beta: &BlsScalar,
@@ -406,55 +668,145 @@ pub(crate) mod alloc {
             z_challenge: &BlsScalar,
             l1_eval: BlsScalar,
             verifier_key: &VerifierKey,
-        ) -> Commitment {
-            let mut scalars: Vec<_> = Vec::with_capacity(6);
-            let mut points: Vec<G1Affine> = Vec::with_capacity(6);
+            scratch: &mut VerifierScratch,
+        ) {
+            let scalars = &mut scratch.scalars;
+            let points = &mut scratch.points;
+            scalars.clear();
+            points.clear();
 
             verifier_key.arithmetic.compute_linearisation_commitment(
-                &mut scalars,
-                &mut points,
+                scalars,
+                points,
                 &self.evaluations,
             );
 
             verifier_key.range.compute_linearisation_commitment(
                 &range_sep_challenge,
-                &mut scalars,
-                &mut points,
+                scalars,
+                points,
                 &self.evaluations,
             );
 
             verifier_key.logic.compute_linearisation_commitment(
                 &logic_sep_challenge,
-                &mut scalars,
-                &mut points,
+                scalars,
+                points,
                 &self.evaluations,
             );
 
             verifier_key.fixed_base.compute_linearisation_commitment(
                 &fixed_base_sep_challenge,
-                &mut scalars,
-                &mut points,
+                scalars,
+                points,
                 &self.evaluations,
             );
 
             verifier_key.variable_base.compute_linearisation_commitment(
                 &var_base_sep_challenge,
-                &mut scalars,
-                &mut points,
+                scalars,
+                points,
                 &self.evaluations,
             );
 
             verifier_key.permutation.compute_linearisation_commitment(
-                &mut scalars,
-                &mut points,
+                scalars,
+                points,
                 &self.evaluations,
                 z_challenge,
                 (alpha, beta, gamma),
                 &l1_eval,
                 self.z_comm.0,
             );
+        }
+    }
+
+    #[cfg(feature = "verifier-steps")]
+    impl Proof {
+        /// Replays the transcript of the [`Proof`] up to the evaluation
+        /// challenge, returning the [`Challenges`] drawn from it.
+        ///
+        /// The `transcript` is expected to hold the circuit description, as
+        /// the `preprocessed_transcript` of the
+        /// [`Verifier`](crate::proof_system::Verifier) does, and is left as
+        /// the verifier leaves it after drawing the evaluation challenge.
+        /// The challenges are drawn with the `labels` the proof was computed
+        /// with, which are [`TranscriptLabels::DEFAULT`] unless set with
+        /// [`Prover::set_transcript_labels`].
+        ///
+        /// [`Prover::set_transcript_labels`]:
+        /// crate::proof_system::Prover::set_transcript_labels
+        pub fn compute_challenges(
+            &self,
+            transcript: &mut Transcript,
+            labels: &TranscriptLabels,
+        ) -> Challenges {
+            self.challenges(transcript, labels)
+        }
+
+        /// Returns the commitment to the linearisation polynomial of the
+        /// [`Proof`], computed by the verifier as the multi-scalar
+        /// multiplication of the commitments of the `verifier_key` and of
+        /// the proof with coefficients depending on the `challenges`.
+        pub fn compute_linearisation_commitment(
+            &self,
+            verifier_key: &VerifierKey,
+            challenges: &Challenges,
+        ) -> Result<G1Affine, Error> {
+            let domain =
+                EvaluationDomain::new(verifier_key.padded_circuit_size())?;
+            let z_h_eval = domain.evaluate_vanishing_polynomial(&challenges.z);
+            let l1_eval = compute_first_lagrange_evaluation(
+                &domain,
+                &z_h_eval,
+                &challenges.z,
+            );
+
+            let r_comm = self.linearisation_commitment(
+                &challenges.alpha,
+                &challenges.beta,
+                &challenges.gamma,
+                (
+                    &challenges.range_sep,
+                    &challenges.logic_sep,
+                    &challenges.fixed_base_sep,
+                    &challenges.var_base_sep,
+                ),
+                &challenges.z,
+                l1_eval,
+                verifier_key,
+                &mut VerifierScratch::default(),
+            );
+
+            Ok(r_comm.0)
+        }
+
+        /// Replays the verification of the [`Proof`] against the dense
+        /// `pub_inputs`, returning the points `(W, C)` of G1 the final
+        /// pairing check is performed on.
+        ///
+        /// The proof is valid if `e(W, beta_h) * e(C, h)` is the identity,
+        /// where `beta_h` and `h` are the G2 points of the `opening_key`.
+        /// The `transcript` and the `labels` are expected to be the ones of
+        /// [`Proof::compute_challenges`].
+        pub fn compute_pairing_inputs(
+            &self,
+            verifier_key: &VerifierKey,
+            opening_key: &OpeningKey,
+            transcript: &mut Transcript,
+            pub_inputs: &[BlsScalar],
+            labels: &TranscriptLabels,
+        ) -> Result<(G1Affine, G1Affine), Error> {
+            let (points, openings) = self.opening_claims(
+                verifier_key,
+                transcript,
+                pub_inputs,
+                labels,
+                &mut VerifierScratch::default(),
+            )?;
 
-            Commitment::from(msm_variable_base(&points, &scalars))
+            Ok(opening_key
+                .pairing_inputs(&points, &openings, transcript, labels.u))
         }
     }
 
@@ -472,6 +824,7 @@ pub(crate) mod alloc {
         evaluations: &[BlsScalar],
         point: &BlsScalar,
         domain: &EvaluationDomain,
+        scratch: &mut VerifierScratch,
     ) -> BlsScalar {
         let numerator = (point.pow(&[domain.size() as u64, 0, 0, 0])
             - BlsScalar::one())
@@ -484,12 +837,18 @@ pub(crate) mod alloc {
         #[cfg(feature = "std")]
         let range = (0..evaluations.len()).into_par_iter();
 
-        let non_zero_evaluations: Vec<usize> = range
-            .filter(|&i| {
-                let evaluation = &evaluations[i];
-                evaluation != &BlsScalar::zero()
-            })
-            .collect();
+        let non_zero_evaluations = &mut scratch.pi_indices;
+        non_zero_evaluations.clear();
+        let non_zero = range.filter(|&i| {
+            let evaluation = &evaluations[i];
+            evaluation != &BlsScalar::zero()
+        });
+
+        #[cfg(not(feature = "std"))]
+        non_zero_evaluations.extend(non_zero);
+
+        #[cfg(feature = "std")]
+        non_zero_evaluations.par_extend(non_zero);
 
         // Only compute the denominators with non-zero evaluations
         #[cfg(not(feature = "std"))]
@@ -498,17 +857,25 @@ pub(crate) mod alloc {
         #[cfg(feature = "std")]
         let range = (0..non_zero_evaluations.len()).into_par_iter();
 
-        let mut denominators: Vec<BlsScalar> = range
-            .clone()
-            .map(|i| {
-                // index of non-zero evaluation
-                let index = non_zero_evaluations[i];
+        let non_zero_evaluations = &*non_zero_evaluations;
+        let denominators = &mut scratch.denominators;
+        denominators.clear();
+        let inverses = range.clone().map(|i| {
+            // index of non-zero evaluation
+            let index = non_zero_evaluations[i];
 
-                (domain.group_gen_inv.pow(&[index as u64, 0, 0, 0]) * point)
-                    - BlsScalar::one()
-            })
-            .collect();
-        batch_inversion(&mut denominators);
+            (domain.group_gen_inv.pow(&[index as u64, 0, 0, 0]) * point)
+                - BlsScalar::one()
+        });
+
+        #[cfg(not(feature = "std"))]
+        denominators.extend(inverses);
+
+        #[cfg(feature = "std")]
+        denominators.par_extend(inverses);
+
+        batch_inversion(denominators);
+        let denominators = &*denominators;
 
         let result: BlsScalar = range
             .map(|i| {
@@ -563,8 +930,107 @@ mod proof_tests {
             },
         };
 
-        let proof_bytes = proof.to_bytes();
+        let mut proof_bytes = proof.to_bytes();
         let got_proof = Proof::from_bytes(&proof_bytes).unwrap();
         assert_eq!(got_proof, proof);
+
+        // Bytes of a different version are rejected
+        proof_bytes[3] = proof_bytes[3].wrapping_add(1);
+        assert!(matches!(
+            Proof::from_bytes(&proof_bytes),
+            Err(Error::VersionMismatch)
+        ));
+    }
+
+    #[cfg(all(feature = "std", feature = "verifier-steps"))]
+    #[test]
+    fn test_verifier_steps() -> Result<(), Error> {
+        use super::alloc::VerifierScratch;
+        use crate::commitment_scheme::kzg10::PublicParameters;
+        use crate::constraint_system::helper::dummy_gadget;
+        use crate::proof_system::{Prover, Verifier};
+        use crate::transcript::TranscriptLabels;
+        use dusk_bls12_381::multiscalar_mul::msm_variable_base;
+        use dusk_bls12_381::{pairing, G1Affine, Gt};
+
+        let pp = PublicParameters::setup(1 << 7, &mut OsRng)?;
+
+        let mut prover = Prover::new(b"steps");
+        dummy_gadget(10, prover.mut_cs());
+        let (ck, _) = pp.trim(1 << 6)?;
+        let pi = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck)?;
+
+        let mut verifier = Verifier::new(b"steps");
+        dummy_gadget(10, verifier.mut_cs());
+        let (ck, opening_key) = pp.trim(1 << 6)?;
+        verifier.preprocess(&ck)?;
+        verifier.verify(&proof, &opening_key, &pi)?;
+        let verifier_key = verifier.verifier_key.as_ref().unwrap();
+
+        // The challenges only depend on the transcript, the labels and the
+        // proof
+        let labels = &TranscriptLabels::DEFAULT;
+        let challenges = proof.compute_challenges(
+            &mut verifier.preprocessed_transcript.clone(),
+            labels,
+        );
+        assert_eq!(
+            proof.compute_challenges(
+                &mut verifier.preprocessed_transcript.clone(),
+                labels
+            ),
+            challenges
+        );
+        let mut other_labels = TranscriptLabels::DEFAULT;
+        other_labels.zeta = b"other z";
+        assert_ne!(
+            proof.compute_challenges(
+                &mut verifier.preprocessed_transcript.clone(),
+                &other_labels
+            ),
+            challenges
+        );
+
+        // The linearisation commitment is the one of the verification
+        let mut scratch = VerifierScratch::default();
+        proof.opening_claims(
+            verifier_key,
+            &mut verifier.preprocessed_transcript.clone(),
+            &pi,
+            labels,
+            &mut scratch,
+        )?;
+        assert_eq!(
+            proof
+                .compute_linearisation_commitment(verifier_key, &challenges)?,
+            G1Affine::from(msm_variable_base(
+                &scratch.points,
+                &scratch.scalars
+            ))
+        );
+
+        let is_valid = |proof: &Proof| -> Result<bool, Error> {
+            let (w, c) = proof.compute_pairing_inputs(
+                verifier_key,
+                &opening_key,
+                &mut verifier.preprocessed_transcript.clone(),
+                &pi,
+                labels,
+            )?;
+            Ok(
+                pairing(&w, &opening_key.beta_h) + pairing(&c, &opening_key.h)
+                    == Gt::identity(),
+            )
+        };
+        assert!(is_valid(&proof)?);
+
+        // Should fail since an evaluation of the proof is tampered with
+        let mut tampered = proof;
+        tampered.evaluations.a_eval += BlsScalar::one();
+        assert!(!is_valid(&tampered)?);
+        assert!(verifier.verify(&tampered, &opening_key, &pi).is_err());
+
+        Ok(())
     }
 }
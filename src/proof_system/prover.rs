@@ -5,19 +5,46 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::{
+    backend::{ifft, CpuBackend},
     commitment_scheme::kzg10::CommitKey,
     constraint_system::{StandardComposer, Variable},
     error::Error,
     fft::{EvaluationDomain, Polynomial},
     proof_system::{
-        linearisation_poly, proof::Proof, quotient_poly, ProverKey,
+        linearisation_poly, proof::Proof, quotient_poly, Challenges, ProverKey,
     },
-    transcript::TranscriptProtocol,
+    transcript::{TranscriptLabels, TranscriptProtocol},
 };
+use alloc::string::String;
 use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
 use merlin::Transcript;
 
+/// Default number of blinding gates appended to the circuit when the
+/// [`Prover`] runs in hiding mode.
+///
+/// The wire polynomials `a`, `b` and `d` are opened at two points (`z` and
+/// `z * omega`), so at least three random evaluations per wire are needed for
+/// the openings to be independent of the witness. This is the equivalent to
+/// blinding each wire polynomial with a random polynomial of degree two
+/// multiplied by the vanishing polynomial of the domain.
+pub const BLINDING_ROWS: usize = 3;
+
+/// Stage of the computation of a [`Proof`], reported by
+/// [`Prover::prove_with_progress`] when it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProverStage {
+    /// Interpolation of the wire polynomials and commitment to them.
+    CommitWires,
+    /// Computation of the permutation polynomial and commitment to it.
+    Permutation,
+    /// Computation of the quotient polynomial and commitment to its parts.
+    Quotient,
+    /// Evaluation of the polynomials at the challenge and computation of
+    /// the opening witnesses.
+    Opening,
+}
+
 /// Abstraction structure designed to construct a circuit and generate
 /// [`Proof`]s for it.
 #[allow(missing_debug_implementations)]
@@ -29,6 +56,20 @@ pub struct Prover {
     /// Store the messages exchanged during the preprocessing stage
     /// This is copied each time, we make a proof
     pub preprocessed_transcript: Transcript,
+    /// Labels of the challenges drawn from the transcript
+    pub(crate) transcript_labels: TranscriptLabels,
+    /// Whether the witness is checked against the gates before proving
+    pub(crate) sanity_checks: bool,
+
+    /// Whether the witness polynomials are blinded before being committed
+    #[cfg(feature = "std")]
+    pub(crate) hiding: bool,
+    /// Whether the blinding gates were already added to the current circuit
+    #[cfg(feature = "std")]
+    pub(crate) blinded: bool,
+    /// Number of blinding gates appended to the circuit in hiding mode
+    #[cfg(feature = "std")]
+    pub(crate) blinding_rows: usize,
 }
 
 impl Prover {
@@ -42,12 +83,120 @@ impl Prover {
         if self.prover_key.is_some() {
             return Err(Error::CircuitAlreadyPreprocessed);
         }
+        #[cfg(feature = "std")]
+        self.blind_circuit();
+
         let pk = self
             .cs
             .preprocess_prover(commit_key, &mut self.preprocessed_transcript)?;
         self.prover_key = Some(pk);
         Ok(())
     }
+
+    /// Enables or disables the zero-knowledge (hiding) mode of the `Prover`.
+    ///
+    /// When enabled, [`BLINDING_ROWS`] gates holding random witnesses, or
+    /// the number set with [`Prover::set_blinding_rows`], are
+    /// appended to the circuit before it's preprocessed and before each proof
+    /// is computed, so that the evaluations of the wire polynomials contained
+    /// in the [`Proof`] don't leak information about the witness. As a
+    /// consequence, two proofs of the same witness will differ.
+    ///
+    /// # Note
+    /// The blinding gates are part of the circuit description, so the
+    /// [`Verifier`](super::Verifier) must enable the hiding mode as well with
+    /// [`Verifier::set_hiding`](super::Verifier::set_hiding).
+    ///
+    /// Only the wire polynomials are blinded, the permutation polynomial is
+    /// not.
+    #[cfg(feature = "std")]
+    pub fn set_hiding(&mut self, hiding: bool) {
+        self.hiding = hiding;
+    }
+
+    /// Sets the number of blinding gates appended to the circuit in hiding
+    /// mode, which is [`BLINDING_ROWS`] by default.
+    ///
+    /// Each blinding gate adds a random evaluation to every wire polynomial,
+    /// so the openings stay independent of the witness as long as there are
+    /// more blinding gates than points each polynomial is opened at. The
+    /// blinding gates are part of the circuit, so more of them can increase
+    /// the size of the domain, and thus of the [`CommitKey`] needed.
+    ///
+    /// # Errors
+    /// Returns [`Error::InsufficientBlindingRows`] if `rows` is lower than
+    /// [`BLINDING_ROWS`], and [`Error::CircuitAlreadyPreprocessed`] if the
+    /// circuit has already been blinded or preprocessed.
+    ///
+    /// # Note
+    /// The [`Verifier`](super::Verifier) must set the same number with
+    /// [`Verifier::set_blinding_rows`](super::Verifier::set_blinding_rows).
+    #[cfg(feature = "std")]
+    pub fn set_blinding_rows(&mut self, rows: usize) -> Result<(), Error> {
+        if rows < BLINDING_ROWS {
+            return Err(Error::InsufficientBlindingRows {
+                rows,
+                min: BLINDING_ROWS,
+            });
+        }
+        if self.blinded || self.prover_key.is_some() {
+            return Err(Error::CircuitAlreadyPreprocessed);
+        }
+        self.blinding_rows = rows;
+        Ok(())
+    }
+
+    /// Sets the labels of the challenges drawn from the transcript, to
+    /// generate [`Proof`]s compatible with another implementation.
+    ///
+    /// The proofs are only valid for a [`Verifier`](super::Verifier) using
+    /// the same labels, see [`TranscriptLabels`]. The default labels are the
+    /// ones expected by [`verify_proof`](crate::circuit::verify_proof).
+    pub fn set_transcript_labels(&mut self, labels: TranscriptLabels) {
+        self.transcript_labels = labels;
+    }
+
+    /// Sets whether the witness is checked to satisfy the gates of the
+    /// circuit before any proving work, which is enabled by default in debug
+    /// builds only.
+    ///
+    /// When enabled, proving an unsatisfied circuit fails with
+    /// [`Error::UnsatisfiedConstraint`] holding the index of the first
+    /// unsatisfied gate, see
+    /// [`StandardComposer::first_unsatisfied_gate`]. The check takes a pass
+    /// over every gate, which can be skipped if the witness generation is
+    /// trusted.
+    pub fn set_sanity_checks(&mut self, sanity_checks: bool) {
+        self.sanity_checks = sanity_checks;
+    }
+
+    /// Returns [`Error::UnsatisfiedConstraint`] if the sanity checks are
+    /// enabled and the witness doesn't satisfy some gate.
+    fn check_witness(&self) -> Result<(), Error> {
+        if !self.sanity_checks {
+            return Ok(());
+        }
+
+        match self.cs.first_unsatisfied_gate() {
+            Some(gate_index) => {
+                Err(Error::UnsatisfiedConstraint { gate_index })
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Appends the blinding gates to the circuit if the hiding mode is enabled
+    /// and they were not added yet.
+    #[cfg(feature = "std")]
+    fn blind_circuit(&mut self) {
+        if self.hiding && !self.blinded {
+            self.cs.add_blinding_factors(
+                self.blinding_rows,
+                &mut rand_core::OsRng,
+            );
+            self.blinded = true;
+        }
+    }
 }
 
 impl Default for Prover {
@@ -59,10 +208,31 @@ impl Default for Prover {
 impl Prover {
     /// Creates a new `Prover` instance.
     pub fn new(label: &'static [u8]) -> Prover {
+        Prover::with_transcript(Transcript::new(label))
+    }
+
+    /// Creates a new `Prover` instance seeded with a `transcript`, which may
+    /// already hold a prefix of messages binding the proofs to some outer
+    /// context.
+    ///
+    /// The common prefix can be hashed once and the transcript cloned for
+    /// each `Prover`. The proofs are only accepted by a
+    /// [`Verifier`](super::Verifier) created with
+    /// [`Verifier::with_transcript`](super::Verifier::with_transcript) from a
+    /// transcript holding the same messages.
+    pub fn with_transcript(transcript: Transcript) -> Prover {
         Prover {
             prover_key: None,
             cs: StandardComposer::new(),
-            preprocessed_transcript: Transcript::new(label),
+            preprocessed_transcript: transcript,
+            transcript_labels: TranscriptLabels::DEFAULT,
+            sanity_checks: cfg!(debug_assertions),
+            #[cfg(feature = "std")]
+            hiding: false,
+            #[cfg(feature = "std")]
+            blinded: false,
+            #[cfg(feature = "std")]
+            blinding_rows: BLINDING_ROWS,
         }
     }
 
@@ -72,6 +242,14 @@ impl Prover {
             prover_key: None,
             cs: StandardComposer::with_expected_size(size),
             preprocessed_transcript: Transcript::new(label),
+            transcript_labels: TranscriptLabels::DEFAULT,
+            sanity_checks: cfg!(debug_assertions),
+            #[cfg(feature = "std")]
+            hiding: false,
+            #[cfg(feature = "std")]
+            blinded: false,
+            #[cfg(feature = "std")]
+            blinding_rows: BLINDING_ROWS,
         }
     }
 
@@ -125,8 +303,16 @@ impl Prover {
     /// Resets the witnesses in the prover object.
     /// This function is used when the user wants to make multiple proofs with
     /// the same circuit.
+    ///
+    /// The [`ProverKey`] and the keyed transcript are kept, so the circuit
+    /// can be built again with [`Prover::mut_cs`] and proven without calling
+    /// [`Prover::preprocess`] again.
     pub fn clear_witness(&mut self) {
         self.cs = StandardComposer::new();
+        #[cfg(feature = "std")]
+        {
+            self.blinded = false;
+        }
     }
 
     /// Clears all data in the `Prover` instance.
@@ -154,8 +340,23 @@ impl Prover {
         &self,
         commit_key: &CommitKey,
         prover_key: &ProverKey,
+    ) -> Result<Proof, Error> {
+        self.check_witness()?;
+        self.compute_proof(commit_key, prover_key, &mut |_| ())
+    }
+
+    /// Computes the [`Proof`] of the circuit with the preprocessed
+    /// `prover_key`, without checking the witness, reporting each
+    /// [`ProverStage`] to `progress`.
+    fn compute_proof(
+        &self,
+        commit_key: &CommitKey,
+        prover_key: &ProverKey,
+        progress: &mut dyn FnMut(ProverStage),
     ) -> Result<Proof, Error> {
         let domain = EvaluationDomain::new(self.cs.circuit_size())?;
+        let backend = commit_key.backend();
+        trace_enter!(_prove, "prove", size = domain.size());
 
         // Since the caller is passing a pre-processed circuit
         // We assume that the Transcript has been seeded with the preprocessed
@@ -164,47 +365,77 @@ impl Prover {
 
         //1. Compute witness Polynomials
         //
+        trace_enter!(round_1, "round 1");
+        progress(ProverStage::CommitWires);
+        timing_phase!(FFT);
+        trace_enter!(fft, "witness ifft", size = domain.size());
         // Convert Variables to BlsScalars padding them to the
         // correct domain size.
         let pad = vec![BlsScalar::zero(); domain.size() - self.cs.w_l.len()];
-        let w_l_scalar = &[&self.to_scalars(&self.cs.w_l)[..], &pad].concat();
-        let w_r_scalar = &[&self.to_scalars(&self.cs.w_r)[..], &pad].concat();
-        let w_o_scalar = &[&self.to_scalars(&self.cs.w_o)[..], &pad].concat();
-        let w_4_scalar = &[&self.to_scalars(&self.cs.w_4)[..], &pad].concat();
+        let w_l_scalar = [&self.to_scalars(&self.cs.w_l)[..], &pad].concat();
+        let w_r_scalar = [&self.to_scalars(&self.cs.w_r)[..], &pad].concat();
+        let w_o_scalar = [&self.to_scalars(&self.cs.w_o)[..], &pad].concat();
+        let w_4_scalar = [&self.to_scalars(&self.cs.w_4)[..], &pad].concat();
+        drop(pad);
 
         // Witnesses are now in evaluation form, convert them to coefficients
         // So that we may commit to them
-        let w_l_poly =
-            Polynomial::from_coefficients_vec(domain.ifft(w_l_scalar));
-        let w_r_poly =
-            Polynomial::from_coefficients_vec(domain.ifft(w_r_scalar));
-        let w_o_poly =
-            Polynomial::from_coefficients_vec(domain.ifft(w_o_scalar));
-        let w_4_poly =
-            Polynomial::from_coefficients_vec(domain.ifft(w_4_scalar));
+        let w_l_poly = Polynomial::from_coefficients_vec(ifft(
+            backend,
+            &domain,
+            &w_l_scalar,
+        ));
+        let w_r_poly = Polynomial::from_coefficients_vec(ifft(
+            backend,
+            &domain,
+            &w_r_scalar,
+        ));
+        let w_o_poly = Polynomial::from_coefficients_vec(ifft(
+            backend,
+            &domain,
+            &w_o_scalar,
+        ));
+        let w_4_poly = Polynomial::from_coefficients_vec(ifft(
+            backend,
+            &domain,
+            &w_4_scalar,
+        ));
+
+        trace_exit!(fft);
 
         // Commit to witness polynomials
+        timing_phase!(MSM);
+        trace_enter!(msm, "witness msm", size = domain.size());
         let w_l_poly_commit = commit_key.commit(&w_l_poly)?;
         let w_r_poly_commit = commit_key.commit(&w_r_poly)?;
         let w_o_poly_commit = commit_key.commit(&w_o_poly)?;
         let w_4_poly_commit = commit_key.commit(&w_4_poly)?;
+        trace_exit!(msm);
 
         // Add witness polynomial commitments to transcript
+        timing_phase!(TRANSCRIPT);
         transcript.append_commitment(b"w_l", &w_l_poly_commit);
         transcript.append_commitment(b"w_r", &w_r_poly_commit);
         transcript.append_commitment(b"w_o", &w_o_poly_commit);
         transcript.append_commitment(b"w_4", &w_4_poly_commit);
+        trace_exit!(round_1);
 
         // 2. Compute permutation polynomial
         //
         //
+        trace_enter!(round_2, "round 2");
+        progress(ProverStage::Permutation);
         // Compute permutation challenges; `beta` and `gamma`
-        let beta = transcript.challenge_scalar(b"beta");
+        let labels = &self.transcript_labels;
+        let beta = transcript.challenge_scalar(labels.beta);
         transcript.append_scalar(b"beta", &beta);
-        let gamma = transcript.challenge_scalar(b"gamma");
+        let gamma = transcript.challenge_scalar(labels.gamma);
 
+        timing_phase!(FFT);
+        trace_enter!(fft, "permutation poly", size = domain.size());
         let z_poly = Polynomial::from_coefficients_slice(
             &self.cs.perm.compute_permutation_poly(
+                backend,
                 &domain,
                 (&w_l_scalar, &w_r_scalar, &w_o_scalar, &w_4_scalar),
                 &beta,
@@ -218,22 +449,42 @@ impl Prover {
             ),
         );
 
+        // The witness evaluations are not needed anymore
+        drop(w_l_scalar);
+        drop(w_r_scalar);
+        drop(w_o_scalar);
+        drop(w_4_scalar);
+        trace_exit!(fft);
+
         // Commit to permutation polynomial
         //
+        timing_phase!(MSM);
+        trace_enter!(msm, "permutation msm", size = domain.size());
         let z_poly_commit = commit_key.commit(&z_poly)?;
+        trace_exit!(msm);
 
         // Add permutation polynomial commitment to transcript
+        timing_phase!(TRANSCRIPT);
         transcript.append_commitment(b"z", &z_poly_commit);
+        trace_exit!(round_2);
 
         // 3. Compute public inputs polynomial
-        let pi_poly = Polynomial::from_coefficients_vec(
-            domain.ifft(&self.cs.construct_dense_pi_vec()),
-        );
+        trace_enter!(round_3, "round 3");
+        progress(ProverStage::Quotient);
+        timing_phase!(FFT);
+        trace_enter!(fft, "public inputs ifft", size = domain.size());
+        let pi_poly = Polynomial::from_coefficients_vec(ifft(
+            backend,
+            &domain,
+            &self.cs.construct_dense_pi_vec(),
+        ));
+        trace_exit!(fft);
 
         // 4. Compute quotient polynomial
         //
         // Compute quotient challenge; `alpha`
-        let alpha = transcript.challenge_scalar(b"alpha");
+        timing_phase!(TRANSCRIPT);
+        let alpha = transcript.challenge_scalar(labels.alpha);
         let range_sep_challenge =
             transcript.challenge_scalar(b"range separation challenge");
         let logic_sep_challenge =
@@ -243,7 +494,10 @@ impl Prover {
         let var_base_sep_challenge =
             transcript.challenge_scalar(b"variable base separation challenge");
 
+        timing_phase!(FFT);
+        trace_enter!(fft, "quotient poly", size = 4 * domain.size());
         let t_poly = quotient_poly::compute(
+            backend,
             &domain,
             &prover_key,
             &z_poly,
@@ -260,27 +514,39 @@ impl Prover {
             ),
         )?;
 
+        drop(pi_poly);
+        trace_exit!(fft);
+
         // Split quotient polynomial into 4 degree `n` polynomials
         let (t_1_poly, t_2_poly, t_3_poly, t_4_poly) =
             self.split_tx_poly(domain.size(), &t_poly);
 
         // Commit to splitted quotient polynomial
+        timing_phase!(MSM);
+        trace_enter!(msm, "quotient msm", size = domain.size());
         let t_1_commit = commit_key.commit(&t_1_poly)?;
         let t_2_commit = commit_key.commit(&t_2_poly)?;
         let t_3_commit = commit_key.commit(&t_3_poly)?;
         let t_4_commit = commit_key.commit(&t_4_poly)?;
+        trace_exit!(msm);
 
         // Add quotient polynomial commitments to transcript
+        timing_phase!(TRANSCRIPT);
         transcript.append_commitment(b"t_1", &t_1_commit);
         transcript.append_commitment(b"t_2", &t_2_commit);
         transcript.append_commitment(b"t_3", &t_3_commit);
         transcript.append_commitment(b"t_4", &t_4_commit);
+        trace_exit!(round_3);
 
         // 4. Compute linearisation polynomial
         //
+        trace_enter!(round_4, "round 4");
+        progress(ProverStage::Opening);
         // Compute evaluation challenge; `z`
-        let z_challenge = transcript.challenge_scalar(b"z");
+        let z_challenge = transcript.challenge_scalar(labels.zeta);
 
+        timing_phase!(OPENING);
+        trace_enter!(lin, "linearisation poly", size = domain.size());
         let (lin_poly, evaluations) = linearisation_poly::compute(
             &domain,
             &prover_key,
@@ -302,7 +568,12 @@ impl Prover {
             &z_poly,
         );
 
+        // The quotient is only needed in its splitted form from now on
+        drop(t_poly);
+        trace_exit!(lin);
+
         // Add evaluations to transcript
+        timing_phase!(TRANSCRIPT);
         transcript.append_scalar(b"a_eval", &evaluations.proof.a_eval);
         transcript.append_scalar(b"b_eval", &evaluations.proof.b_eval);
         transcript.append_scalar(b"c_eval", &evaluations.proof.c_eval);
@@ -331,9 +602,12 @@ impl Prover {
         transcript.append_scalar(b"perm_eval", &evaluations.proof.perm_eval);
         transcript.append_scalar(b"t_eval", &evaluations.quot_eval);
         transcript.append_scalar(b"r_eval", &evaluations.proof.lin_poly_eval);
+        trace_exit!(round_4);
 
         // 5. Compute Openings using KZG10
         //
+        trace_enter!(_round_5, "round 5");
+        timing_phase!(OPENING);
         // We merge the quotient polynomial using the `z_challenge` so the SRS
         // is linear in the circuit size `n`
         let quot = Self::compute_quotient_opening_poly(
@@ -361,8 +635,11 @@ impl Prover {
             ],
             &z_challenge,
             &mut transcript,
+            labels.v,
         );
+        trace_enter!(msm, "opening msm", size = domain.size());
         let w_z_comm = commit_key.commit(&aggregate_witness)?;
+        trace_exit!(msm);
 
         // Compute aggregate witness to polynomials evaluated at the shifted
         // evaluation challenge
@@ -370,8 +647,11 @@ impl Prover {
             &[z_poly, w_l_poly, w_r_poly, w_4_poly],
             &(z_challenge * domain.group_gen),
             &mut transcript,
+            labels.v,
         );
+        trace_enter!(msm, "opening msm", size = domain.size());
         let w_zx_comm = commit_key.commit(&shifted_aggregate_witness)?;
+        trace_exit!(msm);
 
         // Create Proof
         Ok(Proof {
@@ -398,8 +678,30 @@ impl Prover {
     /// If the circuit is not pre-processed, then the preprocessed circuit will
     /// also be computed.
     pub fn prove(&mut self, commit_key: &CommitKey) -> Result<Proof, Error> {
+        self.prove_with_progress(commit_key, |_| ())
+    }
+
+    /// Proves a circuit is satisfied as [`Prover::prove`] does, calling
+    /// `progress` with each [`ProverStage`] of the computation of the proof
+    /// when it starts, in order.
+    ///
+    /// The stages are only reported once the circuit is preprocessed, and
+    /// none is reported if the sanity checks reject the witness, see
+    /// [`Prover::set_sanity_checks`].
+    pub fn prove_with_progress(
+        &mut self,
+        commit_key: &CommitKey,
+        mut progress: impl FnMut(ProverStage),
+    ) -> Result<Proof, Error> {
         let prover_key: &ProverKey;
 
+        #[cfg(feature = "std")]
+        self.blind_circuit();
+
+        // Fail before preprocessing the circuit, which is wasted if the
+        // witness is wrong
+        self.check_witness()?;
+
         if self.prover_key.is_none() {
             // Preprocess circuit
             let prover_key = self.cs.preprocess_prover(
@@ -412,11 +714,631 @@ impl Prover {
 
         prover_key = self.prover_key.as_ref().unwrap();
 
-        let proof = self.prove_with_preprocessed(commit_key, prover_key)?;
+        let proof =
+            self.compute_proof(commit_key, prover_key, &mut progress)?;
 
         // Clear witness and reset composer variables
         self.clear_witness();
 
         Ok(proof)
     }
+
+    /// Returns a human-readable description of each identity which doesn't
+    /// hold for the `proof` of the circuit, computed with the `prover_key`,
+    /// to find out why it fails to verify.
+    ///
+    /// The gate identities, the range constraints and the copy constraints
+    /// added with
+    /// [`StandardComposer::assert_equal_wire`] are checked on the witness
+    /// at every gate of the circuit. Then the transcript of the `proof` is
+    /// replayed to get its challenges. The quotient identity
+    /// `t(z) * Z_H(z) = N(z)`, whose numerator `N` combines the gate, the
+    /// permutation and the public input polynomials, is checked at the
+    /// evaluation challenge `z`, at which the wire, selector, sigma and
+    /// permutation polynomials of the prover are evaluated and compared to
+    /// the evaluations held by the `proof` as well.
+    ///
+    /// The circuit of the `Prover` is expected to hold the witness the
+    /// `proof` was computed from, which [`Prover::prove`] clears, unlike
+    /// [`Prover::prove_with_preprocessed`]. This method interpolates the
+    /// polynomials of the circuit and is only meant for debugging.
+    pub fn explain_failure(
+        &self,
+        proof: &Proof,
+        prover_key: &ProverKey,
+    ) -> Vec<String> {
+        let mut failures: Vec<String> = self
+            .cs
+            .unsatisfied_range_gates()
+            .iter()
+            .map(|failure| format!("{}", failure))
+            .collect();
+        self.cs.unsatisfied_equalities().iter().for_each(|(a, b)| {
+            failures.push(format!(
+                "The copy constraint between {:?} and {:?} doesn't hold: {:?} \
+                 != {:?}",
+                a, b, self.cs.variables[a], self.cs.variables[b]
+            ))
+        });
+
+        let pi_vec = self.cs.construct_dense_pi_vec();
+        (0..self.cs.n).for_each(|i| {
+            self.cs
+                .gate_identities(i, &pi_vec)
+                .iter()
+                .filter(|(_, evaluation)| *evaluation != BlsScalar::zero())
+                .for_each(|(identity, evaluation)| {
+                    failures.push(format!(
+                        "Gate {}: the {} identity evaluates to {:?}",
+                        i, identity, evaluation
+                    ))
+                })
+        });
+
+        let domain = match EvaluationDomain::new(self.cs.circuit_size()) {
+            Ok(domain) if domain.size() == prover_key.n => domain,
+            _ => {
+                failures.push(format!(
+                    "The circuit of {} gates doesn't match the size {} of \
+                     the prover key",
+                    self.cs.circuit_size(),
+                    prover_key.n
+                ));
+                return failures;
+            }
+        };
+
+        // Replay the transcript of the proof up to the evaluation challenge
+        let challenges = proof.challenges(
+            &mut self.preprocessed_transcript.clone(),
+            &self.transcript_labels,
+        );
+        let Challenges {
+            beta,
+            gamma,
+            alpha,
+            z: z_challenge,
+            ..
+        } = challenges;
+        let shifted_z_challenge = z_challenge * domain.group_gen;
+
+        let scalars = |wire: &[Variable]| {
+            let mut scalars = self.to_scalars(wire);
+            scalars.resize(domain.size(), BlsScalar::zero());
+            scalars
+        };
+        let w_l_scalar = scalars(&self.cs.w_l);
+        let w_r_scalar = scalars(&self.cs.w_r);
+        let w_o_scalar = scalars(&self.cs.w_o);
+        let w_4_scalar = scalars(&self.cs.w_4);
+        let poly = |scalars: &[BlsScalar]| {
+            Polynomial::from_coefficients_vec(domain.ifft(scalars))
+        };
+        let w_l_poly = poly(&w_l_scalar);
+        let w_r_poly = poly(&w_r_scalar);
+        let w_o_poly = poly(&w_o_scalar);
+        let w_4_poly = poly(&w_4_scalar);
+
+        let permutation = &prover_key.permutation;
+        let z_poly = self.cs.perm.compute_permutation_poly(
+            &CpuBackend,
+            &domain,
+            (&w_l_scalar, &w_r_scalar, &w_o_scalar, &w_4_scalar),
+            &beta,
+            &gamma,
+            (
+                &permutation.left_sigma.0,
+                &permutation.right_sigma.0,
+                &permutation.out_sigma.0,
+                &permutation.fourth_sigma.0,
+            ),
+        );
+
+        // The quotient identity only holds if the witness satisfies every
+        // constraint, since otherwise the numerator isn't divisible by the
+        // vanishing polynomial
+        let pi_poly = poly(&pi_vec);
+        let quotient = quotient_poly::compute(
+            &CpuBackend,
+            &domain,
+            prover_key,
+            &z_poly,
+            (&w_l_poly, &w_r_poly, &w_o_poly, &w_4_poly),
+            &pi_poly,
+            &(
+                alpha,
+                beta,
+                gamma,
+                challenges.range_sep,
+                challenges.logic_sep,
+                challenges.fixed_base_sep,
+                challenges.var_base_sep,
+            ),
+        );
+        if let Ok(t_poly) = quotient {
+            let (_, prover_evaluations) = linearisation_poly::compute(
+                &domain,
+                prover_key,
+                &(
+                    alpha,
+                    beta,
+                    gamma,
+                    challenges.range_sep,
+                    challenges.logic_sep,
+                    challenges.fixed_base_sep,
+                    challenges.var_base_sep,
+                    z_challenge,
+                ),
+                &w_l_poly,
+                &w_r_poly,
+                &w_o_poly,
+                &w_4_poly,
+                &t_poly,
+                &z_poly,
+            );
+            let e = &prover_evaluations.proof;
+            let z_h_eval = domain.evaluate_vanishing_polynomial(&z_challenge);
+            let l1_eval = z_h_eval
+                * (BlsScalar::from(domain.size() as u64)
+                    * (z_challenge - BlsScalar::one()))
+                .invert()
+                .unwrap();
+            let numerator = e.lin_poly_eval + pi_poly.evaluate(&z_challenge)
+                - (e.a_eval + beta * e.left_sigma_eval + gamma)
+                    * (e.b_eval + beta * e.right_sigma_eval + gamma)
+                    * (e.c_eval + beta * e.out_sigma_eval + gamma)
+                    * (e.d_eval + gamma)
+                    * e.perm_eval
+                    * alpha
+                - l1_eval * alpha.square();
+            let t_z_h = prover_evaluations.quot_eval * z_h_eval;
+            if t_z_h != numerator {
+                failures.push(format!(
+                    "The quotient identity doesn't hold at the evaluation \
+                     challenge: t(z) * Z_H(z) is {:?} but the numerator is \
+                     {:?}",
+                    t_z_h, numerator
+                ));
+            }
+        }
+
+        let evaluations = &proof.evaluations;
+        let arithmetic = &prover_key.arithmetic;
+        [
+            ("a", evaluations.a_eval, &w_l_poly, z_challenge),
+            ("b", evaluations.b_eval, &w_r_poly, z_challenge),
+            ("c", evaluations.c_eval, &w_o_poly, z_challenge),
+            ("d", evaluations.d_eval, &w_4_poly, z_challenge),
+            (
+                "a_next",
+                evaluations.a_next_eval,
+                &w_l_poly,
+                shifted_z_challenge,
+            ),
+            (
+                "b_next",
+                evaluations.b_next_eval,
+                &w_r_poly,
+                shifted_z_challenge,
+            ),
+            (
+                "d_next",
+                evaluations.d_next_eval,
+                &w_4_poly,
+                shifted_z_challenge,
+            ),
+            (
+                "q_arith",
+                evaluations.q_arith_eval,
+                &arithmetic.q_arith.0,
+                z_challenge,
+            ),
+            ("q_c", evaluations.q_c_eval, &arithmetic.q_c.0, z_challenge),
+            ("q_l", evaluations.q_l_eval, &arithmetic.q_l.0, z_challenge),
+            ("q_r", evaluations.q_r_eval, &arithmetic.q_r.0, z_challenge),
+            (
+                "left_sigma",
+                evaluations.left_sigma_eval,
+                &permutation.left_sigma.0,
+                z_challenge,
+            ),
+            (
+                "right_sigma",
+                evaluations.right_sigma_eval,
+                &permutation.right_sigma.0,
+                z_challenge,
+            ),
+            (
+                "out_sigma",
+                evaluations.out_sigma_eval,
+                &permutation.out_sigma.0,
+                z_challenge,
+            ),
+            ("perm", evaluations.perm_eval, &z_poly, shifted_z_challenge),
+        ]
+        .iter()
+        .filter(|(_, evaluation, poly, point)| {
+            poly.evaluate(point) != *evaluation
+        })
+        .for_each(|(name, _, _, _)| {
+            failures.push(format!(
+                "The {} evaluation of the proof doesn't match the polynomial \
+                 of the prover at the evaluation challenge",
+                name
+            ))
+        });
+
+        failures
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment_scheme::kzg10::PublicParameters;
+    use crate::constraint_system::helper::*;
+    use crate::proof_system::Verifier;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_hiding_proofs() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        prover.set_hiding(true);
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+
+        let mut proofs = Vec::new();
+        for _ in 0..2 {
+            proofs.push(prover.prove(&ck).unwrap());
+            dummy_gadget(10, prover.mut_cs());
+        }
+
+        // Blinding is not deterministic
+        assert_ne!(proofs[0], proofs[1]);
+
+        let mut verifier = Verifier::new(b"demo");
+        verifier.set_hiding(true);
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+
+        for proof in proofs {
+            assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_custom_blinding_rows() {
+        let public_parameters =
+            PublicParameters::setup(2 * 40, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 32).unwrap();
+
+        // The extra blinding gates take the circuit over 16 gates
+        let mut prover = Prover::new(b"demo");
+        prover.set_hiding(true);
+        assert!(matches!(
+            prover.set_blinding_rows(BLINDING_ROWS - 1),
+            Err(Error::InsufficientBlindingRows { rows: 2, min: 3 })
+        ));
+        prover.set_blinding_rows(8).unwrap();
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        assert!(prover.circuit_size() > 16);
+
+        // The prover key already holds the blinding gates
+        assert!(matches!(
+            prover.set_blinding_rows(4),
+            Err(Error::CircuitAlreadyPreprocessed)
+        ));
+
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::new(b"demo");
+        verifier.set_hiding(true);
+        assert!(matches!(
+            verifier.set_blinding_rows(0),
+            Err(Error::InsufficientBlindingRows { rows: 0, min: 3 })
+        ));
+        verifier.set_blinding_rows(8).unwrap();
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(matches!(
+            verifier.set_blinding_rows(8),
+            Err(Error::CircuitAlreadyPreprocessed)
+        ));
+        assert_eq!(
+            verifier
+                .verifier_key
+                .as_ref()
+                .unwrap()
+                .padded_circuit_size(),
+            32
+        );
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // Should fail since the verifier expects the default blinding
+        let mut verifier = Verifier::new(b"demo");
+        verifier.set_hiding(true);
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_err());
+    }
+
+    #[test]
+    fn test_wire_commitments() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+
+        // Commit to the left wire witness outside of the prover
+        let domain = EvaluationDomain::new(prover.circuit_size()).unwrap();
+        let w_l_scalar = prover.to_scalars(&prover.cs.w_l);
+        let w_l_poly =
+            Polynomial::from_coefficients_vec(domain.ifft(&w_l_scalar));
+        let w_l_commit = ck.commit(&w_l_poly).unwrap();
+
+        let proof = prover.prove(&ck).unwrap();
+        assert_eq!(proof.wire_commitments()[0], w_l_commit.0);
+    }
+
+    #[test]
+    fn test_explain_failure() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let prover_key = prover.prover_key.as_ref().unwrap();
+
+        let mut proof =
+            prover.prove_with_preprocessed(&ck, prover_key).unwrap();
+        assert!(prover.explain_failure(&proof, prover_key).is_empty());
+
+        // The tampered evaluation doesn't match the witness
+        proof.evaluations.b_eval += BlsScalar::one();
+        assert_eq!(
+            prover.explain_failure(&proof, prover_key),
+            ["The b evaluation of the proof doesn't match the polynomial of \
+              the prover at the evaluation challenge"]
+        );
+
+        // The unsatisfied gate is reported along with its identity
+        let mut prover = Prover::new(b"demo");
+        prover.set_sanity_checks(false);
+        dummy_gadget(10, prover.mut_cs());
+        let gate_index = prover.circuit_size();
+        let one = prover.mut_cs().add_input(BlsScalar::one());
+        prover
+            .mut_cs()
+            .constrain_to_constant(one, BlsScalar::zero(), None);
+        prover.preprocess(&ck).unwrap();
+        let prover_key = prover.prover_key.as_ref().unwrap();
+
+        let proof = prover.prove_with_preprocessed(&ck, prover_key).unwrap();
+        let failures = prover.explain_failure(&proof, prover_key);
+        assert_eq!(failures.len(), 2);
+        assert!(failures[0].starts_with(&format!(
+            "Gate {}: the arithmetic identity",
+            gate_index
+        )));
+        assert!(failures[1].starts_with("The quotient identity doesn't hold"));
+
+        // The unsatisfied curve gates and copy constraints are reported too
+        let mut prover = Prover::new(b"demo");
+        prover.set_sanity_checks(false);
+        dummy_gadget(10, prover.mut_cs());
+        let a = prover.mut_cs().add_input(BlsScalar::from(5u64));
+        let b = prover.mut_cs().add_input(BlsScalar::from(6u64));
+        prover
+            .mut_cs()
+            .constrain_to_constant(a, BlsScalar::from(5u64), None);
+        prover
+            .mut_cs()
+            .constrain_to_constant(b, BlsScalar::from(6u64), None);
+        prover.mut_cs().assert_equal_wire(a, b);
+        let generator = prover.mut_cs().add_affine(dusk_jubjub::GENERATOR);
+        let gate_index = prover.circuit_size();
+        let sum = prover.mut_cs().point_addition_gate(generator, generator);
+        prover.mut_cs().variables.insert(*sum.x(), BlsScalar::one());
+        prover.preprocess(&ck).unwrap();
+        let prover_key = prover.prover_key.as_ref().unwrap();
+
+        let proof = prover.prove_with_preprocessed(&ck, prover_key).unwrap();
+        let failures = prover.explain_failure(&proof, prover_key);
+        assert_eq!(failures.len(), 3);
+        assert!(failures[0].starts_with("The copy constraint between"));
+        assert!(failures[1].starts_with(&format!(
+            "Gate {}: the variable base identity",
+            gate_index
+        )));
+        assert!(failures[2].starts_with("The quotient identity doesn't hold"));
+    }
+
+    #[test]
+    fn test_prove_with_progress() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(10, prover.mut_cs());
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+
+        let mut stages = Vec::new();
+        let proof = prover
+            .prove_with_progress(&ck, |stage| stages.push(stage))
+            .unwrap();
+        assert_eq!(
+            stages,
+            [
+                ProverStage::CommitWires,
+                ProverStage::Permutation,
+                ProverStage::Quotient,
+                ProverStage::Opening
+            ]
+        );
+
+        let mut verifier = Verifier::new(b"demo");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+    }
+
+    #[test]
+    fn test_prover_reuse() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        prover.key_transcript(b"key", b"additional seed information");
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+
+        // Discard a partially built circuit and build it again
+        prover.clear_witness();
+        dummy_gadget(3, prover.mut_cs());
+        prover.clear_witness();
+        assert_eq!(prover.circuit_size(), 3);
+        dummy_gadget(10, prover.mut_cs());
+
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+        assert!(prover.prover_key.is_some());
+
+        let mut verifier = Verifier::new(b"demo");
+        verifier.key_transcript(b"key", b"additional seed information");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+    }
+
+    #[test]
+    fn test_prefixed_transcript() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut context = Transcript::new(b"demo");
+        context.append_message(b"context", b"outer protocol session");
+
+        let mut prover = Prover::with_transcript(context.clone());
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::with_transcript(context);
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // Should fail since the proof is bound to another context
+        let mut other = Transcript::new(b"demo");
+        other.append_message(b"context", b"another session");
+
+        let mut verifier = Verifier::with_transcript(other);
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_err());
+
+        // And so does verifying it without the context
+        let mut verifier = Verifier::new(b"demo");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_err());
+    }
+
+    #[test]
+    fn test_sanity_checks() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(2 * 20).unwrap();
+
+        let unsatisfied = |prover: &mut Prover| {
+            dummy_gadget(10, prover.mut_cs());
+            let one = prover.mut_cs().add_input(BlsScalar::one());
+            prover
+                .mut_cs()
+                .constrain_to_constant(one, BlsScalar::zero(), None);
+        };
+
+        let mut prover = Prover::new(b"demo");
+        prover.set_sanity_checks(true);
+        unsatisfied(&mut prover);
+        let gate = prover.circuit_size() - 1;
+        assert_eq!(prover.cs.first_unsatisfied_gate(), Some(gate));
+
+        // Fails before the circuit is preprocessed
+        match prover.prove(&ck) {
+            Err(Error::UnsatisfiedConstraint { gate_index }) => {
+                assert_eq!(gate_index, gate)
+            }
+            _ => panic!("The unsatisfied circuit shouldn't be proven"),
+        }
+        assert!(prover.prover_key.is_none());
+
+        prover.set_sanity_checks(false);
+        assert!(prover.prove(&ck).is_ok());
+
+        // A satisfied circuit is proven with the checks enabled
+        let mut prover = Prover::new(b"demo");
+        prover.set_sanity_checks(true);
+        dummy_gadget(10, prover.mut_cs());
+        assert_eq!(prover.cs.first_unsatisfied_gate(), None);
+        assert!(prover.prove(&ck).is_ok());
+    }
+
+    #[test]
+    fn test_transcript_labels() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        let labels = TranscriptLabels {
+            beta: b"my_beta",
+            zeta: b"my_zeta",
+            v: b"my_v",
+            ..TranscriptLabels::default()
+        };
+
+        let mut prover = Prover::new(b"demo");
+        prover.set_transcript_labels(labels);
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::new(b"demo");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+
+        // Should fail since the challenges are drawn with other labels
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_err());
+
+        verifier.set_transcript_labels(labels);
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // The batching challenge is only drawn by the verifier
+        verifier.set_transcript_labels(TranscriptLabels {
+            u: b"my_u",
+            ..labels
+        });
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+    }
 }
@@ -5,18 +5,22 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::{
+    backend::{coset_fft, coset_ifft_in_place, ComputeBackend},
     error::Error,
     fft::{EvaluationDomain, Polynomial},
     proof_system::ProverKey,
 };
-use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
 #[cfg(feature = "std")]
 use rayon::prelude::*;
 
+/// Number of coset points evaluated at once while computing the quotient.
+const QUOTIENT_CHUNK_SIZE: usize = 1 << 10;
+
 /// Computes the Quotient [`Polynomial`] given the [`EvaluationDomain`], a
 /// [`ProverKey`] and some other info.
 pub(crate) fn compute(
+    backend: &dyn ComputeBackend,
     domain: &EvaluationDomain,
     prover_key: &ProverKey,
     z_poly: &Polynomial,
@@ -45,76 +49,109 @@ pub(crate) fn compute(
         BlsScalar,
     ),
 ) -> Result<Polynomial, Error> {
-    // Compute 4n eval of z(X)
     let domain_4n = EvaluationDomain::new(4 * domain.size())?;
-    let mut z_eval_4n = domain_4n.coset_fft(&z_poly);
-    z_eval_4n.push(z_eval_4n[0]);
-    z_eval_4n.push(z_eval_4n[1]);
-    z_eval_4n.push(z_eval_4n[2]);
-    z_eval_4n.push(z_eval_4n[3]);
+    let size = domain_4n.size();
+
+    // The evaluation at the next point of the domain of size n is 4 points
+    // ahead on the coset of size 4n, wrapping around its end
+    let next = |i: usize| (i + 4) % size;
+
+    // The numerator of the quotient is accumulated term by term into the
+    // evaluations of the public inputs, so each coset evaluation is only
+    // held while its terms are added and the wires are only held along
+    // with z(X) and the accumulator.
+    let mut quotient = coset_fft(backend, &domain_4n, public_inputs_poly);
+    let z_eval_4n = coset_fft(backend, &domain_4n, z_poly);
+
+    let l1_poly_alpha =
+        compute_first_lagrange_poly_scaled(domain, alpha.square());
+    let l1_alpha_sq_evals =
+        coset_fft(backend, &domain_4n, &l1_poly_alpha.coeffs);
+    drop(l1_poly_alpha);
+    for_each_eval(&mut quotient, |i, t_i| {
+        *t_i += prover_key.permutation.compute_quotient_term_check_one_i(
+            &z_eval_4n[i],
+            &l1_alpha_sq_evals[i],
+        );
+    });
+    drop(l1_alpha_sq_evals);
 
     // Compute 4n evaluations of the wire polynomials
-    let mut wl_eval_4n = domain_4n.coset_fft(&w_l_poly);
-    wl_eval_4n.push(wl_eval_4n[0]);
-    wl_eval_4n.push(wl_eval_4n[1]);
-    wl_eval_4n.push(wl_eval_4n[2]);
-    wl_eval_4n.push(wl_eval_4n[3]);
-    let mut wr_eval_4n = domain_4n.coset_fft(&w_r_poly);
-    wr_eval_4n.push(wr_eval_4n[0]);
-    wr_eval_4n.push(wr_eval_4n[1]);
-    wr_eval_4n.push(wr_eval_4n[2]);
-    wr_eval_4n.push(wr_eval_4n[3]);
-    let wo_eval_4n = domain_4n.coset_fft(&w_o_poly);
+    let wl_eval_4n = coset_fft(backend, &domain_4n, w_l_poly);
+    let wr_eval_4n = coset_fft(backend, &domain_4n, w_r_poly);
+    let wo_eval_4n = coset_fft(backend, &domain_4n, w_o_poly);
+    let w4_eval_4n = coset_fft(backend, &domain_4n, w_4_poly);
+
+    // The gate and copy terms are added and the numerator is divided by the
+    // vanishing polynomial in the same pass
+    for_each_eval(&mut quotient, |i, t_i| {
+        let t_1 = compute_circuit_satisfiability_equation_i(
+            (i, next(i)),
+            (
+                range_challenge,
+                logic_challenge,
+                fixed_base_challenge,
+                var_base_challenge,
+            ),
+            prover_key,
+            (&wl_eval_4n, &wr_eval_4n, &wo_eval_4n, &w4_eval_4n),
+        );
 
-    let mut w4_eval_4n = domain_4n.coset_fft(&w_4_poly);
-    w4_eval_4n.push(w4_eval_4n[0]);
-    w4_eval_4n.push(w4_eval_4n[1]);
-    w4_eval_4n.push(w4_eval_4n[2]);
-    w4_eval_4n.push(w4_eval_4n[3]);
+        let t_2 = prover_key.permutation.compute_quotient_i(
+            i,
+            &wl_eval_4n[i],
+            &wr_eval_4n[i],
+            &wo_eval_4n[i],
+            &w4_eval_4n[i],
+            &z_eval_4n[i],
+            &z_eval_4n[next(i)],
+            &alpha,
+            &beta,
+            &gamma,
+        );
 
-    let t_1 = compute_circuit_satisfiability_equation(
-        &domain,
-        (
-            range_challenge,
-            logic_challenge,
-            fixed_base_challenge,
-            var_base_challenge,
-        ),
-        prover_key,
-        (&wl_eval_4n, &wr_eval_4n, &wo_eval_4n, &w4_eval_4n),
-        public_inputs_poly,
-    );
+        let denominator = prover_key.v_h_coset_4n()[i];
+        *t_i = (*t_i + t_1 + t_2) * denominator.invert().unwrap();
+    });
 
-    let t_2 = compute_permutation_checks(
-        domain,
-        prover_key,
-        (&wl_eval_4n, &wr_eval_4n, &wo_eval_4n, &w4_eval_4n),
-        &z_eval_4n,
-        (alpha, beta, gamma),
-    );
+    // Free the coset evaluations before interpolating the quotient
+    drop(z_eval_4n);
+    drop(wl_eval_4n);
+    drop(wr_eval_4n);
+    drop(wo_eval_4n);
+    drop(w4_eval_4n);
+
+    coset_ifft_in_place(backend, &domain_4n, &mut quotient);
+
+    Ok(Polynomial::from_coefficients_vec(quotient))
+}
 
+/// Calls `f` with the index of each evaluation of the `quotient` and a
+/// mutable reference to it, over chunks of [`QUOTIENT_CHUNK_SIZE`]
+/// evaluations processed in parallel with the `std` feature.
+fn for_each_eval<F>(quotient: &mut [BlsScalar], f: F)
+where
+    F: Fn(usize, &mut BlsScalar) + Send + Sync,
+{
     #[cfg(not(feature = "std"))]
-    let range = (0..domain_4n.size()).into_iter();
+    let chunks = quotient.chunks_mut(QUOTIENT_CHUNK_SIZE);
 
     #[cfg(feature = "std")]
-    let range = (0..domain_4n.size()).into_par_iter();
+    let chunks = quotient.par_chunks_mut(QUOTIENT_CHUNK_SIZE);
 
-    let quotient: Vec<_> = range
-        .map(|i| {
-            let numerator = t_1[i] + t_2[i];
-            let denominator = prover_key.v_h_coset_4n()[i];
-            numerator * denominator.invert().unwrap()
-        })
-        .collect();
-
-    Ok(Polynomial::from_coefficients_vec(
-        domain_4n.coset_ifft(&quotient),
-    ))
+    chunks.enumerate().for_each(|(chunk_index, chunk)| {
+        let offset = chunk_index * QUOTIENT_CHUNK_SIZE;
+        chunk
+            .iter_mut()
+            .enumerate()
+            .for_each(|(j, t_i)| f(offset + j, t_i));
+    });
 }
 
-// Ensures that the circuit is satisfied
-fn compute_circuit_satisfiability_equation(
-    domain: &EvaluationDomain,
+// Ensures that the circuit is satisfied at the `i`th point of the coset,
+// whose next point is the `next`th one
+fn compute_circuit_satisfiability_equation_i(
+    (i, next): (usize, usize),
     (
         range_challenge,
         logic_challenge,
@@ -128,124 +165,66 @@ fn compute_circuit_satisfiability_equation(
         &[BlsScalar],
         &[BlsScalar],
     ),
-    pi_poly: &Polynomial,
-) -> Vec<BlsScalar> {
-    let domain_4n = EvaluationDomain::new(4 * domain.size()).unwrap();
-    let pi_eval_4n = domain_4n.coset_fft(pi_poly);
-
-    #[cfg(not(feature = "std"))]
-    let range = (0..domain_4n.size()).into_iter();
+) -> BlsScalar {
+    let wl = &wl_eval_4n[i];
+    let wr = &wr_eval_4n[i];
+    let wo = &wo_eval_4n[i];
+    let w4 = &w4_eval_4n[i];
+    let wl_next = &wl_eval_4n[next];
+    let wr_next = &wr_eval_4n[next];
+    let w4_next = &w4_eval_4n[next];
 
-    #[cfg(feature = "std")]
-    let range = (0..domain_4n.size()).into_par_iter();
-
-    let t: Vec<_> = range
-        .map(|i| {
-            let wl = &wl_eval_4n[i];
-            let wr = &wr_eval_4n[i];
-            let wo = &wo_eval_4n[i];
-            let w4 = &w4_eval_4n[i];
-            let wl_next = &wl_eval_4n[i + 4];
-            let wr_next = &wr_eval_4n[i + 4];
-            let w4_next = &w4_eval_4n[i + 4];
-            let pi = &pi_eval_4n[i];
+    let a = prover_key.arithmetic.compute_quotient_i(i, wl, wr, wo, w4);
 
-            let a = prover_key.arithmetic.compute_quotient_i(i, wl, wr, wo, w4);
-
-            let b = prover_key.range.compute_quotient_i(
-                i,
-                range_challenge,
-                wl,
-                wr,
-                wo,
-                w4,
-                w4_next,
-            );
+    let b = prover_key.range.compute_quotient_i(
+        i,
+        range_challenge,
+        wl,
+        wr,
+        wo,
+        w4,
+        w4_next,
+    );
 
-            let c = prover_key.logic.compute_quotient_i(
-                i,
-                logic_challenge,
-                &wl,
-                &wl_next,
-                &wr,
-                &wr_next,
-                &wo,
-                &w4,
-                &w4_next,
-            );
+    let c = prover_key.logic.compute_quotient_i(
+        i,
+        logic_challenge,
+        &wl,
+        &wl_next,
+        &wr,
+        &wr_next,
+        &wo,
+        &w4,
+        &w4_next,
+    );
 
-            let d = prover_key.fixed_base.compute_quotient_i(
-                i,
-                fixed_base_challenge,
-                &wl,
-                &wl_next,
-                &wr,
-                &wr_next,
-                &wo,
-                &w4,
-                &w4_next,
-            );
+    let d = prover_key.fixed_base.compute_quotient_i(
+        i,
+        fixed_base_challenge,
+        &wl,
+        &wl_next,
+        &wr,
+        &wr_next,
+        &wo,
+        &w4,
+        &w4_next,
+    );
 
-            let e = prover_key.variable_base.compute_quotient_i(
-                i,
-                var_base_challenge,
-                &wl,
-                &wl_next,
-                &wr,
-                &wr_next,
-                &wo,
-                &w4,
-                &w4_next,
-            );
+    let e = prover_key.variable_base.compute_quotient_i(
+        i,
+        var_base_challenge,
+        &wl,
+        &wl_next,
+        &wr,
+        &wr_next,
+        &wo,
+        &w4,
+        &w4_next,
+    );
 
-            (a + pi) + b + c + d + e
-        })
-        .collect();
-    t
+    a + b + c + d + e
 }
 
-fn compute_permutation_checks(
-    domain: &EvaluationDomain,
-    prover_key: &ProverKey,
-    (wl_eval_4n, wr_eval_4n, wo_eval_4n, w4_eval_4n): (
-        &[BlsScalar],
-        &[BlsScalar],
-        &[BlsScalar],
-        &[BlsScalar],
-    ),
-    z_eval_4n: &[BlsScalar],
-    (alpha, beta, gamma): (&BlsScalar, &BlsScalar, &BlsScalar),
-) -> Vec<BlsScalar> {
-    let domain_4n = EvaluationDomain::new(4 * domain.size()).unwrap();
-    let l1_poly_alpha =
-        compute_first_lagrange_poly_scaled(domain, alpha.square());
-    let l1_alpha_sq_evals = domain_4n.coset_fft(&l1_poly_alpha.coeffs);
-
-    #[cfg(not(feature = "std"))]
-    let range = (0..domain_4n.size()).into_iter();
-
-    #[cfg(feature = "std")]
-    let range = (0..domain_4n.size()).into_par_iter();
-
-    let t: Vec<_> = range
-        .map(|i| {
-            prover_key.permutation.compute_quotient_i(
-                i,
-                &wl_eval_4n[i],
-                &wr_eval_4n[i],
-                &wo_eval_4n[i],
-                &w4_eval_4n[i],
-                &z_eval_4n[i],
-                &z_eval_4n[i + 4],
-                &alpha,
-                &l1_alpha_sq_evals[i],
-                &beta,
-                &gamma,
-            )
-        })
-        .collect();
-    t
-}
 fn compute_first_lagrange_poly_scaled(
     domain: &EvaluationDomain,
     scale: BlsScalar,
@@ -255,3 +234,77 @@ fn compute_first_lagrange_poly_scaled(
     domain.ifft_in_place(&mut x_evals);
     Polynomial::from_coefficients_vec(x_evals)
 }
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alloc_counter;
+    use crate::backend::CpuBackend;
+    use crate::commitment_scheme::kzg10::PublicParameters;
+    use crate::proof_system::Prover;
+    use dusk_bytes::Serializable;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_quotient_peak_memory() {
+        let public_parameters =
+            PublicParameters::setup(1 << 12, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(1 << 11).unwrap();
+
+        let mut prover = Prover::new(b"quotient");
+        let composer = prover.mut_cs();
+        let a = composer.add_input(BlsScalar::from(3u64));
+        for _ in 0..1000 {
+            composer.mul(BlsScalar::one(), a, a, BlsScalar::zero(), None);
+        }
+        prover.preprocess(&ck).unwrap();
+        let prover_key = prover.prover_key.as_ref().unwrap();
+
+        // The quotient is computed whether the polynomials satisfy the
+        // circuit or not
+        let domain = EvaluationDomain::new(prover_key.n).unwrap();
+        let poly = || Polynomial::rand(domain.size(), &mut OsRng);
+        let (z, w_l, w_r, w_o, w_4, pi) =
+            (poly(), poly(), poly(), poly(), poly(), poly());
+        let challenges = (
+            BlsScalar::from(2u64),
+            BlsScalar::from(3u64),
+            BlsScalar::from(5u64),
+            BlsScalar::from(7u64),
+            BlsScalar::from(11u64),
+            BlsScalar::from(13u64),
+            BlsScalar::from(17u64),
+        );
+        let quotient = || {
+            compute(
+                &CpuBackend,
+                &domain,
+                prover_key,
+                &z,
+                (&w_l, &w_r, &w_o, &w_4),
+                &pi,
+                &challenges,
+            )
+            .unwrap()
+        };
+
+        // Warm up the thread pool, so its allocations aren't measured
+        let expected = quotient();
+        let (obtained, stats) = alloc_counter::measure(quotient);
+        assert_eq!(obtained, expected);
+
+        // At most the accumulator, z(X) and the four wires are held at once
+        // as coset evaluations, along with the twiddle factors of an FFT
+        // over the coset, half as long, and the copy of a polynomial being
+        // extended to the coset. Holding every coset evaluation until the
+        // end took over eight of them.
+        let eval_bytes = 4 * domain.size() * BlsScalar::SIZE;
+        assert!(
+            stats.peak_bytes <= 6 * eval_bytes + 3 * eval_bytes / 4,
+            "peak of {} bytes for coset evaluations of {} bytes",
+            stats.peak_bytes,
+            eval_bytes
+        );
+    }
+}
@@ -7,8 +7,11 @@
 use crate::commitment_scheme::kzg10::{CommitKey, OpeningKey};
 use crate::constraint_system::StandardComposer;
 use crate::error::Error;
+#[cfg(feature = "std")]
+use crate::proof_system::prover::BLINDING_ROWS;
 use crate::proof_system::widget::VerifierKey;
-use crate::proof_system::Proof;
+use crate::proof_system::{Proof, VerifierScratch};
+use crate::transcript::TranscriptLabels;
 use dusk_bls12_381::BlsScalar;
 use merlin::Transcript;
 
@@ -25,6 +28,16 @@ pub struct Verifier {
     /// is not copied, then the verification procedure will modify
     /// the transcript, making it unusable for future proofs.
     pub preprocessed_transcript: Transcript,
+    /// Labels of the challenges drawn from the transcript
+    pub(crate) transcript_labels: TranscriptLabels,
+
+    /// Whether the circuit includes the blinding gates of a hiding
+    /// [`Prover`](super::Prover)
+    #[cfg(feature = "std")]
+    pub(crate) hiding: bool,
+    /// Number of blinding gates of the circuit in hiding mode
+    #[cfg(feature = "std")]
+    pub(crate) blinding_rows: usize,
 }
 
 impl Default for Verifier {
@@ -36,10 +49,26 @@ impl Default for Verifier {
 impl Verifier {
     /// Creates a new `Verifier` instance.
     pub fn new(label: &'static [u8]) -> Verifier {
+        Verifier::with_transcript(Transcript::new(label))
+    }
+
+    /// Creates a new `Verifier` instance seeded with a `transcript`, which
+    /// may already hold a prefix of messages binding the proofs to some
+    /// outer context.
+    ///
+    /// Only the proofs of a [`Prover`](super::Prover) created with
+    /// [`Prover::with_transcript`](super::Prover::with_transcript) from a
+    /// transcript holding the same messages are accepted.
+    pub fn with_transcript(transcript: Transcript) -> Verifier {
         Verifier {
             verifier_key: None,
             cs: StandardComposer::new(),
-            preprocessed_transcript: Transcript::new(label),
+            preprocessed_transcript: transcript,
+            transcript_labels: TranscriptLabels::DEFAULT,
+            #[cfg(feature = "std")]
+            hiding: false,
+            #[cfg(feature = "std")]
+            blinding_rows: BLINDING_ROWS,
         }
     }
 
@@ -49,6 +78,11 @@ impl Verifier {
             verifier_key: None,
             cs: StandardComposer::with_expected_size(size),
             preprocessed_transcript: Transcript::new(label),
+            transcript_labels: TranscriptLabels::DEFAULT,
+            #[cfg(feature = "std")]
+            hiding: false,
+            #[cfg(feature = "std")]
+            blinding_rows: BLINDING_ROWS,
         }
     }
 
@@ -66,6 +100,14 @@ impl Verifier {
     /// descriptor so that the `Verifier` instance can verify [`Proof`]s
     /// for this circuit descriptor instance.
     pub fn preprocess(&mut self, commit_key: &CommitKey) -> Result<(), Error> {
+        #[cfg(feature = "std")]
+        if self.hiding {
+            self.cs.add_blinding_factors(
+                self.blinding_rows,
+                &mut rand_core::OsRng,
+            );
+        }
+
         let vk = self.cs.preprocess_verifier(
             commit_key,
             &mut self.preprocessed_transcript,
@@ -75,6 +117,50 @@ impl Verifier {
         Ok(())
     }
 
+    /// Enables or disables the hiding mode of the `Verifier`.
+    ///
+    /// This must match the mode of the [`Prover`](super::Prover) that
+    /// generated the [`Proof`]s, see
+    /// [`Prover::set_hiding`](super::Prover::set_hiding).
+    #[cfg(feature = "std")]
+    pub fn set_hiding(&mut self, hiding: bool) {
+        self.hiding = hiding;
+    }
+
+    /// Sets the number of blinding gates of the circuit in hiding mode.
+    ///
+    /// This must match the number of the [`Prover`](super::Prover) that
+    /// generated the [`Proof`]s, see
+    /// [`Prover::set_blinding_rows`](super::Prover::set_blinding_rows).
+    ///
+    /// # Errors
+    /// Returns [`Error::InsufficientBlindingRows`] if `rows` is lower than
+    /// [`BLINDING_ROWS`], and [`Error::CircuitAlreadyPreprocessed`] if the
+    /// circuit has already been preprocessed.
+    #[cfg(feature = "std")]
+    pub fn set_blinding_rows(&mut self, rows: usize) -> Result<(), Error> {
+        if rows < BLINDING_ROWS {
+            return Err(Error::InsufficientBlindingRows {
+                rows,
+                min: BLINDING_ROWS,
+            });
+        }
+        if self.verifier_key.is_some() {
+            return Err(Error::CircuitAlreadyPreprocessed);
+        }
+        self.blinding_rows = rows;
+        Ok(())
+    }
+
+    /// Sets the labels of the challenges drawn from the transcript.
+    ///
+    /// They must match the labels of the [`Prover`](super::Prover) that
+    /// generated the [`Proof`]s, see
+    /// [`Prover::set_transcript_labels`](super::Prover::set_transcript_labels).
+    pub fn set_transcript_labels(&mut self, labels: TranscriptLabels) {
+        self.transcript_labels = labels;
+    }
+
     /// Keys the [`Transcript`] with additional seed information
     /// Wrapper around [`Transcript::append_message`].
     pub fn key_transcript(&mut self, label: &'static [u8], message: &[u8]) {
@@ -91,11 +177,13 @@ impl Verifier {
         let mut cloned_transcript = self.preprocessed_transcript.clone();
         let verifier_key = self.verifier_key.as_ref().unwrap();
 
-        proof.verify(
+        proof.verify_with_scratch(
             verifier_key,
             &mut cloned_transcript,
             opening_key,
             public_inputs,
+            &self.transcript_labels,
+            &mut VerifierScratch::default(),
         )
     }
 }
@@ -0,0 +1,64 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Versioning of the byte format of the [`Proof`](super::Proof) and the
+//! proving and verifying keys.
+//!
+//! Their serialized representation starts with a header made of three magic
+//! bytes followed by the [`SERIALIZATION_VERSION`]. Deserializing bytes with
+//! a different header fails with [`Error::VersionMismatch`], instead of
+//! silently producing a corrupted structure.
+
+use crate::error::Error;
+use dusk_bytes::BadLength;
+
+/// Version of the byte format of proofs and keys produced by this crate.
+///
+/// It's increased every time the layout of any of them changes.
+pub const SERIALIZATION_VERSION: u8 = 1;
+
+/// Size in bytes of the header prepended to the serialized proofs and keys.
+pub(crate) const HEADER_SIZE: usize = 4;
+
+/// Magic bytes followed by the serialization version.
+pub(crate) const HEADER: [u8; HEADER_SIZE] =
+    [b'P', b'L', b'K', SERIALIZATION_VERSION];
+
+/// Reads the header from the buffer, advancing it, and checks it matches the
+/// one of the current [`SERIALIZATION_VERSION`].
+pub(crate) fn read_header(buf: &mut &[u8]) -> Result<(), Error> {
+    if buf.len() < HEADER_SIZE {
+        return Err(Error::bad_length(buf.len(), HEADER_SIZE));
+    }
+
+    let (header, rest) = buf.split_at(HEADER_SIZE);
+    if header != HEADER {
+        return Err(Error::VersionMismatch);
+    }
+    *buf = rest;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_header() {
+        let bytes = [b'P', b'L', b'K', SERIALIZATION_VERSION, 42];
+        let mut buf = &bytes[..];
+        assert!(read_header(&mut buf).is_ok());
+        assert_eq!(buf, &[42]);
+
+        let bytes = [b'P', b'L', b'K', SERIALIZATION_VERSION + 1, 42];
+        let mut buf = &bytes[..];
+        assert!(matches!(read_header(&mut buf), Err(Error::VersionMismatch)));
+
+        let mut buf = &bytes[..2];
+        assert!(read_header(&mut buf).is_err());
+    }
+}
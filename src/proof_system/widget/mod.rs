@@ -9,7 +9,10 @@ pub mod ecc;
 pub mod logic;
 pub mod permutation;
 pub mod range;
+use super::version::{self, HEADER, HEADER_SIZE};
 use crate::commitment_scheme::kzg10::Commitment;
+use crate::error::Error;
+use dusk_bls12_381::G1Affine;
 use dusk_bytes::{DeserializableSlice, Serializable};
 
 /// PLONK circuit Verification Key.
@@ -34,8 +37,27 @@ pub struct VerifierKey {
     pub(crate) permutation: permutation::VerifierKey,
 }
 
-impl Serializable<{ 15 * Commitment::SIZE + u64::SIZE }> for VerifierKey {
-    type Error = dusk_bytes::Error;
+/// Estimated cost of the verification of a [`Proof`](super::Proof), returned
+/// by [`VerifierKey::estimated_verification_cost`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerificationCost {
+    /// Number of pairings of the final check.
+    pub pairings: usize,
+    /// Number of scalar multiplications in G1, which are either part of a
+    /// multi-scalar multiplication or performed one by one.
+    pub g1_msm_terms: usize,
+    /// Approximate number of multiplications in the scalar field, without
+    /// the public inputs.
+    pub field_ops_approx: usize,
+    /// Approximate number of multiplications in the scalar field added by
+    /// each non-zero public input.
+    pub field_ops_per_public_input: usize,
+}
+
+impl Serializable<{ HEADER_SIZE + 15 * Commitment::SIZE + u64::SIZE }>
+    for VerifierKey
+{
+    type Error = Error;
 
     #[allow(unused_must_use)]
     fn to_bytes(&self) -> [u8; Self::SIZE] {
@@ -43,6 +65,7 @@ impl Serializable<{ 15 * Commitment::SIZE + u64::SIZE }> for VerifierKey {
         let mut buff = [0u8; Self::SIZE];
         let mut writer = &mut buff[..];
 
+        writer.write(&HEADER);
         writer.write(&(self.n as u64).to_bytes());
         writer.write(&self.arithmetic.q_m.to_bytes());
         writer.write(&self.arithmetic.q_l.to_bytes());
@@ -65,6 +88,7 @@ impl Serializable<{ 15 * Commitment::SIZE + u64::SIZE }> for VerifierKey {
 
     fn from_bytes(buf: &[u8; Self::SIZE]) -> Result<VerifierKey, Self::Error> {
         let mut buffer = &buf[..];
+        version::read_header(&mut buffer)?;
 
         Ok(Self::from_polynomial_commitments(
             u64::from_reader(&mut buffer)? as usize,
@@ -93,6 +117,111 @@ impl VerifierKey {
         self.n.next_power_of_two()
     }
 
+    /// Returns the BLAKE2b-256 hash of the serialized key, which identifies
+    /// it in key registries without comparing the whole key.
+    ///
+    /// The commitments of the key depend on the [`PublicParameters`] the
+    /// circuit was compiled with, so the same circuit compiled with
+    /// different parameters has a different fingerprint.
+    ///
+    /// [`PublicParameters`]: crate::commitment_scheme::kzg10::PublicParameters
+    pub fn fingerprint(&self) -> [u8; 32] {
+        let hash = blake2b_simd::Params::new()
+            .hash_length(32)
+            .hash(&self.to_bytes());
+
+        let mut fingerprint = [0u8; 32];
+        fingerprint.copy_from_slice(hash.as_bytes());
+        fingerprint
+    }
+
+    /// Number of selector commitments of a [`VerifierKey`].
+    pub const SELECTOR_COMMITMENTS: usize = 11;
+
+    /// Number of permutation commitments of a [`VerifierKey`].
+    pub const PERMUTATION_COMMITMENTS: usize = 4;
+
+    /// Constructs a [`VerifierKey`] for a circuit of `n` gates from its
+    /// published commitments, which is the inverse of
+    /// [`VerifierKey::selector_commitments`] and
+    /// [`VerifierKey::permutation_commitments`].
+    ///
+    /// The `selectors` are the commitments to `q_m`, `q_l`, `q_r`, `q_o`,
+    /// `q_4`, `q_c`, `q_arith`, `q_logic`, `q_range`, `q_fixed_group_add`
+    /// and `q_variable_group_add`, and the `permutation` the commitments to
+    /// the left, right, output and fourth sigma polynomials, in this order.
+    ///
+    /// Returns [`Error::InvalidCommitmentCount`] if there are not exactly
+    /// [`VerifierKey::SELECTOR_COMMITMENTS`] selectors and
+    /// [`VerifierKey::PERMUTATION_COMMITMENTS`] permutation commitments.
+    pub fn from_commitments(
+        selectors: &[G1Affine],
+        permutation: &[G1Affine],
+        n: usize,
+    ) -> Result<VerifierKey, Error> {
+        let check = |found: usize, expected: usize| match found == expected {
+            true => Ok(()),
+            false => Err(Error::InvalidCommitmentCount { found, expected }),
+        };
+        check(selectors.len(), Self::SELECTOR_COMMITMENTS)?;
+        check(permutation.len(), Self::PERMUTATION_COMMITMENTS)?;
+
+        let q = |i: usize| Commitment(selectors[i]);
+        let sigma = |i: usize| Commitment(permutation[i]);
+
+        Ok(Self::from_polynomial_commitments(
+            n,
+            q(0),
+            q(1),
+            q(2),
+            q(3),
+            q(4),
+            q(5),
+            q(6),
+            q(7),
+            q(8),
+            q(9),
+            q(10),
+            sigma(0),
+            sigma(1),
+            sigma(2),
+            sigma(3),
+        ))
+    }
+
+    /// Returns the commitments to the selector polynomials, in the order
+    /// expected by [`VerifierKey::from_commitments`].
+    pub fn selector_commitments(
+        &self,
+    ) -> [G1Affine; Self::SELECTOR_COMMITMENTS] {
+        [
+            self.arithmetic.q_m.0,
+            self.arithmetic.q_l.0,
+            self.arithmetic.q_r.0,
+            self.arithmetic.q_o.0,
+            self.arithmetic.q_4.0,
+            self.arithmetic.q_c.0,
+            self.arithmetic.q_arith.0,
+            self.logic.q_logic.0,
+            self.range.q_range.0,
+            self.fixed_base.q_fixed_group_add.0,
+            self.variable_base.q_variable_group_add.0,
+        ]
+    }
+
+    /// Returns the commitments to the sigma polynomials, in the order
+    /// expected by [`VerifierKey::from_commitments`].
+    pub fn permutation_commitments(
+        &self,
+    ) -> [G1Affine; Self::PERMUTATION_COMMITMENTS] {
+        [
+            self.permutation.left_sigma.0,
+            self.permutation.right_sigma.0,
+            self.permutation.out_sigma.0,
+            self.permutation.fourth_sigma.0,
+        ]
+    }
+
     /// Constructs a [`VerifierKey`] from the widget VerifierKey's that are
     /// constructed based on the selector polynomial commitments and the
     /// sigma polynomial commitments.
@@ -157,6 +286,9 @@ impl VerifierKey {
 #[cfg(feature = "alloc")]
 pub(crate) mod alloc {
     use super::*;
+    use crate::proof_system::proof::{
+        Proof, OPENING_WITNESSES, PROOF_COMMITMENTS, QUOTIENT_PARTS,
+    };
     use crate::{
         error::Error,
         fft::{EvaluationDomain, Evaluations, Polynomial},
@@ -167,6 +299,46 @@ pub(crate) mod alloc {
     use merlin::Transcript;
 
     impl VerifierKey {
+        /// Returns the [`VerificationCost`] of a proof of the circuit, counted
+        /// from the commitments of the proof and the terms of the linearisation
+        /// commitment.
+        ///
+        /// The field operations are dominated by the exponentiations and the
+        /// inversions, each of them squaring and multiplying over the 256 bits
+        /// of the exponent. The other operations are roughly accounted for, and
+        /// the hashing of the transcript isn't.
+        pub fn estimated_verification_cost(&self) -> VerificationCost {
+            const EXP_MULS: usize = 2 * 256;
+            // Evaluation of the gate and permutation identities
+            const IDENTITY_MULS: usize = 150;
+
+            let (linearisation_terms, opened_polynomials) =
+                Proof::msm_terms(self);
+            // Each opening flattens its polynomials, and the batch check
+            // combines the polynomial and the witness of each of them before
+            // subtracting the evaluations times the generator
+            let opening_terms = opened_polynomials.iter().sum::<usize>()
+                + 3 * opened_polynomials.len()
+                + 1;
+
+            // The vanishing polynomial, the first Lagrange polynomial, the
+            // quotient evaluation, the public inputs polynomial and its
+            // denominators need one exponentiation or inversion each, and the
+            // quotient commitment one per power of the evaluation challenge
+            let exponentiations = 5 + QUOTIENT_PARTS - 1;
+
+            VerificationCost {
+                pairings: 2,
+                g1_msm_terms: QUOTIENT_PARTS
+                    + linearisation_terms
+                    + opening_terms,
+                field_ops_approx: exponentiations * EXP_MULS + IDENTITY_MULS,
+                // The power of the root of unity of the input, its share of the
+                // batch inversion and its weighting
+                field_ops_per_public_input: EXP_MULS + 5,
+            }
+        }
+
         /// Adds the circuit description to the transcript
         pub(crate) fn seed_transcript(&self, transcript: &mut Transcript) {
             transcript.append_commitment(b"q_m", &self.arithmetic.q_m);
@@ -233,6 +405,17 @@ pub(crate) mod alloc {
         pub(crate) v_h_coset_4n: Evaluations,
     }
 
+    /// Estimated cost of the proving of a circuit, returned by
+    /// [`ProverKey::estimated_proving_cost`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ProvingCost {
+        /// Sizes of the FFTs and inverse FFTs, one entry per transform.
+        pub ffts: Vec<usize>,
+        /// Upper bounds of the sizes of the multi-scalar multiplications,
+        /// one entry per commitment of the proof.
+        pub msm_sizes: Vec<usize>,
+    }
+
     #[cfg(feature = "alloc")]
     impl ProverKey {
         /// Returns the number of [`Polynomial`]s contained in a ProverKey.
@@ -255,16 +438,18 @@ pub(crate) mod alloc {
             let evals_size = self.arithmetic.q_m.1.evals.len()
                 * BlsScalar::SIZE
                 + EvaluationDomain::SIZE;
-            // Create the vec with the capacity counting the 3 u64's plus the 15
-            // Polys and the 17 Evaluations.
+            // Create the vec with the capacity counting the header, the 3
+            // u64's plus the 15 Polys and the 17 Evaluations.
             let mut bytes = vec![
                 0u8;
-                (Self::num_polys() * poly_size
+                (HEADER_SIZE
+                    + Self::num_polys() * poly_size
                     + evals_size * Self::num_evals()
                     + 17 * u64::SIZE) as usize
             ];
 
             let mut writer = &mut bytes[..];
+            writer.write(&HEADER);
             writer.write(&(self.n as u64).to_bytes());
             // Write Evaluation len in bytes.
             writer.write(&(evals_size as u64).to_bytes());
@@ -361,6 +546,7 @@ pub(crate) mod alloc {
         /// Deserialises a slice of bytes into a [`ProverKey`].
         pub fn from_slice(bytes: &[u8]) -> Result<ProverKey, Error> {
             let mut buffer = bytes;
+            version::read_header(&mut buffer)?;
             let n = u64::from_reader(&mut buffer)? as usize;
             let evaluations_size = u64::from_reader(&mut buffer)? as usize;
             // let domain = crate::fft::EvaluationDomain::new(4 * size)?;
@@ -512,6 +698,82 @@ pub(crate) mod alloc {
         pub(crate) fn v_h_coset_4n(&self) -> &Evaluations {
             &self.v_h_coset_4n
         }
+
+        /// Returns an upper bound, in bytes, of the memory used to create a
+        /// [`Proof`](crate::proof_system::Proof) with this key, including the
+        /// key itself.
+        ///
+        /// With `n` the padded circuit size, `s` the size of a [`BlsScalar`]
+        /// and `g` the size of a `G1Affine`, the bound is
+        ///
+        /// `(103 + 32) * n * s + (n + 1) * g`
+        ///
+        /// where:
+        /// - `103 * n` scalars are held by the key, which has 19 polynomials of
+        ///   `n` coefficients and 21 evaluations over the `4n` coset. This
+        ///   includes the copies of the `q_l`, `q_r` and `q_c` selectors held
+        ///   by the logic and fixed base keys.
+        /// - `32 * n` scalars bound the working set of the prover, which peaks
+        ///   while computing the quotient: the 4 wire polynomials, the
+        ///   permutation and public inputs polynomials (`6n`), the evaluations
+        ///   of the wires and the permutation polynomial over the `4n` coset
+        ///   along with the quotient accumulated over it (`24n`), and the
+        ///   twiddle factors of an FFT over the coset (`2n`).
+        /// - `n + 1` points are held by the commit key trimmed to the circuit.
+        ///
+        /// The permutation and linearisation polynomials need less memory
+        /// than the quotient, and the buckets of the multi-scalar
+        /// multiplications are negligible in comparison. The overhead of the
+        /// allocator isn't accounted for.
+        pub fn estimated_prove_memory(&self) -> usize {
+            let n = self.n.next_power_of_two();
+            // The logic key holds a copy of `q_c`, and the fixed base key
+            // copies of `q_l`, `q_r` and `q_c`
+            let copies = 4;
+            let polys = Self::num_polys() + copies;
+            let evals = Self::num_evals() + copies;
+            let scalars = (polys + 4 * evals + 32) * n;
+            let points = n + 1;
+
+            scalars * core::mem::size_of::<BlsScalar>()
+                + points * core::mem::size_of::<dusk_bls12_381::G1Affine>()
+        }
+
+        /// Returns the [`ProvingCost`] of a proof of a circuit of `n` gates,
+        /// counted from the polynomials the prover interpolates and commits
+        /// to.
+        ///
+        /// The prover interpolates the wires and the public inputs, and the
+        /// permutation polynomial out of the evaluations of the sigmas. The
+        /// quotient is computed over the `4n` coset, out of the wires, the
+        /// permutation, the public inputs and the first Lagrange
+        /// polynomials. Each commitment of the proof is a multi-scalar
+        /// multiplication with as many terms as the coefficients of its
+        /// polynomial, which are `n` at most, and `n - 1` for the witnesses
+        /// of the openings.
+        pub fn estimated_proving_cost(n: usize) -> ProvingCost {
+            let n = n.next_power_of_two();
+            let wires = VerifierKey::PERMUTATION_COMMITMENTS;
+            let sigmas = VerifierKey::PERMUTATION_COMMITMENTS;
+
+            // Interpolation of the wires
+            let mut ffts = vec![n; wires];
+            // Evaluation of the sigmas and interpolation of the permutation
+            ffts.extend(vec![n; sigmas + 1]);
+            // Interpolation of the public inputs
+            ffts.push(n);
+            // Evaluation over the coset of the wires, the permutation, the
+            // public inputs and the first Lagrange polynomials, the latter
+            // being interpolated first, then interpolation of the quotient
+            ffts.extend(vec![4 * n; wires + 3]);
+            ffts.push(n);
+            ffts.push(4 * n);
+
+            let mut msm_sizes = vec![n; PROOF_COMMITMENTS - OPENING_WITNESSES];
+            msm_sizes.extend(vec![n - 1; OPENING_WITNESSES]);
+
+            ProvingCost { ffts, msm_sizes }
+        }
     }
 }
 
@@ -618,6 +880,14 @@ mod test {
 
         assert_eq!(pk, prover_key);
         assert_eq!(pk.to_var_bytes(), prover_key.to_var_bytes());
+
+        // Bytes of a different version are rejected
+        let mut prover_key_bytes = prover_key_bytes;
+        prover_key_bytes[3] = prover_key_bytes[3].wrapping_add(1);
+        assert!(matches!(
+            ProverKey::from_slice(&prover_key_bytes),
+            Err(Error::VersionMismatch)
+        ));
     }
 
     #[test]
@@ -687,9 +957,57 @@ mod test {
             permutation,
         };
 
-        let verifier_key_bytes = verifier_key.to_bytes();
+        let mut verifier_key_bytes = verifier_key.to_bytes();
         let got = VerifierKey::from_bytes(&verifier_key_bytes).unwrap();
 
         assert_eq!(got, verifier_key);
+
+        // Bytes of a different version are rejected
+        verifier_key_bytes[3] = verifier_key_bytes[3].wrapping_add(1);
+        assert!(matches!(
+            VerifierKey::from_bytes(&verifier_key_bytes),
+            Err(Error::VersionMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_estimated_costs() {
+        use crate::proof_system::proof::QUOTIENT_PARTS;
+        use crate::proof_system::Proof;
+
+        let proof = Proof::default();
+        assert_eq!(Proof::SERIALIZED_SIZE, proof.to_bytes().len());
+
+        let selectors =
+            [G1Affine::generator(); VerifierKey::SELECTOR_COMMITMENTS];
+        let permutation =
+            [G1Affine::generator(); VerifierKey::PERMUTATION_COMMITMENTS];
+        let verifier_key =
+            VerifierKey::from_commitments(&selectors, &permutation, 1 << 5)
+                .unwrap();
+
+        let cost = verifier_key.estimated_verification_cost();
+        assert_eq!(cost.pairings, 2);
+        // The quotient parts, the linearisation terms, the opened
+        // polynomials and the witness, the evaluation and the point of each
+        // opening, plus the generator of the batch check
+        let (linearisation_terms, opened) = Proof::msm_terms(&verifier_key);
+        assert_eq!(
+            cost.g1_msm_terms,
+            QUOTIENT_PARTS
+                + linearisation_terms
+                + opened.iter().sum::<usize>()
+                + 3 * opened.len()
+                + 1
+        );
+        assert!(cost.field_ops_per_public_input > 0);
+        assert!(cost.field_ops_approx > cost.field_ops_per_public_input);
+
+        let cost = ProverKey::estimated_proving_cost(100);
+        assert_eq!(cost.ffts.len(), 19);
+        assert_eq!(cost.ffts.iter().filter(|size| **size == 128).count(), 11);
+        assert_eq!(cost.ffts.iter().filter(|size| **size == 512).count(), 8);
+        assert_eq!(cost.msm_sizes.len(), 11);
+        assert_eq!(cost.msm_sizes.iter().sum::<usize>(), 11 * 128 - 2);
     }
 }
@@ -34,7 +34,6 @@ impl ProverKey {
         z_i: &BlsScalar,
         z_i_next: &BlsScalar,
         alpha: &BlsScalar,
-        l1_alpha_sq: &BlsScalar,
         beta: &BlsScalar,
         gamma: &BlsScalar,
     ) -> BlsScalar {
@@ -44,8 +43,7 @@ impl ProverKey {
         let b = self.compute_quotient_copy_range_check_i(
             index, w_l_i, w_r_i, w_o_i, w_4_i, z_i_next, alpha, beta, gamma,
         );
-        let c = self.compute_quotient_term_check_one_i(z_i, l1_alpha_sq);
-        a + b + c
+        a + b
     }
     // (a(x) + beta * X + gamma) (b(X) + beta * k1 * X + gamma) (c(X) + beta *
     // k2 * X + gamma)(d(X) + beta * k3 * X + gamma)z(X) * alpha
@@ -99,8 +97,9 @@ impl ProverKey {
 
         -product
     }
-    // L_1(X)[Z(X) - 1]
-    fn compute_quotient_term_check_one_i(
+    // L_1(X)[Z(X) - 1], added apart from the other terms so the coset
+    // evaluations of L_1(X) aren't held along with the ones of the wires
+    pub(crate) fn compute_quotient_term_check_one_i(
         &self,
         z_i: &BlsScalar,
         l1_alpha_sq: &BlsScalar,
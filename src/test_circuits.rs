@@ -0,0 +1,135 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Circuits shared by the tests of the crate, its examples and the tests of
+//! the verifiers generated from it, so they all prove the same statements.
+//!
+//! Only available with the `testing` feature.
+
+use crate::circuit::{Circuit, PublicInputValue};
+use crate::constraint_system::{ArithmeticGate, StandardComposer};
+use crate::error::Error;
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+use dusk_jubjub::{JubJubAffine, JubJubScalar, GENERATOR_EXTENDED};
+
+/// Circuit of the README, which checks:
+/// 1) `a + b = c` where `c` is a PI
+/// 2) `a <= 2^6`
+/// 3) `b <= 2^5`
+/// 4) `a * b = d` where `d` is a PI
+/// 5) `JubJub::GENERATOR * e = f` where `f` is a PI
+#[derive(Debug, Default)]
+pub struct TestCircuit {
+    /// First summand and factor
+    pub a: BlsScalar,
+    /// Second summand and factor
+    pub b: BlsScalar,
+    /// Public sum of `a` and `b`
+    pub c: BlsScalar,
+    /// Public product of `a` and `b`
+    pub d: BlsScalar,
+    /// Scalar multiplying the generator of Jubjub
+    pub e: JubJubScalar,
+    /// Public product of the generator of Jubjub by `e`
+    pub f: JubJubAffine,
+}
+
+impl TestCircuit {
+    /// Returns the circuit proving `20 + 5 = 25`, `20 * 5 = 100` and
+    /// `2 * G = f`.
+    pub fn valid() -> Self {
+        let e = JubJubScalar::from(2u64);
+        TestCircuit {
+            a: BlsScalar::from(20u64),
+            b: BlsScalar::from(5u64),
+            c: BlsScalar::from(25u64),
+            d: BlsScalar::from(100u64),
+            e,
+            f: JubJubAffine::from(GENERATOR_EXTENDED * e),
+        }
+    }
+
+    /// Returns the values of the public inputs of the circuit, in the order
+    /// of their positions.
+    pub fn public_inputs(&self) -> Vec<PublicInputValue> {
+        vec![self.c.into(), self.d.into(), self.f.into()]
+    }
+}
+
+impl Circuit for TestCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xff; 32];
+    fn gadget(&mut self, composer: &mut StandardComposer) -> Result<(), Error> {
+        let a = composer.add_input(self.a);
+        let b = composer.add_input(self.b);
+        // Make first constraint a + b = c
+        ArithmeticGate::new()
+            .left(a)
+            .right(b)
+            .q_l(BlsScalar::one())
+            .q_r(BlsScalar::one())
+            .pi(Some(-self.c))
+            .apply(composer);
+        // Check that a and b are in range
+        composer.range_gate(a, 1 << 6);
+        composer.range_gate(b, 1 << 5);
+        // Make second constraint a * b = d
+        ArithmeticGate::new()
+            .left(a)
+            .right(b)
+            .q_m(BlsScalar::one())
+            .q_o(BlsScalar::one())
+            .pi(Some(-self.d))
+            .apply(composer);
+
+        let e = composer.add_input(self.e.into());
+        let scalar_mul_result =
+            composer.fixed_base_scalar_mul(e, GENERATOR_EXTENDED);
+        // Apply the constrain
+        composer.assert_equal_public_point(scalar_mul_result, self.f);
+        Ok(())
+    }
+    fn padded_circuit_size(&self) -> usize {
+        1 << 11
+    }
+}
+
+/// Circuit checking `a * b = c`, where `c` is a PI, small enough for the
+/// tests that only need a valid proof of some circuit.
+#[derive(Debug, Default)]
+pub struct MulCircuit {
+    /// First factor
+    pub a: BlsScalar,
+    /// Second factor
+    pub b: BlsScalar,
+    /// Public product of `a` and `b`
+    pub c: BlsScalar,
+}
+
+impl MulCircuit {
+    /// Returns the circuit proving `3 * 5 = 15`.
+    pub fn valid() -> Self {
+        MulCircuit {
+            a: BlsScalar::from(3u64),
+            b: BlsScalar::from(5u64),
+            c: BlsScalar::from(15u64),
+        }
+    }
+}
+
+impl Circuit for MulCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0xdd; 32];
+    fn gadget(&mut self, composer: &mut StandardComposer) -> Result<(), Error> {
+        let a = composer.add_input(self.a);
+        let b = composer.add_input(self.b);
+        let c = composer.mul(BlsScalar::one(), a, b, BlsScalar::zero(), None);
+        composer.constrain_to_constant(c, BlsScalar::zero(), Some(-self.c));
+        Ok(())
+    }
+    fn padded_circuit_size(&self) -> usize {
+        1 << 4
+    }
+}
@@ -0,0 +1,241 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Wall-clock timing of the phases of [`Circuit::compile`],
+//! [`Circuit::gen_proof`] and [`verify_proof`].
+//!
+//! The phases are delimited by markers placed along the code paths of the
+//! compilation, the proving and the verification: the time elapsed between
+//! a marker and the next one is accounted to the phase of the former, and a
+//! phase met several times is reported once with the sum of its durations.
+//!
+//! Only the markers met on the thread of the [`PlonkTimer`] are recorded,
+//! so the proofs generated by [`prove_batch`](crate::circuit::prove_batch)
+//! on its thread pool are not measured.
+
+use crate::circuit::{verify_proof, Circuit, PublicInputValue, VerifierData};
+use crate::commitment_scheme::kzg10::PublicParameters;
+use crate::error::Error;
+use crate::proof_system::{Proof, ProverKey, VerifierKey};
+use core::cell::RefCell;
+use core::time::Duration;
+use std::time::Instant;
+use std::vec::Vec;
+
+/// Trimming of the [`PublicParameters`] to the size of the circuit.
+pub const SRS_TRIMMING: &str = "srs trimming";
+/// Building of the circuit and assignment of its witnesses by the
+/// [`Circuit::gadget`].
+pub const WITNESS_ASSIGNMENT: &str = "witness assignment";
+/// Interpolation and evaluation of the polynomials.
+pub const FFT: &str = "fft";
+/// Commitments to the polynomials.
+pub const MSM: &str = "msm";
+/// Hashing of the commitments and evaluations into the transcript to draw
+/// the challenges.
+pub const TRANSCRIPT: &str = "transcript";
+/// Computation of the openings by the prover, including the commitments to
+/// their witnesses, and checks of the openings by the verifier.
+pub const OPENING: &str = "opening";
+
+/// Time spent in one of the phases of a measured call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimingReport {
+    /// Name of the phase, one of the constants of this module.
+    pub phase: &'static str,
+    /// Total time spent in the phase.
+    pub duration: Duration,
+}
+
+/// Reports of the ongoing measurement, along with the current phase.
+#[derive(Debug, Default)]
+struct Recorder {
+    reports: Vec<TimingReport>,
+    current: Option<(&'static str, Instant)>,
+}
+
+impl Recorder {
+    /// Ends the current phase, accounting its duration to its report.
+    fn close(&mut self) {
+        if let Some((phase, start)) = self.current.take() {
+            let duration = start.elapsed();
+            match self.reports.iter_mut().find(|r| r.phase == phase) {
+                Some(report) => report.duration += duration,
+                None => self.reports.push(TimingReport { phase, duration }),
+            }
+        }
+    }
+}
+
+std::thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = RefCell::new(None);
+}
+
+/// Ends the current phase and starts the given one, if a measurement is
+/// ongoing on this thread.
+pub(crate) fn enter(phase: &'static str) {
+    RECORDER.with(|recorder| {
+        if let Some(recorder) = recorder.borrow_mut().as_mut() {
+            recorder.close();
+            recorder.current = Some((phase, Instant::now()));
+        }
+    })
+}
+
+/// Measures the time spent in each phase of the compilation, the proving
+/// and the verification of circuits.
+///
+/// Each method performs the call of the same name and returns its result
+/// along with the [`TimingReport`]s of the phases met, in the order they
+/// were first met.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlonkTimer;
+
+impl PlonkTimer {
+    /// Runs `f`, measuring the phases met on this thread until it returns.
+    pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Vec<TimingReport>) {
+        let outer = RECORDER
+            .with(|recorder| recorder.replace(Some(Recorder::default())));
+
+        let result = f();
+
+        let mut recorder = RECORDER
+            .with(|recorder| recorder.replace(outer))
+            .expect("The recorder is set for the whole measurement");
+        recorder.close();
+
+        (result, recorder.reports)
+    }
+
+    /// Measures [`Circuit::compile`].
+    pub fn compile<C: Circuit>(
+        circuit: &mut C,
+        pub_params: &PublicParameters,
+    ) -> (Result<(ProverKey, VerifierData), Error>, Vec<TimingReport>) {
+        Self::measure(|| circuit.compile(pub_params))
+    }
+
+    /// Measures [`Circuit::gen_proof`].
+    pub fn gen_proof<C: Circuit>(
+        circuit: &mut C,
+        pub_params: &PublicParameters,
+        prover_key: &ProverKey,
+        transcript_init: &'static [u8],
+    ) -> (Result<Proof, Error>, Vec<TimingReport>) {
+        Self::measure(|| {
+            circuit.gen_proof(pub_params, prover_key, transcript_init)
+        })
+    }
+
+    /// Measures [`verify_proof`].
+    pub fn verify_proof(
+        pub_params: &PublicParameters,
+        verifier_key: &VerifierKey,
+        proof: &Proof,
+        pub_inputs_values: &[PublicInputValue],
+        pub_inputs_positions: &[usize],
+        transcript_init: &'static [u8],
+    ) -> (Result<(), Error>, Vec<TimingReport>) {
+        Self::measure(|| {
+            verify_proof(
+                pub_params,
+                verifier_key,
+                proof,
+                pub_inputs_values,
+                pub_inputs_positions,
+                transcript_init,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint_system::StandardComposer;
+    use dusk_bls12_381::BlsScalar;
+    use rand_core::OsRng;
+
+    // Implements a circuit that checks `a * b = c` where `c` is a PI
+    #[derive(Debug, Default)]
+    struct MulCircuit {
+        a: BlsScalar,
+        b: BlsScalar,
+        c: BlsScalar,
+    }
+
+    impl Circuit for MulCircuit {
+        const CIRCUIT_ID: [u8; 32] = [0xee; 32];
+        fn gadget(
+            &mut self,
+            composer: &mut StandardComposer,
+        ) -> Result<(), Error> {
+            let a = composer.add_input(self.a);
+            let b = composer.add_input(self.b);
+            let c =
+                composer.mul(BlsScalar::one(), a, b, BlsScalar::zero(), None);
+            composer.constrain_to_constant(c, BlsScalar::zero(), Some(-self.c));
+            Ok(())
+        }
+        fn padded_circuit_size(&self) -> usize {
+            1 << 4
+        }
+    }
+
+    fn phases(reports: &[TimingReport]) -> Vec<&'static str> {
+        reports.iter().map(|report| report.phase).collect()
+    }
+
+    #[test]
+    fn test_timing_reports() {
+        let pp = PublicParameters::setup(1 << 5, &mut OsRng).unwrap();
+
+        let (res, reports) =
+            PlonkTimer::compile(&mut MulCircuit::default(), &pp);
+        let (pk, vd) = res.unwrap();
+        assert_eq!(
+            phases(&reports),
+            [SRS_TRIMMING, WITNESS_ASSIGNMENT, FFT, MSM, TRANSCRIPT]
+        );
+
+        let mut circuit = MulCircuit {
+            a: BlsScalar::from(3u64),
+            b: BlsScalar::from(5u64),
+            c: BlsScalar::from(15u64),
+        };
+        let (proof, reports) =
+            PlonkTimer::gen_proof(&mut circuit, &pp, &pk, b"Test");
+        let proof = proof.unwrap();
+        assert_eq!(
+            phases(&reports),
+            [
+                SRS_TRIMMING,
+                WITNESS_ASSIGNMENT,
+                FFT,
+                MSM,
+                TRANSCRIPT,
+                OPENING
+            ]
+        );
+
+        let public_inputs: Vec<PublicInputValue> =
+            vec![BlsScalar::from(15u64).into()];
+        let (res, reports) = PlonkTimer::verify_proof(
+            &pp,
+            vd.key(),
+            &proof,
+            &public_inputs,
+            vd.pi_pos(),
+            b"Test",
+        );
+        assert!(res.is_ok());
+        assert_eq!(phases(&reports), [TRANSCRIPT, OPENING, MSM]);
+
+        // Nothing is recorded outside of a measurement
+        circuit.gen_proof(&pp, &pk, b"Test").unwrap();
+        assert!(RECORDER.with(|recorder| recorder.borrow().is_none()));
+    }
+}
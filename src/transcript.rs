@@ -6,11 +6,117 @@
 
 //! This is an extension over the [Merlin Transcript](Transcript)
 //! which adds a few extra functionalities.
+//!
+//! The labels of the challenges drawn while proving and verifying are
+//! exposed as constants, so an interoperating implementation can derive the
+//! same challenges. They can be changed with [`TranscriptLabels`], which
+//! makes the proofs incompatible with the ones using the default labels.
+//!
+//! With the `debug` feature, the challenges drawn by a prover or a verifier
+//! can be dumped with [`record_challenges`], to spot where their
+//! transcripts diverge.
 use crate::commitment_scheme::kzg10::Commitment;
 use dusk_bls12_381::BlsScalar;
 use dusk_bytes::Serializable;
 use merlin::Transcript;
 
+#[cfg(feature = "debug")]
+use core::cell::RefCell;
+#[cfg(feature = "debug")]
+use std::vec::Vec;
+
+/// Default label of the permutation challenge `beta`.
+pub const BETA_LABEL: &[u8] = b"beta";
+/// Default label of the permutation challenge `gamma`.
+pub const GAMMA_LABEL: &[u8] = b"gamma";
+/// Default label of the quotient challenge `alpha`.
+pub const ALPHA_LABEL: &[u8] = b"alpha";
+/// Default label of the evaluation challenge `zeta`.
+pub const ZETA_LABEL: &[u8] = b"z";
+/// Default label of the opening challenge `v`, aggregating the polynomials
+/// opened at the same point.
+pub const V_LABEL: &[u8] = b"aggregate_witness";
+/// Default label of the batching challenge `u`, combining the openings at
+/// different points in the pairing check of the verifier.
+pub const U_LABEL: &[u8] = b"batch";
+
+/// Labels of the challenges drawn from the transcript of a
+/// [`Proof`](crate::proof_system::Proof).
+///
+/// The prover and the verifier of a proof must use the same labels, the
+/// [`Default`] ones being the constants of this module. Since `u` is only
+/// drawn by the verifier, its label doesn't affect the proofs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranscriptLabels {
+    /// Label of the permutation challenge `beta`
+    pub beta: &'static [u8],
+    /// Label of the permutation challenge `gamma`
+    pub gamma: &'static [u8],
+    /// Label of the quotient challenge `alpha`
+    pub alpha: &'static [u8],
+    /// Label of the evaluation challenge `zeta`
+    pub zeta: &'static [u8],
+    /// Label of the opening challenge `v`
+    pub v: &'static [u8],
+    /// Label of the batching challenge `u`
+    pub u: &'static [u8],
+}
+
+impl TranscriptLabels {
+    /// Labels used unless configured otherwise.
+    pub const DEFAULT: TranscriptLabels = TranscriptLabels {
+        beta: BETA_LABEL,
+        gamma: GAMMA_LABEL,
+        alpha: ALPHA_LABEL,
+        zeta: ZETA_LABEL,
+        v: V_LABEL,
+        u: U_LABEL,
+    };
+}
+
+impl Default for TranscriptLabels {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Challenge drawn from a transcript, as dumped by [`record_challenges`].
+#[cfg(feature = "debug")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrawnChallenge {
+    /// Label the challenge was drawn with.
+    pub label: &'static [u8],
+    /// Value of the challenge.
+    pub value: BlsScalar,
+}
+
+#[cfg(feature = "debug")]
+std::thread_local! {
+    static CHALLENGES: RefCell<Option<Vec<DrawnChallenge>>> =
+        RefCell::new(None);
+}
+
+/// Runs `f`, returning its result along with the challenges drawn from any
+/// transcript on this thread until it returns, in order.
+///
+/// The prover draws `beta`, `gamma`, `alpha`, the four separation
+/// challenges of the widgets, `zeta` and `v` twice. The verifier draws the
+/// same challenges, followed by `u`.
+#[cfg(feature = "debug")]
+pub fn record_challenges<T, F: FnOnce() -> T>(
+    f: F,
+) -> (T, Vec<DrawnChallenge>) {
+    let outer = CHALLENGES.with(|dump| dump.replace(Some(Vec::new())));
+
+    let result = f();
+
+    let challenges = CHALLENGES
+        .with(|dump| dump.replace(outer))
+        .expect("The dump is set for the whole recording");
+
+    (result, challenges)
+}
+
 /// Transcript adds an abstraction over the Merlin transcript
 /// For convenience
 pub(crate) trait TranscriptProtocol {
@@ -40,7 +146,19 @@ impl TranscriptProtocol for Transcript {
         let mut buf = [0u8; 64];
         self.challenge_bytes(label, &mut buf);
 
-        BlsScalar::from_bytes_wide(&buf)
+        let challenge = BlsScalar::from_bytes_wide(&buf);
+
+        #[cfg(feature = "debug")]
+        CHALLENGES.with(|dump| {
+            if let Some(dump) = dump.borrow_mut().as_mut() {
+                dump.push(DrawnChallenge {
+                    label,
+                    value: challenge,
+                });
+            }
+        });
+
+        challenge
     }
 
     fn circuit_domain_sep(&mut self, n: u64) {
@@ -48,3 +166,75 @@ impl TranscriptProtocol for Transcript {
         self.append_u64(b"n", n);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_labels() {
+        // Changing these labels breaks the compatibility with the existing
+        // proofs
+        let labels = TranscriptLabels::default();
+        assert_eq!(labels.beta, b"beta");
+        assert_eq!(labels.gamma, b"gamma");
+        assert_eq!(labels.alpha, b"alpha");
+        assert_eq!(labels.zeta, b"z");
+        assert_eq!(labels.v, b"aggregate_witness");
+        assert_eq!(labels.u, b"batch");
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn test_challenge_dumps() {
+        use crate::commitment_scheme::kzg10::PublicParameters;
+        use crate::constraint_system::helper::dummy_gadget;
+        use crate::proof_system::{Prover, Verifier};
+        use rand_core::OsRng;
+
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+
+        let (proof, prover_dump) = record_challenges(|| prover.prove(&ck));
+        let proof = proof.unwrap();
+
+        let mut verifier = Verifier::new(b"demo");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+
+        let (res, verifier_dump) =
+            record_challenges(|| verifier.verify(&proof, &vk, &public_inputs));
+        assert!(res.is_ok());
+
+        let labels = TranscriptLabels::DEFAULT;
+        let prover_labels: Vec<&[u8]> =
+            prover_dump.iter().map(|c| c.label).collect();
+        let expected: [&[u8]; 10] = [
+            labels.beta,
+            labels.gamma,
+            labels.alpha,
+            b"range separation challenge",
+            b"logic separation challenge",
+            b"fixed base separation challenge",
+            b"variable base separation challenge",
+            labels.zeta,
+            labels.v,
+            labels.v,
+        ];
+        assert_eq!(prover_labels, expected);
+
+        // The verifier replays the prover's challenges, then draws `u`
+        assert_eq!(verifier_dump.len(), prover_dump.len() + 1);
+        assert_eq!(verifier_dump[..prover_dump.len()], prover_dump[..]);
+        assert_eq!(verifier_dump[prover_dump.len()].label, labels.u);
+
+        // Nothing is recorded outside of a recording
+        assert!(CHALLENGES.with(|dump| dump.borrow().is_none()));
+    }
+}
@@ -0,0 +1,41 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Writes the inputs of `plonk_verify_wasm` for a proof of the circuit of the
+//! README into `tests/fixtures`, for the Jest tests to verify it.
+
+use dusk_bytes::Serializable;
+use dusk_plonk::prelude::*;
+use dusk_plonk::test_circuits::TestCircuit;
+use dusk_plonk_wasm::TRANSCRIPT_INIT;
+use rand_core::OsRng;
+use std::fs;
+use std::path::Path;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+    let (pk, vd) = TestCircuit::default().compile(&pp)?;
+
+    let mut circuit = TestCircuit::valid();
+    let proof = circuit.gen_proof(&pp, &pk, TRANSCRIPT_INIT)?;
+
+    let mut vk_bytes = pp.opening_key().to_bytes().to_vec();
+    vk_bytes.extend_from_slice(&vd.to_var_bytes());
+    let pi_bytes: Vec<u8> = circuit
+        .public_inputs()
+        .iter()
+        .flat_map(PublicInputValue::iter)
+        .flat_map(|scalar| scalar.to_bytes().to_vec())
+        .collect();
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("vk.bin"), vk_bytes)?;
+    fs::write(dir.join("proof.bin"), proof.to_bytes())?;
+    fs::write(dir.join("pi.bin"), pi_bytes)?;
+
+    Ok(())
+}
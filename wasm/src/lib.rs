@@ -0,0 +1,115 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Minimal verifier of `dusk-plonk` proofs compiled to WebAssembly, exported
+//! to JavaScript with `wasm-bindgen`.
+//!
+//! The only export is [`plonk_verify_wasm`], which takes its inputs as
+//! `Uint8Array`s holding:
+//! - `vk_bytes`: the bytes of the [`OpeningKey`] of the
+//!   [`PublicParameters`](dusk_plonk::prelude::PublicParameters) the circuit
+//!   was compiled with, followed by the ones of its [`VerifierData`], see
+//!   [`OpeningKey::to_bytes`] and [`VerifierData::to_var_bytes`].
+//! - `proof_bytes`: the bytes of the [`Proof`], see [`Proof::to_bytes`].
+//! - `pi_bytes`: the canonical little-endian encodings of the [`BlsScalar`]s of
+//!   the public inputs, one after the other in the order of their positions. A
+//!   `JubJubAffine` public input is encoded as its `x` and `y` coordinates.
+//!
+//! The proofs must be generated with [`TRANSCRIPT_INIT`] as the
+//! `transcript_init` of
+//! [`Circuit::gen_proof`](dusk_plonk::prelude::Circuit::gen_proof).
+
+#![deny(missing_docs)]
+
+use dusk_bytes::{DeserializableSlice, Serializable};
+use dusk_plonk::prelude::*;
+use wasm_bindgen::prelude::*;
+
+/// Label of the transcripts of the proofs verified by [`plonk_verify_wasm`].
+pub const TRANSCRIPT_INIT: &[u8] = b"dusk-plonk-wasm";
+
+/// Returns whether the proof of `proof_bytes` is valid for the circuit of
+/// `vk_bytes` and the public inputs of `pi_bytes`.
+///
+/// Malformed inputs, including a number of public inputs that doesn't match
+/// the positions of the [`VerifierData`], are rejected as invalid proofs.
+#[wasm_bindgen]
+pub fn plonk_verify_wasm(
+    vk_bytes: &[u8],
+    proof_bytes: &[u8],
+    pi_bytes: &[u8],
+) -> bool {
+    verify(vk_bytes, proof_bytes, pi_bytes).is_ok()
+}
+
+fn verify(
+    vk_bytes: &[u8],
+    proof_bytes: &[u8],
+    pi_bytes: &[u8],
+) -> Result<(), Error> {
+    let opening_key = OpeningKey::from_slice(vk_bytes)?;
+    let verifier_data = VerifierData::from_slice(
+        vk_bytes
+            .get(OpeningKey::SIZE..)
+            .ok_or(Error::NotEnoughBytes)?,
+    )?;
+    let proof = Proof::from_slice(proof_bytes)?;
+
+    if pi_bytes.len() != verifier_data.pi_pos().len() * BlsScalar::SIZE {
+        return Err(Error::InvalidPublicInputBytes);
+    }
+    let pub_inputs = pi_bytes
+        .chunks(BlsScalar::SIZE)
+        .map(|bytes| BlsScalar::from_slice(bytes).map(PublicInputValue::from))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    PlonkVerifier::with_opening_key(
+        *verifier_data.key(),
+        opening_key,
+        TRANSCRIPT_INIT,
+    )
+    .verify(&proof, &pub_inputs, verifier_data.pi_pos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dusk_plonk::test_circuits::MulCircuit;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_plonk_verify_wasm() -> Result<(), Error> {
+        let pp = PublicParameters::setup(1 << 5, &mut OsRng)?;
+        let (pk, vd) = MulCircuit::default().compile(&pp)?;
+        let proof = MulCircuit::valid().gen_proof(&pp, &pk, TRANSCRIPT_INIT)?;
+
+        let mut vk_bytes = pp.opening_key().to_bytes().to_vec();
+        vk_bytes.extend_from_slice(&vd.to_var_bytes());
+        let proof_bytes = proof.to_bytes();
+        let pi_bytes = BlsScalar::from(15u64).to_bytes();
+        assert!(plonk_verify_wasm(&vk_bytes, &proof_bytes, &pi_bytes));
+
+        // Should fail since the public input is not the product
+        let wrong_pi_bytes = BlsScalar::from(16u64).to_bytes();
+        assert!(!plonk_verify_wasm(&vk_bytes, &proof_bytes, &wrong_pi_bytes));
+
+        // Should fail since the inputs are truncated
+        assert!(!plonk_verify_wasm(
+            &vk_bytes[..100],
+            &proof_bytes,
+            &pi_bytes
+        ));
+        assert!(!plonk_verify_wasm(
+            &vk_bytes,
+            &proof_bytes[..100],
+            &pi_bytes
+        ));
+        assert!(!plonk_verify_wasm(&vk_bytes, &proof_bytes, &pi_bytes[..16]));
+        assert!(!plonk_verify_wasm(&vk_bytes, &proof_bytes, &[]));
+
+        Ok(())
+    }
+}
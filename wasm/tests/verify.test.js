@@ -0,0 +1,43 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+// The module and the fixtures are built by `npm run pretest`
+
+const fs = require("fs");
+const path = require("path");
+const { plonk_verify_wasm } = require("../pkg/dusk_plonk_wasm");
+
+const fixture = (name) =>
+  new Uint8Array(fs.readFileSync(path.join(__dirname, "fixtures", name)));
+
+const vk = fixture("vk.bin");
+const proof = fixture("proof.bin");
+const pi = fixture("pi.bin");
+
+test("verifies the proof of the README circuit", () => {
+  expect(plonk_verify_wasm(vk, proof, pi)).toBe(true);
+});
+
+test("rejects the proof for other public inputs", () => {
+  // Turns the first public input from 25 into 24
+  const wrongPi = pi.slice();
+  wrongPi[0] ^= 1;
+
+  expect(plonk_verify_wasm(vk, proof, wrongPi)).toBe(false);
+});
+
+test("rejects malformed inputs", () => {
+  expect(plonk_verify_wasm(vk.slice(0, 100), proof, pi)).toBe(false);
+  expect(plonk_verify_wasm(vk, proof.slice(0, 100), pi)).toBe(false);
+  expect(plonk_verify_wasm(vk, proof, pi.slice(0, 32))).toBe(false);
+  expect(plonk_verify_wasm(vk, proof, new Uint8Array())).toBe(false);
+});
+
+test("the module is under 1 MB", () => {
+  const wasm = path.join(__dirname, "..", "pkg", "dusk_plonk_wasm_bg.wasm");
+
+  expect(fs.statSync(wasm).size).toBeLessThan(1 << 20);
+});